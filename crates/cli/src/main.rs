@@ -1,16 +1,27 @@
 mod args;
 
 use args::GlobalArgs;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use std::{path::Path, sync::Arc, time::Duration};
 
 use kora_lib::{
-    admin::token_util::initialize_atas,
+    admin::{
+        config_reload::{get_config_reloader, init_config_reloader, ConfigReloader},
+        keystore_util::{encrypt_keypair_file, generate_keystore, read_passphrase},
+        nonce_util::initialize_nonce_accounts,
+        signers_util::{
+            format_token_amount, generate_memory_keypair, get_signer_balances, list_signers,
+            verify_signers,
+        },
+        token_util::initialize_atas,
+    },
     error::KoraError,
     log::LoggingFormat,
-    rpc::get_rpc_client,
+    rpc::get_rpc_client_with_config,
     rpc_server::{run_rpc_server, KoraRpc, RpcArgs},
-    signer::init::init_signers,
-    state::init_config,
+    signer::{init::init_signers, KeystoreKdf, SignerPoolConfig},
+    state::{get_config, init_config},
     validator::config_validator::ConfigValidator,
     CacheUtil, Config,
 };
@@ -32,6 +43,11 @@ enum Commands {
         #[command(subcommand)]
         rpc_command: RpcCommands,
     },
+    /// Signer management commands
+    Signers {
+        #[command(subcommand)]
+        signers_command: SignersCommands,
+    },
     /// Generate OpenAPI documentation
     #[cfg(feature = "docs")]
     Openapi {
@@ -57,6 +73,125 @@ enum ConfigCommands {
     },
 }
 
+#[derive(Subcommand)]
+enum SignersCommands {
+    /// List the signers of a signers configuration file
+    #[command(
+        about = "List the signers of a signers configuration file",
+        long_about = "List the name, type, weight and public key of each signer in the signers configuration file.\n\nPublic keys are resolved by building each signer backend, so remote signers need their credentials set."
+    )]
+    List {
+        /// Path to signers configuration file
+        #[arg(long)]
+        signers_config: std::path::PathBuf,
+    },
+    /// Show the SOL and payment token balances of each signer
+    #[command(
+        about = "Show the SOL and payment token balances of each signer",
+        long_about = "Show the SOL balance of each signer and the balance of the token account that receives its payments for every token in allowed_spl_paid_tokens.\n\nToken accounts belong to the configured payment address when one is set."
+    )]
+    Balances {
+        /// Path to signers configuration file
+        #[arg(long)]
+        signers_config: std::path::PathBuf,
+    },
+    /// Generate a new keypair for a memory or encrypted_keystore signer
+    #[command(
+        about = "Generate a new keypair for a memory or encrypted_keystore signer",
+        long_about = "Generate a new keypair.\n\nFor a memory signer the base58 private key is printed, to be set in the signer's private_key_env. For a keystore the keypair is written straight into an encrypted keystore for the encrypted_keystore signer."
+    )]
+    Generate {
+        /// Kind of signer to generate a keypair for
+        #[arg(long = "type", value_enum)]
+        signer_type: GenerateTypeArg,
+
+        /// Path to write the keystore to
+        #[arg(short = 'o', long, required_if_eq("signer_type", "keystore"))]
+        output: Option<std::path::PathBuf>,
+
+        /// Environment variable holding the keystore passphrase
+        #[arg(long, conflicts_with = "passphrase_file")]
+        passphrase_env: Option<String>,
+
+        /// File holding the keystore passphrase
+        #[arg(long)]
+        passphrase_file: Option<std::path::PathBuf>,
+
+        /// Key derivation function of the keystore
+        #[arg(long, value_enum, default_value = "scrypt")]
+        kdf: KdfArg,
+
+        /// Overwrite the output file if it exists
+        #[arg(long)]
+        force: bool,
+    },
+    /// Sign a test message with each signer
+    #[command(
+        about = "Sign a test message with each signer",
+        long_about = "Initialize each signer backend and sign a test message with it, without starting the server.\n\nReports the signing latency of each signer, or the error that prevented it from signing. Exits with an error if any signer fails."
+    )]
+    Verify {
+        /// Path to signers configuration file
+        #[arg(long)]
+        signers_config: std::path::PathBuf,
+    },
+    /// Encrypt a keypair file into a keystore for the encrypted_keystore signer
+    #[command(
+        about = "Encrypt a keypair file into a keystore for the encrypted_keystore signer",
+        long_about = "Encrypt a Solana CLI JSON keypair file with AES-256-GCM under a key derived from a passphrase.\n\nThe passphrase is read from an environment variable or a file, the same way the encrypted_keystore signer reads it at startup."
+    )]
+    Encrypt {
+        /// Path to the Solana CLI JSON keypair file to encrypt
+        #[arg(long)]
+        keypair: std::path::PathBuf,
+
+        /// Path to write the keystore to
+        #[arg(short = 'o', long)]
+        output: std::path::PathBuf,
+
+        /// Environment variable holding the passphrase
+        #[arg(
+            long,
+            conflicts_with = "passphrase_file",
+            required_unless_present = "passphrase_file"
+        )]
+        passphrase_env: Option<String>,
+
+        /// File holding the passphrase
+        #[arg(long)]
+        passphrase_file: Option<std::path::PathBuf>,
+
+        /// Key derivation function
+        #[arg(long, value_enum, default_value = "scrypt")]
+        kdf: KdfArg,
+
+        /// Overwrite the output file if it exists
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Clone, ValueEnum)]
+enum GenerateTypeArg {
+    Memory,
+    Keystore,
+}
+
+#[derive(Clone, ValueEnum)]
+enum KdfArg {
+    Scrypt,
+    Argon2id,
+}
+
+impl From<KdfArg> for KeystoreKdf {
+    fn from(kdf: KdfArg) -> Self {
+        match kdf {
+            KdfArg::Scrypt => KeystoreKdf::scrypt(),
+            KdfArg::Argon2id => KeystoreKdf::argon2id(),
+        }
+    }
+}
+
 #[derive(Subcommand)]
 enum RpcCommands {
     /// Start the RPC server
@@ -93,6 +228,23 @@ enum RpcCommands {
         #[arg(long, help_heading = "Transaction Options")]
         chunk_size: Option<usize>,
     },
+    /// Create the durable nonce accounts of the nonce pool
+    #[command(
+        about = "Create the durable nonce accounts of the nonce pool",
+        long_about = "Create the durable nonce accounts configured in [kora.nonce_pool] that don't exist yet.\n\nEach signer in the pool funds its own nonce accounts and is their nonce authority. Nonce account addresses are derived from the signer public key, so the command can be re-run safely."
+    )]
+    InitializeNonceAccounts {
+        #[command(flatten)]
+        rpc_args: Box<RpcArgs>,
+
+        /// Only create nonce accounts for this signer (defaults to all signers)
+        #[arg(long, help_heading = "Signer Options")]
+        signer_key: Option<String>,
+
+        /// Number of nonce accounts to create per transaction
+        #[arg(long, help_heading = "Transaction Options")]
+        chunk_size: Option<usize>,
+    },
 }
 
 #[derive(Parser)]
@@ -115,12 +267,15 @@ async fn main() -> Result<(), KoraError> {
         std::process::exit(1);
     });
 
+    let rpc_config = config.kora.rpc.clone();
+    let sign_timeout = Duration::from_secs(config.kora.sign_timeout_seconds);
+
     init_config(config).unwrap_or_else(|e| {
         print_error(&format!("Failed to initialize config: {e}"));
         std::process::exit(1);
     });
 
-    let rpc_client = get_rpc_client(&cli.global_args.rpc_url);
+    let rpc_client = get_rpc_client_with_config(&cli.global_args.rpc_url, &rpc_config);
 
     match cli.command {
         Some(Commands::Config { config_command }) => {
@@ -189,12 +344,25 @@ async fn main() -> Result<(), KoraError> {
                         std::process::exit(1);
                     }
 
-                    let rpc_client = get_rpc_client(&cli.global_args.rpc_url);
+                    let rpc_client =
+                        get_rpc_client_with_config(&cli.global_args.rpc_url, &rpc_config);
 
-                    let kora_rpc = KoraRpc::new(rpc_client);
+                    let kora_rpc = KoraRpc::new(rpc_client.clone());
 
                     let handles = run_rpc_server(kora_rpc, rpc_args.port).await?;
 
+                    // Signers are only reloaded when they were loaded at startup
+                    let signers_config =
+                        rpc_args.signers_config.clone().filter(|_| !rpc_args.skip_signer);
+                    if let Err(e) = init_config_reloader(ConfigReloader::new(
+                        cli.global_args.config.clone().into(),
+                        signers_config,
+                    )) {
+                        print_error(&format!("Failed to initialize config reloader: {e}"));
+                        std::process::exit(1);
+                    }
+                    spawn_reload_on_sighup(rpc_client);
+
                     wait_for_shutdown_signal().await;
                     println!("Shutting down server...");
 
@@ -238,8 +406,151 @@ async fn main() -> Result<(), KoraError> {
                     }
                     println!("Successfully initialized all payment ATAs");
                 }
+                RpcCommands::InitializeNonceAccounts { rpc_args, signer_key, chunk_size } => {
+                    if !rpc_args.skip_signer {
+                        init_signers(&rpc_args).await.unwrap_or_else(|e| {
+                            print_error(&format!("Failed to initialize signer(s): {e}"));
+                            std::process::exit(1);
+                        });
+                    } else {
+                        print_error("Cannot initialize nonce accounts without a signer.");
+                        std::process::exit(1);
+                    }
+
+                    if let Err(e) =
+                        initialize_nonce_accounts(rpc_client.as_ref(), chunk_size, signer_key).await
+                    {
+                        print_error(&format!("Failed to initialize nonce accounts: {e}"));
+                        std::process::exit(1);
+                    }
+                    println!("Successfully initialized all nonce accounts");
+                }
             }
         }
+        Some(Commands::Signers { signers_command }) => match signers_command {
+            SignersCommands::Encrypt {
+                keypair,
+                output,
+                passphrase_env,
+                passphrase_file,
+                kdf,
+                force,
+            } => {
+                let result = read_passphrase(passphrase_env.as_deref(), passphrase_file.as_deref())
+                    .and_then(|passphrase| {
+                        encrypt_keypair_file(&keypair, &output, &passphrase, kdf.into(), force)
+                    });
+                match result {
+                    Ok(pubkey) => {
+                        println!("Encrypted keypair {pubkey} to {}", output.display())
+                    }
+                    Err(e) => {
+                        print_error(&format!("Failed to encrypt keypair: {e}"));
+                        std::process::exit(1);
+                    }
+                }
+            }
+            SignersCommands::List { signers_config } => {
+                let signers_config = load_signers_config(&signers_config);
+                for signer in list_signers(&signers_config).await {
+                    let pubkey = match signer.pubkey {
+                        Ok(pubkey) => pubkey.to_string(),
+                        Err(e) => format!("unavailable: {e}"),
+                    };
+                    println!(
+                        "{:<24} {:<20} weight {:<4} {pubkey}",
+                        signer.name, signer.signer_type, signer.weight
+                    );
+                }
+            }
+            SignersCommands::Balances { signers_config } => {
+                let signers_config = load_signers_config(&signers_config);
+                let mut signers = Vec::new();
+                for signer in list_signers(&signers_config).await {
+                    match signer.pubkey {
+                        Ok(pubkey) => signers.push((signer.name, pubkey)),
+                        Err(e) => println!("Warning: skipping signer '{}': {e}", signer.name),
+                    }
+                }
+
+                let config = get_config().unwrap_or_else(|e| {
+                    print_error(&format!("Failed to get config: {e}"));
+                    std::process::exit(1);
+                });
+                let balances = get_signer_balances(&config, rpc_client.as_ref(), &signers)
+                    .await
+                    .unwrap_or_else(|e| {
+                        print_error(&format!("Failed to fetch signer balances: {e}"));
+                        std::process::exit(1);
+                    });
+                for signer in balances {
+                    println!("{} ({})", signer.name, signer.pubkey);
+                    println!("  SOL: {}", format_token_amount(signer.lamports, 9));
+                    for token in signer.tokens {
+                        let amount = match token.amount {
+                            Some(amount) => format_token_amount(amount, token.decimals),
+                            None => "no token account".to_string(),
+                        };
+                        println!("  {}: {amount} ({})", token.mint, token.token_account);
+                    }
+                }
+            }
+            SignersCommands::Generate {
+                signer_type,
+                output,
+                passphrase_env,
+                passphrase_file,
+                kdf,
+                force,
+            } => match (signer_type, output) {
+                (GenerateTypeArg::Memory, _) => {
+                    let (pubkey, private_key) = generate_memory_keypair();
+                    println!("Public key: {pubkey}");
+                    println!("Private key (set it in the signer's private_key_env): {private_key}");
+                }
+                (GenerateTypeArg::Keystore, Some(output)) => {
+                    let result =
+                        read_passphrase(passphrase_env.as_deref(), passphrase_file.as_deref())
+                            .and_then(|passphrase| {
+                                generate_keystore(&output, &passphrase, kdf.into(), force)
+                            });
+                    match result {
+                        Ok(pubkey) => {
+                            println!("Generated keypair {pubkey} into {}", output.display())
+                        }
+                        Err(e) => {
+                            print_error(&format!("Failed to generate keystore: {e}"));
+                            std::process::exit(1);
+                        }
+                    }
+                }
+                (GenerateTypeArg::Keystore, None) => {
+                    print_error("--output is required to generate a keystore");
+                    std::process::exit(1);
+                }
+            },
+            SignersCommands::Verify { signers_config } => {
+                let signers_config = load_signers_config(&signers_config);
+                let mut failed = false;
+                for signer in verify_signers(&signers_config, sign_timeout).await {
+                    let pubkey = signer.pubkey.map(|pubkey| pubkey.to_string()).unwrap_or_default();
+                    match signer.result {
+                        Ok(latency) => println!(
+                            "✓ {:<24} {pubkey} signed in {}ms",
+                            signer.name,
+                            latency.as_millis()
+                        ),
+                        Err(e) => {
+                            failed = true;
+                            println!("✗ {:<24} {pubkey} {e}", signer.name);
+                        }
+                    }
+                }
+                if failed {
+                    std::process::exit(1);
+                }
+            }
+        },
 
         #[cfg(feature = "docs")]
         Some(Commands::Openapi { output }) => {
@@ -265,6 +576,12 @@ async fn main() -> Result<(), KoraError> {
             println!("  config validate-with-rpc - Validate configuration with RPC calls");
             println!("  rpc start                - Start RPC server");
             println!("  rpc initialize-atas      - Initialize ATAs for payment tokens");
+            println!("  rpc initialize-nonce-accounts - Create nonce pool accounts");
+            println!("  signers list             - List configured signers");
+            println!("  signers balances         - Show signer SOL and payment token balances");
+            println!("  signers generate         - Generate a memory or keystore keypair");
+            println!("  signers verify           - Sign a test message with each signer");
+            println!("  signers encrypt          - Encrypt a keypair file into a keystore");
             #[cfg(feature = "docs")]
             println!("  openapi                  - Generate OpenAPI documentation");
         }
@@ -277,6 +594,13 @@ fn print_error(message: &str) {
     eprintln!("Error: {message}");
 }
 
+fn load_signers_config(path: &Path) -> SignerPoolConfig {
+    SignerPoolConfig::load_config(path).unwrap_or_else(|e| {
+        print_error(&format!("Failed to load signers config: {e}"));
+        std::process::exit(1);
+    })
+}
+
 #[cfg(unix)]
 async fn wait_for_shutdown_signal() {
     let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
@@ -298,6 +622,34 @@ async fn wait_for_shutdown_signal() {
     }
 }
 
+/// Re-reads the config (and signers) on SIGHUP. A rejected reload keeps the
+/// current config and is only logged.
+#[cfg(unix)]
+fn spawn_reload_on_sighup(rpc_client: Arc<RpcClient>) {
+    let mut sighup = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+        .expect("Failed to install SIGHUP handler");
+    tokio::spawn(async move {
+        while sighup.recv().await.is_some() {
+            log::info!("SIGHUP received, reloading config");
+            let result = match get_config_reloader() {
+                Ok(reloader) => reloader.reload(&rpc_client).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(report) => {
+                    for warning in report.warnings {
+                        log::warn!("Config reload warning: {warning}");
+                    }
+                }
+                Err(e) => log::error!("Config reload failed: {e}"),
+            }
+        }
+    });
+}
+
+#[cfg(not(unix))]
+fn spawn_reload_on_sighup(_rpc_client: Arc<RpcClient>) {}
+
 fn setup_logging(format: &LoggingFormat) {
     let env_filter = std::env::var("RUST_LOG")
         .unwrap_or_else(|_| "info,sqlx=error,sea_orm_migration=error,jsonrpsee_server=warn".into());
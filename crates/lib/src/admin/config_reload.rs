@@ -0,0 +1,193 @@
+use serde::Serialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use std::path::PathBuf;
+use tokio::sync::Mutex;
+
+use crate::{
+    config::Config,
+    error::KoraError,
+    signer::{SignerPool, SignerPoolConfig},
+    state::{get_config, swap_config_and_signer_pool},
+    validator::config_validator::ConfigValidator,
+};
+
+/// Global config reloader, set once the RPC server is running
+static CONFIG_RELOADER: once_cell::sync::OnceCell<ConfigReloader> =
+    once_cell::sync::OnceCell::new();
+
+/// Outcome of a successful reload
+#[derive(Debug, Default)]
+pub struct ConfigReloadReport {
+    /// Validator warnings for the new config
+    pub warnings: Vec<String>,
+    /// Settings that changed on disk but only take effect after a restart
+    pub restart_required: Vec<String>,
+    /// Whether the signer pool was rebuilt from the signers config
+    pub signers_reloaded: bool,
+}
+
+/// Re-reads `kora.toml` (and `signers.toml` when signers were loaded) and swaps
+/// them in if they pass [`ConfigValidator`]
+pub struct ConfigReloader {
+    config_path: PathBuf,
+    signers_config_path: Option<PathBuf>,
+    // Serializes reloads so two triggers can't interleave load, validate and swap
+    lock: Mutex<()>,
+}
+
+impl ConfigReloader {
+    pub fn new(config_path: PathBuf, signers_config_path: Option<PathBuf>) -> Self {
+        Self { config_path, signers_config_path, lock: Mutex::new(()) }
+    }
+
+    pub async fn reload(&self, rpc_client: &RpcClient) -> Result<ConfigReloadReport, KoraError> {
+        let _guard = self.lock.lock().await;
+
+        let current = get_config()?;
+        let mut new_config = Config::load_config(&self.config_path)?;
+        let restart_required = keep_restart_required_settings(&current, &mut new_config);
+
+        let warnings = ConfigValidator::validate_config_with_result_and_signers(
+            &new_config,
+            rpc_client,
+            true,
+            self.signers_config_path.as_ref(),
+        )
+        .await
+        .map_err(|errors| {
+            KoraError::ValidationError(format!(
+                "Config reload rejected, keeping the current config: {}",
+                errors.join("; ")
+            ))
+        })?;
+
+        // Build the new pool before swapping anything, so a signer that fails to
+        // initialize leaves both the current config and pool in place
+        let new_pool = match &self.signers_config_path {
+            Some(path) => {
+                Some(SignerPool::from_config(SignerPoolConfig::load_config(path)?).await?)
+            }
+            None => None,
+        };
+        let signers_reloaded = new_pool.is_some();
+
+        swap_config_and_signer_pool(new_config, new_pool)?;
+
+        log::info!("Config reloaded from {}", self.config_path.display());
+        for setting in &restart_required {
+            log::warn!("Config reload: '{setting}' changed but requires a restart to take effect");
+        }
+
+        Ok(ConfigReloadReport { warnings, restart_required, signers_reloaded })
+    }
+}
+
+fn keep_current<T: Serialize + Clone>(
+    name: &str,
+    current: &T,
+    new: &mut T,
+    restart_required: &mut Vec<String>,
+) {
+    if serde_json::to_value(current).ok() != serde_json::to_value(&*new).ok() {
+        restart_required.push(name.to_string());
+        *new = current.clone();
+    }
+}
+
+/// Settings baked into the server at startup (middleware stack, registered methods,
+/// shared clients and stores) can't change live. Keep their current values in the
+/// new config so it matches what is actually running, and return the ones that
+/// changed on disk.
+pub fn keep_restart_required_settings(current: &Config, new: &mut Config) -> Vec<String> {
+    let mut restart_required = Vec::new();
+
+    keep_current(
+        "kora.rate_limit",
+        &current.kora.rate_limit,
+        &mut new.kora.rate_limit,
+        &mut restart_required,
+    );
+    keep_current(
+        "kora.max_request_body_size",
+        &current.kora.max_request_body_size,
+        &mut new.kora.max_request_body_size,
+        &mut restart_required,
+    );
+    keep_current(
+        "kora.enabled_methods",
+        &current.kora.enabled_methods,
+        &mut new.kora.enabled_methods,
+        &mut restart_required,
+    );
+    keep_current("kora.auth", &current.kora.auth, &mut new.kora.auth, &mut restart_required);
+    keep_current("kora.cache", &current.kora.cache, &mut new.kora.cache, &mut restart_required);
+    keep_current(
+        "kora.usage_limit",
+        &current.kora.usage_limit,
+        &mut new.kora.usage_limit,
+        &mut restart_required,
+    );
+    keep_current("kora.rpc", &current.kora.rpc, &mut new.kora.rpc, &mut restart_required);
+    keep_current("kora.admin", &current.kora.admin, &mut new.kora.admin, &mut restart_required);
+    keep_current(
+        "validation.price_source",
+        &current.validation.price_source,
+        &mut new.validation.price_source,
+        &mut restart_required,
+    );
+    keep_current("metrics", &current.metrics, &mut new.metrics, &mut restart_required);
+
+    restart_required
+}
+
+/// Register the reloader used by SIGHUP and the reloadConfig method
+pub fn init_config_reloader(reloader: ConfigReloader) -> Result<(), KoraError> {
+    CONFIG_RELOADER.set(reloader).map_err(|_| {
+        KoraError::InternalServerError("Config reloader already initialized".to_string())
+    })
+}
+
+pub fn get_config_reloader() -> Result<&'static ConfigReloader, KoraError> {
+    CONFIG_RELOADER
+        .get()
+        .ok_or_else(|| KoraError::InternalServerError("Config reload is not available".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{oracle::PriceSource, tests::config_mock::ConfigMockBuilder};
+
+    #[test]
+    fn test_keep_restart_required_settings_reverts_and_reports_changes() {
+        let current = ConfigMockBuilder::new().build();
+        let mut new = current.clone();
+        new.kora.rate_limit = current.kora.rate_limit + 1;
+        new.kora.auth.api_key = Some("new-key".to_string());
+        new.validation.price_source = PriceSource::Jupiter;
+        new.validation.max_allowed_lamports = current.validation.max_allowed_lamports + 1;
+
+        let restart_required = keep_restart_required_settings(&current, &mut new);
+
+        assert_eq!(
+            restart_required,
+            vec!["kora.rate_limit", "kora.auth", "validation.price_source"]
+        );
+        assert_eq!(new.kora.rate_limit, current.kora.rate_limit);
+        assert_eq!(new.kora.auth.api_key, current.kora.auth.api_key);
+        assert_eq!(new.validation.price_source, current.validation.price_source);
+        // Live settings keep their new value
+        assert_eq!(
+            new.validation.max_allowed_lamports,
+            current.validation.max_allowed_lamports + 1
+        );
+    }
+
+    #[test]
+    fn test_keep_restart_required_settings_unchanged() {
+        let current = ConfigMockBuilder::new().build();
+        let mut new = current.clone();
+
+        assert!(keep_restart_required_settings(&current, &mut new).is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{
+    error::KoraError,
+    sanitize_error,
+    signer::{
+        keystore::{read_passphrase_file, Keystore, KeystoreKdf},
+        KeypairUtil,
+    },
+};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use std::{fs, path::Path};
+
+/// Read a keystore passphrase from exactly one of an env var and a file
+pub fn read_passphrase(
+    passphrase_env: Option<&str>,
+    passphrase_file: Option<&Path>,
+) -> Result<String, KoraError> {
+    let passphrase = match (passphrase_env, passphrase_file) {
+        (Some(env), None) => std::env::var(env).map_err(|_| {
+            KoraError::ValidationError(format!("Environment variable '{env}' is not set"))
+        })?,
+        (None, Some(path)) => read_passphrase_file(path).map_err(|e| {
+            KoraError::ValidationError(format!(
+                "Failed to read passphrase file {}: {}",
+                path.display(),
+                sanitize_error!(e)
+            ))
+        })?,
+        _ => {
+            return Err(KoraError::ValidationError(
+                "Specify exactly one of --passphrase-env and --passphrase-file".to_string(),
+            ))
+        }
+    };
+
+    if passphrase.is_empty() {
+        return Err(KoraError::ValidationError(
+            "Keystore passphrase must not be empty".to_string(),
+        ));
+    }
+    Ok(passphrase)
+}
+
+/// Encrypt a Solana CLI JSON keypair file into a keystore for the `encrypted_keystore`
+/// signer. Returns the public key of the encrypted keypair.
+pub fn encrypt_keypair_file(
+    keypair_path: &Path,
+    output: &Path,
+    passphrase: &str,
+    kdf: KeystoreKdf,
+    force: bool,
+) -> Result<Pubkey, KoraError> {
+    check_output_writable(output, force)?;
+
+    let contents = fs::read_to_string(keypair_path).map_err(|e| {
+        KoraError::ValidationError(format!(
+            "Failed to read keypair file {}: {}",
+            keypair_path.display(),
+            sanitize_error!(e)
+        ))
+    })?;
+    let keypair = KeypairUtil::from_json_keypair(&contents)?;
+
+    save_keystore(&keypair, output, passphrase, kdf)?;
+    Ok(keypair.pubkey())
+}
+
+/// Generate a new keypair straight into a keystore for the `encrypted_keystore` signer,
+/// so the private key never touches the disk unencrypted. Returns its public key.
+pub fn generate_keystore(
+    output: &Path,
+    passphrase: &str,
+    kdf: KeystoreKdf,
+    force: bool,
+) -> Result<Pubkey, KoraError> {
+    check_output_writable(output, force)?;
+
+    let keypair = Keypair::new();
+    save_keystore(&keypair, output, passphrase, kdf)?;
+    Ok(keypair.pubkey())
+}
+
+fn check_output_writable(output: &Path, force: bool) -> Result<(), KoraError> {
+    if output.exists() && !force {
+        return Err(KoraError::ValidationError(format!(
+            "{} already exists, pass --force to overwrite it",
+            output.display()
+        )));
+    }
+    Ok(())
+}
+
+fn save_keystore(
+    keypair: &Keypair,
+    output: &Path,
+    passphrase: &str,
+    kdf: KeystoreKdf,
+) -> Result<(), KoraError> {
+    let keystore = Keystore::encrypt(keypair, passphrase, kdf)?;
+    // Check the keystore opens before reporting success
+    keystore.decrypt(passphrase)?;
+    keystore.save(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::{NamedTempFile, TempDir};
+
+    #[test]
+    fn test_encrypt_keypair_file() {
+        let keypair = Keypair::new();
+        let mut keypair_file = NamedTempFile::new().unwrap();
+        write!(keypair_file, "{:?}", keypair.to_bytes().to_vec()).unwrap();
+        let dir = TempDir::new().unwrap();
+        let output = dir.path().join("keystore.json");
+        let kdf = KeystoreKdf::Scrypt { log_n: 4, r: 8, p: 1 };
+
+        let pubkey =
+            encrypt_keypair_file(keypair_file.path(), &output, "passphrase", kdf.clone(), false)
+                .unwrap();
+        assert_eq!(pubkey, keypair.pubkey());
+        let decrypted = Keystore::load(&output).unwrap().decrypt("passphrase").unwrap();
+        assert_eq!(decrypted.to_bytes(), keypair.to_bytes());
+
+        // Existing keystores are only replaced with force
+        assert!(encrypt_keypair_file(keypair_file.path(), &output, "other", kdf.clone(), false)
+            .is_err());
+        assert!(encrypt_keypair_file(keypair_file.path(), &output, "other", kdf, true).is_ok());
+    }
+
+    #[test]
+    fn test_generate_keystore() {
+        let dir = TempDir::new().unwrap();
+        let output = dir.path().join("keystore.json");
+        let kdf = KeystoreKdf::Scrypt { log_n: 4, r: 8, p: 1 };
+
+        let pubkey = generate_keystore(&output, "passphrase", kdf.clone(), false).unwrap();
+        let decrypted = Keystore::load(&output).unwrap().decrypt("passphrase").unwrap();
+        assert_eq!(decrypted.pubkey(), pubkey);
+
+        assert!(generate_keystore(&output, "passphrase", kdf, false).is_err());
+    }
+
+    #[test]
+    fn test_read_passphrase_requires_one_source() {
+        assert!(read_passphrase(None, None).is_err());
+        assert!(read_passphrase(Some("ENV"), Some(Path::new("file"))).is_err());
+    }
+}
@@ -1 +1,5 @@
+pub mod config_reload;
+pub mod keystore_util;
+pub mod nonce_util;
+pub mod signers_util;
 pub mod token_util;
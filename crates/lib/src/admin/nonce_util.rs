@@ -0,0 +1,163 @@
+use crate::{
+    error::KoraError,
+    nonce::NoncePoolUtil,
+    state::{get_request_signer_with_signer_key, get_signer_pool},
+    transaction::TransactionUtil,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_keychain::SolanaSigner;
+use solana_message::{Message, VersionedMessage};
+use solana_nonce::state::State;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use solana_system_interface::instruction::create_nonce_account_with_seed;
+use std::sync::Arc;
+
+#[cfg(not(test))]
+use crate::state::get_config;
+
+#[cfg(test)]
+use crate::tests::config_mock::mock_state::get_config;
+
+/*
+This funciton is tested via the makefile, as it's a CLI command and requires a validator running.
+*/
+
+const DEFAULT_CHUNK_SIZE: usize = 5;
+
+/// Create the missing pool nonce accounts for the signers in the pool.
+/// Each signer funds its own nonce accounts and is their nonce authority.
+///
+/// If `signer_key` is provided, only that signer's nonce accounts are created.
+pub async fn initialize_nonce_accounts(
+    rpc_client: &RpcClient,
+    chunk_size: Option<usize>,
+    signer_key: Option<String>,
+) -> Result<(), KoraError> {
+    let config = get_config()?;
+    let nonce_pool_config = &config.kora.nonce_pool;
+
+    if !nonce_pool_config.enabled {
+        return Err(KoraError::ValidationError("Nonce pool is not enabled".to_string()));
+    }
+
+    let signers = match signer_key {
+        Some(signer_key) => vec![get_request_signer_with_signer_key(Some(&signer_key))?],
+        None => get_signer_pool()?
+            .get_signers_info()
+            .iter()
+            .map(|info| get_request_signer_with_signer_key(Some(&info.public_key)))
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+
+    let rent = rpc_client
+        .get_minimum_balance_for_rent_exemption(State::size())
+        .await
+        .map_err(|e| KoraError::RpcError(format!("Failed to get rent for nonce account: {e}")))?;
+
+    for signer in &signers {
+        let authority = signer.pubkey();
+        println!("Initializing nonce accounts for signer: {authority}");
+
+        let addresses = NoncePoolUtil::nonce_account_addresses(&authority, nonce_pool_config)?;
+        let accounts = rpc_client.get_multiple_accounts(&addresses).await.map_err(|e| {
+            KoraError::RpcError(format!("Failed to get nonce accounts for {authority}: {e}"))
+        })?;
+
+        let missing = addresses
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| accounts[*index].is_none())
+            .map(|(index, address)| (index as u32, *address))
+            .collect::<Vec<(u32, Pubkey)>>();
+
+        if missing.is_empty() {
+            println!("✓ All nonce accounts already exist for signer: {authority}");
+            continue;
+        }
+
+        create_nonce_accounts_for_signer(
+            rpc_client,
+            signer,
+            &missing,
+            rent,
+            chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE),
+        )
+        .await?;
+    }
+
+    println!("✓ Successfully created all nonce accounts");
+
+    Ok(())
+}
+
+/// Helper function to create nonce accounts for a single signer
+async fn create_nonce_accounts_for_signer(
+    rpc_client: &RpcClient,
+    signer: &Arc<crate::signer::Signer>,
+    nonce_accounts: &[(u32, Pubkey)],
+    rent: u64,
+    chunk_size: usize,
+) -> Result<(), KoraError> {
+    let authority = signer.pubkey();
+    let chunks: Vec<_> = nonce_accounts.chunks(chunk_size.max(1)).collect();
+    let num_chunks = chunks.len();
+
+    println!(
+        "Creating {} nonce accounts in {num_chunks} transaction(s) (chunk size: {chunk_size})...",
+        nonce_accounts.len()
+    );
+
+    for (chunk_idx, chunk) in chunks.iter().enumerate() {
+        let chunk_num = chunk_idx + 1;
+
+        let instructions = chunk
+            .iter()
+            .flat_map(|(index, address)| {
+                create_nonce_account_with_seed(
+                    &authority,
+                    address,
+                    &authority,
+                    &NoncePoolUtil::nonce_account_seed(*index),
+                    &authority,
+                    rent,
+                )
+            })
+            .collect::<Vec<Instruction>>();
+
+        let blockhash = rpc_client
+            .get_latest_blockhash()
+            .await
+            .map_err(|e| KoraError::RpcError(format!("Failed to get blockhash: {e}")))?;
+
+        let message = VersionedMessage::Legacy(Message::new_with_blockhash(
+            &instructions,
+            Some(&authority),
+            &blockhash,
+        ));
+        let mut tx = TransactionUtil::new_unsigned_versioned_transaction(message);
+
+        let signature = signer
+            .sign_message(&tx.message.serialize())
+            .await
+            .map_err(|e| KoraError::SigningError(e.to_string()))?;
+        tx.signatures = vec![signature];
+
+        match rpc_client.send_and_confirm_transaction_with_spinner(&tx).await {
+            Ok(signature) => {
+                println!(
+                    "✓ Chunk {chunk_num}/{num_chunks} successful. Transaction signature: {signature}"
+                );
+                chunk.iter().for_each(|(_, address)| println!("  - Nonce account {address}"));
+            }
+            Err(e) => {
+                println!("✗ Chunk {chunk_num}/{num_chunks} failed: {e}");
+                println!("Please re-run the command to retry nonce account creation.");
+                return Err(KoraError::RpcError(format!(
+                    "Failed to send nonce account creation transaction for chunk {chunk_num}/{num_chunks}: {e}"
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
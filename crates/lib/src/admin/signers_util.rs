@@ -0,0 +1,275 @@
+use crate::{
+    config::Config,
+    error::KoraError,
+    signer::{pool::DEFAULT_WEIGHT, SignerConfig, SignerPool, SignerPoolConfig, SolanaSigner},
+    token::token::TokenType,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer as _};
+use std::{
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// Message signed by `kora signers verify`
+const VERIFY_MESSAGE: &[u8] = b"kora signers verify";
+
+/// A `[[signers]]` entry of `signers.toml` with its resolved public key
+pub struct SignerListing {
+    pub name: String,
+    pub signer_type: &'static str,
+    pub weight: u32,
+    /// The signer's public key, or why its backend could not be built
+    pub pubkey: Result<Pubkey, KoraError>,
+}
+
+/// Balance of one payment token account of a signer
+pub struct TokenBalance {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    /// None when the token account does not exist
+    pub amount: Option<u64>,
+    pub decimals: u8,
+}
+
+pub struct SignerBalances {
+    pub name: String,
+    pub pubkey: Pubkey,
+    pub lamports: u64,
+    /// Accounts that receive this signer's token payments
+    pub tokens: Vec<TokenBalance>,
+}
+
+/// Outcome of signing a test message with one signer
+pub struct SignerVerification {
+    pub name: String,
+    pub pubkey: Option<Pubkey>,
+    /// Signing latency, or the error that prevented a valid signature
+    pub result: Result<Duration, KoraError>,
+}
+
+/// Build each configured signer to resolve its public key. A signer that fails to build
+/// is reported with its error instead of failing the whole listing.
+pub async fn list_signers(config: &SignerPoolConfig) -> Vec<SignerListing> {
+    let mut listings = Vec::with_capacity(config.signers.len());
+    for signer_config in &config.signers {
+        let pubkey = SignerConfig::build_signer_from_config(signer_config)
+            .await
+            .map(|signer| signer.pubkey());
+        listings.push(SignerListing {
+            name: signer_config.name.clone(),
+            signer_type: signer_config.config.type_name(),
+            weight: signer_config.weight.unwrap_or(DEFAULT_WEIGHT),
+            pubkey,
+        });
+    }
+    listings
+}
+
+/// SOL balance of each signer, and the balances of the payment token accounts it gets
+/// paid into for every token in `allowed_spl_paid_tokens`
+pub async fn get_signer_balances(
+    config: &Config,
+    rpc_client: &RpcClient,
+    signers: &[(String, Pubkey)],
+) -> Result<Vec<SignerBalances>, KoraError> {
+    let mints = (&config.validation.allowed_spl_paid_tokens)
+        .into_iter()
+        .map(|mint| {
+            Pubkey::from_str(mint)
+                .map_err(|_| KoraError::ValidationError(format!("Invalid token mint: {mint}")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mint_accounts = rpc_client
+        .get_multiple_accounts(&mints)
+        .await
+        .map_err(|e| KoraError::RpcError(format!("Failed to fetch payment token mints: {e}")))?;
+    let mut token_programs = Vec::with_capacity(mints.len());
+    for (mint, account) in mints.iter().zip(mint_accounts) {
+        let account = account.ok_or_else(|| {
+            KoraError::AccountNotFound(format!("Payment token mint {mint} not found"))
+        })?;
+        let token_program = TokenType::get_token_program_from_owner(&account.owner)?;
+        let decimals = token_program
+            .unpack_mint(mint, &account.data)
+            .map_err(|e| KoraError::InvalidTransaction(format!("Invalid mint {mint}: {e}")))?
+            .decimals();
+        token_programs.push((token_program, decimals));
+    }
+
+    let mut balances = Vec::with_capacity(signers.len());
+    for (name, pubkey) in signers {
+        let lamports = rpc_client.get_balance(pubkey).await.map_err(|e| {
+            KoraError::RpcError(format!("Failed to fetch balance of {pubkey}: {e}"))
+        })?;
+
+        let owner = config.kora.get_payment_address(pubkey)?;
+        let token_accounts: Vec<Pubkey> = mints
+            .iter()
+            .zip(&token_programs)
+            .map(|(mint, (token_program, _))| {
+                token_program.get_associated_token_address(&owner, mint)
+            })
+            .collect();
+        let accounts = rpc_client.get_multiple_accounts(&token_accounts).await.map_err(|e| {
+            KoraError::RpcError(format!("Failed to fetch token accounts of {owner}: {e}"))
+        })?;
+
+        let mut tokens = Vec::with_capacity(mints.len());
+        for (((mint, (token_program, decimals)), token_account), account) in
+            mints.iter().zip(&token_programs).zip(token_accounts).zip(accounts)
+        {
+            let amount = account
+                .map(|account| {
+                    token_program.unpack_token_account(&account.data).map(|state| state.amount())
+                })
+                .transpose()
+                .map_err(|e| {
+                    KoraError::InvalidTransaction(format!(
+                        "Invalid token account {token_account}: {e}"
+                    ))
+                })?;
+            tokens.push(TokenBalance { mint: *mint, token_account, amount, decimals: *decimals });
+        }
+
+        balances.push(SignerBalances { name: name.clone(), pubkey: *pubkey, lamports, tokens });
+    }
+
+    Ok(balances)
+}
+
+/// Initialize each signer backend through [`SignerPool::from_config`] and sign a test
+/// message with it, without starting the server. Each signer gets its own pool so one
+/// broken backend does not hide the others.
+pub async fn verify_signers(
+    config: &SignerPoolConfig,
+    sign_timeout: Duration,
+) -> Vec<SignerVerification> {
+    let mut verifications = Vec::with_capacity(config.signers.len());
+    for signer_config in &config.signers {
+        let single_signer = SignerPoolConfig {
+            signer_pool: config.signer_pool.clone(),
+            signers: vec![signer_config.clone()],
+            treasury: None,
+        };
+
+        let (pubkey, result) = match SignerPool::from_config(single_signer).await {
+            Ok(pool) => match pool.select_next_signer() {
+                Ok(signer) => {
+                    let pubkey = signer.pubkey();
+                    (Some(pubkey), sign_test_message(&pool, &signer, sign_timeout).await)
+                }
+                Err(e) => (None, Err(e)),
+            },
+            Err(e) => (None, Err(e)),
+        };
+
+        verifications.push(SignerVerification { name: signer_config.name.clone(), pubkey, result });
+    }
+    verifications
+}
+
+async fn sign_test_message(
+    pool: &SignerPool,
+    signer: &std::sync::Arc<crate::signer::Signer>,
+    sign_timeout: Duration,
+) -> Result<Duration, KoraError> {
+    let _permit = pool.acquire_sign_permit(signer, sign_timeout).await?;
+    let started = Instant::now();
+    let signature = tokio::time::timeout(sign_timeout, pool.sign_message(signer, VERIFY_MESSAGE))
+        .await
+        .map_err(|_| {
+            KoraError::SigningError(format!("Signing timed out after {}s", sign_timeout.as_secs()))
+        })??;
+    let latency = started.elapsed();
+
+    if !signature.verify(signer.pubkey().as_ref(), VERIFY_MESSAGE) {
+        return Err(KoraError::SigningError(
+            "Signature does not verify against the signer public key".to_string(),
+        ));
+    }
+    Ok(latency)
+}
+
+/// Generate a keypair for a `memory` signer. Returns its public key and the base58
+/// private key to set in the signer's `private_key_env`.
+pub fn generate_memory_keypair() -> (Pubkey, String) {
+    let keypair = Keypair::new();
+    (keypair.pubkey(), keypair.to_base58_string())
+}
+
+/// `amount` in whole units of a token with `decimals`
+pub fn format_token_amount(amount: u64, decimals: u8) -> String {
+    let scale = 10u128.pow(u32::from(decimals));
+    let (whole, fraction) = (u128::from(amount) / scale, u128::from(amount) % scale);
+    if decimals == 0 {
+        whole.to_string()
+    } else {
+        format!("{whole}.{fraction:0width$}", width = usize::from(decimals))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::{config::SignerPoolSettings, MemorySignerConfig, SignerTypeConfig};
+
+    fn memory_signer_config(name: &str, private_key_env: &str) -> SignerConfig {
+        SignerConfig {
+            name: name.to_string(),
+            weight: None,
+            target_balance: None,
+            refill_below: None,
+            policy: None,
+            max_concurrent_signs: None,
+            config: SignerTypeConfig::Memory {
+                config: MemorySignerConfig { private_key_env: private_key_env.to_string() },
+            },
+        }
+    }
+
+    fn pool_config(signers: Vec<SignerConfig>) -> SignerPoolConfig {
+        SignerPoolConfig {
+            signer_pool: SignerPoolSettings {
+                strategy: crate::signer::SelectionStrategy::RoundRobin,
+                min_balance_lamports: None,
+                slow_signer_p95_ms: None,
+                user_affinity: None,
+            },
+            signers,
+            treasury: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_and_verify_signers_report_broken_backends() {
+        let (pubkey, private_key) = generate_memory_keypair();
+        std::env::set_var("KORA_TEST_SIGNERS_UTIL_KEY", private_key);
+        let config = pool_config(vec![
+            memory_signer_config("working", "KORA_TEST_SIGNERS_UTIL_KEY"),
+            memory_signer_config("broken", "KORA_TEST_SIGNERS_UTIL_MISSING_KEY"),
+        ]);
+
+        let listings = list_signers(&config).await;
+        assert_eq!(listings[0].signer_type, "memory");
+        assert_eq!(listings[0].weight, DEFAULT_WEIGHT);
+        assert_eq!(listings[0].pubkey.as_ref().unwrap(), &pubkey);
+        assert!(listings[1].pubkey.is_err());
+
+        let verifications = verify_signers(&config, Duration::from_secs(5)).await;
+        assert_eq!(verifications[0].pubkey, Some(pubkey));
+        assert!(verifications[0].result.is_ok());
+        assert_eq!(verifications[1].pubkey, None);
+        assert!(verifications[1].result.is_err());
+
+        std::env::remove_var("KORA_TEST_SIGNERS_UTIL_KEY");
+    }
+
+    #[test]
+    fn test_format_token_amount() {
+        assert_eq!(format_token_amount(1_500_000, 6), "1.500000");
+        assert_eq!(format_token_amount(42, 9), "0.000000042");
+        assert_eq!(format_token_amount(7, 0), "7");
+    }
+}
@@ -3,7 +3,7 @@ use crate::{
     error::KoraError,
     state::{get_request_signer_with_signer_key, get_signer_pool},
     token::token::TokenType,
-    transaction::TransactionUtil,
+    transaction::{ComputeBudgetUtil, TransactionUtil},
     validator::cross_cluster::probe_missing_mints,
 };
 use solana_client::nonblocking::rpc_client::RpcClient;
@@ -89,7 +89,7 @@ pub async fn initialize_atas(
 /// This function does not use cache and directly checks on-chain
 pub async fn initialize_atas_with_chunk_size(
     rpc_client: &RpcClient,
-    fee_payer: &Arc<solana_keychain::Signer>,
+    fee_payer: &Arc<crate::signer::Signer>,
     addresses_to_initialize_atas: &Vec<Pubkey>,
     compute_unit_price: Option<u64>,
     compute_unit_limit: Option<u32>,
@@ -128,7 +128,7 @@ pub async fn initialize_atas_with_chunk_size(
 /// Helper function to create ATAs for a single signer
 async fn create_atas_for_signer(
     rpc_client: &RpcClient,
-    fee_payer: &Arc<solana_keychain::Signer>,
+    fee_payer: &Arc<crate::signer::Signer>,
     address: &Pubkey,
     atas_to_create: &[ATAToCreate],
     compute_unit_price: Option<u64>,
@@ -191,6 +191,17 @@ async fn create_atas_for_signer(
         ));
 
         let mut tx = TransactionUtil::new_unsigned_versioned_transaction(message);
+
+        // Explicit CLI values take precedence over automatic injection
+        let compute_budget_config = &get_config()?.kora.compute_budget;
+        if compute_budget_config.enabled
+            && compute_unit_price.is_none()
+            && compute_unit_limit.is_none()
+        {
+            ComputeBudgetUtil::inject_compute_budget(rpc_client, &mut tx, compute_budget_config)
+                .await?;
+        }
+
         let message_bytes = tx.message.serialize();
         let signature = fee_payer
             .sign_message(&message_bytes)
@@ -0,0 +1,354 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::{str::FromStr, sync::Arc};
+use utoipa::ToSchema;
+
+use crate::{
+    anchor::{decoder::DecodedAnchorInstruction, idl::LoadedIdl},
+    error::KoraError,
+};
+
+/// Anchor IDLs to decode instructions with, and constraints on the decoded values
+///
+/// Example TOML:
+/// ```toml
+/// [[validation.anchor.idls]]
+/// path = "idls/marketplace.json"
+///
+/// [[validation.anchor.constraints]]
+/// program_id = "Market1111111111111111111111111111111111111"
+/// instruction = "deposit"
+/// arg = "amount"
+/// max = 1000000
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct AnchorConfig {
+    pub idls: Vec<AnchorIdlConfig>,
+    pub constraints: Vec<AnchorConstraint>,
+    #[serde(skip)]
+    loaded_idls: Vec<Arc<LoadedIdl>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AnchorIdlConfig {
+    /// Path to the Anchor IDL JSON file, relative to the working directory
+    pub path: String,
+    /// Program the IDL describes. Defaults to the address recorded in the IDL.
+    #[serde(default)]
+    pub program_id: Option<String>,
+}
+
+/// A condition every decoded `instruction` of `program_id` must meet, top-level and inner
+/// instructions alike. Set exactly one of `arg` and `account`, and at least one check.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AnchorConstraint {
+    pub program_id: String,
+    /// Instruction name as written in the IDL
+    pub instruction: String,
+    /// Argument path: dot-separated field names and array indices, e.g. `params.amount`
+    #[serde(default)]
+    pub arg: Option<String>,
+    /// Account name from the instruction's accounts, `<group>.<name>` for nested ones
+    #[serde(default)]
+    pub account: Option<String>,
+    /// Smallest allowed value of an integer argument
+    #[serde(default)]
+    pub min: Option<i64>,
+    /// Largest allowed value of an integer argument
+    #[serde(default)]
+    pub max: Option<i64>,
+    /// Required value: a pubkey for accounts, the decimal or string form for arguments
+    #[serde(default)]
+    pub equals: Option<String>,
+    /// Allowed values, in the same form as `equals`
+    #[serde(default)]
+    pub one_of: Option<Vec<String>>,
+}
+
+impl AnchorConfig {
+    /// Load the IDL files. This should be called after deserialization, before
+    /// [`Self::loaded_idls`] is used.
+    pub fn initialize(&mut self) -> Result<(), KoraError> {
+        self.loaded_idls = self
+            .idls
+            .iter()
+            .map(|idl| LoadedIdl::load(&idl.path, idl.program_id.as_deref()).map(Arc::new))
+            .collect::<Result<_, _>>()?;
+        Ok(())
+    }
+
+    pub fn loaded_idls(&self) -> &[Arc<LoadedIdl>] {
+        &self.loaded_idls
+    }
+
+    pub fn loaded_idl(&self, program_id: &Pubkey) -> Option<&LoadedIdl> {
+        self.loaded_idls.iter().find(|idl| &idl.program_id == program_id).map(Arc::as_ref)
+    }
+
+    /// Check every constraint against the decoded instructions it applies to
+    pub fn check_constraints(
+        &self,
+        instructions: &[DecodedAnchorInstruction],
+    ) -> Result<(), KoraError> {
+        for constraint in &self.constraints {
+            for instruction in instructions {
+                if instruction.name == constraint.instruction
+                    && instruction.program_id.to_string() == constraint.program_id
+                {
+                    constraint.check(instruction)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Configuration errors: unknown programs, instructions, args or accounts, and
+    /// constraints without a target or a check
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        for constraint in &self.constraints {
+            let target = constraint.target();
+            let Some(idl) = Pubkey::from_str(&constraint.program_id)
+                .ok()
+                .and_then(|program_id| self.loaded_idl(&program_id))
+            else {
+                errors.push(format!(
+                    "Anchor constraint {target}: no IDL is loaded for program {}",
+                    constraint.program_id
+                ));
+                continue;
+            };
+            let Some(instruction) = idl.instruction(&constraint.instruction) else {
+                errors
+                    .push(format!("Anchor constraint {target}: instruction not found in the IDL"));
+                continue;
+            };
+
+            match (&constraint.arg, &constraint.account) {
+                (Some(arg), None) => {
+                    let top_level = arg.split('.').next().unwrap_or_default();
+                    if !instruction.args.iter().any(|idl_arg| idl_arg.name == top_level) {
+                        errors.push(format!(
+                            "Anchor constraint {target}: argument not found in the IDL"
+                        ));
+                    }
+                }
+                (None, Some(account)) => {
+                    if !instruction.account_names().contains(account) {
+                        errors.push(format!(
+                            "Anchor constraint {target}: account not found in the IDL"
+                        ));
+                    }
+                    if constraint.min.is_some() || constraint.max.is_some() {
+                        errors.push(format!(
+                            "Anchor constraint {target}: min and max only apply to arguments"
+                        ));
+                    }
+                }
+                _ => errors.push(format!(
+                    "Anchor constraint {target}: set exactly one of arg and account"
+                )),
+            }
+
+            if constraint.min.is_none()
+                && constraint.max.is_none()
+                && constraint.equals.is_none()
+                && constraint.one_of.is_none()
+            {
+                errors.push(format!(
+                    "Anchor constraint {target}: set at least one of min, max, equals and one_of"
+                ));
+            }
+        }
+        errors
+    }
+}
+
+impl AnchorConstraint {
+    /// `instruction.arg` or `instruction.account`, for messages
+    fn target(&self) -> String {
+        let field = self.arg.as_deref().or(self.account.as_deref()).unwrap_or_default();
+        format!("{}.{field}", self.instruction)
+    }
+
+    pub fn check(&self, instruction: &DecodedAnchorInstruction) -> Result<(), KoraError> {
+        let target = self.target();
+        let value = match (&self.arg, &self.account) {
+            (Some(arg), _) => {
+                let value = instruction.arg(arg).ok_or_else(|| {
+                    KoraError::InvalidTransaction(format!(
+                        "Anchor constraint {target}: argument not found"
+                    ))
+                })?;
+
+                if self.min.is_some() || self.max.is_some() {
+                    let integer = value.as_integer().ok_or_else(|| {
+                        KoraError::InvalidTransaction(format!(
+                            "Anchor constraint {target}: {value} is not an integer"
+                        ))
+                    })?;
+                    if let Some(min) = self.min.filter(|min| integer < i128::from(*min)) {
+                        return Err(KoraError::InvalidTransaction(format!(
+                            "Anchor constraint {target}: {integer} is below min {min}"
+                        )));
+                    }
+                    if let Some(max) = self.max.filter(|max| integer > i128::from(*max)) {
+                        return Err(KoraError::InvalidTransaction(format!(
+                            "Anchor constraint {target}: {integer} exceeds max {max}"
+                        )));
+                    }
+                }
+                value.to_string()
+            }
+            (None, Some(account)) => instruction
+                .account(account)
+                .ok_or_else(|| {
+                    KoraError::InvalidTransaction(format!(
+                        "Anchor constraint {target}: account not passed"
+                    ))
+                })?
+                .to_string(),
+            (None, None) => return Ok(()),
+        };
+
+        if let Some(expected) = self.equals.as_ref().filter(|expected| **expected != value) {
+            return Err(KoraError::InvalidTransaction(format!(
+                "Anchor constraint {target}: {value} does not equal {expected}"
+            )));
+        }
+        if let Some(allowed) = self.one_of.as_ref().filter(|allowed| !allowed.contains(&value)) {
+            return Err(KoraError::InvalidTransaction(format!(
+                "Anchor constraint {target}: {value} is not one of {allowed:?}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{anchor::AnchorDecoder, transaction::anchor_instruction_discriminator};
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn write_idl(program_id: &Pubkey) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"{{
+                "address": "{program_id}",
+                "instructions": [{{
+                    "name": "deposit",
+                    "accounts": [{{ "name": "vault" }}],
+                    "args": [{{ "name": "amount", "type": "u64" }}]
+                }}]
+            }}"#
+        )
+        .unwrap();
+        file
+    }
+
+    fn constraint(program_id: &Pubkey) -> AnchorConstraint {
+        AnchorConstraint {
+            program_id: program_id.to_string(),
+            instruction: "deposit".to_string(),
+            arg: None,
+            account: None,
+            min: None,
+            max: None,
+            equals: None,
+            one_of: None,
+        }
+    }
+
+    #[test]
+    fn test_check_constraints() {
+        let program_id = Pubkey::new_unique();
+        let vault = Pubkey::new_unique();
+        let file = write_idl(&program_id);
+        let mut config = AnchorConfig {
+            idls: vec![AnchorIdlConfig {
+                path: file.path().to_str().unwrap().to_string(),
+                program_id: None,
+            }],
+            constraints: vec![
+                AnchorConstraint {
+                    arg: Some("amount".to_string()),
+                    max: Some(1_000_000),
+                    ..constraint(&program_id)
+                },
+                AnchorConstraint {
+                    account: Some("vault".to_string()),
+                    equals: Some(vault.to_string()),
+                    ..constraint(&program_id)
+                },
+            ],
+            ..Default::default()
+        };
+        config.initialize().unwrap();
+        assert!(config.validate().is_empty());
+
+        let decode = |amount: u64, vault: Pubkey| {
+            let mut data = anchor_instruction_discriminator("deposit").to_vec();
+            data.extend_from_slice(&amount.to_le_bytes());
+            let instruction = Instruction::new_with_bytes(
+                program_id,
+                &data,
+                vec![AccountMeta::new(vault, false)],
+            );
+            AnchorDecoder::decode_instructions(config.loaded_idls(), &[instruction]).unwrap()
+        };
+
+        assert!(config.check_constraints(&decode(1_000_000, vault)).is_ok());
+
+        let err = config.check_constraints(&decode(1_000_001, vault)).unwrap_err();
+        assert!(err.to_string().contains("deposit.amount: 1000001 exceeds max 1000000"), "{err}");
+
+        let err = config.check_constraints(&decode(1, Pubkey::new_unique())).unwrap_err();
+        assert!(err.to_string().contains("does not equal"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_reports_unknown_targets() {
+        let program_id = Pubkey::new_unique();
+        let file = write_idl(&program_id);
+        let mut config = AnchorConfig {
+            idls: vec![AnchorIdlConfig {
+                path: file.path().to_str().unwrap().to_string(),
+                program_id: None,
+            }],
+            constraints: vec![
+                AnchorConstraint {
+                    arg: Some("fee".to_string()),
+                    max: Some(1),
+                    ..constraint(&program_id)
+                },
+                AnchorConstraint {
+                    account: Some("vault".to_string()),
+                    max: Some(1),
+                    ..constraint(&program_id)
+                },
+                AnchorConstraint { arg: Some("amount".to_string()), ..constraint(&program_id) },
+                AnchorConstraint {
+                    arg: Some("amount".to_string()),
+                    max: Some(1),
+                    ..constraint(&Pubkey::new_unique())
+                },
+            ],
+            ..Default::default()
+        };
+        config.initialize().unwrap();
+
+        let errors = config.validate();
+        assert_eq!(errors.len(), 4, "{errors:?}");
+        assert!(errors[0].contains("argument not found in the IDL"));
+        assert!(errors[1].contains("min and max only apply to arguments"));
+        assert!(errors[2].contains("set at least one of"));
+        assert!(errors[3].contains("no IDL is loaded"));
+    }
+}
@@ -0,0 +1,396 @@
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use std::fmt;
+
+use crate::{
+    anchor::idl::{IdlFields, IdlType, IdlTypeDefTy, LoadedIdl},
+    error::KoraError,
+};
+
+/// Nesting depth past which decoding gives up, so recursive IDL types can't overflow
+/// the stack
+const MAX_DECODE_DEPTH: usize = 32;
+
+/// A Borsh-decoded value of an Anchor instruction argument
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnchorValue {
+    Bool(bool),
+    Unsigned(u128),
+    Signed(i128),
+    Float(f64),
+    String(String),
+    Bytes(Vec<u8>),
+    Pubkey(Pubkey),
+    Option(Option<Box<AnchorValue>>),
+    Array(Vec<AnchorValue>),
+    /// Struct fields in declaration order. Tuple struct fields are named `0`, `1`, ...
+    Struct(Vec<(String, AnchorValue)>),
+    Enum {
+        variant: String,
+        fields: Vec<(String, AnchorValue)>,
+    },
+}
+
+impl AnchorValue {
+    /// The value at `path`: dot-separated struct or enum field names and array indices.
+    /// `Some` options are looked through.
+    pub fn get(&self, path: &str) -> Option<&AnchorValue> {
+        let mut value = self;
+        for segment in path.split('.') {
+            value = value.unwrap_option()?;
+            value = match value {
+                AnchorValue::Struct(fields) | AnchorValue::Enum { fields, .. } => {
+                    fields.iter().find(|(name, _)| name == segment).map(|(_, value)| value)?
+                }
+                AnchorValue::Array(items) => items.get(segment.parse::<usize>().ok()?)?,
+                _ => return None,
+            };
+        }
+        Some(value)
+    }
+
+    fn unwrap_option(&self) -> Option<&AnchorValue> {
+        match self {
+            AnchorValue::Option(value) => value.as_deref()?.unwrap_option(),
+            value => Some(value),
+        }
+    }
+
+    /// The value as an integer, None if it is not one. Unsigned values above `i128::MAX`
+    /// saturate.
+    pub fn as_integer(&self) -> Option<i128> {
+        match self.unwrap_option()? {
+            AnchorValue::Unsigned(value) => Some(i128::try_from(*value).unwrap_or(i128::MAX)),
+            AnchorValue::Signed(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AnchorValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnchorValue::Bool(value) => write!(f, "{value}"),
+            AnchorValue::Unsigned(value) => write!(f, "{value}"),
+            AnchorValue::Signed(value) => write!(f, "{value}"),
+            AnchorValue::Float(value) => write!(f, "{value}"),
+            AnchorValue::String(value) => write!(f, "{value}"),
+            AnchorValue::Bytes(value) => write!(f, "{}", hex::encode(value)),
+            AnchorValue::Pubkey(value) => write!(f, "{value}"),
+            AnchorValue::Option(None) => write!(f, "null"),
+            AnchorValue::Option(Some(value)) => write!(f, "{value}"),
+            value => write!(f, "{value:?}"),
+        }
+    }
+}
+
+/// An instruction decoded with the IDL of its program
+#[derive(Debug, Clone)]
+pub struct DecodedAnchorInstruction {
+    /// Index in `VersionedTransactionResolved::all_instructions`
+    pub instruction_index: usize,
+    pub program_id: Pubkey,
+    /// Instruction name as written in the IDL
+    pub name: String,
+    pub args: AnchorValue,
+    /// Account names (see [`IdlInstruction::account_names`](super::idl::IdlInstruction::account_names))
+    /// with the accounts passed for them
+    pub accounts: Vec<(String, Pubkey)>,
+}
+
+impl DecodedAnchorInstruction {
+    /// Argument at `path`, e.g. `amount` or `params.limits.0`
+    pub fn arg(&self, path: &str) -> Option<&AnchorValue> {
+        self.args.get(path)
+    }
+
+    pub fn account(&self, name: &str) -> Option<&Pubkey> {
+        self.accounts.iter().find(|(account, _)| account == name).map(|(_, pubkey)| pubkey)
+    }
+}
+
+pub struct AnchorDecoder {}
+
+impl AnchorDecoder {
+    /// Decode every instruction of a program with a loaded IDL. Instructions matching no
+    /// discriminator of their program's IDL are skipped.
+    pub fn decode_instructions(
+        idls: &[std::sync::Arc<LoadedIdl>],
+        instructions: &[Instruction],
+    ) -> Result<Vec<DecodedAnchorInstruction>, KoraError> {
+        let mut decoded = Vec::new();
+        for (instruction_index, instruction) in instructions.iter().enumerate() {
+            let Some(idl) = idls.iter().find(|idl| idl.program_id == instruction.program_id) else {
+                continue;
+            };
+            if let Some(mut instruction) = Self::decode_instruction(idl, instruction)? {
+                instruction.instruction_index = instruction_index;
+                decoded.push(instruction);
+            }
+        }
+        Ok(decoded)
+    }
+
+    pub fn decode_instruction(
+        idl: &LoadedIdl,
+        instruction: &Instruction,
+    ) -> Result<Option<DecodedAnchorInstruction>, KoraError> {
+        let Some((idl_instruction, discriminator_len)) = idl.find_instruction(&instruction.data)
+        else {
+            return Ok(None);
+        };
+
+        // Like Anchor, decode the args from the start of the data and ignore trailing bytes
+        let mut reader = Reader { idl, data: &instruction.data[discriminator_len..], depth: 0 };
+        let mut args = Vec::with_capacity(idl_instruction.args.len());
+        for arg in &idl_instruction.args {
+            let value = reader.read(&arg.ty).map_err(|e| {
+                KoraError::InvalidTransaction(format!(
+                    "Failed to decode argument '{}' of {} instruction {}: {e}",
+                    arg.name, idl.program_id, idl_instruction.name
+                ))
+            })?;
+            args.push((arg.name.clone(), value));
+        }
+
+        let accounts = idl_instruction
+            .account_names()
+            .into_iter()
+            .zip(instruction.accounts.iter().map(|meta| meta.pubkey))
+            .collect();
+
+        Ok(Some(DecodedAnchorInstruction {
+            instruction_index: 0,
+            program_id: instruction.program_id,
+            name: idl_instruction.name.clone(),
+            args: AnchorValue::Struct(args),
+            accounts,
+        }))
+    }
+}
+
+struct Reader<'a> {
+    idl: &'a LoadedIdl,
+    data: &'a [u8],
+    depth: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], String> {
+        if self.data.len() < len {
+            return Err("unexpected end of instruction data".to_string());
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let mut bytes = [0u8; N];
+        bytes.copy_from_slice(self.take(N)?);
+        Ok(bytes)
+    }
+
+    /// A `u32` length prefix, bounded by the remaining data so a forged length can't
+    /// trigger a huge allocation
+    fn take_len(&mut self) -> Result<usize, String> {
+        let len = u32::from_le_bytes(self.take_array()?) as usize;
+        if len > self.data.len() {
+            return Err(format!("length {len} exceeds the remaining instruction data"));
+        }
+        Ok(len)
+    }
+
+    fn read(&mut self, ty: &IdlType) -> Result<AnchorValue, String> {
+        if self.depth >= MAX_DECODE_DEPTH {
+            return Err("type nesting is too deep".to_string());
+        }
+        self.depth += 1;
+        let value = self.read_inner(ty);
+        self.depth -= 1;
+        value
+    }
+
+    fn read_inner(&mut self, ty: &IdlType) -> Result<AnchorValue, String> {
+        Ok(match ty {
+            IdlType::Bool => match self.take(1)?[0] {
+                0 => AnchorValue::Bool(false),
+                1 => AnchorValue::Bool(true),
+                tag => return Err(format!("invalid bool {tag}")),
+            },
+            IdlType::U8 => AnchorValue::Unsigned(u8::from_le_bytes(self.take_array()?).into()),
+            IdlType::U16 => AnchorValue::Unsigned(u16::from_le_bytes(self.take_array()?).into()),
+            IdlType::U32 => AnchorValue::Unsigned(u32::from_le_bytes(self.take_array()?).into()),
+            IdlType::U64 => AnchorValue::Unsigned(u64::from_le_bytes(self.take_array()?).into()),
+            IdlType::U128 => AnchorValue::Unsigned(u128::from_le_bytes(self.take_array()?)),
+            IdlType::I8 => AnchorValue::Signed(i8::from_le_bytes(self.take_array()?).into()),
+            IdlType::I16 => AnchorValue::Signed(i16::from_le_bytes(self.take_array()?).into()),
+            IdlType::I32 => AnchorValue::Signed(i32::from_le_bytes(self.take_array()?).into()),
+            IdlType::I64 => AnchorValue::Signed(i64::from_le_bytes(self.take_array()?).into()),
+            IdlType::I128 => AnchorValue::Signed(i128::from_le_bytes(self.take_array()?)),
+            IdlType::F32 => AnchorValue::Float(f32::from_le_bytes(self.take_array()?).into()),
+            IdlType::F64 => AnchorValue::Float(f64::from_le_bytes(self.take_array()?)),
+            IdlType::String => {
+                let len = self.take_len()?;
+                let bytes = self.take(len)?.to_vec();
+                AnchorValue::String(String::from_utf8(bytes).map_err(|e| e.to_string())?)
+            }
+            IdlType::Bytes => {
+                let len = self.take_len()?;
+                AnchorValue::Bytes(self.take(len)?.to_vec())
+            }
+            IdlType::Pubkey => AnchorValue::Pubkey(Pubkey::new_from_array(self.take_array()?)),
+            IdlType::Vec(item) => {
+                let len = self.take_len()?;
+                AnchorValue::Array((0..len).map(|_| self.read(item)).collect::<Result<_, _>>()?)
+            }
+            IdlType::Array(item, len) => {
+                AnchorValue::Array((0..*len).map(|_| self.read(item)).collect::<Result<_, _>>()?)
+            }
+            IdlType::Option(item) => match self.take(1)?[0] {
+                0 => AnchorValue::Option(None),
+                1 => AnchorValue::Option(Some(Box::new(self.read(item)?))),
+                tag => return Err(format!("invalid option tag {tag}")),
+            },
+            IdlType::COption(item) => match u32::from_le_bytes(self.take_array()?) {
+                0 => AnchorValue::Option(None),
+                1 => AnchorValue::Option(Some(Box::new(self.read(item)?))),
+                tag => return Err(format!("invalid coption tag {tag}")),
+            },
+            IdlType::Defined(defined) => {
+                let type_def = self
+                    .idl
+                    .type_def(defined.name())
+                    .ok_or_else(|| format!("type {} is not defined in the IDL", defined.name()))?;
+                match &type_def.ty {
+                    IdlTypeDefTy::Struct { fields } => {
+                        AnchorValue::Struct(self.read_fields(fields.as_ref())?)
+                    }
+                    IdlTypeDefTy::Enum { variants } => {
+                        let index = self.take(1)?[0] as usize;
+                        let variant = variants
+                            .get(index)
+                            .ok_or_else(|| format!("invalid {} variant {index}", type_def.name))?;
+                        AnchorValue::Enum {
+                            variant: variant.name.clone(),
+                            fields: self.read_fields(variant.fields.as_ref())?,
+                        }
+                    }
+                    IdlTypeDefTy::Type { alias } => self.read(alias)?,
+                }
+            }
+        })
+    }
+
+    fn read_fields(
+        &mut self,
+        fields: Option<&IdlFields>,
+    ) -> Result<Vec<(String, AnchorValue)>, String> {
+        match fields {
+            None => Ok(vec![]),
+            Some(IdlFields::Named(fields)) => {
+                fields.iter().map(|field| Ok((field.name.clone(), self.read(&field.ty)?))).collect()
+            }
+            Some(IdlFields::Tuple(types)) => types
+                .iter()
+                .enumerate()
+                .map(|(i, ty)| Ok((i.to_string(), self.read(ty)?)))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::anchor_instruction_discriminator;
+    use solana_sdk::instruction::AccountMeta;
+    use std::sync::Arc;
+
+    fn marketplace_idl(program_id: &Pubkey) -> LoadedIdl {
+        let discriminator = anchor_instruction_discriminator("deposit");
+        let json = format!(
+            r#"{{
+                "address": "{program_id}",
+                "instructions": [{{
+                    "name": "deposit",
+                    "discriminator": {discriminator:?},
+                    "accounts": [{{ "name": "vault", "writable": true }}, {{ "name": "user", "signer": true }}],
+                    "args": [
+                        {{ "name": "amount", "type": "u64" }},
+                        {{ "name": "params", "type": {{ "defined": {{ "name": "DepositParams" }} }} }}
+                    ]
+                }}],
+                "types": [
+                    {{
+                        "name": "DepositParams",
+                        "type": {{
+                            "kind": "struct",
+                            "fields": [
+                                {{ "name": "memo", "type": {{ "option": "string" }} }},
+                                {{ "name": "side", "type": {{ "defined": {{ "name": "Side" }} }} }},
+                                {{ "name": "limits", "type": {{ "array": ["i16", 2] }} }}
+                            ]
+                        }}
+                    }},
+                    {{
+                        "name": "Side",
+                        "type": {{ "kind": "enum", "variants": [{{ "name": "Bid" }}, {{ "name": "Ask" }}] }}
+                    }}
+                ]
+            }}"#
+        );
+        LoadedIdl::from_json(&json, None).unwrap()
+    }
+
+    fn deposit_data(amount: u64) -> Vec<u8> {
+        let mut data = anchor_instruction_discriminator("deposit").to_vec();
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&[1, 2, 0, 0, 0]);
+        data.extend_from_slice(b"hi");
+        data.push(1);
+        data.extend_from_slice(&(-5i16).to_le_bytes());
+        data.extend_from_slice(&7i16.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_decode_instruction_args_and_accounts() {
+        let program_id = Pubkey::new_unique();
+        let (vault, user) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let idl = Arc::new(marketplace_idl(&program_id));
+        let instructions = vec![
+            Instruction::new_with_bytes(Pubkey::new_unique(), &[1, 2, 3], vec![]),
+            Instruction::new_with_bytes(
+                program_id,
+                &deposit_data(1_500),
+                vec![AccountMeta::new(vault, false), AccountMeta::new_readonly(user, true)],
+            ),
+        ];
+
+        let decoded = AnchorDecoder::decode_instructions(&[idl], &instructions).unwrap();
+        assert_eq!(decoded.len(), 1);
+        let deposit = &decoded[0];
+        assert_eq!(deposit.instruction_index, 1);
+        assert_eq!(deposit.name, "deposit");
+        assert_eq!(deposit.arg("amount"), Some(&AnchorValue::Unsigned(1_500)));
+        assert_eq!(deposit.arg("params.memo").unwrap().to_string(), "hi");
+        assert_eq!(
+            deposit.arg("params.side"),
+            Some(&AnchorValue::Enum { variant: "Ask".to_string(), fields: vec![] })
+        );
+        assert_eq!(deposit.arg("params.limits.0").unwrap().as_integer(), Some(-5));
+        assert_eq!(deposit.account("vault"), Some(&vault));
+        assert_eq!(deposit.account("user"), Some(&user));
+    }
+
+    #[test]
+    fn test_decode_truncated_instruction_fails() {
+        let program_id = Pubkey::new_unique();
+        let idl = marketplace_idl(&program_id);
+        let data = anchor_instruction_discriminator("deposit").to_vec();
+        let instruction = Instruction::new_with_bytes(program_id, &data, vec![]);
+
+        let result = AnchorDecoder::decode_instruction(&idl, &instruction);
+        assert!(matches!(result, Err(KoraError::InvalidTransaction(_))));
+    }
+}
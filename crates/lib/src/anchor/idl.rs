@@ -0,0 +1,277 @@
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+use crate::{error::KoraError, transaction::anchor_instruction_discriminator};
+
+/// The parts of an Anchor IDL needed to decode instructions. Accepts both the current
+/// format (Anchor >= 0.30) and the legacy one, where discriminators are derived from the
+/// instruction name and the program address lives in `metadata`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnchorIdl {
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    metadata: Option<IdlMetadata>,
+    pub instructions: Vec<IdlInstruction>,
+    #[serde(default)]
+    pub types: Vec<IdlTypeDef>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct IdlMetadata {
+    #[serde(default)]
+    address: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdlInstruction {
+    pub name: String,
+    #[serde(default)]
+    pub discriminator: Option<Vec<u8>>,
+    #[serde(default)]
+    pub accounts: Vec<IdlAccountItem>,
+    #[serde(default)]
+    pub args: Vec<IdlField>,
+}
+
+/// A single account, or a group of accounts from a nested `Accounts` struct
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum IdlAccountItem {
+    Composite { name: String, accounts: Vec<IdlAccountItem> },
+    Single { name: String },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdlField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: IdlType,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdlType {
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    F32,
+    U64,
+    I64,
+    F64,
+    U128,
+    I128,
+    String,
+    Bytes,
+    #[serde(alias = "publicKey")]
+    Pubkey,
+    Vec(Box<IdlType>),
+    Option(Box<IdlType>),
+    COption(Box<IdlType>),
+    Array(Box<IdlType>, usize),
+    Defined(IdlDefined),
+}
+
+/// Reference to a type in `types`: `{"defined": "Name"}` (legacy) or
+/// `{"defined": {"name": "Name"}}`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum IdlDefined {
+    Name(String),
+    Object { name: String },
+}
+
+impl IdlDefined {
+    pub fn name(&self) -> &str {
+        match self {
+            IdlDefined::Name(name) | IdlDefined::Object { name } => name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdlTypeDef {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: IdlTypeDefTy,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum IdlTypeDefTy {
+    Struct {
+        #[serde(default)]
+        fields: Option<IdlFields>,
+    },
+    Enum {
+        variants: Vec<IdlEnumVariant>,
+    },
+    Type {
+        alias: IdlType,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum IdlFields {
+    Named(Vec<IdlField>),
+    Tuple(Vec<IdlType>),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdlEnumVariant {
+    pub name: String,
+    #[serde(default)]
+    pub fields: Option<IdlFields>,
+}
+
+/// An IDL bound to its program, with the discriminator of each instruction resolved
+#[derive(Debug, Clone)]
+pub struct LoadedIdl {
+    pub program_id: Pubkey,
+    pub idl: AnchorIdl,
+    /// Discriminator of `idl.instructions[i]` at index `i`
+    discriminators: Vec<Vec<u8>>,
+}
+
+impl LoadedIdl {
+    /// Parse `json`. `program_id` overrides the address recorded in the IDL.
+    pub fn from_json(json: &str, program_id: Option<&str>) -> Result<Self, KoraError> {
+        let idl: AnchorIdl = serde_json::from_str(json)
+            .map_err(|e| KoraError::ValidationError(format!("Invalid Anchor IDL: {e}")))?;
+
+        let address = program_id
+            .or(idl.address.as_deref())
+            .or(idl.metadata.as_ref().and_then(|metadata| metadata.address.as_deref()))
+            .ok_or_else(|| {
+                KoraError::ValidationError(
+                    "Anchor IDL has no program address, set program_id".to_string(),
+                )
+            })?;
+        let program_id = Pubkey::from_str(address).map_err(|e| {
+            KoraError::ValidationError(format!("Invalid Anchor IDL program address {address}: {e}"))
+        })?;
+
+        let discriminators = idl
+            .instructions
+            .iter()
+            .map(|instruction| match &instruction.discriminator {
+                Some(discriminator) => discriminator.clone(),
+                None => {
+                    anchor_instruction_discriminator(&to_snake_case(&instruction.name)).to_vec()
+                }
+            })
+            .collect();
+
+        Ok(Self { program_id, idl, discriminators })
+    }
+
+    /// Read and parse the IDL file at `path`
+    pub fn load(path: &str, program_id: Option<&str>) -> Result<Self, KoraError> {
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            KoraError::ValidationError(format!("Failed to read Anchor IDL {path}: {e}"))
+        })?;
+        Self::from_json(&json, program_id)
+            .map_err(|e| KoraError::ValidationError(format!("{path}: {e}")))
+    }
+
+    /// The instruction whose discriminator prefixes `data`, with the discriminator length
+    pub fn find_instruction(&self, data: &[u8]) -> Option<(&IdlInstruction, usize)> {
+        self.idl
+            .instructions
+            .iter()
+            .zip(&self.discriminators)
+            .find(|(_, discriminator)| !discriminator.is_empty() && data.starts_with(discriminator))
+            .map(|(instruction, discriminator)| (instruction, discriminator.len()))
+    }
+
+    pub fn instruction(&self, name: &str) -> Option<&IdlInstruction> {
+        self.idl.instructions.iter().find(|instruction| instruction.name == name)
+    }
+
+    pub fn type_def(&self, name: &str) -> Option<&IdlTypeDef> {
+        self.idl.types.iter().find(|type_def| type_def.name == name)
+    }
+}
+
+impl IdlInstruction {
+    /// Account names in account-meta order. Accounts of nested groups are named
+    /// `<group>.<account>`.
+    pub fn account_names(&self) -> Vec<String> {
+        fn flatten(items: &[IdlAccountItem], prefix: &str, names: &mut Vec<String>) {
+            for item in items {
+                match item {
+                    IdlAccountItem::Single { name } => names.push(format!("{prefix}{name}")),
+                    IdlAccountItem::Composite { name, accounts } => {
+                        flatten(accounts, &format!("{prefix}{name}."), names)
+                    }
+                }
+            }
+        }
+
+        let mut names = Vec::new();
+        flatten(&self.accounts, "", &mut names);
+        names
+    }
+}
+
+/// Legacy IDLs name instructions in camelCase, but the discriminator is derived from the
+/// snake_case Rust name
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_idl_derives_discriminators() {
+        let program_id = Pubkey::new_unique();
+        let json = format!(
+            r#"{{
+                "metadata": {{ "address": "{program_id}" }},
+                "instructions": [{{
+                    "name": "initializeVault",
+                    "accounts": [
+                        {{ "name": "vault", "isMut": true, "isSigner": false }},
+                        {{ "name": "common", "accounts": [{{ "name": "authority" }}] }}
+                    ],
+                    "args": [{{ "name": "owner", "type": "publicKey" }}]
+                }}]
+            }}"#
+        );
+
+        let idl = LoadedIdl::from_json(&json, None).unwrap();
+        assert_eq!(idl.program_id, program_id);
+
+        let data = anchor_instruction_discriminator("initialize_vault");
+        let (instruction, len) = idl.find_instruction(&data).unwrap();
+        assert_eq!(instruction.name, "initializeVault");
+        assert_eq!(len, 8);
+        assert_eq!(instruction.account_names(), vec!["vault", "common.authority"]);
+    }
+
+    #[test]
+    fn test_idl_without_address_requires_program_id() {
+        let json = r#"{ "instructions": [] }"#;
+        assert!(LoadedIdl::from_json(json, None).is_err());
+        assert!(LoadedIdl::from_json(json, Some(&Pubkey::new_unique().to_string())).is_ok());
+    }
+}
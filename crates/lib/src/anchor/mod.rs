@@ -0,0 +1,7 @@
+pub mod config;
+pub mod decoder;
+pub mod idl;
+
+pub use config::{AnchorConfig, AnchorConstraint, AnchorIdlConfig};
+pub use decoder::{AnchorDecoder, AnchorValue, DecodedAnchorInstruction};
+pub use idl::LoadedIdl;
@@ -6,6 +6,7 @@ use crate::{
     lighthouse::LighthouseUtil,
     plugin::{PluginExecutionContext, TransactionPluginRunner},
     signer::bundle_signer::BundleSigner,
+    state::config_for_signer,
     token::token::{PaymentLamportTotals, TokenUtil, TransferHookValidationFlow},
     transaction::{TransactionUtil, VersionedTransactionResolved},
     usage_limit::UsageTracker,
@@ -70,6 +71,20 @@ impl BundleProcessor {
         Ok((filtered, index_to_position))
     }
 
+    /// Programs invoked by the top-level instructions of any of the bundle's transactions
+    pub fn get_program_ids(encoded_txs: &[String]) -> Result<Vec<Pubkey>, KoraError> {
+        let mut program_ids = Vec::new();
+        for encoded in encoded_txs {
+            let transaction = TransactionUtil::decode_b64_transaction(encoded)?;
+            for program_id in TransactionUtil::get_program_ids(&transaction) {
+                if !program_ids.contains(&program_id) {
+                    program_ids.push(program_id);
+                }
+            }
+        }
+        Ok(program_ids)
+    }
+
     /// Merge signed transactions back into the original list, preserving order.
     /// `index_to_position` maps original transaction index -> position in signed_transactions vec.
     pub fn merge_signed_transactions(
@@ -99,6 +114,7 @@ impl BundleProcessor {
         plugin_context: Option<PluginExecutionContext>,
         processing_mode: BundleProcessingMode<'a>,
     ) -> Result<Self, KoraError> {
+        let config = &config_for_signer(config, &fee_payer);
         let validator = TransactionValidator::new(config, fee_payer)?;
         let plugin_runner = TransactionPluginRunner::from_config(config);
         let transfer_hook_validation_flow =
@@ -239,7 +255,7 @@ impl BundleProcessor {
 
     pub async fn sign_all(
         self,
-        signer: &Arc<solana_keychain::Signer>,
+        signer: &Arc<crate::signer::Signer>,
         fee_payer: &Pubkey,
         rpc_client: &RpcClient,
         config: &Config,
@@ -250,7 +266,7 @@ impl BundleProcessor {
 
     async fn sign_all_internal(
         mut self,
-        signer: &Arc<solana_keychain::Signer>,
+        signer: &Arc<crate::signer::Signer>,
         fee_payer: &Pubkey,
         rpc_client: &RpcClient,
         config: &Config,
@@ -6,6 +6,7 @@ use solana_commitment_config::CommitmentConfig;
 use solana_sdk::{account::Account, hash::Hash, pubkey::Pubkey};
 use std::{collections::HashMap, str::FromStr, sync::Arc, time::Duration};
 use tokio::sync::{Mutex, OnceCell};
+use utoipa::ToSchema;
 
 use crate::{
     config::Config,
@@ -27,6 +28,8 @@ use crate::tests::oracle_mock::get_price_oracle;
 const ACCOUNT_CACHE_KEY: &str = "account";
 const BLOCKHASH_CACHE_KEY: &str = "kora:blockhash";
 const PRICE_CACHE_KEY_PREFIX: &str = "kora:price";
+/// Keys per SCAN page and per DEL command when flushing
+const CACHE_FLUSH_BATCH_SIZE: usize = 500;
 /// TTL for cached blockhash in seconds. Blockhashes are valid for ~60s,
 /// but we use a short TTL to keep the hash fresh.
 const BLOCKHASH_TTL: u64 = 5;
@@ -59,6 +62,17 @@ pub struct CachedAccount {
     pub cached_at: i64, // Unix timestamp
 }
 
+/// Group of cache entries removed by [`CacheUtil::flush`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheFlushScope {
+    /// Every entry below, but nothing else stored in the same Redis
+    All,
+    Accounts,
+    Prices,
+    Blockhash,
+}
+
 /// Cache utility for Solana RPC calls
 pub struct CacheUtil;
 
@@ -217,6 +231,75 @@ impl CacheUtil {
         Ok(())
     }
 
+    /// Returns the shared Redis pool, or `None` when the cache is disabled or not initialized
+    pub(crate) fn get_pool() -> Option<Pool> {
+        CACHE_POOL.get().and_then(|pool| pool.clone())
+    }
+
+    /// Delete the cached entries in `scope` and return how many were removed. Only Kora's
+    /// cache keys are touched, so usage counters and other state sharing the Redis instance
+    /// survive.
+    pub async fn flush(scope: CacheFlushScope) -> Result<u64, KoraError> {
+        let pool = Self::get_pool()
+            .ok_or_else(|| KoraError::InvalidRequest("Cache is not enabled".to_string()))?;
+        let mut conn = Self::get_connection(&pool).await?;
+
+        let mut keys = Vec::new();
+        if matches!(scope, CacheFlushScope::All | CacheFlushScope::Accounts) {
+            keys.extend(Self::scan_keys(&mut conn, &format!("{ACCOUNT_CACHE_KEY}:*")).await?);
+        }
+        if matches!(scope, CacheFlushScope::All | CacheFlushScope::Prices) {
+            keys.extend(Self::scan_keys(&mut conn, &format!("{PRICE_CACHE_KEY_PREFIX}:*")).await?);
+        }
+        if matches!(scope, CacheFlushScope::All | CacheFlushScope::Blockhash) {
+            keys.push(BLOCKHASH_CACHE_KEY.to_string());
+        }
+
+        let mut deleted = 0;
+        for chunk in keys.chunks(CACHE_FLUSH_BATCH_SIZE) {
+            let removed: u64 = conn.del(chunk).await.map_err(|e| {
+                KoraError::InternalServerError(format!(
+                    "Failed to flush cache: {}",
+                    sanitize_error!(e)
+                ))
+            })?;
+            deleted += removed;
+        }
+
+        log::info!("Flushed {deleted} cache entries ({scope:?})");
+        Ok(deleted)
+    }
+
+    /// Collect keys matching `pattern` with SCAN, which unlike KEYS doesn't block Redis
+    async fn scan_keys(
+        conn: &mut deadpool_redis::Connection,
+        pattern: &str,
+    ) -> Result<Vec<String>, KoraError> {
+        let mut keys = Vec::new();
+        let mut cursor: u64 = 0;
+        loop {
+            let (next, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(pattern)
+                .arg("COUNT")
+                .arg(CACHE_FLUSH_BATCH_SIZE)
+                .query_async(conn)
+                .await
+                .map_err(|e| {
+                    KoraError::InternalServerError(format!(
+                        "Failed to scan cache keys: {}",
+                        sanitize_error!(e)
+                    ))
+                })?;
+            keys.extend(batch);
+            if next == 0 {
+                return Ok(keys);
+            }
+            cursor = next;
+        }
+    }
+
     /// Check if cache is enabled and available
     fn is_cache_enabled(config: &Config) -> bool {
         config.kora.cache.enabled && config.kora.cache.resolved_url().is_some()
@@ -883,6 +966,16 @@ mod tests {
         assert_ne!(jupiter_key, mock_key);
     }
 
+    #[tokio::test]
+    async fn test_flush_requires_cache() {
+        // The ignored Redis tests initialize the shared pool
+        if CacheUtil::get_pool().is_some() {
+            return;
+        }
+        let result = CacheUtil::flush(CacheFlushScope::All).await;
+        assert!(matches!(result, Err(KoraError::InvalidRequest(_))));
+    }
+
     #[tokio::test]
     async fn test_get_or_fetch_token_prices_empty_returns_empty() {
         let _m = ConfigMockBuilder::new().with_cache_enabled(false).build_and_setup();
@@ -1124,6 +1217,34 @@ mod tests {
             .unwrap();
     }
 
+    // Run with: KORA_REDIS_URL="redis://127.0.0.1:6379" cargo test -p kora-lib test_redis -- --include-ignored
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_flush_accounts_keeps_other_keys() {
+        let redis_url = env::var("KORA_REDIS_URL")
+            .expect("KORA_REDIS_URL must be set to run Redis integration tests");
+        let _m = ConfigMockBuilder::new()
+            .with_cache_enabled(true)
+            .with_cache_url(Some(redis_url))
+            .build_and_setup();
+
+        let _ = CacheUtil::init().await;
+
+        let pool = CACHE_POOL.get().unwrap().as_ref().unwrap();
+        let pubkey = Pubkey::new_unique();
+        let account = create_mock_token_account(&pubkey, &Pubkey::new_unique());
+        let cached = CachedAccount { account, cached_at: Utc::now().timestamp() };
+        let account_key = CacheUtil::get_account_key(&pubkey);
+        CacheUtil::set_in_cache(pool, &account_key, &cached, 60).await.unwrap();
+        CacheUtil::set_blockhash_in_cache(pool, &Hash::new_unique()).await.unwrap();
+
+        let deleted = CacheUtil::flush(CacheFlushScope::Accounts).await.unwrap();
+
+        assert!(deleted >= 1);
+        assert!(CacheUtil::get_from_cache(pool, &account_key).await.unwrap().is_none());
+        assert!(CacheUtil::get_blockhash_from_cache(pool).await.unwrap().is_some());
+    }
+
     // Run with: KORA_REDIS_URL="redis://127.0.0.1:6379" cargo test -p kora-lib test_redis -- --include-ignored
     #[tokio::test]
     #[ignore]
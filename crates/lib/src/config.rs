@@ -8,11 +8,17 @@ use utoipa::ToSchema;
 use crate::{
     bundle::JitoConfig,
     constant::{
-        DEFAULT_CACHE_ACCOUNT_TTL, DEFAULT_CACHE_DEFAULT_TTL, DEFAULT_CACHE_PRICE_TTL,
-        DEFAULT_FEE_PAYER_BALANCE_METRICS_EXPIRY_SECONDS, DEFAULT_MAX_REQUEST_BODY_SIZE,
-        DEFAULT_MAX_TIMESTAMP_AGE, DEFAULT_METRICS_ENDPOINT, DEFAULT_METRICS_PORT,
-        DEFAULT_METRICS_SCRAPE_INTERVAL, DEFAULT_PROTECTED_METHODS,
-        DEFAULT_RECAPTCHA_SCORE_THRESHOLD,
+        DEFAULT_ADMIN_BIND_ADDRESS, DEFAULT_ADMIN_PORT, DEFAULT_CACHE_ACCOUNT_TTL,
+        DEFAULT_CACHE_DEFAULT_TTL, DEFAULT_CACHE_PRICE_TTL,
+        DEFAULT_COMPUTE_UNIT_LIMIT_HEADROOM_PERCENT,
+        DEFAULT_FEE_PAYER_BALANCE_METRICS_EXPIRY_SECONDS, DEFAULT_MAX_BATCH_CONCURRENCY,
+        DEFAULT_MAX_BATCH_TRANSACTIONS, DEFAULT_MAX_COMPUTE_UNIT_PRICE,
+        DEFAULT_MAX_REQUEST_BODY_SIZE, DEFAULT_MAX_TIMESTAMP_AGE, DEFAULT_METRICS_ENDPOINT,
+        DEFAULT_METRICS_PORT, DEFAULT_METRICS_SCRAPE_INTERVAL, DEFAULT_NONCE_ACCOUNTS_PER_SIGNER,
+        DEFAULT_NONCE_LEASE_DURATION_SECONDS, DEFAULT_PRIORITY_FEE_PERCENTILE,
+        DEFAULT_PROTECTED_METHODS, DEFAULT_REBROADCAST_INTERVAL_MS,
+        DEFAULT_RECAPTCHA_SCORE_THRESHOLD, DEFAULT_RPC_MAX_CONSECUTIVE_ERRORS,
+        DEFAULT_RPC_REPROBE_INTERVAL_SECONDS, DEFAULT_RPC_SEND_FANOUT,
     },
     error::KoraError,
     fee::price::{PriceConfig, PriceModel},
@@ -22,11 +28,15 @@ use crate::{
         get_all_account_extension_names, get_all_mint_extension_names,
         parse_account_extension_string, parse_mint_extension_string,
     },
+    transaction::anchor_instruction_discriminator,
 };
 
 // Re-export usage limit configs
 pub use crate::usage_limit::{UsageLimitConfig, UsageLimitRuleConfig};
 
+// Re-export Anchor configs
+pub use crate::anchor::{AnchorConfig, AnchorConstraint, AnchorIdlConfig};
+
 #[derive(Clone, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Config {
@@ -130,6 +140,15 @@ pub enum ProgramsConfig {
     Allowlist(Vec<String>),
 }
 
+impl ProgramsConfig {
+    pub fn allows(&self, program_id: &Pubkey) -> bool {
+        match self {
+            ProgramsConfig::All => true,
+            ProgramsConfig::Allowlist(programs) => programs.contains(&program_id.to_string()),
+        }
+    }
+}
+
 impl utoipa::PartialSchema for ProgramsConfig {
     fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
         string_or_string_array_schema()
@@ -215,6 +234,20 @@ pub struct ValidationConfig {
     /// Default: empty (no restriction).
     #[serde(default)]
     pub require_one_of_programs: Vec<String>,
+    /// Per-program allowlists of instructions. Instructions of a program with a rule,
+    /// top-level or inner, must match one of its discriminators.
+    /// Default: empty (every instruction of an allowed program is accepted).
+    #[serde(default)]
+    pub program_rules: Vec<ProgramRule>,
+    /// Anchor IDLs to decode instructions with, and constraints on their arguments and accounts
+    #[serde(default)]
+    pub anchor: AnchorConfig,
+    /// Candidate validation config evaluated alongside this one without being enforced.
+    /// Transactions this config accepts but the candidate would reject are logged and
+    /// counted in `kora_shadow_validation_rejections_total` by rule, and are still signed.
+    #[serde(default)]
+    #[schema(no_recursion)]
+    pub shadow: Option<Box<ValidationConfig>>,
     /// When true, checks configured mint addresses against other known clusters
     /// and warns if a mint is found on a different cluster than the one connected.
     /// Disabled by default: the check contacts public RPC endpoints, which may be undesirable
@@ -225,6 +258,49 @@ pub struct ValidationConfig {
     pub cross_cluster_endpoints: Vec<String>,
 }
 
+/// Restricts a program to the instructions whose data starts with one of the allowed
+/// discriminators
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ProgramRule {
+    pub program_id: String,
+    /// Hex-encoded instruction data prefixes of any length, e.g. an 8-byte Anchor
+    /// discriminator (`"afaf6d1f0d989bed"`) or a 1-byte native instruction tag (`"03"`)
+    #[serde(default)]
+    pub allowed_discriminators: Vec<String>,
+    /// Anchor instruction names (e.g. `"initialize"`), allowed by their 8-byte discriminator
+    #[serde(default)]
+    pub allowed_anchor_instructions: Vec<String>,
+}
+
+impl ProgramRule {
+    /// Every instruction data prefix this rule allows
+    pub fn discriminators(&self) -> Result<Vec<Vec<u8>>, KoraError> {
+        let mut discriminators = Vec::with_capacity(
+            self.allowed_discriminators.len() + self.allowed_anchor_instructions.len(),
+        );
+        for discriminator in &self.allowed_discriminators {
+            let bytes = hex::decode(discriminator.trim_start_matches("0x")).map_err(|e| {
+                KoraError::ValidationError(format!(
+                    "Invalid discriminator '{discriminator}' for program {}: {e}",
+                    self.program_id
+                ))
+            })?;
+            if bytes.is_empty() {
+                return Err(KoraError::ValidationError(format!(
+                    "Empty discriminator for program {}",
+                    self.program_id
+                )));
+            }
+            discriminators.push(bytes);
+        }
+        for name in &self.allowed_anchor_instructions {
+            discriminators.push(anchor_instruction_discriminator(name).to_vec());
+        }
+        Ok(discriminators)
+    }
+}
+
 fn default_cross_cluster_endpoints() -> Vec<String> {
     vec![
         "https://api.mainnet-beta.solana.com".to_string(),
@@ -252,6 +328,7 @@ pub struct FeePayerPolicy {
     pub alt: AltInstructionPolicy,
     pub bpf_loader_upgradeable: BpfLoaderUpgradeableInstructionPolicy,
     pub loader_v4: LoaderV4InstructionPolicy,
+    pub stake: StakeInstructionPolicy,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
@@ -423,6 +500,36 @@ pub struct LoaderV4InstructionPolicy {
     pub allow_finalize: bool,
 }
 
+/// Stake program policy. The fee payer is in use when it is any authority or custodian of the
+/// instruction, including the staker, withdrawer or new authority set in the instruction data.
+/// Receiving withdrawn lamports is always allowed. Funding a stake account goes through the
+/// System program and is covered by `system.allow_create_account` and `system.allow_transfer`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct StakeInstructionPolicy {
+    /// Allow fee payer to be the staker, withdrawer or custodian in Initialize/InitializeChecked
+    /// instructions
+    pub allow_initialize: bool,
+    /// Allow fee payer to be the current or new authority in Authorize, AuthorizeChecked and
+    /// their WithSeed variants
+    pub allow_authorize: bool,
+    /// Allow fee payer to be the stake authority in DelegateStake/Redelegate instructions
+    pub allow_delegate: bool,
+    /// Allow fee payer to be the stake authority in Split instructions
+    pub allow_split: bool,
+    /// Allow fee payer to be the withdraw authority or custodian in Withdraw instructions
+    pub allow_withdraw: bool,
+    /// Allow fee payer to be the stake authority in Deactivate instructions
+    pub allow_deactivate: bool,
+    /// Allow fee payer to be the stake authority in Merge instructions
+    pub allow_merge: bool,
+    /// Allow fee payer to be the current or new custodian in SetLockup/SetLockupChecked
+    /// instructions
+    pub allow_set_lockup: bool,
+    /// Allow fee payer to be the stake authority in MoveStake/MoveLamports instructions
+    pub allow_move: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
 #[serde(default, deny_unknown_fields)]
 pub struct Token2022Config {
@@ -537,9 +644,16 @@ pub struct EnabledMethods {
     pub estimate_transaction_fee: bool,
     pub get_supported_tokens: bool,
     pub get_payer_signer: bool,
+    pub get_payment_instruction: bool,
+    pub get_transaction_status: bool,
+    /// Also enables releaseNonce
+    pub lease_nonce: bool,
     pub sign_transaction: bool,
+    pub sign_transactions: bool,
     pub sign_and_send_transaction: bool,
     pub transfer_transaction: bool,
+    pub validate_transaction: bool,
+    pub reload_config: bool,
     pub get_blockhash: bool,
     pub get_config: bool,
     pub get_version: bool,
@@ -556,9 +670,15 @@ impl EnabledMethods {
             self.estimate_transaction_fee,
             self.get_supported_tokens,
             self.get_payer_signer,
+            self.get_payment_instruction,
+            self.get_transaction_status,
+            self.lease_nonce,
             self.sign_transaction,
+            self.sign_transactions,
             self.sign_and_send_transaction,
             self.transfer_transaction,
+            self.validate_transaction,
+            self.reload_config,
             self.get_blockhash,
             self.get_config,
             self.get_version,
@@ -587,15 +707,34 @@ impl EnabledMethods {
         if self.get_payer_signer {
             methods.push("getPayerSigner".to_string());
         }
+        if self.get_payment_instruction {
+            methods.push("getPaymentInstruction".to_string());
+        }
+        if self.get_transaction_status {
+            methods.push("getTransactionStatus".to_string());
+        }
+        if self.lease_nonce {
+            methods.push("leaseNonce".to_string());
+            methods.push("releaseNonce".to_string());
+        }
         if self.sign_transaction {
             methods.push("signTransaction".to_string());
         }
+        if self.sign_transactions {
+            methods.push("signTransactions".to_string());
+        }
         if self.sign_and_send_transaction {
             methods.push("signAndSendTransaction".to_string());
         }
         if self.transfer_transaction {
             methods.push("transferTransaction".to_string());
         }
+        if self.validate_transaction {
+            methods.push("validateTransaction".to_string());
+        }
+        if self.reload_config {
+            methods.push("reloadConfig".to_string());
+        }
         if self.get_blockhash {
             methods.push("getBlockhash".to_string());
         }
@@ -617,7 +756,7 @@ impl EnabledMethods {
 
 impl IntoIterator for &EnabledMethods {
     type Item = bool;
-    type IntoIter = std::array::IntoIter<bool, 13>;
+    type IntoIter = std::array::IntoIter<bool, 19>;
 
     fn into_iter(self) -> Self::IntoIter {
         [
@@ -625,9 +764,15 @@ impl IntoIterator for &EnabledMethods {
             self.estimate_transaction_fee,
             self.get_supported_tokens,
             self.get_payer_signer,
+            self.get_payment_instruction,
+            self.get_transaction_status,
+            self.lease_nonce,
             self.sign_transaction,
+            self.sign_transactions,
             self.sign_and_send_transaction,
             self.transfer_transaction,
+            self.validate_transaction,
+            self.reload_config,
             self.get_blockhash,
             self.get_config,
             self.get_version,
@@ -646,9 +791,15 @@ impl Default for EnabledMethods {
             estimate_transaction_fee: true,
             get_supported_tokens: true,
             get_payer_signer: true,
+            get_payment_instruction: true,
+            get_transaction_status: true,
+            lease_nonce: false,
             sign_transaction: true,
+            sign_transactions: false,
             sign_and_send_transaction: true,
             transfer_transaction: true,
+            validate_transaction: true,
+            reload_config: false,
             get_blockhash: true,
             get_config: true,
             get_version: true,
@@ -727,6 +878,18 @@ pub struct KoraConfig {
     pub bundle: BundleConfig,
     /// Lighthouse configuration for fee payer balance protection
     pub lighthouse: LighthouseConfig,
+    /// Rebroadcast configuration for signAndSendTransaction
+    pub sender: SenderConfig,
+    /// Solana RPC endpoints with health-based failover
+    pub rpc: RpcConfig,
+    /// Automatic priority fee and compute unit limit injection
+    pub compute_budget: ComputeBudgetConfig,
+    /// Kora-owned durable nonce accounts leased to clients
+    pub nonce_pool: NoncePoolConfig,
+    /// Limits for batch signing with signTransactions
+    pub batch: BatchConfig,
+    /// Authenticated admin RPC server on its own port
+    pub admin: AdminConfig,
     /// When true, forces signature verification on all requests regardless of client's sig_verify parameter.
     /// Prevents TOCTOU attacks where simulation passes but on-chain execution differs.
     pub force_sig_verify: bool,
@@ -749,6 +912,12 @@ impl Default for KoraConfig {
             plugins: PluginsConfig::default(),
             bundle: BundleConfig::default(),
             lighthouse: LighthouseConfig::default(),
+            sender: SenderConfig::default(),
+            rpc: RpcConfig::default(),
+            compute_budget: ComputeBudgetConfig::default(),
+            nonce_pool: NoncePoolConfig::default(),
+            batch: BatchConfig::default(),
+            admin: AdminConfig::default(),
             force_sig_verify: false,
             sign_timeout_seconds: 10,
             sign_max_retries: 2,
@@ -783,6 +952,167 @@ impl Default for LighthouseConfig {
     }
 }
 
+/// Configuration for how signAndSendTransaction delivers transactions to the network
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct SenderConfig {
+    /// Keep re-sending the signed transaction until it is confirmed or its blockhash
+    /// expires. Applies to every `respond_after` mode.
+    pub rebroadcast_enabled: bool,
+    /// Delay between rebroadcasts in milliseconds
+    pub rebroadcast_interval_ms: u64,
+}
+
+impl Default for SenderConfig {
+    fn default() -> Self {
+        Self {
+            rebroadcast_enabled: false,
+            rebroadcast_interval_ms: DEFAULT_REBROADCAST_INTERVAL_MS,
+        }
+    }
+}
+
+/// Solana RPC endpoints used instead of `--rpc-url` when at least one is configured
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct RpcConfig {
+    /// Endpoints to fail over between. Empty means `--rpc-url` is used alone.
+    pub endpoints: Vec<RpcEndpointConfig>,
+    /// Number of healthy endpoints each sendTransaction is fanned out to
+    pub send_fanout: usize,
+    /// Consecutive transport errors after which an endpoint is ejected
+    pub max_consecutive_errors: u32,
+    /// Seconds an ejected endpoint waits before it is probed again
+    pub reprobe_interval_seconds: u64,
+}
+
+impl Default for RpcConfig {
+    fn default() -> Self {
+        Self {
+            endpoints: vec![],
+            send_fanout: DEFAULT_RPC_SEND_FANOUT,
+            max_consecutive_errors: DEFAULT_RPC_MAX_CONSECUTIVE_ERRORS,
+            reprobe_interval_seconds: DEFAULT_RPC_REPROBE_INTERVAL_SECONDS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RpcEndpointConfig {
+    pub url: String,
+    /// Lower values are preferred among healthy endpoints
+    #[serde(default)]
+    pub priority: u32,
+}
+
+/// Configuration for the ComputeBudget instructions Kora inserts into transactions
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct ComputeBudgetConfig {
+    /// Set the compute unit price and limit on transactions Kora builds, and let
+    /// signTransaction clients opt in with `inject_compute_budget`
+    pub enabled: bool,
+    /// Percentile of `getRecentPrioritizationFees` over the writable accounts used as
+    /// the compute unit price (0-100)
+    pub priority_fee_percentile: u8,
+    /// Upper bound for the injected compute unit price, in micro-lamports
+    pub max_compute_unit_price: u64,
+    /// Extra compute units added on top of the simulated consumption, in percent
+    pub compute_unit_limit_headroom_percent: u32,
+}
+
+impl Default for ComputeBudgetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            priority_fee_percentile: DEFAULT_PRIORITY_FEE_PERCENTILE,
+            max_compute_unit_price: DEFAULT_MAX_COMPUTE_UNIT_PRICE,
+            compute_unit_limit_headroom_percent: DEFAULT_COMPUTE_UNIT_LIMIT_HEADROOM_PERCENT,
+        }
+    }
+}
+
+/// Configuration for the pool of durable nonce accounts Kora leases to clients.
+///
+/// Each signer in the pool is the authority of `accounts_per_signer` nonce accounts at
+/// addresses derived from its pubkey (created with `kora rpc initialize-nonce-accounts`).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct NoncePoolConfig {
+    /// Enable nonce leasing. Advance instructions on a leased nonce are accepted even
+    /// when `validation.allow_durable_transactions` is false.
+    pub enabled: bool,
+    /// Number of nonce accounts per signer
+    pub accounts_per_signer: u32,
+    /// Seconds a lease is held before the nonce can be leased again
+    pub lease_duration_seconds: u64,
+}
+
+impl Default for NoncePoolConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            accounts_per_signer: DEFAULT_NONCE_ACCOUNTS_PER_SIGNER,
+            lease_duration_seconds: DEFAULT_NONCE_LEASE_DURATION_SECONDS,
+        }
+    }
+}
+
+/// Configuration for signTransactions. Each transaction in a batch is validated, limited
+/// and signed on its own, so one rejected transaction does not fail the others.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct BatchConfig {
+    /// Maximum number of transactions accepted in one signTransactions request
+    pub max_transactions: usize,
+    /// Maximum number of transactions of one request signed at the same time, so a single
+    /// batch can't hold every signer permit
+    pub max_concurrency: usize,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            max_transactions: DEFAULT_MAX_BATCH_TRANSACTIONS,
+            max_concurrency: DEFAULT_MAX_BATCH_CONCURRENCY,
+        }
+    }
+}
+
+/// Configuration for the admin RPC server (signer management, cache and usage
+/// maintenance, global pause). It listens on its own port and requires its own API key.
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct AdminConfig {
+    pub enabled: bool,
+    pub port: u16,
+    /// IP address the admin server listens on. Defaults to loopback; set `0.0.0.0` to expose
+    /// it beyond the host.
+    pub bind_address: String,
+    /// API key required in the `x-api-key` header. `KORA_ADMIN_API_KEY` takes precedence.
+    pub api_key: Option<String>,
+}
+
+impl Default for AdminConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_ADMIN_PORT,
+            bind_address: DEFAULT_ADMIN_BIND_ADDRESS.to_string(),
+            api_key: None,
+        }
+    }
+}
+
+impl AdminConfig {
+    pub(crate) const API_KEY_ENV: &'static str = "KORA_ADMIN_API_KEY";
+
+    pub(crate) fn resolved_api_key(&self) -> Option<String> {
+        AuthConfig::resolve_secret(Self::API_KEY_ENV, self.api_key.as_deref())
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, ToSchema)]
 #[serde(default, deny_unknown_fields)]
 pub struct AuthConfig {
@@ -887,6 +1217,29 @@ impl Config {
             ))
         })?;
 
+        // Load the Anchor IDLs so instructions can be decoded without touching the disk
+        config.validation.anchor.initialize().map_err(|e| {
+            KoraError::InternalServerError(format!(
+                "Failed to load Anchor IDLs: {}",
+                sanitize_error!(e)
+            ))
+        })?;
+
+        if let Some(shadow) = config.validation.shadow.as_deref_mut() {
+            shadow.token_2022.initialize().map_err(|e| {
+                KoraError::InternalServerError(format!(
+                    "Failed to initialize shadow Token2022 config: {}",
+                    sanitize_error!(e)
+                ))
+            })?;
+            shadow.anchor.initialize().map_err(|e| {
+                KoraError::InternalServerError(format!(
+                    "Failed to load shadow Anchor IDLs: {}",
+                    sanitize_error!(e)
+                ))
+            })?;
+        }
+
         Ok(config)
     }
 }
@@ -1015,6 +1368,73 @@ mod tests {
         assert_eq!(config.validation.allowed_programs, ProgramsConfig::All);
     }
 
+    #[test]
+    fn test_parse_program_rules_from_toml() {
+        let toml_content = r#"
+                            [validation]
+                            max_allowed_lamports = 1
+                            max_signatures = 1
+                            allowed_programs = "All"
+                            allowed_tokens = []
+                            allowed_spl_paid_tokens = []
+                            disallowed_accounts = []
+                            price_source = "Mock"
+
+                            [[validation.program_rules]]
+                            program_id = "11111111111111111111111111111111"
+                            allowed_discriminators = ["0x02000000"]
+                            allowed_anchor_instructions = ["initialize"]
+
+                            [kora]
+                            rate_limit = 1
+                            "#;
+        let config = crate::tests::toml_mock::create_invalid_config(toml_content)
+            .expect("TOML with program_rules should parse");
+        let discriminators = config.validation.program_rules[0].discriminators().unwrap();
+        assert_eq!(
+            discriminators,
+            vec![vec![2, 0, 0, 0], anchor_instruction_discriminator("initialize").to_vec()]
+        );
+    }
+
+    #[test]
+    fn test_parse_shadow_validation_config_from_toml() {
+        let toml_content = r#"
+                            [validation]
+                            max_allowed_lamports = 1
+                            max_signatures = 1
+                            allowed_programs = "All"
+                            allowed_tokens = []
+                            allowed_spl_paid_tokens = []
+                            disallowed_accounts = []
+                            price_source = "Mock"
+
+                            [validation.shadow]
+                            max_allowed_lamports = 1
+                            max_signatures = 1
+                            allowed_programs = ["11111111111111111111111111111111"]
+                            allowed_tokens = []
+                            allowed_spl_paid_tokens = []
+                            disallowed_accounts = []
+                            price_source = "Mock"
+
+                            [validation.shadow.fee_payer_policy.system]
+                            allow_transfer = false
+
+                            [kora]
+                            rate_limit = 1
+                            "#;
+        let config = crate::tests::toml_mock::create_invalid_config(toml_content)
+            .expect("TOML with a shadow validation config should parse");
+        let shadow = config.validation.shadow.expect("shadow config should be set");
+        assert_eq!(
+            shadow.allowed_programs,
+            ProgramsConfig::Allowlist(vec!["11111111111111111111111111111111".to_string()])
+        );
+        assert!(!shadow.fee_payer_policy.system.allow_transfer);
+        assert!(shadow.shadow.is_none());
+    }
+
     #[test]
     fn test_allowed_programs_json_serialization() {
         // ProgramsConfig::All must serialize as the string "All" — the user-facing
@@ -18,8 +18,13 @@ pub const DEFAULT_MAX_TIMESTAMP_AGE: i64 = 300;
 pub const MIN_RECAPTCHA_SCORE: f64 = 0.0;
 pub const MAX_RECAPTCHA_SCORE: f64 = 1.0;
 pub const DEFAULT_RECAPTCHA_SCORE_THRESHOLD: f64 = 0.5;
-pub const DEFAULT_PROTECTED_METHODS: &[&str] =
-    &["signTransaction", "signAndSendTransaction", "signBundle", "signAndSendBundle"];
+pub const DEFAULT_PROTECTED_METHODS: &[&str] = &[
+    "signTransaction",
+    "signTransactions",
+    "signAndSendTransaction",
+    "signBundle",
+    "signAndSendBundle",
+];
 
 // External Services
 pub const JUPITER_API_URL: &str = "https://api.jup.ag";
@@ -29,10 +34,12 @@ pub const RECAPTCHA_TIMEOUT_SECS: u64 = 5;
 // Lighthouse Program ID
 pub const LIGHTHOUSE_PROGRAM_ID: Pubkey = pubkey!("L2TExMFKdjpN9kozasaurPirfHy9P8sbXoAN1qA3S95");
 
-// High-risk native programs that have no fee-payer instruction parser in Kora.
-// These programs can directly control funds and should not be added to
-// `allowed_programs` without understanding the implications.
+// High-risk native program that has no fee-payer instruction parser in Kora.
+// It can directly control funds and should not be added to `allowed_programs`
+// without understanding the implications.
 pub const VOTE_PROGRAM_ID: Pubkey = pubkey!("Vote111111111111111111111111111111111111111");
+// Stake program. Parsed by the instruction parser registry; policy enforcement lives in
+// `StakeInstructionPolicy`.
 pub const STAKE_PROGRAM_ID: Pubkey = pubkey!("Stake11111111111111111111111111111111111111");
 pub const BPF_LOADER_UPGRADEABLE_PROGRAM_ID: Pubkey =
     pubkey!("BPFLoaderUpgradeab1e11111111111111111111111");
@@ -41,6 +48,13 @@ pub const BPF_LOADER_UPGRADEABLE_PROGRAM_ID: Pubkey =
 // in Kora; policy enforcement lives in `LoaderV4InstructionPolicy`.
 pub const LOADER_V4_PROGRAM_ID: Pubkey = pubkey!("LoaderV411111111111111111111111111111111111");
 
+// Programs with a built-in parser in the instruction parser registry
+pub const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+pub const MEMO_V1_PROGRAM_ID: Pubkey = pubkey!("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo");
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+pub const BUBBLEGUM_PROGRAM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+
 // Deploy registry (examples/devnet-deploy-paymaster/registry-program). The DeployAuthority
 // plugin gates loader mutations against this program's owner PDAs; gating is active only when
 // this id is in allowed_programs.
@@ -52,12 +66,59 @@ pub const DEFAULT_METRICS_ENDPOINT: &str = "/metrics";
 pub const DEFAULT_METRICS_PORT: u16 = 8080;
 pub const DEFAULT_METRICS_SCRAPE_INTERVAL: u64 = 60;
 
+// Admin
+pub const DEFAULT_ADMIN_PORT: u16 = 8081;
+pub const DEFAULT_ADMIN_BIND_ADDRESS: &str = "127.0.0.1";
+pub const DEFAULT_SIGNER_REMOVAL_GRACE_SECONDS: u64 = 300;
+
+// Signer latency tracking
+pub const SIGNER_LATENCY_WINDOW_SAMPLES: usize = 100;
+pub const SIGNER_LATENCY_WINDOW_SECONDS: u64 = 300;
+
+// Signer affinity
+pub const DEFAULT_USER_AFFINITY_TTL_SECONDS: u64 = 86400; // 24 hours
+
+// Treasury top-up
+pub const DEFAULT_TOP_UP_INTERVAL_SECONDS: u64 = 60;
+pub const TREASURY_TOP_UP_LOCK_SECONDS: u64 = 120; // Outlives a send and confirm
+
 // Cache
 pub const DEFAULT_CACHE_DEFAULT_TTL: u64 = 300; // 5 minutes
 pub const DEFAULT_CACHE_ACCOUNT_TTL: u64 = 60; // 1 minute for account data
 pub const DEFAULT_CACHE_PRICE_TTL: u64 = 0; // 0 disables price caching
+pub const TRANSACTION_STATUS_TTL: u64 = 3600; // 1 hour for sent transaction statuses
+pub const TRANSACTION_STATUS_PRUNE_INTERVAL: u64 = 1024; // In-memory status writes between evictions
 pub const DEFAULT_FEE_PAYER_BALANCE_METRICS_EXPIRY_SECONDS: u64 = 30; // 30 seconds
 
+// Sender
+pub const DEFAULT_REBROADCAST_INTERVAL_MS: u64 = 2000;
+
+// RPC endpoints
+pub const DEFAULT_RPC_SEND_FANOUT: usize = 1;
+pub const DEFAULT_RPC_MAX_CONSECUTIVE_ERRORS: u32 = 3;
+pub const DEFAULT_RPC_REPROBE_INTERVAL_SECONDS: u64 = 30;
+
+// Compute budget
+pub const DEFAULT_PRIORITY_FEE_PERCENTILE: u8 = 75;
+pub const DEFAULT_MAX_COMPUTE_UNIT_PRICE: u64 = 1_000_000; // micro-lamports per CU
+pub const DEFAULT_COMPUTE_UNIT_LIMIT_HEADROOM_PERCENT: u32 = 10;
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+// Nonce pool
+pub const DEFAULT_NONCE_ACCOUNTS_PER_SIGNER: u32 = 10;
+pub const DEFAULT_NONCE_LEASE_DURATION_SECONDS: u64 = 3600; // 1 hour
+pub const NONCE_ACCOUNT_SEED_PREFIX: &str = "kora-nonce";
+pub const NONCE_ADVANCE_LEASE_SECONDS: u64 = 30; // Holds a dirty nonce while Kora advances it
+
+// Batch signing
+pub const DEFAULT_MAX_BATCH_TRANSACTIONS: usize = 25;
+pub const DEFAULT_MAX_BATCH_CONCURRENCY: usize = 4;
+
+// Idempotency
+pub const IDEMPOTENCY_RESPONSE_TTL: u64 = 86400; // 24 hours for completed responses
+pub const IDEMPOTENCY_IN_PROGRESS_TTL: u64 = 300; // 5 minutes for requests still being processed
+pub const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 256;
+
 pub const DEFAULT_USAGE_LIMIT_MAX_TRANSACTIONS: u64 = 0; // 0 = unlimited
 pub const DEFAULT_USAGE_LIMIT_FALLBACK_IF_UNAVAILABLE: bool = false;
 
@@ -18,6 +18,9 @@ pub enum KoraError {
     #[error("Signing error: {0}")]
     SigningError(String),
 
+    #[error("Signer busy: {0}")]
+    SignerBusy(String),
+
     #[error("Invalid transaction: {0}")]
     InvalidTransaction(String),
 
@@ -138,6 +141,7 @@ pub enum KoraErrorCode {
 
     // Signing errors (-32020 to -32029)
     SigningError = -32020,
+    SignerBusy = -32021,
 
     // Auth / Rate limiting (-32030 to -32039)
     RateLimitExceeded = -32030,
@@ -173,6 +177,7 @@ impl KoraError {
             KoraError::FeeEstimationFailed(_) => KoraErrorCode::FeeEstimationFailed,
             KoraError::TransactionExecutionFailed(_) => KoraErrorCode::TransactionExecutionFailed,
             KoraError::SigningError(_) => KoraErrorCode::SigningError,
+            KoraError::SignerBusy(_) => KoraErrorCode::SignerBusy,
             KoraError::RateLimitExceeded => KoraErrorCode::RateLimitExceeded,
             KoraError::UsageLimitExceeded(_) => KoraErrorCode::UsageLimitExceeded,
             KoraError::Unauthorized(_) => KoraErrorCode::Unauthorized,
@@ -409,6 +414,7 @@ mod tests {
             (KoraError::FeeEstimationFailed("test".to_string()), -32005),
             (KoraError::TransactionExecutionFailed("test".to_string()), -32006),
             (KoraError::SigningError("test".to_string()), -32020),
+            (KoraError::SignerBusy("test".to_string()), -32021),
             (KoraError::RateLimitExceeded, -32030),
             (KoraError::UsageLimitExceeded("test".to_string()), -32031),
             (KoraError::Unauthorized("test".to_string()), -32032),
@@ -457,6 +463,7 @@ mod tests {
             KoraError::AccountNotFound("".into()),
             KoraError::RpcError("".into()),
             KoraError::SigningError("".into()),
+            KoraError::SignerBusy("".into()),
             KoraError::InvalidTransaction("".into()),
             KoraError::TransactionExecutionFailed("".into()),
             KoraError::FeeEstimationFailed("".into()),
@@ -207,7 +207,7 @@ impl FeeConfigUtil {
         let base_fee =
             TransactionFeeUtil::get_estimate_fee_resolved(rpc_client, transaction).await?;
 
-        // Priority fees are now included in the calculate done by the RPC getFeeForMessage
+        // Priority fees, including a compute budget injected by Kora, are now included in the calculate done by the RPC getFeeForMessage
         // ATA and Token account creation fees are captured in the calculate fee payer outflow (System Transfer)
 
         // If the Kora signer is not inclded in the signers, we add another base fee, since each transaction will be 5000 lamports
@@ -0,0 +1,8 @@
+pub mod replay;
+pub mod store;
+
+pub use replay::IdempotencyUtil;
+pub use store::{
+    get_idempotency_store, init_idempotency_store, IdempotencyRecord, IdempotencyStore,
+    InMemoryIdempotencyStore, RedisIdempotencyStore,
+};
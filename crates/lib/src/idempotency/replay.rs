@@ -0,0 +1,382 @@
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use std::{cell::RefCell, future::Future};
+
+use crate::{
+    constant::{IDEMPOTENCY_IN_PROGRESS_TTL, IDEMPOTENCY_RESPONSE_TTL, MAX_IDEMPOTENCY_KEY_LENGTH},
+    error::KoraError,
+    idempotency::store::{get_idempotency_store, IdempotencyRecord},
+    rpc_server::auth::authenticated_client_id,
+};
+
+tokio::task_local! {
+    /// Signature of the transaction an idempotent request is about to broadcast
+    static BROADCAST_SIGNATURE: RefCell<Option<String>>;
+}
+
+pub struct IdempotencyUtil;
+
+impl IdempotencyUtil {
+    /// Hash of the method and its transaction payload, used to reject a key that is
+    /// reused for a different request
+    pub fn fingerprint(method: &str, transactions: &[String]) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(method.as_bytes());
+        for transaction in transactions {
+            hasher.update([0]);
+            hasher.update(transaction.as_bytes());
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    /// Keys are scoped per API key the request authenticated with, and per user ID when
+    /// one is given. Hashed so that no part can collide with another through a separator.
+    fn store_key(user_id: Option<&str>, idempotency_key: &str) -> String {
+        let mut hasher = Sha256::new();
+        match authenticated_client_id() {
+            Some(client_id) => {
+                hasher.update(b"client\0");
+                hasher.update(client_id.as_bytes());
+            }
+            None => hasher.update(b"no_api_key"),
+        }
+        hasher.update([0]);
+        match user_id {
+            Some(user_id) => {
+                hasher.update(b"user\0");
+                hasher.update(user_id.as_bytes());
+            }
+            None => hasher.update(b"no_user"),
+        }
+        hasher.update([0]);
+        hasher.update(idempotency_key.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    /// Marks the current idempotent request as past the point of no return: `signature`
+    /// is about to be broadcast, so a failure from here on is recorded instead of letting
+    /// a retry sign, send and charge it again. No-op outside [`Self::run_once`].
+    pub fn record_broadcast(signature: &str) {
+        let _ = BROADCAST_SIGNATURE.try_with(|broadcast| {
+            *broadcast.borrow_mut() = Some(signature.to_string());
+        });
+    }
+
+    fn validate_key(idempotency_key: &str) -> Result<(), KoraError> {
+        if idempotency_key.is_empty() {
+            return Err(KoraError::InvalidRequest("idempotency_key must not be empty".to_string()));
+        }
+        if idempotency_key.len() > MAX_IDEMPOTENCY_KEY_LENGTH {
+            return Err(KoraError::InvalidRequest(format!(
+                "idempotency_key must be at most {MAX_IDEMPOTENCY_KEY_LENGTH} characters"
+            )));
+        }
+        Ok(())
+    }
+
+    fn replay<T: DeserializeOwned>(
+        record: IdempotencyRecord,
+        fingerprint: &str,
+    ) -> Result<T, KoraError> {
+        if record.fingerprint != fingerprint {
+            return Err(KoraError::InvalidRequest(
+                "idempotency_key was already used for a different request".to_string(),
+            ));
+        }
+
+        match (record.response, record.signature) {
+            (Some(response), _) => serde_json::from_value(response).map_err(|e| {
+                KoraError::SerializationError(format!(
+                    "Failed to deserialize idempotent response: {e}"
+                ))
+            }),
+            (None, Some(signature)) => Err(KoraError::InvalidRequest(format!(
+                "A request with this idempotency_key already broadcast transaction {signature} \
+                 and failed: {}. Check its status with getTransactionStatus",
+                record.error.unwrap_or_default()
+            ))),
+            (None, None) => Err(KoraError::InvalidRequest(
+                "A request with this idempotency_key is still being processed".to_string(),
+            )),
+        }
+    }
+
+    /// Run `request` at most once per (user or API key, idempotency key) within
+    /// [`IDEMPOTENCY_RESPONSE_TTL`]. Retries get the original response back without
+    /// the request being signed, sent or counted against usage limits again.
+    ///
+    /// Requests that fail before their transaction is broadcast are not recorded, so they
+    /// can be retried with the same key. Once [`Self::record_broadcast`] was called, a
+    /// failure is recorded with the signature and replayed as an error.
+    pub async fn run_once<T, F>(
+        idempotency_key: Option<&str>,
+        user_id: Option<&str>,
+        fingerprint: String,
+        request: F,
+    ) -> Result<T, KoraError>
+    where
+        T: Serialize + DeserializeOwned,
+        F: Future<Output = Result<T, KoraError>>,
+    {
+        let Some(idempotency_key) = idempotency_key else {
+            return request.await;
+        };
+        Self::validate_key(idempotency_key)?;
+
+        let store = get_idempotency_store();
+        let key = Self::store_key(user_id, idempotency_key);
+        let in_progress = IdempotencyRecord::in_progress(fingerprint.clone());
+
+        if !store.try_reserve(&key, &in_progress, IDEMPOTENCY_IN_PROGRESS_TTL).await? {
+            // The record can expire between the reserve and the get, treat it as in flight
+            let record = store.get(&key).await?.unwrap_or(in_progress);
+            return Self::replay(record, &fingerprint);
+        }
+
+        let (result, broadcast_signature) = BROADCAST_SIGNATURE
+            .scope(RefCell::new(None), async {
+                let result = request.await;
+                (result, BROADCAST_SIGNATURE.with(|broadcast| broadcast.borrow().clone()))
+            })
+            .await;
+
+        let outcome = match (&result, broadcast_signature) {
+            (Ok(response), _) => serde_json::to_value(response)
+                .map(|response| IdempotencyRecord {
+                    response: Some(response),
+                    ..IdempotencyRecord::in_progress(fingerprint)
+                })
+                .map_err(|e| KoraError::SerializationError(e.to_string())),
+            // The transaction may land, so a retry must not sign and send it again
+            (Err(e), Some(signature)) => Ok(IdempotencyRecord {
+                signature: Some(signature),
+                error: Some(e.to_string()),
+                ..IdempotencyRecord::in_progress(fingerprint)
+            }),
+            (Err(_), None) => {
+                if let Err(e) = store.remove(&key).await {
+                    log::warn!("Failed to release idempotency key: {e}");
+                }
+                return result;
+            }
+        };
+
+        let stored = match outcome {
+            Ok(record) => store.set(&key, &record, IDEMPOTENCY_RESPONSE_TTL).await,
+            Err(e) => Err(e),
+        };
+        // The request already went through, a retry will see it as still in progress
+        if let Err(e) = stored {
+            log::warn!("Failed to store idempotent response: {e}");
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Response {
+        signature: String,
+    }
+
+    async fn respond(
+        calls: &AtomicUsize,
+        result: Result<(), KoraError>,
+    ) -> Result<Response, KoraError> {
+        let call = calls.fetch_add(1, Ordering::SeqCst);
+        result.map(|_| Response { signature: format!("sig-{call}") })
+    }
+
+    fn unique_key() -> String {
+        solana_sdk::pubkey::Pubkey::new_unique().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_run_once_replays_original_response() {
+        let calls = AtomicUsize::new(0);
+        let key = unique_key();
+        let fingerprint = IdempotencyUtil::fingerprint("method", &["tx".to_string()]);
+
+        let first = IdempotencyUtil::run_once(
+            Some(&key),
+            None,
+            fingerprint.clone(),
+            respond(&calls, Ok(())),
+        )
+        .await
+        .unwrap();
+        let retry =
+            IdempotencyUtil::run_once(Some(&key), None, fingerprint, respond(&calls, Ok(())))
+                .await
+                .unwrap();
+
+        assert_eq!(first, retry);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_once_without_key_always_runs() {
+        let calls = AtomicUsize::new(0);
+        let fingerprint = IdempotencyUtil::fingerprint("method", &["tx".to_string()]);
+
+        for _ in 0..2 {
+            IdempotencyUtil::run_once(None, None, fingerprint.clone(), respond(&calls, Ok(())))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_run_once_scopes_keys_per_user() {
+        let calls = AtomicUsize::new(0);
+        let key = unique_key();
+        let fingerprint = IdempotencyUtil::fingerprint("method", &["tx".to_string()]);
+
+        for user_id in [Some("alice"), Some("bob"), None] {
+            IdempotencyUtil::run_once(
+                Some(&key),
+                user_id,
+                fingerprint.clone(),
+                respond(&calls, Ok(())),
+            )
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_run_once_rejects_key_reused_for_different_request() {
+        let calls = AtomicUsize::new(0);
+        let key = unique_key();
+
+        IdempotencyUtil::run_once(
+            Some(&key),
+            None,
+            IdempotencyUtil::fingerprint("method", &["tx".to_string()]),
+            respond(&calls, Ok(())),
+        )
+        .await
+        .unwrap();
+        let result = IdempotencyUtil::run_once(
+            Some(&key),
+            None,
+            IdempotencyUtil::fingerprint("method", &["other_tx".to_string()]),
+            respond(&calls, Ok(())),
+        )
+        .await;
+
+        assert!(matches!(result, Err(KoraError::InvalidRequest(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_once_failed_request_can_be_retried() {
+        let calls = AtomicUsize::new(0);
+        let key = unique_key();
+        let fingerprint = IdempotencyUtil::fingerprint("method", &["tx".to_string()]);
+
+        let failed = IdempotencyUtil::run_once(
+            Some(&key),
+            None,
+            fingerprint.clone(),
+            respond(&calls, Err(KoraError::RpcError("unavailable".to_string()))),
+        )
+        .await;
+        assert!(failed.is_err());
+
+        let retry =
+            IdempotencyUtil::run_once(Some(&key), None, fingerprint, respond(&calls, Ok(())))
+                .await
+                .unwrap();
+
+        assert_eq!(retry.signature, "sig-1");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_run_once_rejects_concurrent_duplicate() {
+        let key = unique_key();
+        let fingerprint = IdempotencyUtil::fingerprint("method", &["tx".to_string()]);
+        let in_progress = IdempotencyRecord::in_progress(fingerprint.clone());
+        get_idempotency_store()
+            .try_reserve(&IdempotencyUtil::store_key(None, &key), &in_progress, 60)
+            .await
+            .unwrap();
+
+        let calls = AtomicUsize::new(0);
+        let result =
+            IdempotencyUtil::run_once(Some(&key), None, fingerprint, respond(&calls, Ok(()))).await;
+
+        assert!(matches!(result, Err(KoraError::InvalidRequest(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_run_once_rejects_invalid_key() {
+        let calls = AtomicUsize::new(0);
+        let fingerprint = IdempotencyUtil::fingerprint("method", &["tx".to_string()]);
+        let too_long = "k".repeat(MAX_IDEMPOTENCY_KEY_LENGTH + 1);
+
+        for key in ["", too_long.as_str()] {
+            let result = IdempotencyUtil::run_once(
+                Some(key),
+                None,
+                fingerprint.clone(),
+                respond(&calls, Ok(())),
+            )
+            .await;
+            assert!(matches!(result, Err(KoraError::InvalidRequest(_))));
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_run_once_records_failure_after_broadcast() {
+        let calls = AtomicUsize::new(0);
+        let key = unique_key();
+        let fingerprint = IdempotencyUtil::fingerprint("method", &["tx".to_string()]);
+
+        let failed = IdempotencyUtil::run_once(Some(&key), None, fingerprint.clone(), async {
+            IdempotencyUtil::record_broadcast("sig-0");
+            respond(&calls, Err(KoraError::RpcError("confirmation timed out".to_string()))).await
+        })
+        .await;
+        assert!(matches!(failed, Err(KoraError::RpcError(_))));
+
+        let retry =
+            IdempotencyUtil::run_once(Some(&key), None, fingerprint, respond(&calls, Ok(()))).await;
+
+        assert!(matches!(
+            retry,
+            Err(KoraError::InvalidRequest(message))
+                if message.contains("sig-0") && message.contains("confirmation timed out")
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_store_key_is_scoped_per_api_key() {
+        use crate::rpc_server::auth::with_authenticated_api_key;
+
+        let anonymous = IdempotencyUtil::store_key(None, "key");
+        let first =
+            with_authenticated_api_key("first-api-key", || IdempotencyUtil::store_key(None, "key"));
+        let second = with_authenticated_api_key("second-api-key", || {
+            IdempotencyUtil::store_key(None, "key")
+        });
+
+        assert_ne!(anonymous, first);
+        assert_ne!(first, second);
+    }
+}
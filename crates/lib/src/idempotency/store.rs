@@ -0,0 +1,296 @@
+use async_trait::async_trait;
+use deadpool_redis::{Connection, Pool};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
+
+use crate::{cache::CacheUtil, error::KoraError, sanitize_error};
+
+const IDEMPOTENCY_KEY_PREFIX: &str = "kora:idempotency";
+
+/// Global idempotency store. Falls back to an in-memory store when
+/// [`init_idempotency_store`] was never called (e.g. in unit tests).
+static IDEMPOTENCY_STORE: once_cell::sync::OnceCell<Arc<dyn IdempotencyStore>> =
+    once_cell::sync::OnceCell::new();
+
+/// A request recorded under an idempotency key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdempotencyRecord {
+    /// Hash of the request payload, so a key can't be reused for a different request
+    pub fingerprint: String,
+    /// Serialized response, None while the original request is still being processed
+    pub response: Option<serde_json::Value>,
+    /// Transaction broadcast by a request that then failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Error of a request that failed after broadcasting `signature`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl IdempotencyRecord {
+    /// Record of a request that is still being processed
+    pub fn in_progress(fingerprint: String) -> Self {
+        Self { fingerprint, response: None, signature: None, error: None }
+    }
+}
+
+/// Trait for storing idempotency records. Expired records are dropped by the store itself.
+#[async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    /// Store the record unless the key is already taken.
+    /// Returns false when a record already exists for the key.
+    async fn try_reserve(
+        &self,
+        key: &str,
+        record: &IdempotencyRecord,
+        ttl_seconds: u64,
+    ) -> Result<bool, KoraError>;
+
+    /// Store the record, replacing any existing one
+    async fn set(
+        &self,
+        key: &str,
+        record: &IdempotencyRecord,
+        ttl_seconds: u64,
+    ) -> Result<(), KoraError>;
+
+    /// Get the record for a key (returns None if unknown or expired)
+    async fn get(&self, key: &str) -> Result<Option<IdempotencyRecord>, KoraError>;
+
+    /// Remove the record for a key
+    async fn remove(&self, key: &str) -> Result<(), KoraError>;
+}
+
+fn serialize_record(record: &IdempotencyRecord) -> Result<String, KoraError> {
+    serde_json::to_string(record).map_err(|e| {
+        KoraError::SerializationError(format!("Failed to serialize idempotency record: {e}"))
+    })
+}
+
+/// Redis-based implementation, shared across Kora instances
+pub struct RedisIdempotencyStore {
+    pool: Pool,
+}
+
+impl RedisIdempotencyStore {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn get_key(key: &str) -> String {
+        format!("{IDEMPOTENCY_KEY_PREFIX}:{key}")
+    }
+
+    async fn get_connection(&self) -> Result<Connection, KoraError> {
+        self.pool.get().await.map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to get Redis connection: {}",
+                e
+            )))
+        })
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for RedisIdempotencyStore {
+    async fn try_reserve(
+        &self,
+        key: &str,
+        record: &IdempotencyRecord,
+        ttl_seconds: u64,
+    ) -> Result<bool, KoraError> {
+        let mut conn = self.get_connection().await?;
+
+        // SET NX EX: only one instance can process a given key at a time
+        let reserved: Option<String> = redis::cmd("SET")
+            .arg(Self::get_key(key))
+            .arg(serialize_record(record)?)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl_seconds)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| {
+                KoraError::InternalServerError(sanitize_error!(format!(
+                    "Failed to reserve idempotency key: {}",
+                    e
+                )))
+            })?;
+
+        Ok(reserved.is_some())
+    }
+
+    async fn set(
+        &self,
+        key: &str,
+        record: &IdempotencyRecord,
+        ttl_seconds: u64,
+    ) -> Result<(), KoraError> {
+        let mut conn = self.get_connection().await?;
+
+        let serialized = serialize_record(record)?;
+
+        let _: () =
+            conn.set_ex(Self::get_key(key), serialized, ttl_seconds).await.map_err(|e| {
+                KoraError::InternalServerError(sanitize_error!(format!(
+                    "Failed to store idempotency record: {}",
+                    e
+                )))
+            })?;
+
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<IdempotencyRecord>, KoraError> {
+        let mut conn = self.get_connection().await?;
+        let data: Option<String> = conn.get(Self::get_key(key)).await.map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to get idempotency record: {}",
+                e
+            )))
+        })?;
+
+        data.map(|data| {
+            serde_json::from_str(&data).map_err(|e| {
+                KoraError::SerializationError(format!(
+                    "Failed to deserialize idempotency record: {e}"
+                ))
+            })
+        })
+        .transpose()
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), KoraError> {
+        let mut conn = self.get_connection().await?;
+
+        let _: () = conn.del(Self::get_key(key)).await.map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to remove idempotency record: {}",
+                e
+            )))
+        })?;
+
+        Ok(())
+    }
+}
+
+/// In-memory implementation, local to this Kora instance
+#[derive(Default)]
+pub struct InMemoryIdempotencyStore {
+    data: Mutex<HashMap<String, (IdempotencyRecord, i64)>>,
+}
+
+impl InMemoryIdempotencyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for InMemoryIdempotencyStore {
+    async fn try_reserve(
+        &self,
+        key: &str,
+        record: &IdempotencyRecord,
+        ttl_seconds: u64,
+    ) -> Result<bool, KoraError> {
+        let now = chrono::Utc::now().timestamp();
+        let mut data = self.data.lock().await;
+
+        // Evict expired records on write so the map stays bounded by the TTL window
+        data.retain(|_, (_, expires_at)| *expires_at > now);
+        if data.contains_key(key) {
+            return Ok(false);
+        }
+
+        data.insert(key.to_string(), (record.clone(), now + ttl_seconds as i64));
+        Ok(true)
+    }
+
+    async fn set(
+        &self,
+        key: &str,
+        record: &IdempotencyRecord,
+        ttl_seconds: u64,
+    ) -> Result<(), KoraError> {
+        let now = chrono::Utc::now().timestamp();
+        let mut data = self.data.lock().await;
+
+        data.insert(key.to_string(), (record.clone(), now + ttl_seconds as i64));
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<IdempotencyRecord>, KoraError> {
+        let now = chrono::Utc::now().timestamp();
+        let data = self.data.lock().await;
+
+        Ok(data
+            .get(key)
+            .filter(|(_, expires_at)| *expires_at > now)
+            .map(|(record, _)| record.clone()))
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), KoraError> {
+        self.data.lock().await.remove(key);
+        Ok(())
+    }
+}
+
+/// Initialize the global idempotency store. Uses Redis when the cache is enabled,
+/// otherwise an in-memory store. Must run after [`CacheUtil::init`].
+pub fn init_idempotency_store() -> Result<(), KoraError> {
+    let (store, backend): (Arc<dyn IdempotencyStore>, &str) = match CacheUtil::get_pool() {
+        Some(pool) => (Arc::new(RedisIdempotencyStore::new(pool)), "Redis"),
+        None => (Arc::new(InMemoryIdempotencyStore::new()), "in-memory"),
+    };
+
+    IDEMPOTENCY_STORE.set(store).map_err(|_| {
+        KoraError::InternalServerError("Idempotency store already initialized".to_string())
+    })?;
+
+    log::info!("Idempotency store initialized ({backend})");
+    Ok(())
+}
+
+/// Returns the global idempotency store
+pub fn get_idempotency_store() -> Arc<dyn IdempotencyStore> {
+    IDEMPOTENCY_STORE.get_or_init(|| Arc::new(InMemoryIdempotencyStore::new())).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn record(response: Option<serde_json::Value>) -> IdempotencyRecord {
+        IdempotencyRecord { response, ..IdempotencyRecord::in_progress("fingerprint".to_string()) }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_reserve_is_exclusive() {
+        let store = InMemoryIdempotencyStore::new();
+
+        assert!(store.try_reserve("key", &record(None), 60).await.unwrap());
+        assert!(!store.try_reserve("key", &record(None), 60).await.unwrap());
+        assert!(store.try_reserve("other", &record(None), 60).await.unwrap());
+
+        let completed = record(Some(json!({ "signature": "sig" })));
+        store.set("key", &completed, 60).await.unwrap();
+        assert_eq!(store.get("key").await.unwrap(), Some(completed));
+
+        store.remove("key").await.unwrap();
+        assert_eq!(store.get("key").await.unwrap(), None);
+        assert!(store.try_reserve("key", &record(None), 60).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_expired_record() {
+        let store = InMemoryIdempotencyStore::new();
+        store.data.lock().await.insert("key".to_string(), (record(None), 0));
+
+        assert_eq!(store.get("key").await.unwrap(), None);
+        assert!(store.try_reserve("key", &record(None), 60).await.unwrap());
+    }
+}
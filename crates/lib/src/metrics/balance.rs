@@ -2,7 +2,12 @@
 use crate::state::get_config;
 #[cfg(test)]
 use crate::tests::config_mock::mock_state::get_config;
-use crate::{cache::CacheUtil, config::Config, error::KoraError, state::get_signers_info};
+use crate::{
+    cache::CacheUtil,
+    config::Config,
+    error::KoraError,
+    state::{get_signer_pool, get_signers_info},
+};
 use prometheus::{register_gauge_vec, GaugeVec};
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
@@ -49,7 +54,8 @@ impl BalanceTracker {
         Ok(())
     }
 
-    /// Track all signers' balances and update Prometheus metrics
+    /// Track all signers' balances, update Prometheus metrics and hand the balances to the
+    /// signer pool for balance-aware selection
     pub async fn track_all_signer_balances(
         config: &Config,
         rpc_client: &Arc<RpcClient>,
@@ -75,6 +81,11 @@ impl BalanceTracker {
 
                 match CacheUtil::get_account(config, rpc_client, &pubkey, false).await {
                     Ok(account) => {
+                        // Only successful reads reach the pool, a failed poll must not make
+                        // a funded signer look drained
+                        if let Ok(pool) = get_signer_pool() {
+                            pool.record_signer_balance(&pubkey, account.lamports);
+                        }
                         balance_results.push((signer_info, account.lamports));
                     }
                     Err(e) => {
@@ -164,7 +175,7 @@ mod tests {
     use super::*;
     use crate::{
         config::FeePayerBalanceMetricsConfig,
-        signer::{pool::SignerWithMetadata, SignerPool},
+        signer::{pool::SignerWithMetadata, Signer, SignerPool},
         state::update_signer_pool,
         tests::{
             account_mock::create_mock_account_with_balance,
@@ -172,7 +183,6 @@ mod tests {
             config_mock::{ConfigMockBuilder, MetricsConfigBuilder},
         },
     };
-    use solana_keychain::Signer;
     use solana_sdk::signature::Keypair;
 
     fn setup_test_signer_pool() {
@@ -1,11 +1,17 @@
 pub mod balance;
 pub mod handler;
 pub mod middleware;
+pub mod sender;
+pub mod shadow;
+pub mod signer;
 
 pub use balance::BalanceTracker;
 pub use handler::{MetricsHandlerLayer, MetricsHandlerService};
 pub use middleware::{HttpMetricsLayer, HttpMetricsService};
 pub use prometheus;
+pub use sender::SenderMetrics;
+pub use shadow::ShadowMetrics;
+pub use signer::SignerMetrics;
 use solana_client::nonblocking::rpc_client::RpcClient;
 use tokio::task::JoinHandle;
 
@@ -0,0 +1,57 @@
+use prometheus::{HistogramOpts, HistogramVec};
+use std::sync::OnceLock;
+
+static SENDER_METRICS: OnceLock<SenderMetrics> = OnceLock::new();
+
+/// Metrics for the signAndSendTransaction rebroadcast loop
+pub struct SenderMetrics {
+    pub send_attempts: HistogramVec,
+    pub landing_latency_seconds: HistogramVec,
+}
+
+impl SenderMetrics {
+    fn new() -> Self {
+        let send_attempts = HistogramVec::new(
+            HistogramOpts::new(
+                "transaction_send_attempts",
+                "Number of broadcasts per transaction until it landed or expired",
+            )
+            .namespace("kora")
+            .buckets(vec![1.0, 2.0, 3.0, 5.0, 10.0, 20.0, 50.0, 100.0]),
+            &["respond_after", "outcome"],
+        )
+        .unwrap_or_else(|e| {
+            log::error!("Failed to create transaction_send_attempts metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        let landing_latency_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "transaction_landing_latency_seconds",
+                "Time from first broadcast until the transaction was confirmed",
+            )
+            .namespace("kora")
+            .buckets(vec![0.5, 1.0, 2.0, 5.0, 10.0, 20.0, 30.0, 60.0, 90.0]),
+            &["respond_after"],
+        )
+        .unwrap_or_else(|e| {
+            log::error!("Failed to create transaction_landing_latency_seconds metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        prometheus::register(Box::new(send_attempts.clone())).unwrap_or_else(|e| {
+            log::error!("Failed to register transaction_send_attempts metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+        prometheus::register(Box::new(landing_latency_seconds.clone())).unwrap_or_else(|e| {
+            log::error!("Failed to register transaction_landing_latency_seconds metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        Self { send_attempts, landing_latency_seconds }
+    }
+
+    pub fn get() -> &'static SenderMetrics {
+        SENDER_METRICS.get_or_init(SenderMetrics::new)
+    }
+}
@@ -0,0 +1,37 @@
+use prometheus::{IntCounterVec, Opts};
+use std::sync::OnceLock;
+
+static SHADOW_METRICS: OnceLock<ShadowMetrics> = OnceLock::new();
+
+/// Metrics for the candidate config in `validation.shadow`
+pub struct ShadowMetrics {
+    pub rejections: IntCounterVec,
+}
+
+impl ShadowMetrics {
+    fn new() -> Self {
+        let rejections = IntCounterVec::new(
+            Opts::new(
+                "shadow_validation_rejections_total",
+                "Transactions the shadow validation config would reject, by rule",
+            )
+            .namespace("kora"),
+            &["rule"],
+        )
+        .unwrap_or_else(|e| {
+            log::error!("Failed to create shadow_validation_rejections_total metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        prometheus::register(Box::new(rejections.clone())).unwrap_or_else(|e| {
+            log::error!("Failed to register shadow_validation_rejections_total metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        Self { rejections }
+    }
+
+    pub fn get() -> &'static ShadowMetrics {
+        SHADOW_METRICS.get_or_init(ShadowMetrics::new)
+    }
+}
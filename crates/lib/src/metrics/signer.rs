@@ -0,0 +1,38 @@
+use prometheus::{HistogramOpts, HistogramVec};
+use std::sync::OnceLock;
+
+static SIGNER_METRICS: OnceLock<SignerMetrics> = OnceLock::new();
+
+/// Metrics for signing calls against pool signers
+pub struct SignerMetrics {
+    pub sign_duration_seconds: HistogramVec,
+}
+
+impl SignerMetrics {
+    fn new() -> Self {
+        let sign_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "signer_sign_duration_seconds",
+                "Duration of each signing attempt per signer, by outcome",
+            )
+            .namespace("kora")
+            .buckets(vec![0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0]),
+            &["signer_name", "outcome"],
+        )
+        .unwrap_or_else(|e| {
+            log::error!("Failed to create signer_sign_duration_seconds metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        prometheus::register(Box::new(sign_duration_seconds.clone())).unwrap_or_else(|e| {
+            log::error!("Failed to register signer_sign_duration_seconds metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        Self { sign_duration_seconds }
+    }
+
+    pub fn get() -> &'static SignerMetrics {
+        SIGNER_METRICS.get_or_init(SignerMetrics::new)
+    }
+}
@@ -4,15 +4,18 @@
 #![recursion_limit = "256"]
 
 pub mod admin;
+pub mod anchor;
 pub mod bundle;
 pub mod cache;
 pub mod config;
 pub mod constant;
 pub mod error;
 pub mod fee;
+pub mod idempotency;
 pub mod lighthouse;
 pub mod log;
 pub mod metrics;
+pub mod nonce;
 pub mod oracle;
 pub mod plugin;
 pub mod rpc;
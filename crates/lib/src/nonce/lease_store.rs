@@ -0,0 +1,315 @@
+use async_trait::async_trait;
+use deadpool_redis::{Connection, Pool};
+use once_cell::sync::Lazy;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
+
+use crate::{cache::CacheUtil, error::KoraError, sanitize_error};
+
+const NONCE_LEASE_KEY_PREFIX: &str = "kora:nonce_lease";
+const NONCE_HANDED_OUT_KEY_PREFIX: &str = "kora:nonce_handed_out";
+
+/// Global nonce lease store. Falls back to an in-memory store when
+/// [`init_nonce_lease_store`] was never called (e.g. in unit tests).
+static NONCE_LEASE_STORE: once_cell::sync::OnceCell<Arc<dyn NonceLeaseStore>> =
+    once_cell::sync::OnceCell::new();
+
+/// Leases the nonce account in KEYS[1] unless it is leased, and records the handed out
+/// nonce in KEYS[2]
+static TRY_LEASE_SCRIPT: Lazy<redis::Script> = Lazy::new(|| {
+    redis::Script::new(
+        r"
+            if redis.call('SET', KEYS[1], ARGV[1], 'NX', 'EX', ARGV[2]) then
+                redis.call('SET', KEYS[2], ARGV[3])
+                return 1
+            end
+            return 0
+            ",
+    )
+});
+
+/// Deletes the lease in KEYS[1] only if it is held by the lessee in ARGV[1]
+static RELEASE_SCRIPT: Lazy<redis::Script> = Lazy::new(|| {
+    redis::Script::new(
+        r"
+            local data = redis.call('GET', KEYS[1])
+            if not data then return 0 end
+            local lessee = cjson.decode(data)['lessee']
+            if type(lessee) ~= 'string' then lessee = '' end
+            if lessee == ARGV[1] then return redis.call('DEL', KEYS[1]) end
+            return 0
+            ",
+    )
+});
+
+/// A nonce account handed out to a client until `expires_at`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NonceLease {
+    pub nonce_account: String,
+    /// Kora signer that is the nonce authority
+    pub nonce_authority: String,
+    /// Durable nonce the account held when it was leased
+    #[serde(default)]
+    pub nonce: String,
+    /// Client the nonce was leased to, None when API key auth is off
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lessee: Option<String>,
+    /// Unix timestamp after which the nonce can be leased again
+    pub expires_at: i64,
+}
+
+/// Trait for storing nonce leases. Expired leases are reclaimed by the store itself.
+#[async_trait]
+pub trait NonceLeaseStore: Send + Sync {
+    /// Store the lease unless its nonce account is already leased, and remember its
+    /// nonce as handed out. Returns false when another lease is still active.
+    async fn try_lease(&self, lease: &NonceLease) -> Result<bool, KoraError>;
+
+    /// Get the active lease for a nonce account (returns None if free or expired)
+    async fn get(&self, nonce_account: &str) -> Result<Option<NonceLease>, KoraError>;
+
+    /// End the active lease of a nonce account if `lessee` holds it.
+    /// Returns false when the account is free or leased to someone else.
+    async fn release(&self, nonce_account: &str, lessee: Option<&str>) -> Result<bool, KoraError>;
+
+    /// Last nonce handed out for a nonce account. Until the account is advanced past it,
+    /// a transaction signed by the previous lessee can still land.
+    async fn handed_out_nonce(&self, nonce_account: &str) -> Result<Option<String>, KoraError>;
+}
+
+fn lease_ttl_seconds(lease: &NonceLease) -> u64 {
+    (lease.expires_at - chrono::Utc::now().timestamp()).max(1) as u64
+}
+
+/// Redis-based implementation, shared across Kora instances
+pub struct RedisNonceLeaseStore {
+    pool: Pool,
+}
+
+impl RedisNonceLeaseStore {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn get_key(nonce_account: &str) -> String {
+        format!("{NONCE_LEASE_KEY_PREFIX}:{nonce_account}")
+    }
+
+    fn handed_out_key(nonce_account: &str) -> String {
+        format!("{NONCE_HANDED_OUT_KEY_PREFIX}:{nonce_account}")
+    }
+
+    async fn get_connection(&self) -> Result<Connection, KoraError> {
+        self.pool.get().await.map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to get Redis connection: {}",
+                e
+            )))
+        })
+    }
+}
+
+#[async_trait]
+impl NonceLeaseStore for RedisNonceLeaseStore {
+    async fn try_lease(&self, lease: &NonceLease) -> Result<bool, KoraError> {
+        let mut conn = self.get_connection().await?;
+        let serialized = serde_json::to_string(lease).map_err(|e| {
+            KoraError::SerializationError(format!("Failed to serialize nonce lease: {e}"))
+        })?;
+
+        // SET NX EX: only one instance can lease a free nonce, and Redis drops the key on expiry
+        let acquired: i64 = TRY_LEASE_SCRIPT
+            .key(Self::get_key(&lease.nonce_account))
+            .key(Self::handed_out_key(&lease.nonce_account))
+            .arg(serialized)
+            .arg(lease_ttl_seconds(lease))
+            .arg(&lease.nonce)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| {
+                KoraError::InternalServerError(sanitize_error!(format!(
+                    "Failed to lease nonce account {}: {}",
+                    lease.nonce_account, e
+                )))
+            })?;
+
+        Ok(acquired == 1)
+    }
+
+    async fn get(&self, nonce_account: &str) -> Result<Option<NonceLease>, KoraError> {
+        let mut conn = self.get_connection().await?;
+        let data: Option<String> = conn.get(Self::get_key(nonce_account)).await.map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to get nonce lease for {}: {}",
+                nonce_account, e
+            )))
+        })?;
+
+        data.map(|data| {
+            serde_json::from_str(&data).map_err(|e| {
+                KoraError::SerializationError(format!("Failed to deserialize nonce lease: {e}"))
+            })
+        })
+        .transpose()
+    }
+
+    async fn release(&self, nonce_account: &str, lessee: Option<&str>) -> Result<bool, KoraError> {
+        let mut conn = self.get_connection().await?;
+        let released: i64 = RELEASE_SCRIPT
+            .key(Self::get_key(nonce_account))
+            .arg(lessee.unwrap_or_default())
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| {
+                KoraError::InternalServerError(sanitize_error!(format!(
+                    "Failed to release nonce account {}: {}",
+                    nonce_account, e
+                )))
+            })?;
+
+        Ok(released == 1)
+    }
+
+    async fn handed_out_nonce(&self, nonce_account: &str) -> Result<Option<String>, KoraError> {
+        let mut conn = self.get_connection().await?;
+        conn.get(Self::handed_out_key(nonce_account)).await.map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to get handed out nonce for {}: {}",
+                nonce_account, e
+            )))
+        })
+    }
+}
+
+/// In-memory implementation, local to this Kora instance
+#[derive(Default)]
+pub struct InMemoryNonceLeaseStore {
+    leases: Mutex<HashMap<String, NonceLease>>,
+    handed_out: Mutex<HashMap<String, String>>,
+}
+
+impl InMemoryNonceLeaseStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl NonceLeaseStore for InMemoryNonceLeaseStore {
+    async fn try_lease(&self, lease: &NonceLease) -> Result<bool, KoraError> {
+        let now = chrono::Utc::now().timestamp();
+        let mut leases = self.leases.lock().await;
+
+        if leases.get(&lease.nonce_account).is_some_and(|active| active.expires_at > now) {
+            return Ok(false);
+        }
+
+        leases.insert(lease.nonce_account.clone(), lease.clone());
+        self.handed_out.lock().await.insert(lease.nonce_account.clone(), lease.nonce.clone());
+        Ok(true)
+    }
+
+    async fn get(&self, nonce_account: &str) -> Result<Option<NonceLease>, KoraError> {
+        let now = chrono::Utc::now().timestamp();
+        let leases = self.leases.lock().await;
+
+        Ok(leases.get(nonce_account).filter(|lease| lease.expires_at > now).cloned())
+    }
+
+    async fn release(&self, nonce_account: &str, lessee: Option<&str>) -> Result<bool, KoraError> {
+        let now = chrono::Utc::now().timestamp();
+        let mut leases = self.leases.lock().await;
+
+        let held = leases
+            .get(nonce_account)
+            .is_some_and(|lease| lease.expires_at > now && lease.lessee.as_deref() == lessee);
+        if held {
+            leases.remove(nonce_account);
+        }
+        Ok(held)
+    }
+
+    async fn handed_out_nonce(&self, nonce_account: &str) -> Result<Option<String>, KoraError> {
+        Ok(self.handed_out.lock().await.get(nonce_account).cloned())
+    }
+}
+
+/// Initialize the global nonce lease store. Uses Redis when the cache is enabled,
+/// otherwise an in-memory store. Must run after [`CacheUtil::init`].
+pub fn init_nonce_lease_store() -> Result<(), KoraError> {
+    let (store, backend): (Arc<dyn NonceLeaseStore>, &str) = match CacheUtil::get_pool() {
+        Some(pool) => (Arc::new(RedisNonceLeaseStore::new(pool)), "Redis"),
+        None => (Arc::new(InMemoryNonceLeaseStore::new()), "in-memory"),
+    };
+
+    NONCE_LEASE_STORE.set(store).map_err(|_| {
+        KoraError::InternalServerError("Nonce lease store already initialized".to_string())
+    })?;
+
+    log::info!("Nonce lease store initialized ({backend})");
+    Ok(())
+}
+
+/// Returns the global nonce lease store
+pub fn get_nonce_lease_store() -> Arc<dyn NonceLeaseStore> {
+    NONCE_LEASE_STORE.get_or_init(|| Arc::new(InMemoryNonceLeaseStore::new())).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lease(nonce_account: &str, expires_at: i64) -> NonceLease {
+        NonceLease {
+            nonce_account: nonce_account.to_string(),
+            nonce_authority: "authority".to_string(),
+            nonce: "nonce-value".to_string(),
+            lessee: Some("client".to_string()),
+            expires_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_lease_is_exclusive() {
+        let store = InMemoryNonceLeaseStore::new();
+        let expires_at = chrono::Utc::now().timestamp() + 60;
+
+        assert!(store.try_lease(&lease("nonce", expires_at)).await.unwrap());
+        assert!(!store.try_lease(&lease("nonce", expires_at)).await.unwrap());
+        assert!(store.try_lease(&lease("other", expires_at)).await.unwrap());
+        assert_eq!(store.get("nonce").await.unwrap(), Some(lease("nonce", expires_at)));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_reclaims_expired_lease() {
+        let store = InMemoryNonceLeaseStore::new();
+        let expired = lease("nonce", chrono::Utc::now().timestamp() - 1);
+        store.leases.lock().await.insert("nonce".to_string(), expired);
+
+        assert_eq!(store.get("nonce").await.unwrap(), None);
+
+        let renewed = lease("nonce", chrono::Utc::now().timestamp() + 60);
+        assert!(store.try_lease(&renewed).await.unwrap());
+        assert_eq!(store.get("nonce").await.unwrap(), Some(renewed));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_release_requires_lessee() {
+        let store = InMemoryNonceLeaseStore::new();
+        let expires_at = chrono::Utc::now().timestamp() + 60;
+        assert!(store.try_lease(&lease("nonce", expires_at)).await.unwrap());
+
+        assert!(!store.release("nonce", Some("other")).await.unwrap());
+        assert!(!store.release("nonce", None).await.unwrap());
+        assert!(store.get("nonce").await.unwrap().is_some());
+
+        assert!(store.release("nonce", Some("client")).await.unwrap());
+        assert_eq!(store.get("nonce").await.unwrap(), None);
+        assert!(!store.release("nonce", Some("client")).await.unwrap());
+
+        // The handed out nonce outlives the lease
+        assert_eq!(store.handed_out_nonce("nonce").await.unwrap(), Some("nonce-value".to_string()));
+    }
+}
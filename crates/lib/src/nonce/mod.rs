@@ -0,0 +1,8 @@
+pub mod lease_store;
+pub mod pool;
+
+pub use lease_store::{
+    get_nonce_lease_store, init_nonce_lease_store, InMemoryNonceLeaseStore, NonceLease,
+    NonceLeaseStore, RedisNonceLeaseStore,
+};
+pub use pool::NoncePoolUtil;
@@ -0,0 +1,487 @@
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_commitment_config::CommitmentConfig;
+use solana_message::{Message, VersionedMessage};
+use solana_nonce::{state::State, versions::Versions};
+use solana_sdk::{account::Account, hash::Hash, pubkey::Pubkey};
+use solana_system_interface::{
+    instruction::{advance_nonce_account, SystemInstruction},
+    program::ID as SYSTEM_PROGRAM_ID,
+};
+
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    config::{Config, NoncePoolConfig},
+    constant::{
+        instruction_indexes::system_advance_nonce_account, NONCE_ACCOUNT_SEED_PREFIX,
+        NONCE_ADVANCE_LEASE_SECONDS,
+    },
+    error::KoraError,
+    nonce::{get_nonce_lease_store, NonceLease},
+    rpc_server::auth::authenticated_client_id,
+    sanitize_error,
+    signer::{Signer, SolanaSigner},
+    state::get_signer_pool,
+    transaction::{sign_with_retry, TransactionUtil},
+};
+
+/// Lessee of the short lease that holds a nonce account while Kora advances it
+const NONCE_ADVANCE_LESSEE: &str = "kora:advance";
+
+pub struct NoncePoolUtil {}
+
+impl NoncePoolUtil {
+    /// Seed of the nonce account at `index`, used with `create_account_with_seed`
+    pub fn nonce_account_seed(index: u32) -> String {
+        format!("{NONCE_ACCOUNT_SEED_PREFIX}-{index}")
+    }
+
+    /// Address of the nonce account at `index` for a signer
+    pub fn nonce_account_address(authority: &Pubkey, index: u32) -> Result<Pubkey, KoraError> {
+        Pubkey::create_with_seed(authority, &Self::nonce_account_seed(index), &SYSTEM_PROGRAM_ID)
+            .map_err(|e| {
+                KoraError::InternalServerError(format!("Failed to derive nonce account: {e}"))
+            })
+    }
+
+    /// All pool nonce account addresses for a signer
+    pub fn nonce_account_addresses(
+        authority: &Pubkey,
+        config: &NoncePoolConfig,
+    ) -> Result<Vec<Pubkey>, KoraError> {
+        (0..config.accounts_per_signer)
+            .map(|index| Self::nonce_account_address(authority, index))
+            .collect()
+    }
+
+    /// Returns the authority and current durable nonce of an initialized nonce account
+    pub fn parse_nonce_account(account: &Account) -> Option<(Pubkey, Hash)> {
+        if account.owner != SYSTEM_PROGRAM_ID {
+            return None;
+        }
+
+        match bincode::deserialize::<Versions>(&account.data).ok()?.state() {
+            State::Initialized(data) => Some((data.authority, data.blockhash())),
+            State::Uninitialized => None,
+        }
+    }
+
+    /// Leases a free, initialized nonce account whose authority is `signer`, bound to the
+    /// requesting client. Returns the lease and the nonce to use as the transaction's
+    /// recent blockhash.
+    ///
+    /// An account whose nonce was handed out before is dirty: the previous lessee may
+    /// still hold a transaction signed with it. It is only leased again once Kora has
+    /// advanced it, which this call starts.
+    pub async fn lease_nonce(
+        kora_config: &Config,
+        rpc_client: &RpcClient,
+        signer: &Arc<Signer>,
+    ) -> Result<(NonceLease, Hash), KoraError> {
+        let config = &kora_config.kora.nonce_pool;
+        if !config.enabled {
+            return Err(KoraError::ValidationError("Nonce pool is not enabled".to_string()));
+        }
+
+        let authority = signer.pubkey();
+        let addresses = Self::nonce_account_addresses(&authority, config)?;
+        if addresses.is_empty() {
+            return Err(KoraError::InvalidRequest("No nonce accounts are configured".to_string()));
+        }
+
+        // Nonce values change on every advance, so they are never read from the cache
+        let accounts = rpc_client.get_multiple_accounts(&addresses).await.map_err(|e| {
+            KoraError::RpcError(format!("Failed to get nonce accounts: {}", sanitize_error!(e)))
+        })?;
+
+        // Start at a random offset so concurrent requests don't all race for the first account
+        let offset = rand::random::<u64>() as usize % addresses.len();
+        let store = get_nonce_lease_store();
+        let lessee = authenticated_client_id();
+
+        for i in 0..addresses.len() {
+            let index = (offset + i) % addresses.len();
+            let Some((nonce_authority, nonce)) =
+                accounts[index].as_ref().and_then(Self::parse_nonce_account)
+            else {
+                continue;
+            };
+            if nonce_authority != authority {
+                continue;
+            }
+
+            let nonce_account = addresses[index].to_string();
+            let nonce_value = nonce.to_string();
+            if store.handed_out_nonce(&nonce_account).await?.as_deref() == Some(&nonce_value) {
+                if let Err(e) = Self::advance_dirty_nonce(
+                    kora_config,
+                    rpc_client,
+                    signer,
+                    &addresses[index],
+                    &nonce,
+                )
+                .await
+                {
+                    log::warn!("Failed to advance nonce account {nonce_account}: {e}");
+                }
+                continue;
+            }
+
+            let lease = NonceLease {
+                nonce_account,
+                nonce_authority: authority.to_string(),
+                nonce: nonce_value,
+                lessee: lessee.clone(),
+                expires_at: chrono::Utc::now().timestamp() + config.lease_duration_seconds as i64,
+            };
+            if store.try_lease(&lease).await? {
+                return Ok((lease, nonce));
+            }
+        }
+
+        Err(KoraError::InvalidRequest(format!(
+            "No nonce account available to lease for {authority}"
+        )))
+    }
+
+    /// Sends an AdvanceNonceAccount for a nonce account that still holds a handed out
+    /// nonce, unless it is leased. A short lease keeps other requests from leasing or
+    /// advancing it meanwhile.
+    async fn advance_dirty_nonce(
+        config: &Config,
+        rpc_client: &RpcClient,
+        signer: &Arc<Signer>,
+        nonce_account: &Pubkey,
+        nonce: &Hash,
+    ) -> Result<(), KoraError> {
+        let authority = signer.pubkey();
+        let advance_lease = NonceLease {
+            nonce_account: nonce_account.to_string(),
+            nonce_authority: authority.to_string(),
+            nonce: nonce.to_string(),
+            lessee: Some(NONCE_ADVANCE_LESSEE.to_string()),
+            expires_at: chrono::Utc::now().timestamp() + NONCE_ADVANCE_LEASE_SECONDS as i64,
+        };
+        // Still leased to a client, or already being advanced
+        if !get_nonce_lease_store().try_lease(&advance_lease).await? {
+            return Ok(());
+        }
+
+        let blockhash = rpc_client.get_latest_blockhash().await.map_err(|e| {
+            KoraError::RpcError(format!("Failed to get blockhash: {}", sanitize_error!(e)))
+        })?;
+        let message = VersionedMessage::Legacy(Message::new_with_blockhash(
+            &[advance_nonce_account(nonce_account, &authority)],
+            Some(&authority),
+            &blockhash,
+        ));
+        let mut transaction = TransactionUtil::new_unsigned_versioned_transaction(message);
+        // Signed like every request: under the signer's concurrency limit and sign timeout,
+        // with its latency and health reported to the pool
+        let message_bytes = transaction.message.serialize();
+        let sign_timeout = Duration::from_secs(config.kora.sign_timeout_seconds);
+        let pool = get_signer_pool()?;
+        let _permit = pool.acquire_sign_permit(signer, sign_timeout).await?;
+        let signature = match sign_with_retry(
+            sign_timeout,
+            config.kora.sign_max_retries,
+            "nonce advance signing",
+            "Nonce advance signing",
+            || async {
+                pool.sign_message(signer, &message_bytes)
+                    .await
+                    .map_err(|e| KoraError::SigningError(sanitize_error!(e)))
+            },
+        )
+        .await
+        {
+            Ok(sig) => {
+                pool.record_signing_success(signer);
+                sig
+            }
+            Err(err) => {
+                pool.record_signing_failure(signer);
+                return Err(err);
+            }
+        };
+        transaction.signatures = vec![signature];
+
+        rpc_client.send_transaction(&transaction).await.map_err(|e| {
+            KoraError::RpcError(format!("Failed to advance nonce: {}", sanitize_error!(e)))
+        })?;
+        log::info!("Advancing handed out nonce of {nonce_account}: {signature}");
+
+        Ok(())
+    }
+
+    /// Whether `nonce_account` is currently leased to the requesting client with
+    /// `authority` as its nonce authority
+    pub async fn is_leased(nonce_account: &Pubkey, authority: &Pubkey) -> Result<bool, KoraError> {
+        let lease = get_nonce_lease_store().get(&nonce_account.to_string()).await?;
+
+        Ok(lease.is_some_and(|lease| {
+            lease.nonce_authority == authority.to_string()
+                && lease.lessee == authenticated_client_id()
+        }))
+    }
+
+    /// Ends the requesting client's lease of `nonce_account`. The account is advanced
+    /// before it is leased again.
+    pub async fn release_nonce(nonce_account: &Pubkey) -> Result<(), KoraError> {
+        let released = get_nonce_lease_store()
+            .release(&nonce_account.to_string(), authenticated_client_id().as_deref())
+            .await?;
+        if !released {
+            return Err(KoraError::InvalidRequest(format!(
+                "Nonce account {nonce_account} is not leased to this client"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Nonce account of a durable transaction, i.e. one whose first instruction is
+    /// AdvanceNonceAccount. Such a transaction stays valid until that account is advanced.
+    pub fn durable_nonce_account(message: &VersionedMessage) -> Option<Pubkey> {
+        let instruction = message.instructions().first()?;
+        let account_keys = message.static_account_keys();
+
+        if account_keys.get(usize::from(instruction.program_id_index)) != Some(&SYSTEM_PROGRAM_ID) {
+            return None;
+        }
+        if !matches!(
+            bincode::deserialize::<SystemInstruction>(&instruction.data),
+            Ok(SystemInstruction::AdvanceNonceAccount)
+        ) {
+            return None;
+        }
+
+        let index = instruction.accounts.get(system_advance_nonce_account::NONCE_ACCOUNT_INDEX)?;
+        account_keys.get(usize::from(*index)).copied()
+    }
+
+    /// Whether a transaction signed with `recent_blockhash` can no longer land. A durable
+    /// transaction expires once its nonce account no longer holds that nonce, any other
+    /// once the blockhash is no longer valid.
+    pub async fn is_recent_blockhash_expired(
+        rpc_client: &RpcClient,
+        recent_blockhash: &Hash,
+        nonce_account: Option<&Pubkey>,
+    ) -> Result<bool, KoraError> {
+        let Some(nonce_account) = nonce_account else {
+            let valid = rpc_client
+                .is_blockhash_valid(recent_blockhash, CommitmentConfig::processed())
+                .await
+                .map_err(|e| KoraError::RpcError(sanitize_error!(e)))?;
+            return Ok(!valid);
+        };
+
+        // Confirmed, so a transaction that advanced the nonce itself is already visible
+        // as landed to callers that poll its signature afterwards
+        let account = rpc_client
+            .get_account_with_commitment(nonce_account, CommitmentConfig::confirmed())
+            .await
+            .map_err(|e| KoraError::RpcError(sanitize_error!(e)))?
+            .value;
+
+        Ok(account
+            .as_ref()
+            .and_then(Self::parse_nonce_account)
+            .is_none_or(|(_, nonce)| nonce != *recent_blockhash))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{config_mock::ConfigMockBuilder, rpc_mock::RpcMockBuilder};
+    use solana_nonce::state::{Data, DurableNonce};
+    use solana_sdk::signature::Keypair;
+    use solana_system_interface::instruction::transfer;
+
+    fn create_nonce_account(authority: &Pubkey, blockhash: &Hash) -> Account {
+        let data = Data::new(*authority, DurableNonce::from_blockhash(blockhash), 5000);
+        Account {
+            lamports: 1_447_680,
+            data: bincode::serialize(&Versions::new(State::Initialized(data))).unwrap(),
+            owner: SYSTEM_PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    fn memory_signer() -> Arc<Signer> {
+        Arc::new(Signer::from_memory(&Keypair::new().to_base58_string()).unwrap())
+    }
+
+    fn pool_config(accounts_per_signer: u32) -> NoncePoolConfig {
+        NoncePoolConfig { enabled: true, accounts_per_signer, lease_duration_seconds: 60 }
+    }
+
+    fn config_with_pool(nonce_pool: NoncePoolConfig) -> Config {
+        let mut config = ConfigMockBuilder::new().build();
+        config.kora.nonce_pool = nonce_pool;
+        config
+    }
+
+    #[test]
+    fn test_nonce_account_addresses_are_deterministic() {
+        let authority = Pubkey::new_unique();
+        let addresses =
+            NoncePoolUtil::nonce_account_addresses(&authority, &pool_config(3)).unwrap();
+
+        assert_eq!(addresses.len(), 3);
+        assert_eq!(addresses[1], NoncePoolUtil::nonce_account_address(&authority, 1).unwrap());
+        assert_ne!(addresses[0], addresses[1]);
+    }
+
+    #[test]
+    fn test_parse_nonce_account() {
+        let authority = Pubkey::new_unique();
+        let blockhash = Hash::new_unique();
+        let account = create_nonce_account(&authority, &blockhash);
+        let durable_nonce = DurableNonce::from_blockhash(&blockhash);
+
+        assert_eq!(
+            NoncePoolUtil::parse_nonce_account(&account),
+            Some((authority, *durable_nonce.as_hash()))
+        );
+
+        let not_nonce = Account { owner: Pubkey::new_unique(), ..account };
+        assert_eq!(NoncePoolUtil::parse_nonce_account(&not_nonce), None);
+    }
+
+    #[tokio::test]
+    async fn test_lease_nonce_leases_each_account_once() {
+        let signer = memory_signer();
+        let authority = signer.pubkey();
+        let account = create_nonce_account(&authority, &Hash::new_unique());
+        let rpc_client =
+            RpcMockBuilder::new().with_multiple_accounts_info(vec![Some(account)]).build();
+        let config = config_with_pool(pool_config(1));
+
+        let (lease, _) = NoncePoolUtil::lease_nonce(&config, &rpc_client, &signer).await.unwrap();
+
+        let nonce_account = NoncePoolUtil::nonce_account_address(&authority, 0).unwrap();
+        assert_eq!(lease.nonce_account, nonce_account.to_string());
+        assert!(NoncePoolUtil::is_leased(&nonce_account, &authority).await.unwrap());
+        assert!(!NoncePoolUtil::is_leased(&nonce_account, &Pubkey::new_unique()).await.unwrap());
+
+        let account = create_nonce_account(&authority, &Hash::new_unique());
+        let rpc_client =
+            RpcMockBuilder::new().with_multiple_accounts_info(vec![Some(account)]).build();
+        let result = NoncePoolUtil::lease_nonce(&config, &rpc_client, &signer).await;
+        assert!(matches!(result, Err(KoraError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_lease_nonce_skips_foreign_authority() {
+        let account = create_nonce_account(&Pubkey::new_unique(), &Hash::new_unique());
+        let rpc_client =
+            RpcMockBuilder::new().with_multiple_accounts_info(vec![Some(account)]).build();
+
+        let result = NoncePoolUtil::lease_nonce(
+            &config_with_pool(pool_config(1)),
+            &rpc_client,
+            &memory_signer(),
+        )
+        .await;
+
+        assert!(matches!(result, Err(KoraError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_lease_nonce_advances_released_nonce_before_leasing_it_again() {
+        let signer = memory_signer();
+        let authority = signer.pubkey();
+        let nonce_account = NoncePoolUtil::nonce_account_address(&authority, 0).unwrap();
+        let handed_out = Hash::new_unique();
+        let config = config_with_pool(pool_config(1));
+
+        let rpc_client = RpcMockBuilder::new()
+            .with_multiple_accounts_info(vec![Some(create_nonce_account(&authority, &handed_out))])
+            .build();
+        NoncePoolUtil::lease_nonce(&config, &rpc_client, &signer).await.unwrap();
+        NoncePoolUtil::release_nonce(&nonce_account).await.unwrap();
+        assert!(matches!(
+            NoncePoolUtil::release_nonce(&nonce_account).await,
+            Err(KoraError::InvalidRequest(_))
+        ));
+
+        // Still on the handed out nonce, so Kora advances it instead of leasing it
+        let rpc_client = RpcMockBuilder::new()
+            .with_multiple_accounts_info(vec![Some(create_nonce_account(&authority, &handed_out))])
+            .with_blockhash()
+            .build();
+        let result = NoncePoolUtil::lease_nonce(&config, &rpc_client, &signer).await;
+        assert!(matches!(result, Err(KoraError::InvalidRequest(_))));
+
+        // The advance holds the account until it expires
+        let lease = get_nonce_lease_store().get(&nonce_account.to_string()).await.unwrap();
+        assert_eq!(lease.and_then(|lease| lease.lessee), Some(NONCE_ADVANCE_LESSEE.to_string()));
+        assert!(!NoncePoolUtil::is_leased(&nonce_account, &authority).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_lease_nonce_disabled() {
+        let rpc_client = RpcMockBuilder::new().build();
+        let config = config_with_pool(NoncePoolConfig::default());
+
+        let result = NoncePoolUtil::lease_nonce(&config, &rpc_client, &memory_signer()).await;
+
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_durable_nonce_account() {
+        let authority = Pubkey::new_unique();
+        let nonce_account = Pubkey::new_unique();
+        let advance = advance_nonce_account(&nonce_account, &authority);
+        let transfer = transfer(&authority, &Pubkey::new_unique(), 1);
+
+        let durable =
+            VersionedMessage::Legacy(Message::new(&[advance.clone(), transfer.clone()], None));
+        assert_eq!(NoncePoolUtil::durable_nonce_account(&durable), Some(nonce_account));
+
+        // The advance only makes a transaction durable as its first instruction
+        let not_first = VersionedMessage::Legacy(Message::new(&[transfer, advance], None));
+        assert_eq!(NoncePoolUtil::durable_nonce_account(&not_first), None);
+    }
+
+    #[tokio::test]
+    async fn test_is_recent_blockhash_expired_checks_nonce_account() {
+        let authority = Pubkey::new_unique();
+        let nonce_account = Pubkey::new_unique();
+        let nonce = Hash::new_unique();
+        let durable_nonce = *DurableNonce::from_blockhash(&nonce).as_hash();
+        let account = create_nonce_account(&authority, &nonce);
+
+        let rpc_client = RpcMockBuilder::new().with_account_info(&account).build();
+        assert!(!NoncePoolUtil::is_recent_blockhash_expired(
+            &rpc_client,
+            &durable_nonce,
+            Some(&nonce_account)
+        )
+        .await
+        .unwrap());
+
+        // Advanced past the transaction's nonce
+        let rpc_client = RpcMockBuilder::new().with_account_info(&account).build();
+        assert!(NoncePoolUtil::is_recent_blockhash_expired(
+            &rpc_client,
+            &Hash::new_unique(),
+            Some(&nonce_account)
+        )
+        .await
+        .unwrap());
+
+        let rpc_client = RpcMockBuilder::new().with_account_not_found().build();
+        assert!(NoncePoolUtil::is_recent_blockhash_expired(
+            &rpc_client,
+            &durable_nonce,
+            Some(&nonce_account)
+        )
+        .await
+        .unwrap());
+    }
+}
@@ -124,4 +124,25 @@ impl TransactionPluginRunner {
 
         Ok(())
     }
+
+    /// Runs every plugin and returns all their errors instead of stopping at the first one
+    pub async fn run_all(
+        &self,
+        transaction: &mut VersionedTransactionResolved,
+        config: &Config,
+        rpc_client: &RpcClient,
+        fee_payer: &Pubkey,
+        context: PluginExecutionContext,
+    ) -> Vec<KoraError> {
+        let mut errors = Vec::new();
+        for plugin in &self.plugins {
+            if let Err(e) =
+                plugin.validate(transaction, config, rpc_client, fee_payer, context).await
+            {
+                errors.push(e);
+            }
+        }
+
+        errors
+    }
 }
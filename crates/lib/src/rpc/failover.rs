@@ -0,0 +1,463 @@
+use async_trait::async_trait;
+use futures::future::join_all;
+use parking_lot::Mutex;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    rpc_request::{RpcError, RpcRequest, RpcResponseErrorData},
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+use solana_rpc_client::http_sender::HttpSender;
+use std::time::{Duration, Instant};
+
+use crate::config::RpcConfig;
+
+/// Health state of a single RPC endpoint
+#[derive(Default)]
+struct EndpointHealth {
+    consecutive_errors: u32,
+    /// Set while the endpoint is ejected; it is probed again once this passes
+    ejected_until: Option<Instant>,
+}
+
+struct Endpoint {
+    url: String,
+    priority: u32,
+    sender: Box<dyn RpcSender + Send + Sync>,
+    health: Mutex<EndpointHealth>,
+}
+
+impl Endpoint {
+    fn is_ejected(&self, now: Instant) -> bool {
+        self.health.lock().ejected_until.is_some_and(|until| now < until)
+    }
+
+    fn consecutive_errors(&self) -> u32 {
+        self.health.lock().consecutive_errors
+    }
+
+    fn record_success(&self) {
+        let mut health = self.health.lock();
+        if health.ejected_until.is_some() {
+            log::info!("RPC endpoint {} recovered", self.url);
+        }
+        *health = EndpointHealth::default();
+    }
+
+    fn record_failure(&self, max_consecutive_errors: u32, reprobe_interval: Duration) {
+        let mut health = self.health.lock();
+        health.consecutive_errors = health.consecutive_errors.saturating_add(1);
+
+        // A failed re-probe ejects again right away since the counter was never reset
+        if health.consecutive_errors >= max_consecutive_errors {
+            if health.ejected_until.is_none() {
+                log::warn!(
+                    "Ejecting RPC endpoint {} after {} consecutive errors",
+                    self.url,
+                    health.consecutive_errors
+                );
+            }
+            health.ejected_until = Some(Instant::now() + reprobe_interval);
+        }
+    }
+}
+
+/// [`RpcSender`] that spreads requests over several endpoints.
+///
+/// Reads go to the preferred healthy endpoint (lowest priority value, then fewest
+/// recent errors) and fall through to the next one on transport failures. Sends fan
+/// out to the first `send_fanout` endpoints. Endpoints that keep failing are ejected
+/// and receive traffic again once `reprobe_interval_seconds` has elapsed.
+///
+/// Wrapped in an `RpcClient`, so it plugs in wherever `Arc<RpcClient>` is used.
+pub struct FailoverRpcSender {
+    endpoints: Vec<Endpoint>,
+    send_fanout: usize,
+    max_consecutive_errors: u32,
+    reprobe_interval: Duration,
+}
+
+impl FailoverRpcSender {
+    pub fn new(config: &RpcConfig, timeout: Duration) -> Self {
+        let senders = config
+            .endpoints
+            .iter()
+            .map(|endpoint| {
+                let sender: Box<dyn RpcSender + Send + Sync> =
+                    Box::new(HttpSender::new_with_timeout(&endpoint.url, timeout));
+                (endpoint.url.clone(), endpoint.priority, sender)
+            })
+            .collect();
+
+        Self::with_senders(config, senders)
+    }
+
+    fn with_senders(
+        config: &RpcConfig,
+        senders: Vec<(String, u32, Box<dyn RpcSender + Send + Sync>)>,
+    ) -> Self {
+        let endpoints = senders
+            .into_iter()
+            .map(|(url, priority, sender)| Endpoint {
+                url,
+                priority,
+                sender,
+                health: Mutex::new(EndpointHealth::default()),
+            })
+            .collect();
+
+        Self {
+            endpoints,
+            send_fanout: config.send_fanout.max(1),
+            max_consecutive_errors: config.max_consecutive_errors.max(1),
+            reprobe_interval: Duration::from_secs(config.reprobe_interval_seconds),
+        }
+    }
+
+    /// Endpoints in the order they should be tried. Ejected endpoints come last so
+    /// requests still have somewhere to go when every endpoint is ejected.
+    fn ordered_endpoints(&self) -> Vec<&Endpoint> {
+        let now = Instant::now();
+        let mut endpoints: Vec<&Endpoint> = self.endpoints.iter().collect();
+        endpoints.sort_by_cached_key(|endpoint| {
+            (endpoint.is_ejected(now), endpoint.priority, endpoint.consecutive_errors())
+        });
+        endpoints
+    }
+
+    async fn send_to(
+        &self,
+        endpoint: &Endpoint,
+        request: RpcRequest,
+        params: serde_json::Value,
+    ) -> ClientResult<serde_json::Value> {
+        let result = endpoint.sender.send(request, params).await;
+        match &result {
+            Err(e) if is_endpoint_failure(e) => {
+                log::warn!("RPC endpoint {} failed {request}: {e}", endpoint.url);
+                endpoint.record_failure(self.max_consecutive_errors, self.reprobe_interval);
+            }
+            _ => endpoint.record_success(),
+        }
+        result
+    }
+
+    async fn send_with_failover(
+        &self,
+        request: RpcRequest,
+        params: serde_json::Value,
+    ) -> ClientResult<serde_json::Value> {
+        let mut last_error = None;
+        for endpoint in self.ordered_endpoints() {
+            match self.send_to(endpoint, request, params.clone()).await {
+                Err(e) if is_endpoint_failure(&e) => last_error = Some(e),
+                result => return result,
+            }
+        }
+        Err(last_error.unwrap_or_else(|| no_endpoints_error(request)))
+    }
+
+    /// Sends a transaction to several endpoints at once. Returns the first success;
+    /// otherwise prefers an error from a node that answered (e.g. a preflight
+    /// failure) over transport errors.
+    async fn send_with_fanout(
+        &self,
+        request: RpcRequest,
+        params: serde_json::Value,
+    ) -> ClientResult<serde_json::Value> {
+        let endpoints = self.ordered_endpoints();
+        let results = join_all(
+            endpoints
+                .iter()
+                .take(self.send_fanout)
+                .map(|endpoint| self.send_to(endpoint, request, params.clone())),
+        )
+        .await;
+
+        let mut answered_error = None;
+        let mut last_error = None;
+        for result in results {
+            match result {
+                Ok(value) => return Ok(value),
+                Err(e) if is_endpoint_failure(&e) => last_error = Some(e),
+                Err(e) => answered_error = answered_error.or(Some(e)),
+            }
+        }
+
+        if let Some(e) = answered_error {
+            return Err(e);
+        }
+
+        // Every fanned-out endpoint was unreachable, try the rest one at a time
+        for endpoint in endpoints.iter().skip(self.send_fanout) {
+            match self.send_to(endpoint, request, params.clone()).await {
+                Err(e) if is_endpoint_failure(&e) => last_error = Some(e),
+                result => return result,
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| no_endpoints_error(request)))
+    }
+}
+
+#[async_trait]
+impl RpcSender for FailoverRpcSender {
+    async fn send(
+        &self,
+        request: RpcRequest,
+        params: serde_json::Value,
+    ) -> ClientResult<serde_json::Value> {
+        match request {
+            RpcRequest::SendTransaction => self.send_with_fanout(request, params).await,
+            _ => self.send_with_failover(request, params).await,
+        }
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.endpoints.iter().map(|endpoint| endpoint.sender.get_transport_stats()).fold(
+            RpcTransportStats::default(),
+            |mut total, stats| {
+                total.request_count += stats.request_count;
+                total.elapsed_time += stats.elapsed_time;
+                total.rate_limited_time += stats.rate_limited_time;
+                total
+            },
+        )
+    }
+
+    fn url(&self) -> String {
+        self.ordered_endpoints().first().map(|endpoint| endpoint.url.clone()).unwrap_or_default()
+    }
+}
+
+/// Whether an error means the endpoint itself is unusable (unreachable, HTTP
+/// failure, unhealthy node) rather than the node rejecting the request.
+fn is_endpoint_failure(error: &ClientError) -> bool {
+    match error.kind() {
+        ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) | ClientErrorKind::Middleware(_) => {
+            true
+        }
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { data, .. }) => {
+            matches!(data, RpcResponseErrorData::NodeUnhealthy { .. })
+        }
+        ClientErrorKind::RpcError(RpcError::RpcRequestError(_)) => true,
+        _ => false,
+    }
+}
+
+fn no_endpoints_error(request: RpcRequest) -> ClientError {
+    ClientError::new_with_request(
+        ClientErrorKind::Custom("No RPC endpoints configured".to_string()),
+        request,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[derive(Clone, Copy)]
+    enum Behavior {
+        Ok,
+        Unreachable,
+        Rejects,
+    }
+
+    struct TestSender {
+        name: &'static str,
+        behavior: Behavior,
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl RpcSender for TestSender {
+        async fn send(
+            &self,
+            _request: RpcRequest,
+            _params: serde_json::Value,
+        ) -> ClientResult<serde_json::Value> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            match self.behavior {
+                Behavior::Ok => Ok(json!(self.name)),
+                Behavior::Unreachable => {
+                    Err(ClientErrorKind::Io(std::io::Error::other("connection refused")).into())
+                }
+                Behavior::Rejects => Err(ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                    code: -32002,
+                    message: "preflight failure".to_string(),
+                    data: RpcResponseErrorData::Empty,
+                })
+                .into()),
+            }
+        }
+
+        fn get_transport_stats(&self) -> RpcTransportStats {
+            RpcTransportStats { request_count: 1, ..Default::default() }
+        }
+
+        fn url(&self) -> String {
+            self.name.to_string()
+        }
+    }
+
+    fn create_sender(
+        config: RpcConfig,
+        endpoints: &[(&'static str, u32, Behavior)],
+    ) -> (FailoverRpcSender, Vec<Arc<AtomicUsize>>) {
+        let counters: Vec<Arc<AtomicUsize>> =
+            endpoints.iter().map(|_| Arc::new(AtomicUsize::new(0))).collect();
+        let senders = endpoints
+            .iter()
+            .zip(&counters)
+            .map(|((name, priority, behavior), calls)| {
+                let sender: Box<dyn RpcSender + Send + Sync> =
+                    Box::new(TestSender { name, behavior: *behavior, calls: Arc::clone(calls) });
+                (name.to_string(), *priority, sender)
+            })
+            .collect();
+
+        (FailoverRpcSender::with_senders(&config, senders), counters)
+    }
+
+    fn config(max_consecutive_errors: u32, reprobe_interval_seconds: u64) -> RpcConfig {
+        RpcConfig { max_consecutive_errors, reprobe_interval_seconds, ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn test_reads_prefer_lowest_priority_value() {
+        let (sender, counters) = create_sender(
+            config(3, 30),
+            &[("backup", 1, Behavior::Ok), ("primary", 0, Behavior::Ok)],
+        );
+
+        let result = sender.send(RpcRequest::GetSlot, json!([])).await.unwrap();
+
+        assert_eq!(result, json!("primary"));
+        assert_eq!(counters[0].load(Ordering::SeqCst), 0);
+        assert_eq!(sender.url(), "primary");
+    }
+
+    #[tokio::test]
+    async fn test_reads_fail_over_on_transport_error() {
+        let (sender, counters) = create_sender(
+            config(3, 30),
+            &[("primary", 0, Behavior::Unreachable), ("backup", 1, Behavior::Ok)],
+        );
+
+        let result = sender.send(RpcRequest::GetSlot, json!([])).await.unwrap();
+
+        assert_eq!(result, json!("backup"));
+        assert_eq!(counters[0].load(Ordering::SeqCst), 1);
+        assert_eq!(sender.endpoints[0].consecutive_errors(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_node_rejection_is_returned_without_failover() {
+        let (sender, counters) = create_sender(
+            config(3, 30),
+            &[("primary", 0, Behavior::Rejects), ("backup", 1, Behavior::Ok)],
+        );
+
+        let result = sender.send(RpcRequest::GetAccountInfo, json!([])).await;
+
+        assert!(result.is_err());
+        assert_eq!(counters[1].load(Ordering::SeqCst), 0);
+        assert_eq!(sender.endpoints[0].consecutive_errors(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_endpoint_ejected_after_consecutive_errors() {
+        let (sender, counters) = create_sender(
+            config(2, 30),
+            &[("primary", 0, Behavior::Unreachable), ("backup", 1, Behavior::Ok)],
+        );
+
+        for _ in 0..3 {
+            sender.send(RpcRequest::GetSlot, json!([])).await.unwrap();
+        }
+
+        // Ejected after the second failure, so the third read skips it
+        assert_eq!(counters[0].load(Ordering::SeqCst), 2);
+        assert_eq!(counters[1].load(Ordering::SeqCst), 3);
+        assert!(sender.endpoints[0].is_ejected(Instant::now()));
+        assert_eq!(sender.url(), "backup");
+    }
+
+    #[tokio::test]
+    async fn test_ejected_endpoint_is_reprobed_after_interval() {
+        let (sender, counters) = create_sender(
+            config(1, 0),
+            &[("primary", 0, Behavior::Unreachable), ("backup", 1, Behavior::Ok)],
+        );
+
+        sender.send(RpcRequest::GetSlot, json!([])).await.unwrap();
+        sender.send(RpcRequest::GetSlot, json!([])).await.unwrap();
+
+        // A zero re-probe interval makes the endpoint eligible again immediately
+        assert_eq!(counters[0].load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_recovered_endpoint_resets_health() {
+        let (sender, _) = create_sender(config(1, 30), &[("primary", 0, Behavior::Ok)]);
+        sender.endpoints[0].record_failure(1, Duration::from_secs(30));
+        assert!(sender.endpoints[0].is_ejected(Instant::now()));
+
+        sender.send(RpcRequest::GetSlot, json!([])).await.unwrap();
+
+        assert!(!sender.endpoints[0].is_ejected(Instant::now()));
+        assert_eq!(sender.endpoints[0].consecutive_errors(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_fans_out() {
+        let (sender, counters) = create_sender(
+            RpcConfig { send_fanout: 2, ..Default::default() },
+            &[("a", 0, Behavior::Unreachable), ("b", 1, Behavior::Ok), ("c", 2, Behavior::Ok)],
+        );
+
+        let result = sender.send(RpcRequest::SendTransaction, json!([])).await.unwrap();
+
+        assert_eq!(result, json!("b"));
+        assert_eq!(counters[0].load(Ordering::SeqCst), 1);
+        assert_eq!(counters[1].load(Ordering::SeqCst), 1);
+        assert_eq!(counters[2].load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_falls_back_when_fanout_unreachable() {
+        let (sender, counters) = create_sender(
+            RpcConfig { send_fanout: 1, ..Default::default() },
+            &[("a", 0, Behavior::Unreachable), ("b", 1, Behavior::Ok)],
+        );
+
+        let result = sender.send(RpcRequest::SendTransaction, json!([])).await.unwrap();
+
+        assert_eq!(result, json!("b"));
+        assert_eq!(counters[1].load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_prefers_node_rejection_over_transport_error() {
+        let (sender, _) = create_sender(
+            RpcConfig { send_fanout: 2, ..Default::default() },
+            &[("a", 0, Behavior::Unreachable), ("b", 1, Behavior::Rejects)],
+        );
+
+        let error = sender.send(RpcRequest::SendTransaction, json!([])).await.unwrap_err();
+
+        assert!(!is_endpoint_failure(&error));
+    }
+
+    #[test]
+    fn test_transport_stats_are_aggregated() {
+        let (sender, _) =
+            create_sender(RpcConfig::default(), &[("a", 0, Behavior::Ok), ("b", 1, Behavior::Ok)]);
+
+        assert_eq!(sender.get_transport_stats().request_count, 2);
+    }
+}
@@ -0,0 +1,38 @@
+mod failover;
+
+pub use failover::FailoverRpcSender;
+
+use std::{sync::Arc, time::Duration};
+
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_client::RpcClientConfig};
+use solana_commitment_config::CommitmentConfig;
+
+use crate::config::RpcConfig;
+
+const RPC_TIMEOUT: Duration = Duration::from_secs(90);
+
+pub fn get_rpc_client(rpc_url: &str) -> Arc<RpcClient> {
+    Arc::new(RpcClient::new_with_timeout_and_commitment(
+        rpc_url.to_string(),
+        RPC_TIMEOUT,
+        CommitmentConfig::confirmed(),
+    ))
+}
+
+/// Builds the RPC client from `[kora.rpc]`, falling back to `rpc_url` alone when no
+/// endpoints are configured.
+pub fn get_rpc_client_with_config(rpc_url: &str, rpc_config: &RpcConfig) -> Arc<RpcClient> {
+    if rpc_config.endpoints.is_empty() {
+        return get_rpc_client(rpc_url);
+    }
+
+    log::info!(
+        "Using {} configured RPC endpoints with failover (--rpc-url is ignored)",
+        rpc_config.endpoints.len()
+    );
+
+    Arc::new(RpcClient::new_sender(
+        FailoverRpcSender::new(rpc_config, RPC_TIMEOUT),
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ))
+}
@@ -0,0 +1,82 @@
+use crate::{
+    error::KoraError,
+    signer::{pool::SignerWithMetadata, SignerAvailability, SignerConfig},
+    state::modify_signer_pool,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AddSignerRequest {
+    /// Signer in the same format as a `[[signers]]` entry of `signers.toml`
+    pub signer: SignerConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AddSignerResponse {
+    pub name: String,
+    pub signer_key: String,
+    pub availability: SignerAvailability,
+}
+
+/// Add a signer to the running pool. Signers already in the pool keep their health and
+/// availability. The signer is local to this instance and is dropped by the next config
+/// reload unless it is also added to `signers.toml`.
+pub async fn add_signer(request: AddSignerRequest) -> Result<AddSignerResponse, KoraError> {
+    let config = request.signer;
+    config.validate_individual_signer_config(0)?;
+    config.validate_top_up_threshold()?;
+
+    // Remote signers can take a while to initialize, build outside the pool lock
+    let signer = SignerWithMetadata::from_config(&config).await?;
+    let signer_key = signer.pubkey().to_string();
+
+    modify_signer_pool(|pool| pool.with_added_signer(signer).map(Some))?;
+    log::info!("Added signer '{}' ({signer_key}) to the pool", config.name);
+
+    Ok(AddSignerResponse {
+        name: config.name,
+        signer_key,
+        availability: SignerAvailability::Active,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        signer::{MemorySignerConfig, SignerTypeConfig},
+        tests::{common::setup_or_get_test_signer, config_mock::ConfigMockBuilder},
+    };
+
+    fn memory_signer_config(private_key_env: &str) -> SignerConfig {
+        SignerConfig {
+            name: "added_signer".to_string(),
+            weight: None,
+            target_balance: None,
+            refill_below: None,
+            policy: None,
+            max_concurrent_signs: None,
+            config: SignerTypeConfig::Memory {
+                config: MemorySignerConfig { private_key_env: private_key_env.to_string() },
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_signer_rejects_invalid_config() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+
+        let missing_key = memory_signer_config("KORA_TEST_ADD_SIGNER_MISSING_KEY");
+        let result = add_signer(AddSignerRequest { signer: missing_key }).await;
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+
+        std::env::set_var("KORA_TEST_ADD_SIGNER_KEY", "unused");
+        let mut half_top_up = memory_signer_config("KORA_TEST_ADD_SIGNER_KEY");
+        half_top_up.target_balance = Some(1_000_000);
+        let result = add_signer(AddSignerRequest { signer: half_top_up }).await;
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+        std::env::remove_var("KORA_TEST_ADD_SIGNER_KEY");
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{
+    cache::{CacheFlushScope, CacheUtil},
+    error::KoraError,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FlushCacheRequest {
+    /// Which cached entries to remove (default: all)
+    #[serde(default = "default_scope")]
+    pub scope: CacheFlushScope,
+}
+
+fn default_scope() -> CacheFlushScope {
+    CacheFlushScope::All
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FlushCacheResponse {
+    /// Number of cache entries removed
+    pub deleted: u64,
+}
+
+pub async fn flush_cache(request: FlushCacheRequest) -> Result<FlushCacheResponse, KoraError> {
+    let deleted = CacheUtil::flush(request.scope).await?;
+
+    Ok(FlushCacheResponse { deleted })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flush_cache_request_defaults_to_all() {
+        let request: FlushCacheRequest = serde_json::from_str("{}").unwrap();
+        assert_eq!(request.scope, CacheFlushScope::All);
+
+        let request: FlushCacheRequest = serde_json::from_str(r#"{"scope": "prices"}"#).unwrap();
+        assert_eq!(request.scope, CacheFlushScope::Prices);
+    }
+}
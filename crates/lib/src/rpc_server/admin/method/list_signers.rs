@@ -0,0 +1,30 @@
+use crate::{error::KoraError, signer::SignerInfo, state::get_signers_info};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListSignersResponse {
+    /// Every signer in the pool with its availability and health
+    pub signers: Vec<SignerInfo>,
+}
+
+pub async fn list_signers() -> Result<ListSignersResponse, KoraError> {
+    Ok(ListSignersResponse { signers: get_signers_info()? })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{common::setup_or_get_test_signer, config_mock::ConfigMockBuilder};
+
+    #[tokio::test]
+    async fn test_list_signers_includes_health() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+
+        let response = list_signers().await.unwrap();
+
+        assert!(!response.signers.is_empty());
+        assert!(response.signers.iter().all(|signer| !signer.public_key.is_empty()));
+    }
+}
@@ -0,0 +1,7 @@
+pub mod add_signer;
+pub mod flush_cache;
+pub mod list_signers;
+pub mod pause;
+pub mod remove_signer;
+pub mod set_signer_availability;
+pub mod usage;
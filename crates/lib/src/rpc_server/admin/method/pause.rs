@@ -0,0 +1,35 @@
+use crate::{
+    error::KoraError,
+    state::{is_paused, set_paused},
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GetPauseResponse {
+    /// Whether signing methods are currently rejected
+    pub paused: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SetPauseRequest {
+    pub paused: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SetPauseResponse {
+    pub paused: bool,
+    pub previous: bool,
+}
+
+pub async fn get_pause() -> Result<GetPauseResponse, KoraError> {
+    Ok(GetPauseResponse { paused: is_paused() })
+}
+
+/// Pause or resume every method that signs or leases on this instance. Other instances
+/// behind the same load balancer need their own call.
+pub async fn set_pause(request: SetPauseRequest) -> Result<SetPauseResponse, KoraError> {
+    let previous = set_paused(request.paused);
+
+    Ok(SetPauseResponse { paused: request.paused, previous })
+}
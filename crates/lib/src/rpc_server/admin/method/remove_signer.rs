@@ -0,0 +1,109 @@
+use crate::{
+    config::Config,
+    constant::DEFAULT_SIGNER_REMOVAL_GRACE_SECONDS,
+    error::KoraError,
+    state::{get_signer_pool, modify_signer_pool},
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use utoipa::ToSchema;
+
+#[cfg(not(test))]
+use crate::state::get_config;
+
+#[cfg(test)]
+use crate::tests::config_mock::mock_state::get_config;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RemoveSignerRequest {
+    /// Public key of the signer to remove
+    pub signer_key: String,
+    /// How long the signer keeps serving requests pinned to it with `signer_key` before
+    /// it is removed. Defaults to 300 seconds, or the nonce lease duration if longer.
+    #[serde(default)]
+    pub grace_period_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RemoveSignerResponse {
+    pub signer_key: String,
+    /// Unix timestamp at which the signer is removed from the pool
+    pub removal_at: u64,
+}
+
+/// Long enough for clients to finish pinned flows, including transactions built on a
+/// durable nonce leased for the signer
+fn default_grace_period_seconds(config: &Config) -> u64 {
+    let nonce_pool = &config.kora.nonce_pool;
+    if nonce_pool.enabled {
+        DEFAULT_SIGNER_REMOVAL_GRACE_SECONDS.max(nonce_pool.lease_duration_seconds)
+    } else {
+        DEFAULT_SIGNER_REMOVAL_GRACE_SECONDS
+    }
+}
+
+/// Drain a signer now and remove it from the pool once the grace period is over. Setting
+/// the signer back to `active` before then cancels the removal.
+pub async fn remove_signer(
+    request: RemoveSignerRequest,
+) -> Result<RemoveSignerResponse, KoraError> {
+    let config = &get_config()?;
+    let grace_period_seconds =
+        request.grace_period_seconds.unwrap_or_else(|| default_grace_period_seconds(config));
+    let removal_at = (chrono::Utc::now().timestamp() as u64).saturating_add(grace_period_seconds);
+
+    get_signer_pool()?.schedule_signer_removal(&request.signer_key, removal_at)?;
+
+    if grace_period_seconds == 0 {
+        remove_scheduled_signer(&request.signer_key, removal_at)?;
+    } else {
+        let signer_key = request.signer_key.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(grace_period_seconds)).await;
+            if let Err(e) = remove_scheduled_signer(&signer_key, removal_at) {
+                log::error!("Failed to remove signer {signer_key}: {e}");
+            }
+        });
+    }
+
+    Ok(RemoveSignerResponse { signer_key: request.signer_key, removal_at })
+}
+
+fn remove_scheduled_signer(signer_key: &str, removal_at: u64) -> Result<(), KoraError> {
+    if modify_signer_pool(|pool| pool.without_scheduled_signer(signer_key, removal_at))? {
+        log::info!("Removed signer {signer_key} from the pool");
+    } else {
+        log::info!("Signer {signer_key} was not removed, its removal was cancelled");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{common::setup_or_get_test_signer, config_mock::ConfigMockBuilder};
+
+    #[tokio::test]
+    async fn test_remove_signer_unknown_signer() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+
+        let result = remove_signer(RemoveSignerRequest {
+            signer_key: solana_sdk::pubkey::Pubkey::new_unique().to_string(),
+            grace_period_seconds: Some(0),
+        })
+        .await;
+
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_default_grace_period_covers_nonce_leases() {
+        let mut config = ConfigMockBuilder::new().build();
+        assert_eq!(default_grace_period_seconds(&config), DEFAULT_SIGNER_REMOVAL_GRACE_SECONDS);
+
+        config.kora.nonce_pool.enabled = true;
+        config.kora.nonce_pool.lease_duration_seconds = 3600;
+        assert_eq!(default_grace_period_seconds(&config), 3600);
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{error::KoraError, signer::SignerAvailability, state::get_signer_pool};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SetSignerAvailabilityRequest {
+    /// Public key of the signer to update
+    pub signer_key: String,
+    /// `draining` stops pool selection but keeps serving requests pinned to the signer,
+    /// `disabled` stops all use, `active` restores it
+    pub availability: SignerAvailability,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SetSignerAvailabilityResponse {
+    pub signer_key: String,
+    pub previous: SignerAvailability,
+    pub availability: SignerAvailability,
+}
+
+/// Drain, disable or re-activate a signer. The change is local to this instance and is
+/// kept across config reloads. Re-activating a signer cancels its scheduled removal.
+pub async fn set_signer_availability(
+    request: SetSignerAvailabilityRequest,
+) -> Result<SetSignerAvailabilityResponse, KoraError> {
+    let previous =
+        get_signer_pool()?.set_signer_availability(&request.signer_key, request.availability)?;
+
+    Ok(SetSignerAvailabilityResponse {
+        signer_key: request.signer_key,
+        previous,
+        availability: request.availability,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{common::setup_or_get_test_signer, config_mock::ConfigMockBuilder};
+
+    #[tokio::test]
+    async fn test_set_signer_availability_unknown_signer() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+
+        let result = set_signer_availability(SetSignerAvailabilityRequest {
+            signer_key: solana_sdk::pubkey::Pubkey::new_unique().to_string(),
+            availability: SignerAvailability::Disabled,
+        })
+        .await;
+
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+    }
+}
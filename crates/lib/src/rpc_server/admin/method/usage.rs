@@ -0,0 +1,39 @@
+use crate::{
+    error::KoraError,
+    usage_limit::{UsageCounter, UsageTracker},
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UsageRequest {
+    /// User ID the counters are tracked under (the wallet address in paid mode)
+    pub user_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GetUsageResponse {
+    pub user_id: String,
+    /// Usage of each configured rule in its current window
+    pub counters: Vec<UsageCounter>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ResetUsageResponse {
+    pub user_id: String,
+    /// Number of counters reset
+    pub reset: usize,
+}
+
+pub async fn get_usage(request: UsageRequest) -> Result<GetUsageResponse, KoraError> {
+    let counters = UsageTracker::get_usage(&request.user_id).await?;
+
+    Ok(GetUsageResponse { user_id: request.user_id, counters })
+}
+
+/// Reset the user's counters in the current window of every rule
+pub async fn reset_usage(request: UsageRequest) -> Result<ResetUsageResponse, KoraError> {
+    let reset = UsageTracker::reset_usage(&request.user_id).await?;
+
+    Ok(ResetUsageResponse { user_id: request.user_id, reset })
+}
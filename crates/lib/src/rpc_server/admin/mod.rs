@@ -0,0 +1,6 @@
+pub mod method;
+pub mod rpc;
+pub mod server;
+
+pub use rpc::AdminRpc;
+pub use server::run_admin_server_if_required;
@@ -0,0 +1,119 @@
+use log::info;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use std::sync::Arc;
+
+use crate::{
+    error::KoraError,
+    rpc_server::{
+        admin::method::{
+            add_signer::{add_signer, AddSignerRequest, AddSignerResponse},
+            flush_cache::{flush_cache, FlushCacheRequest, FlushCacheResponse},
+            list_signers::{list_signers, ListSignersResponse},
+            pause::{get_pause, set_pause, GetPauseResponse, SetPauseRequest, SetPauseResponse},
+            remove_signer::{remove_signer, RemoveSignerRequest, RemoveSignerResponse},
+            set_signer_availability::{
+                set_signer_availability, SetSignerAvailabilityRequest,
+                SetSignerAvailabilityResponse,
+            },
+            usage::{get_usage, reset_usage, GetUsageResponse, ResetUsageResponse, UsageRequest},
+        },
+        method::reload_config::{reload_config, ReloadConfigResponse},
+    },
+};
+
+/// Operator methods served by the admin server, never by the public RPC server
+#[derive(Clone)]
+pub struct AdminRpc {
+    rpc_client: Arc<RpcClient>,
+}
+
+impl AdminRpc {
+    pub fn new(rpc_client: Arc<RpcClient>) -> Self {
+        Self { rpc_client }
+    }
+
+    pub async fn list_signers(&self) -> Result<ListSignersResponse, KoraError> {
+        info!("Admin list signers request received");
+        let result = list_signers().await;
+        info!("Admin list signers response: {result:?}");
+        result
+    }
+
+    pub async fn set_signer_availability(
+        &self,
+        request: SetSignerAvailabilityRequest,
+    ) -> Result<SetSignerAvailabilityResponse, KoraError> {
+        info!("Admin set signer availability request: {request:?}");
+        let result = set_signer_availability(request).await;
+        info!("Admin set signer availability response: {result:?}");
+        result
+    }
+
+    pub async fn add_signer(
+        &self,
+        request: AddSignerRequest,
+    ) -> Result<AddSignerResponse, KoraError> {
+        info!("Admin add signer request: {}", request.signer.name);
+        let result = add_signer(request).await;
+        info!("Admin add signer response: {result:?}");
+        result
+    }
+
+    pub async fn remove_signer(
+        &self,
+        request: RemoveSignerRequest,
+    ) -> Result<RemoveSignerResponse, KoraError> {
+        info!("Admin remove signer request: {request:?}");
+        let result = remove_signer(request).await;
+        info!("Admin remove signer response: {result:?}");
+        result
+    }
+
+    pub async fn reload_config(&self) -> Result<ReloadConfigResponse, KoraError> {
+        info!("Admin reload config request received");
+        let result = reload_config(&self.rpc_client).await;
+        info!("Admin reload config response: {result:?}");
+        result
+    }
+
+    pub async fn flush_cache(
+        &self,
+        request: FlushCacheRequest,
+    ) -> Result<FlushCacheResponse, KoraError> {
+        info!("Admin flush cache request: {request:?}");
+        let result = flush_cache(request).await;
+        info!("Admin flush cache response: {result:?}");
+        result
+    }
+
+    pub async fn get_usage(&self, request: UsageRequest) -> Result<GetUsageResponse, KoraError> {
+        info!("Admin get usage request: {request:?}");
+        let result = get_usage(request).await;
+        info!("Admin get usage response: {result:?}");
+        result
+    }
+
+    pub async fn reset_usage(
+        &self,
+        request: UsageRequest,
+    ) -> Result<ResetUsageResponse, KoraError> {
+        info!("Admin reset usage request: {request:?}");
+        let result = reset_usage(request).await;
+        info!("Admin reset usage response: {result:?}");
+        result
+    }
+
+    pub async fn get_pause(&self) -> Result<GetPauseResponse, KoraError> {
+        info!("Admin get pause request received");
+        let result = get_pause().await;
+        info!("Admin get pause response: {result:?}");
+        result
+    }
+
+    pub async fn set_pause(&self, request: SetPauseRequest) -> Result<SetPauseResponse, KoraError> {
+        info!("Admin set pause request: {request:?}");
+        let result = set_pause(request).await;
+        info!("Admin set pause response: {result:?}");
+        result
+    }
+}
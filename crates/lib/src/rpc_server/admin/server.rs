@@ -0,0 +1,162 @@
+use crate::rpc_server::{admin::rpc::AdminRpc, auth::ApiKeyAuthLayer};
+
+#[cfg(not(test))]
+use crate::state::get_config;
+
+#[cfg(test)]
+use crate::tests::config_mock::mock_state::get_config;
+use jsonrpsee::{
+    server::{ServerBuilder, ServerHandle},
+    RpcModule,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+macro_rules! register_admin_method {
+    // For methods without parameters
+    ($module:expr, $method_name:expr, $rpc_method:ident) => {
+        let _ =
+            $module.register_async_method($method_name, |_rpc_params, rpc_context| async move {
+                let rpc = rpc_context.as_ref();
+                rpc.$rpc_method().await.map_err(Into::into)
+            });
+    };
+
+    // For methods with parameters
+    ($module:expr, $method_name:expr, $rpc_method:ident, with_params) => {
+        let _ = $module.register_async_method($method_name, |rpc_params, rpc_context| async move {
+            let rpc = rpc_context.as_ref();
+            let params = rpc_params.parse()?;
+            rpc.$rpc_method(params).await.map_err(Into::into)
+        });
+    };
+}
+
+/// Start the admin server when `[kora.admin]` is enabled. It listens on its own port and
+/// only sits behind the admin API key, so none of the public server's middleware applies.
+pub async fn run_admin_server_if_required(
+    rpc_client: Arc<RpcClient>,
+    rpc_port: u16,
+) -> Result<Option<ServerHandle>, anyhow::Error> {
+    let admin_config = get_config()?.kora.admin.clone();
+
+    if !admin_config.enabled {
+        return Ok(None);
+    }
+
+    if admin_config.port == rpc_port {
+        return Err(anyhow::anyhow!(
+            "kora.admin port {} must differ from the RPC server port",
+            admin_config.port
+        ));
+    }
+
+    let api_key = admin_config.resolved_api_key().ok_or_else(|| {
+        anyhow::anyhow!("kora.admin is enabled but no admin api_key is configured")
+    })?;
+
+    let bind_address: IpAddr = admin_config.bind_address.parse().map_err(|e| {
+        anyhow::anyhow!("Invalid kora.admin bind_address '{}': {e}", admin_config.bind_address)
+    })?;
+    let addr = SocketAddr::new(bind_address, admin_config.port);
+    log::info!("Admin server started on {addr}");
+
+    let middleware = tower::ServiceBuilder::new().layer(ApiKeyAuthLayer::new(api_key));
+
+    let server =
+        ServerBuilder::default().set_middleware(middleware).http_only().build(addr).await?;
+
+    let admin_handle = server
+        .start(build_admin_module(AdminRpc::new(rpc_client)))
+        .map_err(|e| anyhow::anyhow!("Failed to start admin server: {}", e))?;
+
+    Ok(Some(admin_handle))
+}
+
+fn build_admin_module(rpc: AdminRpc) -> RpcModule<AdminRpc> {
+    let mut module = RpcModule::new(rpc);
+
+    register_admin_method!(module, "listSigners", list_signers);
+    register_admin_method!(module, "setSignerAvailability", set_signer_availability, with_params);
+    register_admin_method!(module, "addSigner", add_signer, with_params);
+    register_admin_method!(module, "removeSigner", remove_signer, with_params);
+    register_admin_method!(module, "reloadConfig", reload_config);
+    register_admin_method!(module, "flushCache", flush_cache, with_params);
+    register_admin_method!(module, "getUsage", get_usage, with_params);
+    register_admin_method!(module, "resetUsage", reset_usage, with_params);
+    register_admin_method!(module, "getPause", get_pause);
+    register_admin_method!(module, "setPause", set_pause, with_params);
+
+    module
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{
+        config_mock::{mock_state::setup_config_mock, ConfigMockBuilder},
+        rpc_mock::RpcMockBuilder,
+    };
+
+    #[test]
+    fn test_build_admin_module_registers_all_methods() {
+        let module = build_admin_module(AdminRpc::new(RpcMockBuilder::new().build()));
+
+        let mut methods: Vec<_> = module.method_names().collect();
+        methods.sort();
+        assert_eq!(
+            methods,
+            vec![
+                "addSigner",
+                "flushCache",
+                "getPause",
+                "getUsage",
+                "listSigners",
+                "reloadConfig",
+                "removeSigner",
+                "resetUsage",
+                "setPause",
+                "setSignerAvailability",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_admin_server_disabled_by_default() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+
+        let handle = run_admin_server_if_required(RpcMockBuilder::new().build(), 8080).await;
+
+        assert!(handle.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_admin_server_rejects_rpc_port() {
+        let mut config = ConfigMockBuilder::new().build();
+        config.kora.admin.enabled = true;
+        config.kora.admin.port = 8080;
+        config.kora.admin.api_key = Some("admin-key".to_string());
+        let _m = setup_config_mock(config);
+
+        let result = run_admin_server_if_required(RpcMockBuilder::new().build(), 8080).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_admin_server_rejects_invalid_bind_address() {
+        let mut config = ConfigMockBuilder::new().build();
+        config.kora.admin.enabled = true;
+        config.kora.admin.port = 8090;
+        config.kora.admin.bind_address = "localhost:8090".to_string();
+        config.kora.admin.api_key = Some("admin-key".to_string());
+        let _m = setup_config_mock(config);
+
+        let result = run_admin_server_if_required(RpcMockBuilder::new().build(), 8080).await;
+
+        assert!(result.unwrap_err().to_string().contains("Invalid kora.admin bind_address"));
+    }
+}
@@ -7,9 +7,32 @@ use crate::{
 use hmac::{Hmac, KeyInit, Mac};
 use http::{Request, Response, StatusCode};
 use jsonrpsee::server::logger::Body;
-use sha2::Sha256;
+use sha2::{Digest, Sha256};
 use subtle::ConstantTimeEq;
 
+tokio::task_local! {
+    /// API key the request being served authenticated with
+    static AUTHENTICATED_API_KEY: String;
+}
+
+/// Stable identifier of the API key the current request authenticated with, for keeping
+/// a client on the same signer when it sends no `user_id` and for binding nonce leases to
+/// the client. The key itself is hashed so it never reaches logs or the stores.
+pub fn authenticated_client_id() -> Option<String> {
+    AUTHENTICATED_API_KEY
+        .try_with(|api_key| {
+            let digest = Sha256::digest(api_key.as_bytes());
+            format!("api-key:{}", hex::encode(&digest[..8]))
+        })
+        .ok()
+}
+
+/// Runs `f` as if the request had authenticated with `api_key`
+#[cfg(test)]
+pub(crate) fn with_authenticated_api_key<R>(api_key: &str, f: impl FnOnce() -> R) -> R {
+    AUTHENTICATED_API_KEY.sync_scope(api_key.to_string(), f)
+}
+
 #[derive(Clone)]
 pub struct ApiKeyAuthLayer {
     api_key: String,
@@ -76,7 +99,8 @@ where
             if let Some(provided_key) = req.headers().get(X_API_KEY) {
                 // Constant-time comparison prevents timing attacks
                 if provided_key.as_bytes().ct_eq(api_key.as_bytes()).into() {
-                    return inner.call(req).await;
+                    // Methods run within this future, so they see the key they were called with
+                    return AUTHENTICATED_API_KEY.scope(api_key, inner.call(req)).await;
                 }
             }
 
@@ -273,6 +297,35 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn test_api_key_auth_exposes_client_id_to_inner_service() {
+        let seen = std::sync::Arc::new(std::sync::Mutex::new(None));
+        let inner = tower::service_fn({
+            let seen = seen.clone();
+            move |_: Request<Body>| {
+                let seen = seen.clone();
+                async move {
+                    *seen.lock().unwrap() = authenticated_client_id();
+                    Ok::<_, std::convert::Infallible>(Response::new(Body::empty()))
+                }
+            }
+        });
+        let mut service = ApiKeyAuthLayer::new("test-key".to_string()).layer(inner);
+        let request = Request::builder()
+            .uri("/test")
+            .header(X_API_KEY, "test-key")
+            .body(Body::from(r#"{"jsonrpc":"2.0","method":"getConfig","id":1}"#))
+            .unwrap();
+
+        let response = service.ready().await.unwrap().call(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let client_id = seen.lock().unwrap().clone().unwrap();
+        assert!(client_id.starts_with("api-key:"));
+        assert!(!client_id.contains("test-key"));
+        assert_eq!(authenticated_client_id(), None);
+    }
+
     #[tokio::test]
     async fn test_api_key_auth_invalid_key() {
         let layer = ApiKeyAuthLayer::new("test-key".to_string());
@@ -3,7 +3,7 @@ use crate::{
     error::KoraError,
     fee::fee::FeeConfigUtil,
     rpc_server::middleware_utils::default_sig_verify,
-    state::select_request_signer_with_signer_key,
+    state::select_request_signer_for_programs,
     validator::bundle_validator::BundleValidator,
 };
 use serde::{Deserialize, Serialize};
@@ -27,6 +27,9 @@ pub struct EstimateBundleFeeRequest {
     /// Optional signer signer_key to ensure consistency across related RPC calls
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub signer_key: Option<String>,
+    /// Optional user ID, served by the same signer across calls when signer affinity is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
     /// Whether to verify signatures during simulation (defaults to false)
     #[serde(default = "default_sig_verify")]
     pub sig_verify: bool,
@@ -53,6 +56,7 @@ pub async fn estimate_bundle_fee(
         transactions,
         fee_token,
         signer_key,
+        user_id,
         sig_verify,
         sign_only_indices,
     } = request;
@@ -69,7 +73,13 @@ pub async fn estimate_bundle_fee(
     let (transactions_to_process, _index_to_position) =
         BundleProcessor::extract_transactions_to_process(&transactions, sign_only_indices.clone())?;
 
-    let signer = select_request_signer_with_signer_key(signer_key.as_deref())?;
+    let signer = select_request_signer_for_programs(
+        signer_key.as_deref(),
+        user_id.as_deref(),
+        || BundleProcessor::get_program_ids(&transactions_to_process),
+        &config.validation.allowed_programs,
+    )
+    .await?;
     let fee_payer = signer.pubkey();
     let payment_destination = config.kora.get_payment_address(&fee_payer)?;
 
@@ -157,6 +167,7 @@ mod tests {
             transactions: vec![],
             fee_token: None,
             signer_key: None,
+            user_id: None,
             sig_verify: true,
             sign_only_indices: None,
         };
@@ -179,6 +190,7 @@ mod tests {
             transactions: vec!["some_tx".to_string()],
             fee_token: None,
             signer_key: None,
+            user_id: None,
             sig_verify: true,
             sign_only_indices: None,
         };
@@ -204,6 +216,7 @@ mod tests {
             transactions: vec!["tx".to_string(); 6],
             fee_token: None,
             signer_key: None,
+            user_id: None,
             sig_verify: true,
             sign_only_indices: None,
         };
@@ -229,6 +242,7 @@ mod tests {
             transactions: vec!["some_tx".to_string()],
             fee_token: None,
             signer_key: Some("invalid_pubkey".to_string()),
+            user_id: None,
             sig_verify: true,
             sign_only_indices: None,
         };
@@ -259,6 +273,7 @@ mod tests {
             transactions,
             fee_token: None,
             signer_key: None,
+            user_id: None,
             sig_verify: true,
             sign_only_indices: None,
         };
@@ -289,6 +304,7 @@ mod tests {
             transactions: vec![create_mock_encoded_transaction()],
             fee_token: None,
             signer_key: None,
+            user_id: None,
             sig_verify: true,
             sign_only_indices: None,
         };
@@ -351,6 +367,7 @@ mod tests {
             transactions: vec![create_mock_encoded_transaction()],
             fee_token: None,
             signer_key: None,
+            user_id: None,
             sig_verify: false,
             sign_only_indices: None,
         };
@@ -402,6 +419,7 @@ mod tests {
             transactions: vec![encoded_tx],
             fee_token: None,
             signer_key: Some(signer_pubkey.to_string()),
+            user_id: None,
             sig_verify: false,
             sign_only_indices: None,
         };
@@ -500,6 +518,7 @@ mod tests {
             transactions: vec![encoded_tx1, encoded_tx2],
             fee_token: None,
             signer_key: Some(fee_payer.to_string()),
+            user_id: None,
             sig_verify: false,
             sign_only_indices: None,
         };
@@ -6,9 +6,9 @@ use crate::{
     error::KoraError,
     fee::fee::FeeConfigUtil,
     rpc_server::middleware_utils::default_sig_verify,
-    state::select_request_signer_with_signer_key,
+    state::{config_for_signer, select_request_signer_for_transaction},
     token::token::TransferHookValidationFlow,
-    transaction::{TransactionUtil, VersionedTransactionResolved},
+    transaction::{ComputeBudgetUtil, TransactionUtil, VersionedTransactionResolved},
 };
 
 use serde::{Deserialize, Serialize};
@@ -35,9 +35,16 @@ pub struct EstimateTransactionFeeRequest {
     /// Optional public key of the signer to ensure consistency
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub signer_key: Option<String>,
+    /// Optional user ID, served by the same signer across calls when signer affinity is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
     /// Whether to verify signatures during simulation (defaults to false)
     #[serde(default = "default_sig_verify")]
     pub sig_verify: bool,
+    /// Estimate the fee with Kora's compute unit price and limit applied, as
+    /// `signTransaction` does with `inject_compute_budget` (defaults to false)
+    #[serde(default)]
+    pub inject_compute_budget: bool,
 }
 
 /// Response payload containing the estimated transaction fee.
@@ -57,16 +64,20 @@ pub async fn estimate_transaction_fee(
     rpc_client: &Arc<RpcClient>,
     request: EstimateTransactionFeeRequest,
 ) -> Result<EstimateTransactionFeeResponse, KoraError> {
-    let transaction = TransactionUtil::decode_b64_transaction(&request.transaction)?;
+    let mut transaction = TransactionUtil::decode_b64_transaction(&request.transaction)?;
 
-    let signer = select_request_signer_with_signer_key(request.signer_key.as_deref())?;
     let config = &get_config()?;
-    let payment_destination = config.kora.get_payment_address(&signer.pubkey())?;
-
-    let validation_config = &config.validation;
-    let fee_payer = signer.pubkey();
 
     let sig_verify = request.sig_verify || config.kora.force_sig_verify;
+    if request.inject_compute_budget {
+        ComputeBudgetUtil::inject_requested_compute_budget(
+            rpc_client,
+            &mut transaction,
+            &config.kora.compute_budget,
+            sig_verify,
+        )
+        .await?;
+    }
     let mut resolved_transaction = VersionedTransactionResolved::from_transaction(
         &transaction,
         config,
@@ -76,6 +87,21 @@ pub async fn estimate_transaction_fee(
     )
     .await?;
 
+    let signer = select_request_signer_for_transaction(
+        request.signer_key.as_deref(),
+        request.user_id.as_deref(),
+        config,
+        &mut resolved_transaction,
+        rpc_client,
+        None,
+    )
+    .await?;
+    let fee_payer = signer.pubkey();
+    let config = &config_for_signer(config, &fee_payer);
+    let payment_destination = config.kora.get_payment_address(&fee_payer)?;
+
+    let validation_config = &config.validation;
+
     let fee_calculation = FeeConfigUtil::estimate_kora_fee(
         &mut resolved_transaction,
         &fee_payer,
@@ -126,7 +152,9 @@ mod tests {
             transaction: "invalid_base64!@#$".to_string(),
             fee_token: None,
             signer_key: None,
+            user_id: None,
             sig_verify: true,
+            inject_compute_budget: false,
         };
 
         let result = estimate_transaction_fee(&rpc_client, request).await;
@@ -145,7 +173,9 @@ mod tests {
             transaction: create_mock_encoded_transaction(),
             fee_token: None,
             signer_key: Some("invalid_pubkey".to_string()),
+            user_id: None,
             sig_verify: true,
+            inject_compute_budget: false,
         };
 
         let result = estimate_transaction_fee(&rpc_client, request).await;
@@ -166,7 +196,9 @@ mod tests {
             transaction: create_mock_encoded_transaction(),
             fee_token: Some("invalid_mint_address".to_string()),
             signer_key: None,
+            user_id: None,
             sig_verify: true,
+            inject_compute_budget: false,
         };
 
         let result = estimate_transaction_fee(&rpc_client, request).await;
@@ -0,0 +1,441 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_keychain::SolanaSigner;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use std::{str::FromStr, sync::Arc};
+use utoipa::ToSchema;
+
+use crate::{
+    config::Config,
+    error::KoraError,
+    fee::fee::FeeConfigUtil,
+    rpc_server::middleware_utils::default_sig_verify,
+    state::{config_for_signer, select_request_signer_for_programs},
+    token::token::{TokenUtil, TransferHookValidationFlow},
+    transaction::{TransactionUtil, VersionedTransactionResolved},
+};
+
+#[cfg(not(test))]
+use crate::{cache::CacheUtil, state::get_config};
+
+#[cfg(test)]
+use crate::tests::{cache_mock::MockCacheUtil as CacheUtil, config_mock::mock_state::get_config};
+
+/// Request payload for building the fee payment instruction for a transaction.
+///
+/// Kora estimates the fee for the given transaction and returns the token transfer
+/// (and, if needed, the ATA creation) instruction the client must append so that
+/// the transaction pays Kora in `fee_token`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GetPaymentInstructionRequest {
+    /// Base64-encoded transaction to estimate the fee for
+    pub transaction: String,
+    /// Mint address of the SPL token to pay the fee in
+    pub fee_token: String,
+    /// The wallet that owns the paying token account and signs the payment transfer
+    pub source_wallet: String,
+    /// Optional public key of the signer to ensure consistency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer_key: Option<String>,
+    /// Optional user ID, served by the same signer across calls when signer affinity is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    /// Whether to verify signatures during simulation (defaults to false)
+    #[serde(default = "default_sig_verify")]
+    pub sig_verify: bool,
+}
+
+/// Account metadata of an instruction returned to the client.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct InstructionAccountMeta {
+    /// Account public key
+    pub pubkey: String,
+    /// Whether the account must sign the transaction
+    pub is_signer: bool,
+    /// Whether the account is writable
+    pub is_writable: bool,
+}
+
+/// An instruction ready to be appended to a client transaction.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SerializedInstruction {
+    /// Program invoked by the instruction
+    pub program_id: String,
+    /// Accounts referenced by the instruction, in order
+    pub accounts: Vec<InstructionAccountMeta>,
+    /// Base64-encoded instruction data
+    pub data: String,
+}
+
+impl From<&Instruction> for SerializedInstruction {
+    fn from(instruction: &Instruction) -> Self {
+        Self {
+            program_id: instruction.program_id.to_string(),
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|meta| InstructionAccountMeta {
+                    pubkey: meta.pubkey.to_string(),
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: STANDARD.encode(&instruction.data),
+        }
+    }
+}
+
+/// Response payload containing the fee payment instruction.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GetPaymentInstructionResponse {
+    /// Base64-encoded original transaction, unchanged
+    pub original_transaction: String,
+    /// Instructions to append to the transaction, in order. Contains the ATA creation
+    /// instruction for the payment destination when it does not exist yet, followed by
+    /// the token transfer instruction.
+    pub instructions: Vec<SerializedInstruction>,
+    /// Fee amount in the smallest unit of `payment_token`
+    pub payment_amount: u64,
+    /// Mint address of the token used for payment
+    pub payment_token: String,
+    /// Public key of the payment destination (owner of the destination token account)
+    pub payment_address: String,
+    /// Token account that receives the payment
+    pub payment_token_account: String,
+    /// Public key of the signer used for fee estimation (for client consistency)
+    pub signer_pubkey: String,
+}
+
+pub async fn get_payment_instruction(
+    rpc_client: &Arc<RpcClient>,
+    request: GetPaymentInstructionRequest,
+) -> Result<GetPaymentInstructionResponse, KoraError> {
+    let transaction = TransactionUtil::decode_b64_transaction(&request.transaction)?;
+
+    let source_wallet = Pubkey::from_str(&request.source_wallet)
+        .map_err(|e| KoraError::ValidationError(format!("Invalid source wallet address: {e}")))?;
+    let fee_token = Pubkey::from_str(&request.fee_token)
+        .map_err(|e| KoraError::ValidationError(format!("Invalid fee token address: {e}")))?;
+
+    let config = &get_config()?;
+    let signer = select_request_signer_for_programs(
+        request.signer_key.as_deref(),
+        request.user_id.as_deref(),
+        || Ok(TransactionUtil::get_program_ids(&transaction)),
+        &config.validation.allowed_programs,
+    )
+    .await?;
+    let fee_payer = signer.pubkey();
+    let config = &config_for_signer(config, &fee_payer);
+
+    if !config.validation.is_payment_required() {
+        return Err(KoraError::InvalidRequest(
+            "Payment is not required by the configured price model".to_string(),
+        ));
+    }
+
+    if !config.validation.supports_token(&request.fee_token) {
+        return Err(KoraError::UnsupportedFeeToken(request.fee_token));
+    }
+
+    let payment_address = config.kora.get_payment_address(&fee_payer)?;
+
+    let sig_verify = request.sig_verify || config.kora.force_sig_verify;
+    let mut resolved_transaction = VersionedTransactionResolved::from_transaction(
+        &transaction,
+        config,
+        rpc_client,
+        sig_verify,
+        None,
+    )
+    .await?;
+
+    let fee_calculation = FeeConfigUtil::estimate_kora_fee(
+        &mut resolved_transaction,
+        &fee_payer,
+        true,
+        rpc_client,
+        config,
+        TransferHookValidationFlow::ImmediateSignAndSend,
+        None,
+    )
+    .await?;
+
+    let payment_amount = FeeConfigUtil::calculate_fee_in_token(
+        fee_calculation.total_fee_lamports,
+        Some(&request.fee_token),
+        rpc_client,
+        config,
+    )
+    .await?
+    .ok_or_else(|| {
+        KoraError::FeeEstimationFailed("Fee in payment token could not be computed".to_string())
+    })?;
+
+    let (instructions, payment_token_account) = build_payment_instructions(
+        config,
+        rpc_client,
+        &fee_token,
+        &source_wallet,
+        &payment_address,
+        payment_amount,
+    )
+    .await?;
+
+    Ok(GetPaymentInstructionResponse {
+        original_transaction: request.transaction,
+        instructions: instructions.iter().map(SerializedInstruction::from).collect(),
+        payment_amount,
+        payment_token: fee_token.to_string(),
+        payment_address: payment_address.to_string(),
+        payment_token_account: payment_token_account.to_string(),
+        signer_pubkey: fee_payer.to_string(),
+    })
+}
+
+/// Builds the instructions that pay `payment_amount` of `fee_token` from `source_wallet`'s ATA
+/// to `payment_address`'s ATA, creating the destination ATA first when it does not exist.
+/// Returns them with the destination token account.
+async fn build_payment_instructions(
+    config: &Config,
+    rpc_client: &RpcClient,
+    fee_token: &Pubkey,
+    source_wallet: &Pubkey,
+    payment_address: &Pubkey,
+    payment_amount: u64,
+) -> Result<(Vec<Instruction>, Pubkey), KoraError> {
+    let mint = TokenUtil::get_mint(config, rpc_client, fee_token).await?;
+    let token_program = mint.get_token_program();
+
+    let source_token_account = token_program.get_associated_token_address(source_wallet, fee_token);
+    let payment_token_account =
+        token_program.get_associated_token_address(payment_address, fee_token);
+
+    let mut instructions = vec![];
+
+    match CacheUtil::get_account(config, rpc_client, &payment_token_account, false).await {
+        Ok(_) => {} // account exists, no ATA needed
+        Err(KoraError::AccountNotFound(_)) => {
+            // The paying wallet funds the destination ATA so the fee payer outflow (and
+            // therefore the quoted fee) is unaffected by the extra instruction.
+            instructions.push(token_program.create_associated_token_account_instruction(
+                source_wallet,
+                payment_address,
+                fee_token,
+            ));
+        }
+        Err(e) => return Err(e), // propagate real errors
+    }
+
+    instructions.push(
+        token_program
+            .create_transfer_checked_instruction(
+                &source_token_account,
+                fee_token,
+                &payment_token_account,
+                source_wallet,
+                payment_amount,
+                mint.decimals(),
+            )
+            .map_err(|e| {
+                KoraError::InvalidTransaction(format!("Failed to create payment instruction: {e}"))
+            })?,
+    );
+
+    Ok((instructions, payment_token_account))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fee::price::PriceModel,
+        tests::{
+            account_mock::create_mock_token_account,
+            common::{setup_or_get_test_signer, RpcMockBuilder},
+            config_mock::ConfigMockBuilder,
+            transaction_mock::create_mock_encoded_transaction,
+        },
+    };
+    use solana_sdk::instruction::AccountMeta;
+    use spl_associated_token_account_interface::program::id as ata_program_id;
+
+    fn create_request(fee_token: &str, source_wallet: &str) -> GetPaymentInstructionRequest {
+        GetPaymentInstructionRequest {
+            transaction: create_mock_encoded_transaction(),
+            fee_token: fee_token.to_string(),
+            source_wallet: source_wallet.to_string(),
+            signer_key: None,
+            user_id: None,
+            sig_verify: false,
+        }
+    }
+
+    #[test]
+    fn test_serialized_instruction_from_instruction() {
+        let program_id = Pubkey::new_unique();
+        let signer = Pubkey::new_unique();
+        let writable = Pubkey::new_unique();
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(signer, true),
+                AccountMeta::new(writable, false),
+            ],
+            data: vec![1, 2, 3],
+        };
+
+        let serialized = SerializedInstruction::from(&instruction);
+
+        assert_eq!(serialized.program_id, program_id.to_string());
+        assert_eq!(serialized.accounts.len(), 2);
+        assert_eq!(serialized.accounts[0].pubkey, signer.to_string());
+        assert!(serialized.accounts[0].is_signer);
+        assert!(!serialized.accounts[0].is_writable);
+        assert_eq!(serialized.accounts[1].pubkey, writable.to_string());
+        assert!(!serialized.accounts[1].is_signer);
+        assert!(serialized.accounts[1].is_writable);
+        assert_eq!(serialized.data, STANDARD.encode([1, 2, 3]));
+    }
+
+    #[tokio::test]
+    async fn test_get_payment_instruction_invalid_source_wallet() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+
+        let rpc_client = Arc::new(RpcMockBuilder::new().build());
+        let request = create_request(&Pubkey::new_unique().to_string(), "invalid");
+
+        let result = get_payment_instruction(&rpc_client, request).await;
+
+        assert!(matches!(
+            result,
+            Err(KoraError::ValidationError(message)) if message.contains("Invalid source wallet address")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_payment_instruction_invalid_fee_token() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+
+        let rpc_client = Arc::new(RpcMockBuilder::new().build());
+        let request = create_request("invalid", &Pubkey::new_unique().to_string());
+
+        let result = get_payment_instruction(&rpc_client, request).await;
+
+        assert!(matches!(
+            result,
+            Err(KoraError::ValidationError(message)) if message.contains("Invalid fee token address")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_payment_instruction_unsupported_fee_token() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+
+        let rpc_client = Arc::new(RpcMockBuilder::new().build());
+        let fee_token = Pubkey::new_unique().to_string();
+        let request = create_request(&fee_token, &Pubkey::new_unique().to_string());
+
+        let result = get_payment_instruction(&rpc_client, request).await;
+
+        assert_eq!(result.unwrap_err(), KoraError::UnsupportedFeeToken(fee_token));
+    }
+
+    #[tokio::test]
+    async fn test_get_payment_instruction_rejects_free_pricing() {
+        let _m = ConfigMockBuilder::new().with_price_model(PriceModel::Free).build_and_setup();
+        let _ = setup_or_get_test_signer();
+
+        let rpc_client = Arc::new(RpcMockBuilder::new().build());
+        let request =
+            create_request(&Pubkey::new_unique().to_string(), &Pubkey::new_unique().to_string());
+
+        let result = get_payment_instruction(&rpc_client, request).await;
+
+        assert!(matches!(result, Err(KoraError::InvalidRequest(_))));
+    }
+
+    /// The fee token mint is served by the RPC mock, the destination ATA by the cache mock
+    async fn build_with_destination_ata(
+        lookup: impl Fn(&Pubkey) -> Result<(), KoraError> + Send + 'static,
+    ) -> (Result<(Vec<Instruction>, Pubkey), KoraError>, Pubkey, Pubkey) {
+        let fee_token = Pubkey::new_unique();
+        let source_wallet = Pubkey::new_unique();
+        let payment_address = Pubkey::new_unique();
+
+        let cache_ctx = CacheUtil::get_account_context();
+        cache_ctx.checkpoint();
+        cache_ctx.expect().returning(move |_, _, pubkey, _| {
+            lookup(pubkey).map(|_| create_mock_token_account(&Pubkey::new_unique(), pubkey))
+        });
+
+        let config = get_config().unwrap();
+        let rpc_client = RpcMockBuilder::new().with_mint_account(6).build();
+        let result = build_payment_instructions(
+            &config,
+            &rpc_client,
+            &fee_token,
+            &source_wallet,
+            &payment_address,
+            1_000,
+        )
+        .await;
+
+        (result, source_wallet, payment_address)
+    }
+
+    #[tokio::test]
+    async fn test_build_payment_instructions_creates_missing_destination_ata() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+
+        let (result, source_wallet, payment_address) = build_with_destination_ata(|pubkey| {
+            Err(KoraError::AccountNotFound(pubkey.to_string()))
+        })
+        .await;
+        let (instructions, payment_token_account) = result.unwrap();
+
+        assert_eq!(instructions.len(), 2, "Expected ATA creation followed by the transfer");
+
+        let create_ata = &instructions[0];
+        assert_eq!(create_ata.program_id, ata_program_id());
+        assert_eq!(create_ata.accounts[0].pubkey, source_wallet, "Payer funds the new ATA");
+        assert_eq!(create_ata.accounts[1].pubkey, payment_token_account);
+        assert_eq!(create_ata.accounts[2].pubkey, payment_address);
+
+        let transfer = &instructions[1];
+        assert_eq!(transfer.program_id, spl_token_interface::id());
+        assert_eq!(transfer.accounts[2].pubkey, payment_token_account);
+    }
+
+    #[tokio::test]
+    async fn test_build_payment_instructions_skips_existing_destination_ata() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+
+        let (result, source_wallet, _) = build_with_destination_ata(|_| Ok(())).await;
+        let (instructions, payment_token_account) = result.unwrap();
+
+        assert_eq!(instructions.len(), 1, "Expected only the transfer");
+        assert!(instructions.iter().all(|ix| ix.program_id != ata_program_id()));
+
+        let transfer = &instructions[0];
+        assert_eq!(transfer.program_id, spl_token_interface::id());
+        assert_eq!(transfer.accounts[2].pubkey, payment_token_account);
+        assert_eq!(transfer.accounts[3].pubkey, source_wallet);
+    }
+
+    #[tokio::test]
+    async fn test_build_payment_instructions_propagates_lookup_errors() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+
+        let (result, _, _) = build_with_destination_ata(|_| {
+            Err(KoraError::RpcError("connection reset".to_string()))
+        })
+        .await;
+
+        assert!(matches!(result, Err(KoraError::RpcError(_))));
+    }
+}
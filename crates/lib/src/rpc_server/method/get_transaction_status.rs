@@ -0,0 +1,319 @@
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{hash::Hash, pubkey::Pubkey, signature::Signature};
+use std::{str::FromStr, sync::Arc};
+use utoipa::ToSchema;
+
+use crate::{
+    error::KoraError,
+    nonce::NoncePoolUtil,
+    sanitize_error,
+    transaction::{
+        get_transaction_status_store, record_transaction_status, TransactionStatus,
+        TransactionStatusRecord,
+    },
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GetTransactionStatusRequest {
+    /// Signature returned by signAndSendTransaction
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GetTransactionStatusResponse {
+    pub signature: String,
+    /// One of "queued", "sent", "confirmed", "failed" or "expired"
+    pub status: TransactionStatus,
+    /// Error message when the status is "failed"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+pub async fn get_transaction_status(
+    rpc_client: &Arc<RpcClient>,
+    request: GetTransactionStatusRequest,
+) -> Result<GetTransactionStatusResponse, KoraError> {
+    let signature = Signature::from_str(&request.signature)
+        .map_err(|e| KoraError::ValidationError(format!("Invalid signature: {e}")))?;
+
+    let record =
+        get_transaction_status_store().get(&request.signature).await?.ok_or_else(|| {
+            KoraError::InvalidRequest(format!(
+                "No status found for transaction {}",
+                request.signature
+            ))
+        })?;
+
+    let record = if record.status.is_final() {
+        record
+    } else {
+        refresh_status(rpc_client, &signature, record).await
+    };
+
+    Ok(GetTransactionStatusResponse {
+        signature: request.signature,
+        status: record.status,
+        error: record.error,
+    })
+}
+
+/// Resolves a pending (queued or sent) record against the chain. The RPC is the source
+/// of truth once the transaction lands; until then the record only expires when its
+/// blockhash is no longer valid, or for a durable transaction once its nonce account was
+/// advanced. RPC errors keep the stored record.
+async fn refresh_status(
+    rpc_client: &RpcClient,
+    signature: &Signature,
+    record: TransactionStatusRecord,
+) -> TransactionStatusRecord {
+    let refreshed = match rpc_client.get_signature_statuses(&[*signature]).await {
+        Ok(response) => match response.value.into_iter().next().flatten() {
+            Some(status) => match status.err {
+                Some(err) => {
+                    Some(record.with_status(TransactionStatus::Failed, Some(err.to_string())))
+                }
+                None if status.satisfies_commitment(CommitmentConfig::confirmed()) => {
+                    Some(record.with_status(TransactionStatus::Confirmed, None))
+                }
+                None => None,
+            },
+            None => match parse_expiry(&record) {
+                Some((blockhash, nonce_account)) => {
+                    match NoncePoolUtil::is_recent_blockhash_expired(
+                        rpc_client,
+                        &blockhash,
+                        nonce_account.as_ref(),
+                    )
+                    .await
+                    {
+                        Ok(true) => Some(record.with_status(TransactionStatus::Expired, None)),
+                        Ok(false) => None,
+                        Err(e) => {
+                            log::warn!("Failed to check blockhash validity: {e}");
+                            None
+                        }
+                    }
+                }
+                None => None,
+            },
+        },
+        Err(e) => {
+            log::warn!("Failed to get signature status for {signature}: {}", sanitize_error!(e));
+            None
+        }
+    };
+
+    match refreshed {
+        Some(refreshed) => {
+            record_transaction_status(&signature.to_string(), &refreshed).await;
+            refreshed
+        }
+        None => record,
+    }
+}
+
+/// Blockhash and, for a durable transaction, nonce account deciding a record's expiry
+fn parse_expiry(record: &TransactionStatusRecord) -> Option<(Hash, Option<Pubkey>)> {
+    let blockhash = Hash::from_str(&record.recent_blockhash).ok()?;
+    let nonce_account = match &record.nonce_account {
+        Some(nonce_account) => Some(Pubkey::from_str(nonce_account).ok()?),
+        None => None,
+    };
+    Some((blockhash, nonce_account))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::rpc_mock::RpcMockBuilder;
+    use serde_json::json;
+    use solana_client::rpc_request::RpcRequest;
+
+    async fn store_record(status: TransactionStatus) -> Signature {
+        let signature = Signature::new_unique();
+        let record = TransactionStatusRecord::new(status, Hash::new_unique().to_string());
+        record_transaction_status(&signature.to_string(), &record).await;
+        signature
+    }
+
+    fn request(signature: &Signature) -> GetTransactionStatusRequest {
+        GetTransactionStatusRequest { signature: signature.to_string() }
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction_status_invalid_signature() {
+        let rpc_client = RpcMockBuilder::new().build();
+        let request = GetTransactionStatusRequest { signature: "invalid".to_string() };
+
+        let result = get_transaction_status(&rpc_client, request).await;
+
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction_status_unknown_signature() {
+        let rpc_client = RpcMockBuilder::new().build();
+
+        let result = get_transaction_status(&rpc_client, request(&Signature::new_unique())).await;
+
+        assert!(matches!(result, Err(KoraError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction_status_final_status_skips_rpc() {
+        let signature = Signature::new_unique();
+        let record = TransactionStatusRecord::new(TransactionStatus::Queued, "hash".to_string())
+            .with_status(TransactionStatus::Failed, Some("node unavailable".to_string()));
+        record_transaction_status(&signature.to_string(), &record).await;
+        let rpc_client = RpcMockBuilder::new().build();
+
+        let response = get_transaction_status(&rpc_client, request(&signature)).await.unwrap();
+
+        assert_eq!(response.status, TransactionStatus::Failed);
+        assert_eq!(response.error.as_deref(), Some("node unavailable"));
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction_status_sent_becomes_confirmed() {
+        let signature = store_record(TransactionStatus::Sent).await;
+        let rpc_client = RpcMockBuilder::new()
+            .with_custom_mock(
+                RpcRequest::GetSignatureStatuses,
+                json!({
+                    "context": { "slot": 1 },
+                    "value": [{
+                        "slot": 1,
+                        "confirmations": null,
+                        "err": null,
+                        "status": { "Ok": null },
+                        "confirmationStatus": "confirmed"
+                    }]
+                }),
+            )
+            .build();
+
+        let response = get_transaction_status(&rpc_client, request(&signature)).await.unwrap();
+
+        assert_eq!(response.status, TransactionStatus::Confirmed);
+        assert_eq!(response.error, None);
+
+        let stored =
+            get_transaction_status_store().get(&signature.to_string()).await.unwrap().unwrap();
+        assert_eq!(stored.status, TransactionStatus::Confirmed);
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction_status_sent_becomes_failed_on_chain_error() {
+        let signature = store_record(TransactionStatus::Sent).await;
+        let rpc_client = RpcMockBuilder::new()
+            .with_custom_mock(
+                RpcRequest::GetSignatureStatuses,
+                json!({
+                    "context": { "slot": 1 },
+                    "value": [{
+                        "slot": 1,
+                        "confirmations": null,
+                        "err": "AccountInUse",
+                        "status": { "Err": "AccountInUse" },
+                        "confirmationStatus": "confirmed"
+                    }]
+                }),
+            )
+            .build();
+
+        let response = get_transaction_status(&rpc_client, request(&signature)).await.unwrap();
+
+        assert_eq!(response.status, TransactionStatus::Failed);
+        assert!(response.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction_status_sent_becomes_expired() {
+        let signature = store_record(TransactionStatus::Sent).await;
+        let rpc_client = RpcMockBuilder::new()
+            .with_custom_mock(
+                RpcRequest::GetSignatureStatuses,
+                json!({ "context": { "slot": 1 }, "value": [null] }),
+            )
+            .with_custom_mock(
+                RpcRequest::IsBlockhashValid,
+                json!({ "context": { "slot": 1 }, "value": false }),
+            )
+            .build();
+
+        let response = get_transaction_status(&rpc_client, request(&signature)).await.unwrap();
+
+        assert_eq!(response.status, TransactionStatus::Expired);
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction_status_queued_stays_pending_while_blockhash_valid() {
+        let signature = store_record(TransactionStatus::Queued).await;
+        let rpc_client = RpcMockBuilder::new()
+            .with_custom_mock(
+                RpcRequest::GetSignatureStatuses,
+                json!({ "context": { "slot": 1 }, "value": [null] }),
+            )
+            .with_custom_mock(
+                RpcRequest::IsBlockhashValid,
+                json!({ "context": { "slot": 1 }, "value": true }),
+            )
+            .build();
+
+        let response = get_transaction_status(&rpc_client, request(&signature)).await.unwrap();
+
+        assert_eq!(response.status, TransactionStatus::Queued);
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction_status_durable_expires_once_nonce_advanced() {
+        use solana_nonce::{
+            state::{Data, DurableNonce, State},
+            versions::Versions,
+        };
+        use solana_sdk::account::Account;
+        use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
+
+        let nonce_account = Pubkey::new_unique();
+        let nonce_account_holding = |blockhash: &Hash| {
+            let durable_nonce = DurableNonce::from_blockhash(blockhash);
+            let data = Data::new(Pubkey::new_unique(), durable_nonce, 5000);
+            Account {
+                lamports: 1_447_680,
+                data: bincode::serialize(&Versions::new(State::Initialized(data))).unwrap(),
+                owner: SYSTEM_PROGRAM_ID,
+                executable: false,
+                rent_epoch: 0,
+            }
+        };
+        let seed = Hash::new_unique();
+        let nonce = *DurableNonce::from_blockhash(&seed).as_hash();
+
+        let signature = Signature::new_unique();
+        let record = TransactionStatusRecord::new(TransactionStatus::Sent, nonce.to_string())
+            .with_nonce_account(Some(nonce_account.to_string()));
+        record_transaction_status(&signature.to_string(), &record).await;
+        let not_found = json!({ "context": { "slot": 1 }, "value": [null] });
+
+        // The nonce is far older than any valid blockhash, but still unused
+        let rpc_client = RpcMockBuilder::new()
+            .with_custom_mock(RpcRequest::GetSignatureStatuses, not_found.clone())
+            .with_custom_mock(
+                RpcRequest::IsBlockhashValid,
+                json!({ "context": { "slot": 1 }, "value": false }),
+            )
+            .with_account_info(&nonce_account_holding(&seed))
+            .build();
+        let response = get_transaction_status(&rpc_client, request(&signature)).await.unwrap();
+        assert_eq!(response.status, TransactionStatus::Sent);
+
+        let rpc_client = RpcMockBuilder::new()
+            .with_custom_mock(RpcRequest::GetSignatureStatuses, not_found)
+            .with_account_info(&nonce_account_holding(&Hash::new_unique()))
+            .build();
+        let response = get_transaction_status(&rpc_client, request(&signature)).await.unwrap();
+        assert_eq!(response.status, TransactionStatus::Expired);
+    }
+}
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::{
+    error::KoraError,
+    nonce::NoncePoolUtil,
+    state::{ensure_not_paused, select_request_signer_for_programs},
+};
+
+#[cfg(not(test))]
+use crate::state::get_config;
+
+#[cfg(test)]
+use crate::tests::config_mock::mock_state::get_config;
+
+/// Request payload for leasing a durable nonce account.
+///
+/// The leased nonce lets a transaction stay valid until the nonce is advanced, instead of
+/// expiring with its blockhash. Build the transaction with
+/// `AdvanceNonceAccount(nonce_account, nonce_authority)` as its first instruction,
+/// `nonce` as its recent blockhash and `nonce_authority` as fee payer.
+///
+/// The lease is bound to the API key of the request: only that client can sign with the
+/// nonce or release it.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LeaseNonceRequest {
+    /// Optional public key of the signer that should be the nonce authority
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer_key: Option<String>,
+    /// Optional user ID, served by the same signer across calls when signer affinity is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LeaseNonceResponse {
+    /// Leased nonce account
+    pub nonce_account: String,
+    /// Kora signer that is the nonce authority; pass it as `signer_key` when signing
+    pub nonce_authority: String,
+    /// Current durable nonce, to use as the transaction's recent blockhash
+    pub nonce: String,
+    /// Unix timestamp when the lease expires and the nonce can be leased again
+    pub expires_at: i64,
+}
+
+pub async fn lease_nonce(
+    rpc_client: &Arc<RpcClient>,
+    request: LeaseNonceRequest,
+) -> Result<LeaseNonceResponse, KoraError> {
+    ensure_not_paused()?;
+
+    let config = get_config()?;
+    // The leased nonce is advanced through the System program with the signer as fee payer
+    let signer = select_request_signer_for_programs(
+        request.signer_key.as_deref(),
+        request.user_id.as_deref(),
+        || Ok(vec![SYSTEM_PROGRAM_ID]),
+        &config.validation.allowed_programs,
+    )
+    .await?;
+
+    let (lease, nonce) = NoncePoolUtil::lease_nonce(&config, rpc_client, &signer).await?;
+
+    Ok(LeaseNonceResponse {
+        nonce_account: lease.nonce_account,
+        nonce_authority: lease.nonce_authority,
+        nonce: nonce.to_string(),
+        expires_at: lease.expires_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{
+        common::{setup_or_get_test_signer, RpcMockBuilder},
+        config_mock::ConfigMockBuilder,
+    };
+
+    #[tokio::test]
+    async fn test_lease_nonce_pool_disabled() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+
+        let rpc_client = RpcMockBuilder::new().build();
+
+        let result =
+            lease_nonce(&rpc_client, LeaseNonceRequest { signer_key: None, user_id: None }).await;
+
+        assert!(matches!(
+            result,
+            Err(KoraError::ValidationError(message)) if message.contains("Nonce pool is not enabled")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_lease_nonce_invalid_signer_key() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+
+        let rpc_client = RpcMockBuilder::new().build();
+        let request =
+            LeaseNonceRequest { signer_key: Some("invalid_pubkey".to_string()), user_id: None };
+
+        let result = lease_nonce(&rpc_client, request).await;
+
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+    }
+}
@@ -3,10 +3,17 @@ pub mod estimate_transaction_fee;
 pub mod get_blockhash;
 pub mod get_config;
 pub mod get_payer_signer;
+pub mod get_payment_instruction;
 pub mod get_supported_tokens;
+pub mod get_transaction_status;
 pub mod get_version;
+pub mod lease_nonce;
+pub mod release_nonce;
+pub mod reload_config;
 pub mod sign_and_send_bundle;
 pub mod sign_and_send_transaction;
 pub mod sign_bundle;
 pub mod sign_transaction;
+pub mod sign_transactions;
 pub mod transfer_transaction;
+pub mod validate_transaction;
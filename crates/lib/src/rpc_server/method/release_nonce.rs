@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+use crate::{error::KoraError, nonce::NoncePoolUtil};
+
+/// Request payload for ending a nonce lease before it expires.
+///
+/// Only the client that leased the nonce can release it. Kora advances the nonce before
+/// leasing the account again, so transactions signed with it can no longer land.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReleaseNonceRequest {
+    /// Nonce account returned by leaseNonce
+    pub nonce_account: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReleaseNonceResponse {
+    /// Released nonce account
+    pub nonce_account: String,
+}
+
+pub async fn release_nonce(
+    request: ReleaseNonceRequest,
+) -> Result<ReleaseNonceResponse, KoraError> {
+    let nonce_account = Pubkey::from_str(&request.nonce_account)
+        .map_err(|e| KoraError::ValidationError(format!("Invalid nonce account: {e}")))?;
+
+    NoncePoolUtil::release_nonce(&nonce_account).await?;
+
+    Ok(ReleaseNonceResponse { nonce_account: request.nonce_account })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_release_nonce_invalid_account() {
+        let request = ReleaseNonceRequest { nonce_account: "invalid".to_string() };
+
+        let result = release_nonce(request).await;
+
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_release_nonce_not_leased() {
+        let request = ReleaseNonceRequest { nonce_account: Pubkey::new_unique().to_string() };
+
+        let result = release_nonce(request).await;
+
+        assert!(matches!(result, Err(KoraError::InvalidRequest(_))));
+    }
+}
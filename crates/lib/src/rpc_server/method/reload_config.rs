@@ -0,0 +1,28 @@
+use crate::{admin::config_reload::get_config_reloader, error::KoraError};
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+/// Result of re-reading the config and signers files
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReloadConfigResponse {
+    /// Validator warnings for the new config
+    pub warnings: Vec<String>,
+    /// Settings that changed but only take effect after a restart (e.g. auth, rate_limit)
+    pub restart_required: Vec<String>,
+    /// Whether the signer pool was rebuilt from the signers config
+    pub signers_reloaded: bool,
+}
+
+/// Re-read the config and signers files and swap them in if they pass validation.
+/// Fails without changing anything when the new config is invalid.
+pub async fn reload_config(rpc_client: &Arc<RpcClient>) -> Result<ReloadConfigResponse, KoraError> {
+    let report = get_config_reloader()?.reload(rpc_client).await?;
+
+    Ok(ReloadConfigResponse {
+        warnings: report.warnings,
+        restart_required: report.restart_required,
+        signers_reloaded: report.signers_reloaded,
+    })
+}
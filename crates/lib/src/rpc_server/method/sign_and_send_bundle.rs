@@ -1,7 +1,9 @@
 use crate::{
     bundle::{BundleError, BundleProcessingMode, BundleProcessor, JitoBundleClient, JitoError},
+    idempotency::IdempotencyUtil,
     plugin::PluginExecutionContext,
     rpc_server::middleware_utils::default_sig_verify,
+    state::ensure_not_paused,
     transaction::TransactionUtil,
     validator::bundle_validator::BundleValidator,
     KoraError,
@@ -13,10 +15,10 @@ use std::sync::Arc;
 use utoipa::ToSchema;
 
 #[cfg(not(test))]
-use crate::state::{get_config, select_request_signer_with_signer_key};
+use crate::state::{get_config, select_request_signer_for_programs};
 
 #[cfg(test)]
-use crate::state::select_request_signer_with_signer_key;
+use crate::state::select_request_signer_for_programs;
 #[cfg(test)]
 use crate::tests::config_mock::mock_state::get_config;
 
@@ -36,9 +38,13 @@ pub struct SignAndSendBundleRequest {
     /// Optional indices of transactions to sign (defaults to all if not specified)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sign_only_indices: Option<Vec<usize>>,
+    /// Optional client-chosen key. Retrying with the same key returns the original
+    /// response without signing, sending or counting the bundle again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
 }
 
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SignAndSendBundleResponse {
     /// Array of base64-encoded signed transactions
     pub signed_transactions: Vec<String>,
@@ -52,12 +58,32 @@ pub async fn sign_and_send_bundle(
     rpc_client: &Arc<RpcClient>,
     request: SignAndSendBundleRequest,
 ) -> Result<SignAndSendBundleResponse, KoraError> {
+    let fingerprint = IdempotencyUtil::fingerprint("signAndSendBundle", &request.transactions);
+    let idempotency_key = request.idempotency_key.clone();
+    let user_id = request.user_id.clone();
+
+    IdempotencyUtil::run_once(
+        idempotency_key.as_deref(),
+        user_id.as_deref(),
+        fingerprint,
+        sign_and_send(rpc_client, request),
+    )
+    .await
+}
+
+async fn sign_and_send(
+    rpc_client: &Arc<RpcClient>,
+    request: SignAndSendBundleRequest,
+) -> Result<SignAndSendBundleResponse, KoraError> {
+    ensure_not_paused()?;
+
     let SignAndSendBundleRequest {
         transactions,
         signer_key,
         sig_verify,
         user_id,
         sign_only_indices,
+        ..
     } = request;
     let config = &get_config()?;
 
@@ -72,7 +98,13 @@ pub async fn sign_and_send_bundle(
     let (transactions_to_process, index_to_position) =
         BundleProcessor::extract_transactions_to_process(&transactions, sign_only_indices.clone())?;
 
-    let signer = select_request_signer_with_signer_key(signer_key.as_deref())?;
+    let signer = select_request_signer_for_programs(
+        signer_key.as_deref(),
+        user_id.as_deref(),
+        || BundleProcessor::get_program_ids(&transactions_to_process),
+        &config.validation.allowed_programs,
+    )
+    .await?;
     let fee_payer = signer.pubkey();
     let payment_destination = config.kora.get_payment_address(&fee_payer)?;
 
@@ -138,6 +170,11 @@ pub async fn sign_and_send_bundle(
     }
 
     // Send the full merged bundle to Jito for atomic execution.
+    if let Some(signature) =
+        signed_resolved.first().and_then(|resolved| resolved.transaction.signatures.first())
+    {
+        IdempotencyUtil::record_broadcast(&signature.to_string());
+    }
     let jito_client = JitoBundleClient::new(&config.kora.bundle.jito);
     let bundle_uuid = jito_client.send_bundle(&signed_transactions).await?;
 
@@ -178,6 +215,7 @@ mod tests {
             sig_verify: true,
             user_id: None,
             sign_only_indices: None,
+            idempotency_key: None,
         };
 
         let result = sign_and_send_bundle(&rpc_client, request).await;
@@ -200,6 +238,7 @@ mod tests {
             sig_verify: true,
             user_id: None,
             sign_only_indices: None,
+            idempotency_key: None,
         };
 
         let result = sign_and_send_bundle(&rpc_client, request).await;
@@ -225,6 +264,7 @@ mod tests {
             sig_verify: true,
             user_id: None,
             sign_only_indices: None,
+            idempotency_key: None,
         };
 
         let result = sign_and_send_bundle(&rpc_client, request).await;
@@ -250,6 +290,7 @@ mod tests {
             sig_verify: true,
             user_id: None,
             sign_only_indices: None,
+            idempotency_key: None,
         };
 
         let result = sign_and_send_bundle(&rpc_client, request).await;
@@ -335,6 +376,7 @@ mod tests {
             sig_verify: true,
             user_id: None,
             sign_only_indices: Some(vec![1]),
+            idempotency_key: None,
         };
 
         let result = sign_and_send_bundle(&rpc_client, request).await;
@@ -408,6 +450,7 @@ mod tests {
             sig_verify: true,
             user_id: None,
             sign_only_indices: Some(vec![1]),
+            idempotency_key: None,
         };
 
         let result = sign_and_send_bundle(&rpc_client, request).await;
@@ -529,6 +572,7 @@ mod tests {
             sig_verify: false,
             user_id: None,
             sign_only_indices: None,
+            idempotency_key: None,
         };
 
         let result = sign_and_send_bundle(&rpc_client, request).await;
@@ -1,5 +1,8 @@
 use crate::{
+    idempotency::IdempotencyUtil,
     rpc_server::middleware_utils::default_sig_verify,
+    state::ensure_not_paused,
+    token::token::TransferHookValidationFlow,
     transaction::{
         RespondAfter, TransactionUtil, VersionedTransactionOps, VersionedTransactionResolved,
     },
@@ -13,10 +16,10 @@ use std::sync::Arc;
 use utoipa::ToSchema;
 
 #[cfg(not(test))]
-use crate::state::{get_config, select_request_signer_with_signer_key};
+use crate::state::{get_config, select_request_signer_for_transaction};
 
 #[cfg(test)]
-use crate::state::select_request_signer_with_signer_key;
+use crate::state::select_request_signer_for_transaction;
 #[cfg(test)]
 use crate::tests::config_mock::mock_state::get_config;
 
@@ -38,9 +41,13 @@ pub struct SignAndSendTransactionRequest {
     /// broadcasts in the background.
     #[serde(default)]
     pub respond_after: RespondAfter,
+    /// Optional client-chosen key. Retrying with the same key returns the original
+    /// response without signing, sending or counting the transaction again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
 }
 
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SignAndSendTransactionResponse {
     pub signed_transaction: String,
     /// Public key of the signer used (for client consistency)
@@ -53,13 +60,32 @@ pub async fn sign_and_send_transaction(
     rpc_client: &Arc<RpcClient>,
     request: SignAndSendTransactionRequest,
 ) -> Result<SignAndSendTransactionResponse, KoraError> {
+    let fingerprint = IdempotencyUtil::fingerprint(
+        "signAndSendTransaction",
+        std::slice::from_ref(&request.transaction),
+    );
+    let idempotency_key = request.idempotency_key.clone();
+    let user_id = request.user_id.clone();
+
+    IdempotencyUtil::run_once(
+        idempotency_key.as_deref(),
+        user_id.as_deref(),
+        fingerprint,
+        sign_and_send(rpc_client, request),
+    )
+    .await
+}
+
+async fn sign_and_send(
+    rpc_client: &Arc<RpcClient>,
+    request: SignAndSendTransactionRequest,
+) -> Result<SignAndSendTransactionResponse, KoraError> {
+    ensure_not_paused()?;
+
     let transaction = TransactionUtil::decode_b64_transaction(&request.transaction)?;
 
     let config = &get_config()?;
 
-    let signer = select_request_signer_with_signer_key(request.signer_key.as_deref())?;
-    let fee_payer = signer.pubkey();
-
     let sig_verify = request.sig_verify || config.kora.force_sig_verify;
     let mut resolved_transaction = VersionedTransactionResolved::from_transaction(
         &transaction,
@@ -70,6 +96,17 @@ pub async fn sign_and_send_transaction(
     )
     .await?;
 
+    let signer = select_request_signer_for_transaction(
+        request.signer_key.as_deref(),
+        request.user_id.as_deref(),
+        config,
+        &mut resolved_transaction,
+        rpc_client,
+        Some(TransferHookValidationFlow::ImmediateSignAndSend),
+    )
+    .await?;
+    let fee_payer = signer.pubkey();
+
     // Check usage limit for transaction sender
     UsageTracker::check_transaction_usage_limit(
         config,
@@ -115,6 +152,7 @@ mod tests {
             sig_verify: true,
             user_id: None,
             respond_after: RespondAfter::Confirmed,
+            idempotency_key: None,
         };
 
         let result = sign_and_send_transaction(&rpc_client, request).await;
@@ -122,6 +160,30 @@ mod tests {
         assert!(result.is_err(), "Should fail with decode error");
     }
 
+    #[tokio::test]
+    async fn test_sign_and_send_transaction_failure_releases_idempotency_key() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+
+        let rpc_client = Arc::new(RpcMockBuilder::new().build());
+
+        for _ in 0..2 {
+            let request = SignAndSendTransactionRequest {
+                transaction: "invalid_base64!@#$".to_string(),
+                signer_key: None,
+                sig_verify: true,
+                user_id: None,
+                respond_after: RespondAfter::Confirmed,
+                idempotency_key: Some("failing-request".to_string()),
+            };
+
+            let result = sign_and_send_transaction(&rpc_client, request).await;
+
+            // A retry re-runs the request instead of reporting it as in progress
+            assert!(matches!(result, Err(KoraError::InvalidTransaction(_))));
+        }
+    }
+
     #[tokio::test]
     async fn test_sign_and_send_transaction_invalid_signer_key() {
         let _m = ConfigMockBuilder::new().build_and_setup();
@@ -137,6 +199,7 @@ mod tests {
             sig_verify: true,
             user_id: None,
             respond_after: RespondAfter::Confirmed,
+            idempotency_key: None,
         };
 
         let result = sign_and_send_transaction(&rpc_client, request).await;
@@ -161,6 +224,7 @@ mod tests {
             sig_verify: true,
             user_id: None,
             respond_after: RespondAfter::Signed,
+            idempotency_key: None,
         };
 
         let result = sign_and_send_transaction(&rpc_client, request).await;
@@ -183,6 +247,7 @@ mod tests {
             sig_verify: true,
             user_id: None,
             respond_after: RespondAfter::Signed,
+            idempotency_key: None,
         };
 
         let result = sign_and_send_transaction(&rpc_client, request).await;
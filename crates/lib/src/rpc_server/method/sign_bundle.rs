@@ -2,6 +2,7 @@ use crate::{
     bundle::{BundleError, BundleProcessingMode, BundleProcessor, JitoError},
     plugin::PluginExecutionContext,
     rpc_server::middleware_utils::default_sig_verify,
+    state::ensure_not_paused,
     transaction::TransactionUtil,
     validator::bundle_validator::BundleValidator,
     KoraError,
@@ -13,10 +14,10 @@ use std::sync::Arc;
 use utoipa::ToSchema;
 
 #[cfg(not(test))]
-use crate::state::{get_config, select_request_signer_with_signer_key};
+use crate::state::{get_config, select_request_signer_for_programs};
 
 #[cfg(test)]
-use crate::state::select_request_signer_with_signer_key;
+use crate::state::select_request_signer_for_programs;
 #[cfg(test)]
 use crate::tests::config_mock::mock_state::get_config;
 
@@ -50,6 +51,8 @@ pub async fn sign_bundle(
     rpc_client: &Arc<RpcClient>,
     request: SignBundleRequest,
 ) -> Result<SignBundleResponse, KoraError> {
+    ensure_not_paused()?;
+
     let SignBundleRequest { transactions, signer_key, sig_verify, user_id, sign_only_indices } =
         request;
     let config = &get_config()?;
@@ -65,7 +68,13 @@ pub async fn sign_bundle(
     let (transactions_to_process, index_to_position) =
         BundleProcessor::extract_transactions_to_process(&transactions, sign_only_indices.clone())?;
 
-    let signer = select_request_signer_with_signer_key(signer_key.as_deref())?;
+    let signer = select_request_signer_for_programs(
+        signer_key.as_deref(),
+        user_id.as_deref(),
+        || BundleProcessor::get_program_ids(&transactions_to_process),
+        &config.validation.allowed_programs,
+    )
+    .await?;
     let fee_payer = signer.pubkey();
     let payment_destination = config.kora.get_payment_address(&fee_payer)?;
 
@@ -1,6 +1,10 @@
 use crate::{
     rpc_server::middleware_utils::default_sig_verify,
-    transaction::{TransactionUtil, VersionedTransactionOps, VersionedTransactionResolved},
+    state::ensure_not_paused,
+    token::token::TransferHookValidationFlow,
+    transaction::{
+        ComputeBudgetUtil, TransactionUtil, VersionedTransactionOps, VersionedTransactionResolved,
+    },
     usage_limit::UsageTracker,
     KoraError,
 };
@@ -11,10 +15,10 @@ use std::sync::Arc;
 use utoipa::ToSchema;
 
 #[cfg(not(test))]
-use crate::state::{get_config, select_request_signer_with_signer_key};
+use crate::state::{get_config, select_request_signer_for_transaction};
 
 #[cfg(test)]
-use crate::state::select_request_signer_with_signer_key;
+use crate::state::select_request_signer_for_transaction;
 #[cfg(test)]
 use crate::tests::config_mock::mock_state::get_config;
 
@@ -37,6 +41,11 @@ pub struct SignTransactionRequest {
     /// Optional user ID for usage tracking (required when pricing is Free and usage tracking is enabled)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub user_id: Option<String>,
+    /// Let Kora set the compute unit price and limit (defaults to false). Replaces any
+    /// existing SetComputeUnitPrice/SetComputeUnitLimit instructions and clears all other
+    /// signatures, so the returned transaction must be signed again by the client.
+    #[serde(default)]
+    pub inject_compute_budget: bool,
 }
 
 /// Response payload containing the signed transaction.
@@ -52,14 +61,22 @@ pub async fn sign_transaction(
     rpc_client: &Arc<RpcClient>,
     request: SignTransactionRequest,
 ) -> Result<SignTransactionResponse, KoraError> {
-    let transaction = TransactionUtil::decode_b64_transaction(&request.transaction)?;
+    ensure_not_paused()?;
 
-    let config = &get_config()?;
+    let mut transaction = TransactionUtil::decode_b64_transaction(&request.transaction)?;
 
-    let signer = select_request_signer_with_signer_key(request.signer_key.as_deref())?;
-    let fee_payer = signer.pubkey();
+    let config = &get_config()?;
 
     let sig_verify = request.sig_verify || config.kora.force_sig_verify;
+    if request.inject_compute_budget {
+        ComputeBudgetUtil::inject_requested_compute_budget(
+            rpc_client,
+            &mut transaction,
+            &config.kora.compute_budget,
+            sig_verify,
+        )
+        .await?;
+    }
     let mut resolved_transaction = VersionedTransactionResolved::from_transaction(
         &transaction,
         config,
@@ -69,6 +86,17 @@ pub async fn sign_transaction(
     )
     .await?;
 
+    let signer = select_request_signer_for_transaction(
+        request.signer_key.as_deref(),
+        request.user_id.as_deref(),
+        config,
+        &mut resolved_transaction,
+        rpc_client,
+        Some(TransferHookValidationFlow::DelayedSigning),
+    )
+    .await?;
+    let fee_payer = signer.pubkey();
+
     // Check usage limit for transaction sender
     UsageTracker::check_transaction_usage_limit(
         config,
@@ -135,6 +163,7 @@ mod tests {
             signer_key: None,
             sig_verify: true,
             user_id: None,
+            inject_compute_budget: false,
         };
 
         let result = sign_transaction(&rpc_client, request).await;
@@ -156,6 +185,7 @@ mod tests {
             signer_key: Some("invalid_pubkey".to_string()),
             sig_verify: true,
             user_id: None,
+            inject_compute_budget: false,
         };
 
         let result = sign_transaction(&rpc_client, request).await;
@@ -165,6 +195,32 @@ mod tests {
         assert!(matches!(error, KoraError::ValidationError(_)), "Should return ValidationError");
     }
 
+    #[tokio::test]
+    async fn test_sign_transaction_inject_compute_budget_disabled() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+
+        let _ = setup_or_get_test_usage_limiter().await;
+
+        let rpc_client = Arc::new(RpcMockBuilder::new().build());
+
+        let request = SignTransactionRequest {
+            transaction: create_mock_encoded_transaction(),
+            signer_key: None,
+            sig_verify: false,
+            user_id: None,
+            inject_compute_budget: true,
+        };
+
+        let result = sign_transaction(&rpc_client, request).await;
+
+        assert!(matches!(
+            result,
+            Err(KoraError::ValidationError(message))
+                if message.contains("Compute budget injection is not enabled")
+        ));
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_sign_transaction_pre_sign_validation_error_does_not_pin_probe_lock() {
@@ -181,6 +237,7 @@ mod tests {
             signer_key: Some(target_pubkey.clone()),
             sig_verify: true,
             user_id: None,
+            inject_compute_budget: false,
         };
 
         let result = sign_transaction(&rpc_client, request).await;
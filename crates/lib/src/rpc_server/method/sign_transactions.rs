@@ -0,0 +1,206 @@
+use crate::{
+    rpc_server::{
+        method::sign_transaction::{sign_transaction, SignTransactionRequest},
+        middleware_utils::default_sig_verify,
+    },
+    state::ensure_not_paused,
+    KoraError,
+};
+use futures::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+#[cfg(not(test))]
+use crate::state::get_config;
+
+#[cfg(test)]
+use crate::tests::config_mock::mock_state::get_config;
+
+/// Request payload for signing a batch of independent transactions.
+///
+/// Unlike signBundle, the transactions are unrelated: each one is validated, checked
+/// against usage limits and signed on its own, in parallel. A rejected transaction is
+/// reported in its result and does not fail the rest of the batch.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SignTransactionsRequest {
+    /// Array of base64-encoded Solana transactions (at most `kora.batch.max_transactions`)
+    pub transactions: Vec<String>,
+    /// Optional public key of the signer to ensure consistency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer_key: Option<String>,
+    /// Whether to verify signatures during simulation (defaults to false)
+    #[serde(default = "default_sig_verify")]
+    pub sig_verify: bool,
+    /// Optional user ID for usage tracking, applied to every transaction in the batch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    /// Let Kora set the compute unit price and limit of each transaction (defaults to false)
+    #[serde(default)]
+    pub inject_compute_budget: bool,
+}
+
+/// Error of a single transaction in the batch, in the same shape as a JSON-RPC error
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SignTransactionsItemError {
+    pub code: i32,
+    pub message: String,
+    pub error_type: String,
+}
+
+/// Result of a single transaction, in request order. Exactly one of
+/// `signed_transaction` and `error` is set.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SignTransactionsItem {
+    /// Base64-encoded transaction signed by the Kora fee payer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signed_transaction: Option<String>,
+    /// Public key of the signer used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer_pubkey: Option<String>,
+    /// Why the transaction was not signed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<SignTransactionsItemError>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SignTransactionsResponse {
+    /// One result per request transaction, in request order
+    pub results: Vec<SignTransactionsItem>,
+}
+
+impl From<Result<(String, String), KoraError>> for SignTransactionsItem {
+    fn from(result: Result<(String, String), KoraError>) -> Self {
+        match result {
+            Ok((signed_transaction, signer_pubkey)) => Self {
+                signed_transaction: Some(signed_transaction),
+                signer_pubkey: Some(signer_pubkey),
+                error: None,
+            },
+            Err(e) => Self {
+                signed_transaction: None,
+                signer_pubkey: None,
+                error: Some(SignTransactionsItemError {
+                    code: e.error_code() as i32,
+                    message: e.to_string(),
+                    error_type: format!("{:?}", e.error_code()),
+                }),
+            },
+        }
+    }
+}
+
+pub async fn sign_transactions(
+    rpc_client: &Arc<RpcClient>,
+    request: SignTransactionsRequest,
+) -> Result<SignTransactionsResponse, KoraError> {
+    ensure_not_paused()?;
+
+    let SignTransactionsRequest {
+        transactions,
+        signer_key,
+        sig_verify,
+        user_id,
+        inject_compute_budget,
+    } = request;
+    let config = &get_config()?;
+
+    if transactions.is_empty() {
+        return Err(KoraError::ValidationError("No transactions provided".to_string()));
+    }
+    let max_transactions = config.kora.batch.max_transactions;
+    if transactions.len() > max_transactions {
+        return Err(KoraError::ValidationError(format!(
+            "Too many transactions: {} (max {max_transactions})",
+            transactions.len()
+        )));
+    }
+
+    // Sign at most `max_concurrency` transactions at a time and put each result back in
+    // request order
+    let mut results: Vec<Option<SignTransactionsItem>> =
+        std::iter::repeat_with(|| None).take(transactions.len()).collect();
+    let mut signed =
+        stream::iter(transactions.into_iter().enumerate().map(|(index, transaction)| {
+            let request = SignTransactionRequest {
+                transaction,
+                signer_key: signer_key.clone(),
+                sig_verify,
+                user_id: user_id.clone(),
+                inject_compute_budget,
+            };
+            async move {
+                let result = sign_transaction(rpc_client, request)
+                    .await
+                    .map(|response| (response.signed_transaction, response.signer_pubkey));
+                (index, result)
+            }
+        }))
+        .buffer_unordered(config.kora.batch.max_concurrency.max(1));
+
+    while let Some((index, result)) = signed.next().await {
+        results[index] = Some(result.into());
+    }
+
+    Ok(SignTransactionsResponse { results: results.into_iter().flatten().collect() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::KoraErrorCode,
+        tests::{
+            common::{setup_or_get_test_signer, setup_or_get_test_usage_limiter, RpcMockBuilder},
+            config_mock::ConfigMockBuilder,
+        },
+    };
+
+    fn request(transactions: Vec<String>) -> SignTransactionsRequest {
+        SignTransactionsRequest {
+            transactions,
+            signer_key: None,
+            sig_verify: true,
+            user_id: None,
+            inject_compute_budget: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sign_transactions_rejects_empty_and_oversized_batches() {
+        let _m = ConfigMockBuilder::new().with_max_batch_transactions(2).build_and_setup();
+        let _ = setup_or_get_test_signer();
+
+        let rpc_client = Arc::new(RpcMockBuilder::new().build());
+
+        let result = sign_transactions(&rpc_client, request(vec![])).await;
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+
+        let result = sign_transactions(&rpc_client, request(vec!["tx".to_string(); 3])).await;
+        assert!(matches!(
+            result,
+            Err(KoraError::ValidationError(message)) if message.contains("max 2")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sign_transactions_reports_errors_per_item() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+
+        let _ = setup_or_get_test_usage_limiter().await;
+
+        let rpc_client = Arc::new(RpcMockBuilder::new().build());
+
+        let transactions = vec!["invalid_base64!@#$".to_string(), "also invalid".to_string()];
+        let response = sign_transactions(&rpc_client, request(transactions)).await.unwrap();
+
+        assert_eq!(response.results.len(), 2);
+        for item in &response.results {
+            assert!(item.signed_transaction.is_none());
+            let error = item.error.as_ref().unwrap();
+            assert_eq!(error.code, KoraErrorCode::InvalidTransaction as i32);
+        }
+    }
+}
@@ -3,14 +3,16 @@ use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_keychain::SolanaSigner;
 use solana_message::Message;
 use solana_sdk::{message::VersionedMessage, pubkey::Pubkey};
-use solana_system_interface::instruction::transfer;
+use solana_system_interface::{instruction::transfer, program::ID as SYSTEM_PROGRAM_ID};
+use spl_associated_token_account_interface::program::id as ata_program_id;
 use std::{str::FromStr, sync::Arc};
 use utoipa::ToSchema;
 
 use crate::{
     constant::NATIVE_SOL,
-    state::select_request_signer_with_signer_key,
-    transaction::{TransactionUtil, VersionedMessageExt},
+    state::{ensure_not_paused, select_request_signer_for_programs},
+    token::token::TokenUtil,
+    transaction::{ComputeBudgetUtil, TransactionUtil, VersionedMessageExt},
     validator::transaction_validator::TransactionValidator,
     CacheUtil, KoraError,
 };
@@ -40,6 +42,9 @@ pub struct TransferTransactionRequest {
     /// Optional public key of the signer to ensure consistency
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub signer_key: Option<String>,
+    /// Optional user ID, served by the same signer across calls when signer affinity is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
 }
 
 /// **DEPRECATED**: Use `getPaymentInstruction` instead for fee payment flows.
@@ -66,11 +71,9 @@ pub async fn transfer_transaction(
     rpc_client: &Arc<RpcClient>,
     request: TransferTransactionRequest,
 ) -> Result<TransferTransactionResponse, KoraError> {
-    let signer = select_request_signer_with_signer_key(request.signer_key.as_deref())?;
-    let config = &get_config()?;
-    let signer_pubkey = signer.pubkey();
+    ensure_not_paused()?;
 
-    let validator = TransactionValidator::new(config, signer_pubkey)?;
+    let config = &get_config()?;
 
     let source = Pubkey::from_str(&request.source)
         .map_err(|e| KoraError::ValidationError(format!("Invalid source address: {e}")))?;
@@ -79,6 +82,39 @@ pub async fn transfer_transaction(
     let token_mint = Pubkey::from_str(&request.token)
         .map_err(|e| KoraError::ValidationError(format!("Invalid token address: {e}")))?;
 
+    // The transfer's programs only depend on the token and on whether the destination
+    // token account exists, so they are known before the fee payer is selected
+    let (program_ids, create_destination_ata) = if request.token == NATIVE_SOL {
+        (vec![SYSTEM_PROGRAM_ID], false)
+    } else {
+        let token_program =
+            TokenUtil::get_mint(config, rpc_client, &token_mint).await?.get_token_program();
+        let dest_ata = token_program.get_associated_token_address(&destination, &token_mint);
+
+        let create_destination_ata =
+            match CacheUtil::get_account(config, rpc_client, &dest_ata, false).await {
+                Ok(_) => false, // account exists, no ATA needed
+                Err(KoraError::AccountNotFound(_)) => true,
+                Err(e) => return Err(e), // propagate real errors
+            };
+        let mut program_ids = vec![token_program.program_id()];
+        if create_destination_ata {
+            program_ids.push(ata_program_id());
+        }
+        (program_ids, create_destination_ata)
+    };
+
+    let signer = select_request_signer_for_programs(
+        request.signer_key.as_deref(),
+        request.user_id.as_deref(),
+        || Ok(program_ids),
+        &config.validation.allowed_programs,
+    )
+    .await?;
+    let signer_pubkey = signer.pubkey();
+
+    let validator = TransactionValidator::new(config, signer_pubkey)?;
+
     // Check source and destination are not disallowed
     if validator.is_disallowed_account(&source) {
         return Err(KoraError::InvalidTransaction(format!(
@@ -114,17 +150,13 @@ pub async fn transfer_transaction(
             },
         )?;
 
-        match CacheUtil::get_account(config, rpc_client, &dest_ata, false).await {
-            Ok(_) => {} // account exists, no ATA needed
-            Err(KoraError::AccountNotFound(_)) => {
-                // Create ATA for destination if it doesn't exist (Kora pays for ATA creation)
-                instructions.push(token_program.create_associated_token_account_instruction(
-                    &signer_pubkey, // Kora pays for ATA creation
-                    &destination,
-                    &token_mint.address(),
-                ));
-            }
-            Err(e) => return Err(e), // propagate real errors
+        if create_destination_ata {
+            // Create ATA for destination if it doesn't exist (Kora pays for ATA creation)
+            instructions.push(token_program.create_associated_token_account_instruction(
+                &signer_pubkey, // Kora pays for ATA creation
+                &destination,
+                &token_mint.address(),
+            ));
         }
 
         instructions.push(
@@ -152,7 +184,15 @@ pub async fn transfer_transaction(
         Some(&signer_pubkey), // Kora as fee payer
         &blockhash,
     ));
-    let transaction = TransactionUtil::new_unsigned_versioned_transaction(message);
+    let mut transaction = TransactionUtil::new_unsigned_versioned_transaction(message);
+    if config.kora.compute_budget.enabled {
+        ComputeBudgetUtil::inject_compute_budget(
+            rpc_client,
+            &mut transaction,
+            &config.kora.compute_budget,
+        )
+        .await?;
+    }
 
     let encoded = TransactionUtil::encode_versioned_transaction(&transaction)?;
     let message_encoded = transaction.message.encode_b64_message()?;
@@ -193,6 +233,7 @@ mod tests {
             source: "invalid".to_string(),
             destination: Pubkey::new_unique().to_string(),
             signer_key: None,
+            user_id: None,
         };
 
         let result = transfer_transaction(&rpc_client, request).await;
@@ -222,6 +263,7 @@ mod tests {
             source: Pubkey::new_unique().to_string(),
             destination: "invalid".to_string(),
             signer_key: None,
+            user_id: None,
         };
 
         let result = transfer_transaction(&rpc_client, request).await;
@@ -250,6 +292,7 @@ mod tests {
             source: Pubkey::new_unique().to_string(),
             destination: Pubkey::new_unique().to_string(),
             signer_key: None,
+            user_id: None,
         };
 
         let result = transfer_transaction(&rpc_client, request).await;
@@ -318,6 +361,7 @@ mod tests {
             source: "invalid".to_string(),
             destination: Pubkey::new_unique().to_string(),
             signer_key: Some(target_pubkey.clone()),
+            user_id: None,
         };
 
         let result = transfer_transaction(&rpc_client, request).await;
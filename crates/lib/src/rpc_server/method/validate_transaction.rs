@@ -0,0 +1,219 @@
+use crate::{
+    rpc_server::middleware_utils::default_sig_verify,
+    state::config_for_signer,
+    token::token::TransferHookValidationFlow,
+    transaction::{TransactionUtil, VersionedTransactionResolved},
+    validator::transaction_validator::{TransactionValidator, ValidationFinding, ValidationRule},
+    KoraError,
+};
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_keychain::SolanaSigner;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+#[cfg(not(test))]
+use crate::state::{
+    get_config, select_request_signer_for_programs, select_request_signer_for_transaction,
+};
+
+#[cfg(test)]
+use crate::state::{select_request_signer_for_programs, select_request_signer_for_transaction};
+#[cfg(test)]
+use crate::tests::config_mock::mock_state::get_config;
+
+/// Request payload for a dry-run validation of a transaction.
+///
+/// Runs the same checks as `signTransaction` but reports every violated policy
+/// instead of the first one. The transaction is never signed and usage limits
+/// are checked without being consumed.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ValidateTransactionRequest {
+    /// Base64-encoded Solana transaction
+    pub transaction: String,
+    /// Optional public key of the signer to validate against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer_key: Option<String>,
+    /// Whether to verify signatures during simulation (defaults to false)
+    #[serde(default = "default_sig_verify")]
+    pub sig_verify: bool,
+    /// Optional user ID for usage limit checks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+}
+
+/// Response payload listing every violated policy.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ValidateTransactionResponse {
+    /// Whether signTransaction would accept the transaction (no findings)
+    pub valid: bool,
+    /// Every violated policy, empty when the transaction is valid
+    pub findings: Vec<ValidationFinding>,
+    /// Public key of the signer the transaction was validated against
+    pub signer_pubkey: String,
+}
+
+pub async fn validate_transaction(
+    rpc_client: &Arc<RpcClient>,
+    request: ValidateTransactionRequest,
+) -> Result<ValidateTransactionResponse, KoraError> {
+    let transaction = TransactionUtil::decode_b64_transaction(&request.transaction)?;
+
+    let config = &get_config()?;
+
+    let sig_verify = request.sig_verify || config.kora.force_sig_verify;
+    let (fee_payer, findings) = match VersionedTransactionResolved::from_transaction(
+        &transaction,
+        config,
+        rpc_client,
+        sig_verify,
+        None,
+    )
+    .await
+    {
+        Ok(mut resolved_transaction) => {
+            // Validates against the signer signTransaction would route the transaction to
+            let fee_payer = match select_request_signer_for_transaction(
+                request.signer_key.as_deref(),
+                request.user_id.as_deref(),
+                config,
+                &mut resolved_transaction,
+                rpc_client,
+                Some(TransferHookValidationFlow::DelayedSigning),
+            )
+            .await
+            {
+                Ok(signer) => signer.pubkey(),
+                // Every signer's policy rejects the transaction, so the findings of the
+                // first one explain why
+                Err(KoraError::InvalidTransaction(_) | KoraError::ValidationError(_))
+                    if request.signer_key.is_none() =>
+                {
+                    select_request_signer_for_programs(
+                        None,
+                        request.user_id.as_deref(),
+                        || Ok(TransactionUtil::get_program_ids(&transaction)),
+                        &config.validation.allowed_programs,
+                    )
+                    .await?
+                    .pubkey()
+                }
+                Err(e) => return Err(e),
+            };
+            let config = &config_for_signer(config, &fee_payer);
+            let findings = TransactionValidator::new(config, fee_payer)?
+                .collect_findings(
+                    config,
+                    &mut resolved_transaction,
+                    rpc_client,
+                    request.user_id.as_deref(),
+                )
+                .await?;
+            (fee_payer, findings)
+        }
+        // Inner instructions can't be resolved, so no other check can run
+        Err(e @ KoraError::InvalidTransaction(_)) => {
+            let signer = select_request_signer_for_programs(
+                request.signer_key.as_deref(),
+                request.user_id.as_deref(),
+                || Ok(TransactionUtil::get_program_ids(&transaction)),
+                &config.validation.allowed_programs,
+            )
+            .await?;
+            (signer.pubkey(), vec![ValidationFinding::new(ValidationRule::Simulation, None, &e)])
+        }
+        Err(e) => return Err(e),
+    };
+
+    Ok(ValidateTransactionResponse {
+        valid: findings.is_empty(),
+        findings,
+        signer_pubkey: fee_payer.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{
+        common::{setup_or_get_test_signer, setup_or_get_test_usage_limiter, RpcMockBuilder},
+        config_mock::ConfigMockBuilder,
+        transaction_mock::create_mock_encoded_transaction,
+    };
+    use serde_json::json;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_compute_budget_interface::ComputeBudgetInstruction;
+    use solana_message::{Message, VersionedMessage};
+    use solana_sdk::pubkey::Pubkey;
+
+    #[tokio::test]
+    async fn test_validate_transaction_invalid_encoding() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+
+        let rpc_client = RpcMockBuilder::new().build();
+        let request = ValidateTransactionRequest {
+            transaction: "invalid_base64!@#$".to_string(),
+            signer_key: None,
+            sig_verify: true,
+            user_id: None,
+        };
+
+        let result = validate_transaction(&rpc_client, request).await;
+
+        assert!(matches!(result, Err(KoraError::InvalidTransaction(_))));
+    }
+
+    #[tokio::test]
+    async fn test_validate_transaction_reports_findings_without_signing() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+        setup_or_get_test_usage_limiter().await.unwrap();
+
+        let rpc_client = RpcMockBuilder::new().with_simulation().build();
+        let message = VersionedMessage::Legacy(Message::new(
+            &[ComputeBudgetInstruction::set_compute_unit_limit(200_000)],
+            Some(&Pubkey::new_unique()),
+        ));
+        let transaction = TransactionUtil::new_unsigned_versioned_transaction(message);
+        let request = ValidateTransactionRequest {
+            transaction: TransactionUtil::encode_versioned_transaction(&transaction).unwrap(),
+            signer_key: None,
+            sig_verify: false,
+            user_id: None,
+        };
+
+        let response = validate_transaction(&rpc_client, request).await.unwrap();
+
+        assert!(!response.valid);
+        assert_eq!(response.findings.len(), 1);
+        assert_eq!(response.findings[0].rule, ValidationRule::TransactionStructure);
+    }
+
+    #[tokio::test]
+    async fn test_validate_transaction_simulation_failure_is_a_finding() {
+        let _m = ConfigMockBuilder::new().build_and_setup();
+        let _ = setup_or_get_test_signer();
+
+        let rpc_client = RpcMockBuilder::new()
+            .with_custom_mock(
+                RpcRequest::SimulateTransaction,
+                json!({
+                    "context": { "slot": 1 },
+                    "value": { "err": "AccountNotFound", "logs": [], "accounts": null }
+                }),
+            )
+            .build();
+        let request = ValidateTransactionRequest {
+            transaction: create_mock_encoded_transaction(),
+            signer_key: None,
+            sig_verify: false,
+            user_id: None,
+        };
+
+        let response = validate_transaction(&rpc_client, request).await.unwrap();
+
+        assert!(!response.valid);
+        assert_eq!(response.findings[0].rule, ValidationRule::Simulation);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod admin;
 pub mod args;
 pub mod auth;
 pub mod method;
@@ -1,13 +1,15 @@
 use crate::{
     config::{
-        AltInstructionPolicy, EnabledMethods, FeePayerPolicy, LoaderV4InstructionPolicy,
-        NonceInstructionPolicy, ProgramsConfig, SplTokenConfig, SplTokenInstructionPolicy,
+        AltInstructionPolicy, AnchorConfig, AnchorConstraint, AnchorIdlConfig, EnabledMethods,
+        FeePayerPolicy, LoaderV4InstructionPolicy, NonceInstructionPolicy, ProgramRule,
+        ProgramsConfig, SplTokenConfig, SplTokenInstructionPolicy, StakeInstructionPolicy,
         SystemInstructionPolicy, Token2022Config, Token2022InstructionPolicy, TransferHookPolicy,
         ValidationConfig,
     },
     fee::price::{PriceConfig, PriceModel},
     oracle::oracle::{PriceSource, TokenPrice},
-    transaction::RespondAfter,
+    transaction::{RespondAfter, TransactionStatus},
+    validator::transaction_validator::{ValidationFinding, ValidationRule},
 };
 use std::path::PathBuf;
 use utoipa::{
@@ -23,13 +25,26 @@ use crate::rpc_server::{
         get_blockhash::GetBlockhashResponse,
         get_config::GetConfigResponse,
         get_payer_signer::GetPayerSignerResponse,
+        get_payment_instruction::{
+            GetPaymentInstructionRequest, GetPaymentInstructionResponse, InstructionAccountMeta,
+            SerializedInstruction,
+        },
         get_supported_tokens::GetSupportedTokensResponse,
+        get_transaction_status::{GetTransactionStatusRequest, GetTransactionStatusResponse},
         get_version::GetVersionResponse,
+        lease_nonce::{LeaseNonceRequest, LeaseNonceResponse},
+        release_nonce::{ReleaseNonceRequest, ReleaseNonceResponse},
+        reload_config::ReloadConfigResponse,
         sign_and_send_transaction::{
             SignAndSendTransactionRequest, SignAndSendTransactionResponse,
         },
         sign_transaction::{SignTransactionRequest, SignTransactionResponse},
+        sign_transactions::{
+            SignTransactionsItem, SignTransactionsItemError, SignTransactionsRequest,
+            SignTransactionsResponse,
+        },
         transfer_transaction::{TransferTransactionRequest, TransferTransactionResponse},
+        validate_transaction::{ValidateTransactionRequest, ValidateTransactionResponse},
     },
     KoraRpc,
 };
@@ -55,8 +70,13 @@ const JSON_CONTENT_TYPE: &str = "application/json";
         Token2022InstructionPolicy,
         AltInstructionPolicy,
         LoaderV4InstructionPolicy,
+        StakeInstructionPolicy,
         SplTokenConfig,
         ProgramsConfig,
+        ProgramRule,
+        AnchorConfig,
+        AnchorIdlConfig,
+        AnchorConstraint,
         Token2022Config,
         TransferHookPolicy,
         EnabledMethods,
@@ -67,15 +87,35 @@ const JSON_CONTENT_TYPE: &str = "application/json";
         GetBlockhashResponse,
         GetConfigResponse,
         GetPayerSignerResponse,
+        GetPaymentInstructionRequest,
+        GetPaymentInstructionResponse,
+        InstructionAccountMeta,
+        SerializedInstruction,
         GetSupportedTokensResponse,
+        GetTransactionStatusRequest,
+        GetTransactionStatusResponse,
+        LeaseNonceRequest,
+        LeaseNonceResponse,
+        ReleaseNonceRequest,
+        ReleaseNonceResponse,
+        ReloadConfigResponse,
         GetVersionResponse,
         RespondAfter,
+        TransactionStatus,
         SignAndSendTransactionRequest,
         SignAndSendTransactionResponse,
         SignTransactionRequest,
         SignTransactionResponse,
+        SignTransactionsRequest,
+        SignTransactionsResponse,
+        SignTransactionsItem,
+        SignTransactionsItemError,
         TransferTransactionRequest,
         TransferTransactionResponse,
+        ValidateTransactionRequest,
+        ValidateTransactionResponse,
+        ValidationFinding,
+        ValidationRule,
     ))
 )]
 pub struct ApiDoc;
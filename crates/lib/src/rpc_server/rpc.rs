@@ -20,8 +20,17 @@ use crate::rpc_server::method::{
     get_blockhash::{get_blockhash, GetBlockhashResponse},
     get_config::{get_config, GetConfigResponse},
     get_payer_signer::{get_payer_signer, GetPayerSignerResponse},
+    get_payment_instruction::{
+        get_payment_instruction, GetPaymentInstructionRequest, GetPaymentInstructionResponse,
+    },
     get_supported_tokens::{get_supported_tokens, GetSupportedTokensResponse},
+    get_transaction_status::{
+        get_transaction_status, GetTransactionStatusRequest, GetTransactionStatusResponse,
+    },
     get_version::{get_version, GetVersionResponse},
+    lease_nonce::{lease_nonce, LeaseNonceRequest, LeaseNonceResponse},
+    release_nonce::{release_nonce, ReleaseNonceRequest, ReleaseNonceResponse},
+    reload_config::{reload_config, ReloadConfigResponse},
     sign_and_send_bundle::{
         sign_and_send_bundle, SignAndSendBundleRequest, SignAndSendBundleResponse,
     },
@@ -30,9 +39,13 @@ use crate::rpc_server::method::{
     },
     sign_bundle::{sign_bundle, SignBundleRequest, SignBundleResponse},
     sign_transaction::{sign_transaction, SignTransactionRequest, SignTransactionResponse},
+    sign_transactions::{sign_transactions, SignTransactionsRequest, SignTransactionsResponse},
     transfer_transaction::{
         transfer_transaction, TransferTransactionRequest, TransferTransactionResponse,
     },
+    validate_transaction::{
+        validate_transaction, ValidateTransactionRequest, ValidateTransactionResponse,
+    },
 };
 
 #[derive(Clone)]
@@ -96,6 +109,46 @@ impl KoraRpc {
         result
     }
 
+    pub async fn get_payment_instruction(
+        &self,
+        request: GetPaymentInstructionRequest,
+    ) -> Result<GetPaymentInstructionResponse, KoraError> {
+        info!("Get payment instruction request: {request:?}");
+        let result = get_payment_instruction(&self.rpc_client, request).await;
+        info!("Get payment instruction response: {result:?}");
+        result
+    }
+
+    pub async fn get_transaction_status(
+        &self,
+        request: GetTransactionStatusRequest,
+    ) -> Result<GetTransactionStatusResponse, KoraError> {
+        info!("Get transaction status request: {request:?}");
+        let result = get_transaction_status(&self.rpc_client, request).await;
+        info!("Get transaction status response: {result:?}");
+        result
+    }
+
+    pub async fn lease_nonce(
+        &self,
+        request: LeaseNonceRequest,
+    ) -> Result<LeaseNonceResponse, KoraError> {
+        info!("Lease nonce request: {request:?}");
+        let result = lease_nonce(&self.rpc_client, request).await;
+        info!("Lease nonce response: {result:?}");
+        result
+    }
+
+    pub async fn release_nonce(
+        &self,
+        request: ReleaseNonceRequest,
+    ) -> Result<ReleaseNonceResponse, KoraError> {
+        info!("Release nonce request: {request:?}");
+        let result = release_nonce(request).await;
+        info!("Release nonce response: {result:?}");
+        result
+    }
+
     pub async fn sign_transaction(
         &self,
         request: SignTransactionRequest,
@@ -106,6 +159,16 @@ impl KoraRpc {
         result
     }
 
+    pub async fn sign_transactions(
+        &self,
+        request: SignTransactionsRequest,
+    ) -> Result<SignTransactionsResponse, KoraError> {
+        info!("Sign transactions request: {request:?}");
+        let result = sign_transactions(&self.rpc_client, request).await;
+        info!("Sign transactions response: {result:?}");
+        result
+    }
+
     pub async fn sign_and_send_transaction(
         &self,
         request: SignAndSendTransactionRequest,
@@ -128,6 +191,23 @@ impl KoraRpc {
         result
     }
 
+    pub async fn validate_transaction(
+        &self,
+        request: ValidateTransactionRequest,
+    ) -> Result<ValidateTransactionResponse, KoraError> {
+        info!("Validate transaction request: {request:?}");
+        let result = validate_transaction(&self.rpc_client, request).await;
+        info!("Validate transaction response: {result:?}");
+        result
+    }
+
+    pub async fn reload_config(&self) -> Result<ReloadConfigResponse, KoraError> {
+        info!("Reload config request received");
+        let result = reload_config(&self.rpc_client).await;
+        info!("Reload config response: {result:?}");
+        result
+    }
+
     pub async fn get_blockhash(&self) -> Result<GetBlockhashResponse, KoraError> {
         info!("Get blockhash request received");
         let result = get_blockhash(&self.rpc_client).await;
@@ -202,11 +282,36 @@ impl KoraRpc {
                 request: None,
                 response: GetPayerSignerResponse::schema().1,
             },
+            OpenApiSpec {
+                name: "getPaymentInstruction".to_string(),
+                request: Some(GetPaymentInstructionRequest::schema().1),
+                response: GetPaymentInstructionResponse::schema().1,
+            },
+            OpenApiSpec {
+                name: "getTransactionStatus".to_string(),
+                request: Some(GetTransactionStatusRequest::schema().1),
+                response: GetTransactionStatusResponse::schema().1,
+            },
+            OpenApiSpec {
+                name: "leaseNonce".to_string(),
+                request: Some(LeaseNonceRequest::schema().1),
+                response: LeaseNonceResponse::schema().1,
+            },
+            OpenApiSpec {
+                name: "releaseNonce".to_string(),
+                request: Some(ReleaseNonceRequest::schema().1),
+                response: ReleaseNonceResponse::schema().1,
+            },
             OpenApiSpec {
                 name: "signTransaction".to_string(),
                 request: Some(SignTransactionRequest::schema().1),
                 response: SignTransactionResponse::schema().1,
             },
+            OpenApiSpec {
+                name: "signTransactions".to_string(),
+                request: Some(SignTransactionsRequest::schema().1),
+                response: SignTransactionsResponse::schema().1,
+            },
             OpenApiSpec {
                 name: "signAndSendTransaction".to_string(),
                 request: Some(SignAndSendTransactionRequest::schema().1),
@@ -217,6 +322,16 @@ impl KoraRpc {
                 request: Some(TransferTransactionRequest::schema().1),
                 response: TransferTransactionResponse::schema().1,
             },
+            OpenApiSpec {
+                name: "validateTransaction".to_string(),
+                request: Some(ValidateTransactionRequest::schema().1),
+                response: ValidateTransactionResponse::schema().1,
+            },
+            OpenApiSpec {
+                name: "reloadConfig".to_string(),
+                request: None,
+                response: ReloadConfigResponse::schema().1,
+            },
             OpenApiSpec {
                 name: "getVersion".to_string(),
                 request: None,
@@ -1,14 +1,21 @@
 use crate::{
     config::AuthConfig,
     constant::{X_API_KEY, X_HMAC_SIGNATURE, X_RECAPTCHA_TOKEN, X_TIMESTAMP},
+    idempotency::init_idempotency_store,
     metrics::run_metrics_server_if_required,
+    nonce::init_nonce_lease_store,
     rpc_server::{
+        admin::run_admin_server_if_required,
         auth::{ApiKeyAuthLayer, HmacAuthLayer},
         middleware_utils::MethodValidationLayer,
         recaptcha::RecaptchaLayer,
         recaptcha_util::RecaptchaConfig,
         rpc::KoraRpc,
     },
+    signer::{
+        affinity::init_signer_affinity_store, treasury_store::init_treasury_store, TreasuryTopUp,
+    },
+    transaction::init_transaction_status_store,
     usage_limit::UsageTracker,
 };
 
@@ -32,7 +39,9 @@ use tower_http::cors::CorsLayer;
 pub struct ServerHandles {
     pub rpc_handle: ServerHandle,
     pub metrics_handle: Option<ServerHandle>,
+    pub admin_handle: Option<ServerHandle>,
     pub balance_tracker_handle: Option<JoinHandle<()>>,
+    pub top_up_handle: Option<JoinHandle<()>>,
 }
 
 /// How long to wait for the RPC server to finish in-flight requests before
@@ -46,9 +55,9 @@ const BROADCAST_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
 impl ServerHandles {
     /// Gracefully shut down the RPC server and its background work.
     ///
-    /// Order matters: stop the balance tracker, wait for the RPC server to
+    /// Order matters: stop the balance tracker and treasury top-ups, wait for the RPC server to
     /// finish in-flight requests (so no new background broadcasts are spawned),
-    /// drain the broadcasts that were spawned, then stop the metrics server.
+    /// drain the broadcasts that were spawned, then stop the metrics and admin servers.
     ///
     /// `port` is the RPC port, needed to wake an idle accept loop (see
     /// [`wait_for_rpc_stop`]).
@@ -58,6 +67,11 @@ impl ServerHandles {
             handle.abort();
         }
 
+        if let Some(handle) = self.top_up_handle {
+            log::info!("Stopping treasury top-up background task...");
+            handle.abort();
+        }
+
         wait_for_rpc_stop(self.rpc_handle, port).await;
 
         if !drain_background_tasks(BROADCAST_DRAIN_TIMEOUT).await {
@@ -73,6 +87,12 @@ impl ServerHandles {
                 log::warn!("Error stopping metrics server: {e:?}");
             }
         }
+
+        if let Some(handle) = self.admin_handle {
+            if let Err(e) = handle.stop() {
+                log::warn!("Error stopping admin server: {e:?}");
+            }
+        }
     }
 }
 
@@ -119,6 +139,36 @@ pub async fn run_rpc_server(rpc: KoraRpc, port: u16) -> Result<ServerHandles, an
         return Err(anyhow::anyhow!("Usage limiter initialization failed: {e}"));
     }
 
+    // Initialize transaction status store (Redis when the cache is enabled)
+    if let Err(e) = init_transaction_status_store() {
+        log::error!("Failed to initialize transaction status store: {e}");
+        return Err(anyhow::anyhow!("Transaction status store initialization failed: {e}"));
+    }
+
+    // Initialize nonce lease store (Redis when the cache is enabled)
+    if let Err(e) = init_nonce_lease_store() {
+        log::error!("Failed to initialize nonce lease store: {e}");
+        return Err(anyhow::anyhow!("Nonce lease store initialization failed: {e}"));
+    }
+
+    // Initialize signer affinity store (Redis when the cache is enabled)
+    if let Err(e) = init_signer_affinity_store() {
+        log::error!("Failed to initialize signer affinity store: {e}");
+        return Err(anyhow::anyhow!("Signer affinity store initialization failed: {e}"));
+    }
+
+    // Initialize treasury store (Redis when the cache is enabled)
+    if let Err(e) = init_treasury_store() {
+        log::error!("Failed to initialize treasury store: {e}");
+        return Err(anyhow::anyhow!("Treasury store initialization failed: {e}"));
+    }
+
+    // Initialize idempotency store (Redis when the cache is enabled)
+    if let Err(e) = init_idempotency_store() {
+        log::error!("Failed to initialize idempotency store: {e}");
+        return Err(anyhow::anyhow!("Idempotency store initialization failed: {e}"));
+    }
+
     // Build middleware stack with tracing and CORS
     let cors = CorsLayer::new()
         .allow_origin(tower_http::cors::Any)
@@ -138,7 +188,11 @@ pub async fn run_rpc_server(rpc: KoraRpc, port: u16) -> Result<ServerHandles, an
     let rpc_client = rpc.get_rpc_client().clone();
 
     let (metrics_handle, metrics_layers, balance_tracker_handle) =
-        run_metrics_server_if_required(port, rpc_client).await?;
+        run_metrics_server_if_required(port, rpc_client.clone()).await?;
+
+    let top_up_handle = Some(TreasuryTopUp::start_background_task(rpc_client.clone()));
+
+    let admin_handle = run_admin_server_if_required(rpc_client, port).await?;
 
     // Build whitelist of allowed methods from enabled_methods config
     let allowed_methods = config.kora.enabled_methods.get_enabled_method_names();
@@ -192,7 +246,13 @@ pub async fn run_rpc_server(rpc: KoraRpc, port: u16) -> Result<ServerHandles, an
         .start(rpc_module)
         .map_err(|e| anyhow::anyhow!("Failed to start RPC server: {}", e))?;
 
-    Ok(ServerHandles { rpc_handle, metrics_handle, balance_tracker_handle })
+    Ok(ServerHandles {
+        rpc_handle,
+        metrics_handle,
+        admin_handle,
+        balance_tracker_handle,
+        top_up_handle,
+    })
 }
 
 macro_rules! register_method_if_enabled {
@@ -260,6 +320,39 @@ fn build_rpc_module(rpc: KoraRpc) -> Result<RpcModule<KoraRpc>, anyhow::Error> {
         "getPayerSigner",
         get_payer_signer
     );
+    register_method_if_enabled!(
+        module,
+        enabled_methods,
+        get_payment_instruction,
+        "getPaymentInstruction",
+        get_payment_instruction,
+        with_params
+    );
+    register_method_if_enabled!(
+        module,
+        enabled_methods,
+        get_transaction_status,
+        "getTransactionStatus",
+        get_transaction_status,
+        with_params
+    );
+    register_method_if_enabled!(
+        module,
+        enabled_methods,
+        lease_nonce,
+        "leaseNonce",
+        lease_nonce,
+        with_params
+    );
+    // Releasing a lease comes with leasing
+    register_method_if_enabled!(
+        module,
+        enabled_methods,
+        lease_nonce,
+        "releaseNonce",
+        release_nonce,
+        with_params
+    );
     register_method_if_enabled!(
         module,
         enabled_methods,
@@ -268,6 +361,14 @@ fn build_rpc_module(rpc: KoraRpc) -> Result<RpcModule<KoraRpc>, anyhow::Error> {
         sign_transaction,
         with_params
     );
+    register_method_if_enabled!(
+        module,
+        enabled_methods,
+        sign_transactions,
+        "signTransactions",
+        sign_transactions,
+        with_params
+    );
     register_method_if_enabled!(
         module,
         enabled_methods,
@@ -284,6 +385,21 @@ fn build_rpc_module(rpc: KoraRpc) -> Result<RpcModule<KoraRpc>, anyhow::Error> {
         transfer_transaction,
         with_params
     );
+    register_method_if_enabled!(
+        module,
+        enabled_methods,
+        validate_transaction,
+        "validateTransaction",
+        validate_transaction,
+        with_params
+    );
+    register_method_if_enabled!(
+        module,
+        enabled_methods,
+        reload_config,
+        "reloadConfig",
+        reload_config
+    );
     register_method_if_enabled!(
         module,
         enabled_methods,
@@ -390,14 +506,17 @@ mod tests {
         // Verify that the module has the expected methods
         let module = result.unwrap();
         let method_names: Vec<&str> = module.method_names().collect();
-        assert_eq!(method_names.len(), 10);
+        assert_eq!(method_names.len(), 13);
         assert!(method_names.contains(&"liveness"));
         assert!(method_names.contains(&"estimateTransactionFee"));
         assert!(method_names.contains(&"getSupportedTokens"));
         assert!(method_names.contains(&"getPayerSigner"));
+        assert!(method_names.contains(&"getPaymentInstruction"));
+        assert!(method_names.contains(&"getTransactionStatus"));
         assert!(method_names.contains(&"signTransaction"));
         assert!(method_names.contains(&"signAndSendTransaction"));
         assert!(method_names.contains(&"transferTransaction"));
+        assert!(method_names.contains(&"validateTransaction"));
         assert!(method_names.contains(&"getBlockhash"));
         assert!(method_names.contains(&"getConfig"));
         assert!(method_names.contains(&"getVersion"));
@@ -411,9 +530,15 @@ mod tests {
             estimate_transaction_fee: false,
             get_supported_tokens: false,
             get_payer_signer: false,
+            get_payment_instruction: false,
+            get_transaction_status: false,
+            lease_nonce: false,
             sign_transaction: false,
+            sign_transactions: false,
             sign_and_send_transaction: false,
             transfer_transaction: false,
+            validate_transaction: false,
+            reload_config: false,
             get_blockhash: false,
             get_config: false,
             get_version: false,
@@ -447,9 +572,15 @@ mod tests {
             get_supported_tokens: true,
             estimate_transaction_fee: false,
             get_payer_signer: false,
+            get_payment_instruction: false,
+            get_transaction_status: false,
+            lease_nonce: false,
             sign_transaction: false,
+            sign_transactions: false,
             sign_and_send_transaction: false,
             transfer_transaction: false,
+            validate_transaction: false,
+            reload_config: false,
             get_blockhash: false,
             get_version: false,
             estimate_bundle_fee: false,
@@ -0,0 +1,162 @@
+use async_trait::async_trait;
+use deadpool_redis::{Connection, Pool};
+use redis::AsyncCommands;
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+
+use crate::{cache::CacheUtil, error::KoraError, sanitize_error};
+
+const SIGNER_AFFINITY_KEY_PREFIX: &str = "kora:signer_affinity";
+
+/// Global signer affinity store. Falls back to an in-memory store when
+/// [`init_signer_affinity_store`] was never called (e.g. in unit tests).
+static SIGNER_AFFINITY_STORE: once_cell::sync::OnceCell<Arc<dyn SignerAffinityStore>> =
+    once_cell::sync::OnceCell::new();
+
+/// Trait for remembering which signer serves each user
+#[async_trait]
+pub trait SignerAffinityStore: Send + Sync {
+    /// Get the signer `user_id` was last served by (returns None if unknown or expired)
+    async fn get(&self, user_id: &str) -> Result<Option<Pubkey>, KoraError>;
+
+    /// Map `user_id` to `signer` for `ttl`
+    async fn set(&self, user_id: &str, signer: &Pubkey, ttl: Duration) -> Result<(), KoraError>;
+}
+
+/// Redis-based implementation, shared across Kora instances
+pub struct RedisSignerAffinityStore {
+    pool: Pool,
+}
+
+impl RedisSignerAffinityStore {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn get_key(user_id: &str) -> String {
+        format!("{SIGNER_AFFINITY_KEY_PREFIX}:{user_id}")
+    }
+
+    async fn get_connection(&self) -> Result<Connection, KoraError> {
+        self.pool.get().await.map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to get Redis connection: {}",
+                e
+            )))
+        })
+    }
+}
+
+#[async_trait]
+impl SignerAffinityStore for RedisSignerAffinityStore {
+    async fn get(&self, user_id: &str) -> Result<Option<Pubkey>, KoraError> {
+        let mut conn = self.get_connection().await?;
+        let data: Option<String> = conn.get(Self::get_key(user_id)).await.map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to get signer affinity for {}: {}",
+                user_id, e
+            )))
+        })?;
+
+        data.map(|data| {
+            Pubkey::from_str(&data).map_err(|e| {
+                KoraError::SerializationError(format!("Invalid signer affinity pubkey: {e}"))
+            })
+        })
+        .transpose()
+    }
+
+    async fn set(&self, user_id: &str, signer: &Pubkey, ttl: Duration) -> Result<(), KoraError> {
+        let mut conn = self.get_connection().await?;
+        let _: () = redis::cmd("SET")
+            .arg(Self::get_key(user_id))
+            .arg(signer.to_string())
+            .arg("EX")
+            .arg(ttl.as_secs().max(1))
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| {
+                KoraError::InternalServerError(sanitize_error!(format!(
+                    "Failed to set signer affinity for {}: {}",
+                    user_id, e
+                )))
+            })?;
+        Ok(())
+    }
+}
+
+/// In-memory implementation, local to this Kora instance
+#[derive(Default)]
+pub struct InMemorySignerAffinityStore {
+    signers: Mutex<HashMap<String, (Pubkey, Instant)>>,
+}
+
+impl InMemorySignerAffinityStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl SignerAffinityStore for InMemorySignerAffinityStore {
+    async fn get(&self, user_id: &str) -> Result<Option<Pubkey>, KoraError> {
+        let signers = self.signers.lock().await;
+        Ok(signers
+            .get(user_id)
+            .filter(|(_, expires_at)| *expires_at > Instant::now())
+            .map(|(signer, _)| *signer))
+    }
+
+    async fn set(&self, user_id: &str, signer: &Pubkey, ttl: Duration) -> Result<(), KoraError> {
+        let now = Instant::now();
+        let mut signers = self.signers.lock().await;
+        signers.retain(|_, (_, expires_at)| *expires_at > now);
+        signers.insert(user_id.to_string(), (*signer, now + ttl));
+        Ok(())
+    }
+}
+
+/// Initialize the global signer affinity store. Uses Redis when the cache is enabled,
+/// otherwise an in-memory store. Must run after [`CacheUtil::init`].
+pub fn init_signer_affinity_store() -> Result<(), KoraError> {
+    let (store, backend): (Arc<dyn SignerAffinityStore>, &str) = match CacheUtil::get_pool() {
+        Some(pool) => (Arc::new(RedisSignerAffinityStore::new(pool)), "Redis"),
+        None => (Arc::new(InMemorySignerAffinityStore::new()), "in-memory"),
+    };
+
+    SIGNER_AFFINITY_STORE.set(store).map_err(|_| {
+        KoraError::InternalServerError("Signer affinity store already initialized".to_string())
+    })?;
+
+    log::info!("Signer affinity store initialized ({backend})");
+    Ok(())
+}
+
+/// Returns the global signer affinity store
+pub fn get_signer_affinity_store() -> Arc<dyn SignerAffinityStore> {
+    SIGNER_AFFINITY_STORE.get_or_init(|| Arc::new(InMemorySignerAffinityStore::new())).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_memory_store_expires_affinity() {
+        let store = InMemorySignerAffinityStore::new();
+        let signer = Pubkey::new_unique();
+
+        store.set("user", &signer, Duration::from_secs(60)).await.unwrap();
+        assert_eq!(store.get("user").await.unwrap(), Some(signer));
+        assert_eq!(store.get("other").await.unwrap(), None);
+
+        store.set("user", &signer, Duration::ZERO).await.unwrap();
+        assert_eq!(store.get("user").await.unwrap(), None);
+    }
+}
@@ -14,7 +14,7 @@ pub struct BundleSigner {}
 impl BundleSigner {
     pub async fn sign_transaction_for_bundle(
         resolved: &mut VersionedTransactionResolved,
-        selected_signer: &Arc<solana_keychain::Signer>,
+        selected_signer: &Arc<crate::signer::Signer>,
         fee_payer: &Pubkey,
         blockhash: &Option<solana_sdk::hash::Hash>,
         config: &Config,
@@ -29,14 +29,17 @@ impl BundleSigner {
         let sign_timeout = Duration::from_secs(config.kora.sign_timeout_seconds);
         let max_retries = config.kora.sign_max_retries;
         let signer = reserve_request_signer_by_pubkey(&selected_signer.pubkey())?;
+        let pool = get_signer_pool()?;
+        // Wait for a slot under the signer's concurrency limit before the sign timeout
+        // starts. A busy signer is not an unhealthy one, so this is not a signing failure.
+        let _permit = pool.acquire_sign_permit(&signer, sign_timeout).await?;
         let signature = match sign_with_retry(
             sign_timeout,
             max_retries,
             "bundle signing",
             "Bundle signing",
             || async {
-                signer
-                    .sign_message(&message_bytes)
+                pool.sign_message(&signer, &message_bytes)
                     .await
                     .map_err(|e| KoraError::SigningError(sanitize_error!(e)))
             },
@@ -44,24 +47,11 @@ impl BundleSigner {
         .await
         {
             Ok(sig) => {
-                match get_signer_pool() {
-                    Ok(pool) => pool.record_signing_success(&signer),
-                    Err(e) => log::warn!(
-                        "Could not record bundle signing success to pool: {}",
-                        sanitize_error!(e)
-                    ),
-                }
+                pool.record_signing_success(&signer);
                 sig
             }
             Err(err) => {
-                match get_signer_pool() {
-                    Ok(pool) => pool.record_signing_failure(&signer),
-                    Err(pool_err) => log::error!(
-                        "Bundle signing failed AND pool health tracking unavailable: {}; \
-                         signer failure will not be recorded, automatic failover is disabled",
-                        sanitize_error!(pool_err)
-                    ),
-                }
+                pool.record_signing_failure(&signer);
                 return Err(err);
             }
         };
@@ -86,12 +76,11 @@ impl BundleSigner {
 mod tests {
     use super::*;
     use crate::{
-        signer::{pool::SignerWithMetadata, SignerPool},
+        signer::{pool::SignerWithMetadata, Signer, SignerPool},
         state::{update_config, update_signer_pool},
         tests::config_mock::ConfigMockBuilder,
     };
     use serial_test::serial;
-    use solana_keychain::Signer;
     use solana_message::Message;
     use solana_sdk::{
         hash::Hash, signature::Keypair, signer::Signer as SdkSigner, transaction::Transaction,
@@ -101,7 +90,7 @@ mod tests {
     fn setup_bundle_signer_state(
         fee_payer_keypair: &Keypair,
         config: &Config,
-    ) -> Arc<solana_keychain::Signer> {
+    ) -> Arc<crate::signer::Signer> {
         let external_signer = Signer::from_memory(&fee_payer_keypair.to_base58_string()).unwrap();
         let signer = Arc::new(external_signer);
         let pool = SignerPool::new(vec![SignerWithMetadata::new(
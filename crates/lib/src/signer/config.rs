@@ -1,7 +1,22 @@
-use crate::{error::KoraError, sanitize_error, signer::utils::get_env_var_for_signer};
+use crate::{
+    config::{FeePayerPolicy, ProgramsConfig, ValidationConfig},
+    constant::{DEFAULT_TOP_UP_INTERVAL_SECONDS, DEFAULT_USER_AFFINITY_TTL_SECONDS},
+    error::KoraError,
+    fee::price::{PriceConfig, PriceModel},
+    sanitize_error,
+    signer::{
+        keypair_util::KeypairUtil,
+        keystore::{read_passphrase_file, Keystore},
+        remote_http::{RemoteHttpSigner, RemoteHttpTls},
+        treasury::TopUpThreshold,
+        utils::get_env_var_for_signer,
+        Signer,
+    },
+};
 use serde::{Deserialize, Serialize};
-use solana_keychain::{OpenfortSigner as KeychainOpenfortSigner, Signer};
-use std::{fmt, fs, path::Path};
+use solana_keychain::{OpenfortSigner as KeychainOpenfortSigner, Signer as KeychainSigner};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair};
+use std::{fmt, fs, path::Path, str::FromStr};
 
 /// Configuration for a pool of signers
 #[derive(Clone, Serialize, Deserialize)]
@@ -10,6 +25,9 @@ pub struct SignerPoolConfig {
     pub signer_pool: SignerPoolSettings,
     /// List of individual signer configurations
     pub signers: Vec<SignerConfig>,
+    /// Signer that refills pool signers configured with `target_balance`/`refill_below`
+    #[serde(default)]
+    pub treasury: Option<TreasuryConfig>,
 }
 
 /// Settings for the signer pool behavior
@@ -18,6 +36,40 @@ pub struct SignerPoolSettings {
     /// Selection strategy for choosing signers
     #[serde(default = "default_strategy")]
     pub strategy: SelectionStrategy,
+    /// Signers whose last tracked SOL balance is below this are not selected.
+    /// Balances come from the fee payer balance tracker.
+    #[serde(default)]
+    pub min_balance_lamports: Option<u64>,
+    /// Signers whose p95 signing latency over the last few minutes exceeds this are only
+    /// selected when no faster signer is eligible
+    #[serde(default)]
+    pub slow_signer_p95_ms: Option<u64>,
+    /// Keep serving each `user_id` with the same signer when requests do not pin one
+    #[serde(default)]
+    pub user_affinity: Option<UserAffinityConfig>,
+}
+
+/// Sticky signer selection per `user_id`, or per API key for requests without one. Users
+/// are spread over the eligible signers by consistent hashing, and the signer a user got
+/// is remembered (in Redis when the cache is enabled) so that signers joining the pool do
+/// not move existing users.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserAffinityConfig {
+    /// How long a user stays mapped to its signer after its last request
+    #[serde(default = "default_user_affinity_ttl_seconds")]
+    pub ttl_seconds: u64,
+}
+
+fn default_user_affinity_ttl_seconds() -> u64 {
+    DEFAULT_USER_AFFINITY_TTL_SECONDS
+}
+
+impl SignerPoolSettings {
+    /// Whether selection depends on the balances polled by the balance tracker
+    pub fn uses_signer_balances(&self) -> bool {
+        matches!(self.strategy, SelectionStrategy::BalanceAware)
+            || self.min_balance_lamports.is_some()
+    }
 }
 
 /// Available signer selection strategies
@@ -27,6 +79,8 @@ pub enum SelectionStrategy {
     RoundRobin,
     Random,
     Weighted,
+    /// Prefer signers with the most SOL above `min_balance_lamports`
+    BalanceAware,
 }
 
 impl fmt::Display for SelectionStrategy {
@@ -35,6 +89,7 @@ impl fmt::Display for SelectionStrategy {
             SelectionStrategy::RoundRobin => "round_robin",
             SelectionStrategy::Random => "random",
             SelectionStrategy::Weighted => "weighted",
+            SelectionStrategy::BalanceAware => "balance_aware",
         };
         write!(f, "{s}")
     }
@@ -51,18 +106,125 @@ pub struct SignerConfig {
     pub name: String,
     /// Weight for weighted selection strategy (optional, defaults to 1)
     pub weight: Option<u32>,
+    /// Balance in lamports the treasury tops this signer back up to
+    #[serde(default)]
+    pub target_balance: Option<u64>,
+    /// The treasury tops this signer up once its balance drops below this many lamports
+    #[serde(default)]
+    pub refill_below: Option<u64>,
+    /// Validation settings that replace the `[validation]` ones for this signer
+    #[serde(default)]
+    pub policy: Option<SignerPolicyConfig>,
+    /// Most signing calls in flight against this signer at once. Further calls wait up to
+    /// `sign_timeout_seconds` for a free slot and then fail with a busy error, and the
+    /// signer is avoided in selection while it has none.
+    #[serde(default)]
+    pub max_concurrent_signs: Option<usize>,
+
+    /// Signer-specific configuration
+    #[serde(flatten)]
+    pub config: SignerTypeConfig,
+}
+
+/// Per-signer overrides of `[validation]`, so pool members can serve different jobs.
+/// Each set field replaces the global one whole, unset fields fall back to it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SignerPolicyConfig {
+    #[serde(default)]
+    pub allowed_programs: Option<ProgramsConfig>,
+    #[serde(default)]
+    pub fee_payer_policy: Option<FeePayerPolicy>,
+    #[serde(default)]
+    pub max_allowed_lamports: Option<u64>,
+    #[serde(default)]
+    pub price: Option<PriceConfig>,
+}
+
+impl SignerPolicyConfig {
+    /// `validation` with this policy's overrides applied
+    pub fn apply_to(&self, validation: &ValidationConfig) -> ValidationConfig {
+        let mut validation = validation.clone();
+        if let Some(allowed_programs) = &self.allowed_programs {
+            validation.allowed_programs = allowed_programs.clone();
+        }
+        if let Some(fee_payer_policy) = &self.fee_payer_policy {
+            validation.fee_payer_policy = fee_payer_policy.clone();
+        }
+        if let Some(max_allowed_lamports) = self.max_allowed_lamports {
+            validation.max_allowed_lamports = max_allowed_lamports;
+        }
+        if let Some(price) = &self.price {
+            validation.price = price.clone();
+        }
+        validation
+    }
+}
+
+/// Treasury signer used to fund depleted pool signers
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TreasuryConfig {
+    /// Human-readable name for the treasury signer
+    pub name: String,
+    /// Most lamports the treasury sends per UTC day, across all signers
+    pub daily_cap_lamports: u64,
+    /// Log the transfers that would be sent instead of sending them
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Seconds between balance checks of the pool signers
+    #[serde(default = "default_top_up_interval_seconds")]
+    pub check_interval_seconds: u64,
 
     /// Signer-specific configuration
     #[serde(flatten)]
     pub config: SignerTypeConfig,
 }
 
+fn default_top_up_interval_seconds() -> u64 {
+    DEFAULT_TOP_UP_INTERVAL_SECONDS
+}
+
+impl TreasuryConfig {
+    /// The treasury as a plain signer config, so it is built and validated like pool signers
+    pub fn signer_config(&self) -> SignerConfig {
+        SignerConfig {
+            name: self.name.clone(),
+            weight: None,
+            target_balance: None,
+            refill_below: None,
+            policy: None,
+            max_concurrent_signs: None,
+            config: self.config.clone(),
+        }
+    }
+}
+
 /// Memory signer configuration (local keypair)
 #[derive(Clone, Serialize, Deserialize)]
 pub struct MemorySignerConfig {
     pub private_key_env: String,
 }
 
+/// Solana CLI JSON keypair file signer configuration
+#[derive(Clone, Serialize, Deserialize)]
+pub struct KeypairFileSignerConfig {
+    /// Path to a keypair file as written by `solana-keygen new`
+    pub path: String,
+}
+
+/// Encrypted keystore signer configuration, see `kora signers encrypt`
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EncryptedKeystoreSignerConfig {
+    /// Path to the keystore file
+    pub path: String,
+    /// Env var holding the passphrase. Set exactly one of this and `passphrase_file`.
+    #[serde(default)]
+    pub passphrase_env: Option<String>,
+    /// File holding the passphrase, trailing newlines are ignored
+    #[serde(default)]
+    pub passphrase_file: Option<String>,
+}
+
 /// Configuration for remote signer HTTP request and connection settings
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RemoteSignerHttpConfig {
@@ -120,6 +282,30 @@ pub struct OpenfortSignerConfig {
     pub http_config: Option<RemoteSignerHttpConfig>,
 }
 
+/// Generic HTTP signer configuration for in-house signing services.
+/// See `signer::remote_http` for the request and response format.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RemoteHttpSignerConfig {
+    /// Env var holding the signing endpoint URL
+    pub url_env: String,
+    /// Env var holding the base58 public key the service signs for
+    pub pubkey_env: String,
+    /// Env var holding the shared secret used to HMAC-sign requests
+    #[serde(default)]
+    pub hmac_secret_env: Option<String>,
+    /// PEM client certificate presented for mTLS
+    #[serde(default)]
+    pub client_cert_path: Option<String>,
+    /// PEM PKCS#8 private key for `client_cert_path`
+    #[serde(default)]
+    pub client_key_path: Option<String>,
+    /// Extra PEM CA certificate to trust, for services behind a private CA
+    #[serde(default)]
+    pub ca_cert_path: Option<String>,
+    #[serde(default)]
+    pub http_config: Option<RemoteSignerHttpConfig>,
+}
+
 /// Vault signer configuration
 #[derive(Clone, Serialize, Deserialize)]
 pub struct VaultSignerConfig {
@@ -224,6 +410,16 @@ pub enum SignerTypeConfig {
         #[serde(flatten)]
         config: MemorySignerConfig,
     },
+    /// Solana CLI JSON keypair file signer configuration
+    KeypairFile {
+        #[serde(flatten)]
+        config: KeypairFileSignerConfig,
+    },
+    /// Encrypted keystore signer configuration
+    EncryptedKeystore {
+        #[serde(flatten)]
+        config: EncryptedKeystoreSignerConfig,
+    },
     /// Turnkey signer configuration
     Turnkey {
         #[serde(flatten)]
@@ -279,6 +475,34 @@ pub enum SignerTypeConfig {
         #[serde(flatten)]
         config: OpenfortSignerConfig,
     },
+    /// Generic HTTP signer configuration
+    RemoteHttp {
+        #[serde(flatten)]
+        config: RemoteHttpSignerConfig,
+    },
+}
+
+impl SignerTypeConfig {
+    /// The `type` of the signer as written in `signers.toml`
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Memory { .. } => "memory",
+            Self::KeypairFile { .. } => "keypair_file",
+            Self::EncryptedKeystore { .. } => "encrypted_keystore",
+            Self::Turnkey { .. } => "turnkey",
+            Self::Privy { .. } => "privy",
+            Self::Vault { .. } => "vault",
+            Self::AwsKms { .. } => "aws_kms",
+            Self::Fireblocks { .. } => "fireblocks",
+            Self::GcpKms { .. } => "gcp_kms",
+            Self::Para { .. } => "para",
+            Self::Cdp { .. } => "cdp",
+            Self::Dfns { .. } => "dfns",
+            Self::Crossmint { .. } => "crossmint",
+            Self::Openfort { .. } => "openfort",
+            Self::RemoteHttp { .. } => "remote_http",
+        }
+    }
 }
 
 impl SignerPoolConfig {
@@ -313,6 +537,7 @@ impl SignerPoolConfig {
 
         self.validate_signer_names()?;
         self.validate_strategy_weights()?;
+        self.validate_top_up()?;
 
         Ok(())
     }
@@ -354,15 +579,107 @@ impl SignerPoolConfig {
         }
         Ok(())
     }
+
+    pub fn validate_top_up(&self) -> Result<(), KoraError> {
+        for signer in &self.signers {
+            signer.validate_top_up_threshold()?;
+
+            if TopUpThreshold::from_config(signer).is_some() && self.treasury.is_none() {
+                return Err(KoraError::ValidationError(format!(
+                    "Signer '{}' sets target_balance and refill_below but no [treasury] is configured",
+                    signer.name
+                )));
+            }
+        }
+
+        if let Some(treasury) = &self.treasury {
+            treasury.signer_config().validate_individual_signer_config(self.signers.len())?;
+
+            if self.signers.iter().any(|signer| signer.name == treasury.name) {
+                return Err(KoraError::ValidationError(format!(
+                    "Treasury name '{}' is already used by a pool signer",
+                    treasury.name
+                )));
+            }
+            if treasury.daily_cap_lamports == 0 {
+                return Err(KoraError::ValidationError(
+                    "Treasury daily_cap_lamports must be greater than 0".to_string(),
+                ));
+            }
+            if treasury.check_interval_seconds == 0 {
+                return Err(KoraError::ValidationError(
+                    "Treasury check_interval_seconds must be greater than 0".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl SignerConfig {
+    /// Check that `target_balance` and `refill_below` are set together, with the refill
+    /// point below the target
+    pub fn validate_top_up_threshold(&self) -> Result<(), KoraError> {
+        match (self.target_balance, self.refill_below) {
+            (None, None) => Ok(()),
+            (Some(target_balance), Some(refill_below)) if refill_below >= target_balance => {
+                Err(KoraError::ValidationError(format!(
+                    "Signer '{}' has refill_below ({refill_below}) that is not below target_balance ({target_balance})",
+                    self.name
+                )))
+            }
+            (Some(_), Some(_)) => Ok(()),
+            _ => Err(KoraError::ValidationError(format!(
+                "Signer '{}' must set both target_balance and refill_below",
+                self.name
+            ))),
+        }
+    }
+
+    fn validate_policy(&self) -> Result<(), KoraError> {
+        let Some(policy) = &self.policy else {
+            return Ok(());
+        };
+
+        for program in policy.allowed_programs.iter().flatten() {
+            if Pubkey::from_str(program).is_err() {
+                return Err(KoraError::ValidationError(format!(
+                    "Signer '{}' policy has an invalid program address: {program}",
+                    self.name
+                )));
+            }
+        }
+
+        match policy.price.as_ref().map(|price| &price.model) {
+            Some(PriceModel::Margin { margin }) if *margin < 0.0 => {
+                Err(KoraError::ValidationError(format!(
+                    "Signer '{}' policy has a negative price margin: {margin}",
+                    self.name
+                )))
+            }
+            Some(PriceModel::Fixed { token, .. }) if Pubkey::from_str(token).is_err() => {
+                Err(KoraError::ValidationError(format!(
+                    "Signer '{}' policy has an invalid fixed price token: {token}",
+                    self.name
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Build an external signer from configuration by resolving environment variables
     pub async fn build_signer_from_config(config: &SignerConfig) -> Result<Signer, KoraError> {
-        match &config.config {
+        let keychain_signer = match &config.config {
             SignerTypeConfig::Memory { config: memory_config } => {
                 Self::build_memory_signer(memory_config, &config.name)
             }
+            SignerTypeConfig::KeypairFile { config: keypair_file_config } => {
+                Self::build_keypair_file_signer(keypair_file_config, &config.name)
+            }
+            SignerTypeConfig::EncryptedKeystore { config: keystore_config } => {
+                Self::build_encrypted_keystore_signer(keystore_config, &config.name)
+            }
             SignerTypeConfig::Turnkey { config: turnkey_config } => {
                 Self::build_turnkey_signer(turnkey_config, &config.name)
             }
@@ -396,15 +713,20 @@ impl SignerConfig {
             SignerTypeConfig::Openfort { config: openfort_config } => {
                 Self::build_openfort_signer(openfort_config, &config.name).await
             }
-        }
+            SignerTypeConfig::RemoteHttp { config: remote_http_config } => {
+                return Self::build_remote_http_signer(remote_http_config, &config.name)
+                    .map(Signer::RemoteHttp);
+            }
+        };
+        keychain_signer.map(Signer::from)
     }
 
     fn build_memory_signer(
         config: &MemorySignerConfig,
         signer_name: &str,
-    ) -> Result<Signer, KoraError> {
+    ) -> Result<KeychainSigner, KoraError> {
         let private_key = get_env_var_for_signer(&config.private_key_env, signer_name)?;
-        Signer::from_memory(&private_key).map_err(|e| {
+        KeychainSigner::from_memory(&private_key).map_err(|e| {
             KoraError::SigningError(format!(
                 "Failed to create memory signer '{signer_name}': {}",
                 sanitize_error!(e)
@@ -412,17 +734,74 @@ impl SignerConfig {
         })
     }
 
+    fn build_keypair_file_signer(
+        config: &KeypairFileSignerConfig,
+        signer_name: &str,
+    ) -> Result<KeychainSigner, KoraError> {
+        let contents = fs::read_to_string(&config.path).map_err(|e| {
+            KoraError::SigningError(format!(
+                "Failed to read keypair file for signer '{signer_name}': {}",
+                sanitize_error!(e)
+            ))
+        })?;
+        let keypair = KeypairUtil::from_json_keypair(&contents)?;
+
+        Self::signer_from_keypair(&keypair, signer_name)
+    }
+
+    fn build_encrypted_keystore_signer(
+        config: &EncryptedKeystoreSignerConfig,
+        signer_name: &str,
+    ) -> Result<KeychainSigner, KoraError> {
+        let passphrase = Self::read_keystore_passphrase(config, signer_name)?;
+        let keypair = Keystore::load(&config.path)?.decrypt(&passphrase).map_err(|e| {
+            KoraError::SigningError(format!("Failed to unlock signer '{signer_name}': {e}"))
+        })?;
+
+        Self::signer_from_keypair(&keypair, signer_name)
+    }
+
+    fn read_keystore_passphrase(
+        config: &EncryptedKeystoreSignerConfig,
+        signer_name: &str,
+    ) -> Result<String, KoraError> {
+        match (&config.passphrase_env, &config.passphrase_file) {
+            (Some(env), None) => get_env_var_for_signer(env, signer_name),
+            (None, Some(path)) => read_passphrase_file(path).map_err(|e| {
+                    KoraError::ValidationError(format!(
+                        "Failed to read passphrase file for signer '{signer_name}': {}",
+                        sanitize_error!(e)
+                    ))
+                }),
+            _ => Err(KoraError::ValidationError(format!(
+                "Encrypted keystore signer '{signer_name}' must specify exactly one of passphrase_env and passphrase_file"
+            ))),
+        }
+    }
+
+    fn signer_from_keypair(
+        keypair: &Keypair,
+        signer_name: &str,
+    ) -> Result<KeychainSigner, KoraError> {
+        KeychainSigner::from_memory(&keypair.to_base58_string()).map_err(|e| {
+            KoraError::SigningError(format!(
+                "Failed to create signer '{signer_name}': {}",
+                sanitize_error!(e)
+            ))
+        })
+    }
+
     fn build_turnkey_signer(
         config: &TurnkeySignerConfig,
         signer_name: &str,
-    ) -> Result<Signer, KoraError> {
+    ) -> Result<KeychainSigner, KoraError> {
         let api_public_key = get_env_var_for_signer(&config.api_public_key_env, signer_name)?;
         let api_private_key = get_env_var_for_signer(&config.api_private_key_env, signer_name)?;
         let organization_id = get_env_var_for_signer(&config.organization_id_env, signer_name)?;
         let private_key_id = get_env_var_for_signer(&config.private_key_id_env, signer_name)?;
         let public_key = get_env_var_for_signer(&config.public_key_env, signer_name)?;
 
-        Signer::from_turnkey(
+        KeychainSigner::from_turnkey(
             api_public_key,
             api_private_key,
             organization_id,
@@ -441,12 +820,12 @@ impl SignerConfig {
     async fn build_privy_signer(
         config: &PrivySignerConfig,
         signer_name: &str,
-    ) -> Result<Signer, KoraError> {
+    ) -> Result<KeychainSigner, KoraError> {
         let app_id = get_env_var_for_signer(&config.app_id_env, signer_name)?;
         let app_secret = get_env_var_for_signer(&config.app_secret_env, signer_name)?;
         let wallet_id = get_env_var_for_signer(&config.wallet_id_env, signer_name)?;
 
-        Signer::from_privy(
+        KeychainSigner::from_privy(
             app_id,
             app_secret,
             wallet_id,
@@ -464,13 +843,13 @@ impl SignerConfig {
     fn build_vault_signer(
         config: &VaultSignerConfig,
         signer_name: &str,
-    ) -> Result<Signer, KoraError> {
+    ) -> Result<KeychainSigner, KoraError> {
         let vault_addr = get_env_var_for_signer(&config.vault_addr_env, signer_name)?;
         let vault_token = get_env_var_for_signer(&config.vault_token_env, signer_name)?;
         let key_name = get_env_var_for_signer(&config.key_name_env, signer_name)?;
         let pubkey = get_env_var_for_signer(&config.pubkey_env, signer_name)?;
 
-        Signer::from_vault(
+        KeychainSigner::from_vault(
             vault_addr,
             vault_token,
             key_name,
@@ -488,7 +867,7 @@ impl SignerConfig {
     async fn build_aws_kms_signer(
         config: &AwsKmsSignerConfig,
         signer_name: &str,
-    ) -> Result<Signer, KoraError> {
+    ) -> Result<KeychainSigner, KoraError> {
         let key_id = get_env_var_for_signer(&config.key_id_env, signer_name)?;
         let public_key = get_env_var_for_signer(&config.public_key_env, signer_name)?;
         let region = config
@@ -497,7 +876,7 @@ impl SignerConfig {
             .map(|env| get_env_var_for_signer(env, signer_name))
             .transpose()?;
 
-        Signer::from_aws_kms(key_id, public_key, region).await.map_err(|e| {
+        KeychainSigner::from_aws_kms(key_id, public_key, region).await.map_err(|e| {
             KoraError::SigningError(format!(
                 "Failed to create AWS KMS signer '{signer_name}': {}",
                 sanitize_error!(e)
@@ -508,7 +887,7 @@ impl SignerConfig {
     async fn build_fireblocks_signer(
         config: &FireblocksSignerConfig,
         signer_name: &str,
-    ) -> Result<Signer, KoraError> {
+    ) -> Result<KeychainSigner, KoraError> {
         let api_key = get_env_var_for_signer(&config.api_key_env, signer_name)?;
         let private_key_pem = get_env_var_for_signer(&config.private_key_pem_env, signer_name)?;
         let vault_account_id = get_env_var_for_signer(&config.vault_account_id_env, signer_name)?;
@@ -528,7 +907,7 @@ impl SignerConfig {
                 .map(solana_keychain::HttpClientConfig::from),
         };
 
-        Signer::from_fireblocks(keychain_config).await.map_err(|e| {
+        KeychainSigner::from_fireblocks(keychain_config).await.map_err(|e| {
             KoraError::SigningError(format!(
                 "Failed to create Fireblocks signer '{signer_name}': {}",
                 sanitize_error!(e)
@@ -539,10 +918,10 @@ impl SignerConfig {
     async fn build_gcp_kms_signer(
         config: &GcpKmsSignerConfig,
         signer_name: &str,
-    ) -> Result<Signer, KoraError> {
+    ) -> Result<KeychainSigner, KoraError> {
         let key_name = get_env_var_for_signer(&config.key_name_env, signer_name)?;
         let public_key = get_env_var_for_signer(&config.public_key_env, signer_name)?;
-        Signer::from_gcp_kms(key_name, public_key).await.map_err(|e| {
+        KeychainSigner::from_gcp_kms(key_name, public_key).await.map_err(|e| {
             KoraError::SigningError(format!(
                 "Failed to create GCP KMS signer '{signer_name}': {}",
                 sanitize_error!(e)
@@ -553,10 +932,10 @@ impl SignerConfig {
     async fn build_para_signer(
         config: &ParaSignerConfig,
         signer_name: &str,
-    ) -> Result<Signer, KoraError> {
+    ) -> Result<KeychainSigner, KoraError> {
         let api_key = get_env_var_for_signer(&config.api_key_env, signer_name)?;
         let wallet_id = get_env_var_for_signer(&config.wallet_id_env, signer_name)?;
-        Signer::from_para(api_key, wallet_id, None).await.map_err(|e| {
+        KeychainSigner::from_para(api_key, wallet_id, None).await.map_err(|e| {
             KoraError::SigningError(format!(
                 "Failed to create Para signer '{signer_name}': {}",
                 sanitize_error!(e)
@@ -564,12 +943,15 @@ impl SignerConfig {
         })
     }
 
-    fn build_cdp_signer(config: &CdpSignerConfig, signer_name: &str) -> Result<Signer, KoraError> {
+    fn build_cdp_signer(
+        config: &CdpSignerConfig,
+        signer_name: &str,
+    ) -> Result<KeychainSigner, KoraError> {
         let api_key_id = get_env_var_for_signer(&config.api_key_id_env, signer_name)?;
         let api_key_secret = get_env_var_for_signer(&config.api_key_secret_env, signer_name)?;
         let wallet_secret = get_env_var_for_signer(&config.wallet_secret_env, signer_name)?;
         let address = get_env_var_for_signer(&config.address_env, signer_name)?;
-        Signer::from_cdp(
+        KeychainSigner::from_cdp(
             api_key_id,
             api_key_secret,
             wallet_secret,
@@ -587,7 +969,7 @@ impl SignerConfig {
     async fn build_dfns_signer(
         config: &DfnsSignerConfig,
         signer_name: &str,
-    ) -> Result<Signer, KoraError> {
+    ) -> Result<KeychainSigner, KoraError> {
         let auth_token = get_env_var_for_signer(&config.auth_token_env, signer_name)?;
         let cred_id = get_env_var_for_signer(&config.cred_id_env, signer_name)?;
         let private_key_pem = get_env_var_for_signer(&config.private_key_pem_env, signer_name)?;
@@ -603,7 +985,7 @@ impl SignerConfig {
                 .as_ref()
                 .map(solana_keychain::HttpClientConfig::from),
         };
-        Signer::from_dfns(keychain_config).await.map_err(|e| {
+        KeychainSigner::from_dfns(keychain_config).await.map_err(|e| {
             KoraError::SigningError(format!(
                 "Failed to create Dfns signer '{signer_name}': {}",
                 sanitize_error!(e)
@@ -614,7 +996,7 @@ impl SignerConfig {
     async fn build_crossmint_signer(
         config: &CrossmintSignerConfig,
         signer_name: &str,
-    ) -> Result<Signer, KoraError> {
+    ) -> Result<KeychainSigner, KoraError> {
         let api_key = get_env_var_for_signer(&config.api_key_env, signer_name)?;
         let wallet_locator = get_env_var_for_signer(&config.wallet_locator_env, signer_name)?;
         let signer_secret = config
@@ -631,7 +1013,7 @@ impl SignerConfig {
             poll_interval_ms: config.poll_interval_ms,
             max_poll_attempts: config.max_poll_attempts,
         };
-        Signer::from_crossmint(keychain_config).await.map_err(|e| {
+        KeychainSigner::from_crossmint(keychain_config).await.map_err(|e| {
             KoraError::SigningError(format!(
                 "Failed to create Crossmint signer '{signer_name}': {}",
                 sanitize_error!(e)
@@ -642,7 +1024,7 @@ impl SignerConfig {
     async fn build_openfort_signer(
         config: &OpenfortSignerConfig,
         signer_name: &str,
-    ) -> Result<Signer, KoraError> {
+    ) -> Result<KeychainSigner, KoraError> {
         let secret_key = get_env_var_for_signer(&config.secret_key_env, signer_name)?;
         let account_id = get_env_var_for_signer(&config.account_id_env, signer_name)?;
         let wallet_secret = get_env_var_for_signer(&config.wallet_secret_env, signer_name)?;
@@ -667,7 +1049,55 @@ impl SignerConfig {
             })
             .map_err(map_err)?;
         signer.init().await.map_err(map_err)?;
-        Ok(Signer::Openfort(signer))
+        Ok(KeychainSigner::Openfort(signer))
+    }
+
+    fn build_remote_http_signer(
+        config: &RemoteHttpSignerConfig,
+        signer_name: &str,
+    ) -> Result<RemoteHttpSigner, KoraError> {
+        let url = get_env_var_for_signer(&config.url_env, signer_name)?;
+        let pubkey = get_env_var_for_signer(&config.pubkey_env, signer_name)?;
+        let hmac_secret = config
+            .hmac_secret_env
+            .as_ref()
+            .map(|env| get_env_var_for_signer(env, signer_name))
+            .transpose()?;
+
+        let read_pem = |path: &String| {
+            fs::read(path).map_err(|e| {
+                KoraError::ValidationError(format!(
+                    "Failed to read '{path}' for remote HTTP signer '{signer_name}': {}",
+                    sanitize_error!(e)
+                ))
+            })
+        };
+        let tls = match (&config.client_cert_path, &config.client_key_path) {
+            (Some(cert_path), Some(key_path)) => Some(RemoteHttpTls {
+                client_cert_pem: read_pem(cert_path)?,
+                client_key_pem: read_pem(key_path)?,
+                ca_cert_pem: config.ca_cert_path.as_ref().map(read_pem).transpose()?,
+            }),
+            _ => None,
+        };
+
+        RemoteHttpSigner::new(
+            url,
+            &pubkey,
+            hmac_secret,
+            tls,
+            config
+                .http_config
+                .as_ref()
+                .map(solana_keychain::HttpClientConfig::from)
+                .unwrap_or_default(),
+        )
+        .map_err(|e| {
+            KoraError::SigningError(format!(
+                "Failed to create remote HTTP signer '{signer_name}': {}",
+                sanitize_error!(e)
+            ))
+        })
     }
 
     /// Validate an individual signer configuration
@@ -677,9 +1107,22 @@ impl SignerConfig {
                 "Signer at index {index} must have a non-empty name"
             )));
         }
+        self.validate_policy()?;
+        if self.max_concurrent_signs == Some(0) {
+            return Err(KoraError::ValidationError(format!(
+                "Signer '{}' must allow at least 1 concurrent sign",
+                self.name
+            )));
+        }
 
         match &self.config {
             SignerTypeConfig::Memory { config } => Self::validate_memory_config(config, &self.name),
+            SignerTypeConfig::KeypairFile { config } => {
+                Self::validate_keypair_file_config(config, &self.name)
+            }
+            SignerTypeConfig::EncryptedKeystore { config } => {
+                Self::validate_encrypted_keystore_config(config, &self.name)
+            }
             SignerTypeConfig::Turnkey { config } => {
                 Self::validate_turnkey_config(config, &self.name)
             }
@@ -703,6 +1146,9 @@ impl SignerConfig {
             SignerTypeConfig::Openfort { config } => {
                 Self::validate_openfort_config(config, &self.name)
             }
+            SignerTypeConfig::RemoteHttp { config } => {
+                Self::validate_remote_http_config(config, &self.name)
+            }
         }
     }
 
@@ -719,6 +1165,43 @@ impl SignerConfig {
         Ok(())
     }
 
+    fn validate_keypair_file_config(
+        config: &KeypairFileSignerConfig,
+        signer_name: &str,
+    ) -> Result<(), KoraError> {
+        if config.path.is_empty() {
+            return Err(KoraError::ValidationError(format!(
+                "Keypair file signer '{signer_name}' must specify non-empty path"
+            )));
+        }
+        if !Path::new(&config.path).is_file() {
+            return Err(KoraError::ValidationError(format!(
+                "Keypair file '{}' for signer '{signer_name}' does not exist",
+                config.path
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_encrypted_keystore_config(
+        config: &EncryptedKeystoreSignerConfig,
+        signer_name: &str,
+    ) -> Result<(), KoraError> {
+        if config.path.is_empty() {
+            return Err(KoraError::ValidationError(format!(
+                "Encrypted keystore signer '{signer_name}' must specify non-empty path"
+            )));
+        }
+        if !Path::new(&config.path).is_file() {
+            return Err(KoraError::ValidationError(format!(
+                "Keystore file '{}' for signer '{signer_name}' does not exist",
+                config.path
+            )));
+        }
+        // Checks the passphrase source without keeping the passphrase around
+        Self::read_keystore_passphrase(config, signer_name).map(|_| ())
+    }
+
     fn validate_turnkey_config(
         config: &TurnkeySignerConfig,
         signer_name: &str,
@@ -938,11 +1421,74 @@ impl SignerConfig {
         }
         Ok(())
     }
+
+    fn validate_remote_http_config(
+        config: &RemoteHttpSignerConfig,
+        signer_name: &str,
+    ) -> Result<(), KoraError> {
+        let env_vars = [("url_env", &config.url_env), ("pubkey_env", &config.pubkey_env)];
+        for (field_name, env_var) in env_vars {
+            if env_var.is_empty() {
+                return Err(KoraError::ValidationError(format!(
+                    "Remote HTTP signer '{signer_name}' must specify non-empty {field_name}"
+                )));
+            }
+            get_env_var_for_signer(env_var, signer_name)?;
+        }
+        if let Some(env) = &config.hmac_secret_env {
+            if env.is_empty() {
+                return Err(KoraError::ValidationError(format!(
+                    "Remote HTTP signer '{signer_name}' must specify non-empty hmac_secret_env when set"
+                )));
+            }
+            get_env_var_for_signer(env, signer_name)?;
+        }
+
+        let mtls = match (&config.client_cert_path, &config.client_key_path) {
+            (Some(_), Some(_)) => true,
+            (None, None) => false,
+            _ => {
+                return Err(KoraError::ValidationError(format!(
+                    "Remote HTTP signer '{signer_name}' must set client_cert_path and client_key_path together"
+                )))
+            }
+        };
+        if config.hmac_secret_env.is_none() && !mtls {
+            return Err(KoraError::ValidationError(format!(
+                "Remote HTTP signer '{signer_name}' must set hmac_secret_env or client_cert_path/client_key_path"
+            )));
+        }
+        if config.ca_cert_path.is_some() && !mtls {
+            return Err(KoraError::ValidationError(format!(
+                "Remote HTTP signer '{signer_name}' sets ca_cert_path without an mTLS client certificate"
+            )));
+        }
+        for path in [&config.client_cert_path, &config.client_key_path, &config.ca_cert_path]
+            .into_iter()
+            .flatten()
+        {
+            if !Path::new(path).is_file() {
+                return Err(KoraError::ValidationError(format!(
+                    "File '{path}' for remote HTTP signer '{signer_name}' does not exist"
+                )));
+            }
+        }
+
+        let url = get_env_var_for_signer(&config.url_env, signer_name)?;
+        if !url.starts_with("https://") && (mtls || !url.starts_with("http://")) {
+            return Err(KoraError::ValidationError(format!(
+                "Remote HTTP signer '{signer_name}' URL must use https{}",
+                if mtls { " with mTLS" } else { " or http" }
+            )));
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use solana_sdk::signer::Signer as _;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -986,19 +1532,175 @@ weight = 2
         }
     }
 
+    #[test]
+    fn test_parse_user_affinity() {
+        let parse = |signer_pool: &str| -> SignerPoolConfig {
+            toml::from_str(&format!(
+                "[signer_pool]\n{signer_pool}\n[[signers]]\nname = \"s\"\ntype = \"memory\"\nprivate_key_env = \"KEY\"\n"
+            ))
+            .unwrap()
+        };
+
+        assert!(parse("strategy = \"random\"").signer_pool.user_affinity.is_none());
+
+        let default_ttl = parse("[signer_pool.user_affinity]").signer_pool.user_affinity.unwrap();
+        assert_eq!(default_ttl.ttl_seconds, DEFAULT_USER_AFFINITY_TTL_SECONDS);
+
+        let custom_ttl =
+            parse("[signer_pool.user_affinity]\nttl_seconds = 600").signer_pool.user_affinity;
+        assert_eq!(custom_ttl.unwrap().ttl_seconds, 600);
+    }
+
+    #[test]
+    fn test_parse_signer_policy() {
+        let toml_content = r#"
+[signer_pool]
+strategy = "round_robin"
+
+[[signers]]
+name = "payments"
+type = "memory"
+private_key_env = "SIGNER_1_PRIVATE_KEY"
+
+[signers.policy]
+allowed_programs = ["TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"]
+max_allowed_lamports = 5000
+
+[signers.policy.price]
+type = "free"
+
+[signers.policy.fee_payer_policy.system]
+allow_transfer = true
+"#;
+
+        let config: SignerPoolConfig = toml::from_str(toml_content).unwrap();
+        let policy = config.signers[0].policy.as_ref().unwrap();
+        let global = crate::tests::config_mock::ConfigMockBuilder::new().build().validation;
+
+        let validation = policy.apply_to(&global);
+
+        assert_eq!(
+            validation.allowed_programs,
+            ProgramsConfig::Allowlist(vec![
+                "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string()
+            ])
+        );
+        assert_eq!(validation.max_allowed_lamports, 5000);
+        assert!(matches!(validation.price.model, PriceModel::Free));
+        assert!(validation.fee_payer_policy.system.allow_transfer);
+        // Fields the policy does not set keep the global values
+        assert_eq!(validation.max_signatures, global.max_signatures);
+        assert_eq!(validation.allowed_tokens, global.allowed_tokens);
+    }
+
+    #[test]
+    fn test_validate_signer_policy() {
+        std::env::set_var("KORA_TEST_POLICY_SIGNER_KEY", "unused");
+        let mut signer = SignerConfig {
+            name: "payments".to_string(),
+            weight: None,
+            target_balance: None,
+            refill_below: None,
+            policy: Some(SignerPolicyConfig {
+                allowed_programs: Some(ProgramsConfig::Allowlist(vec!["not-a-pubkey".to_string()])),
+                ..Default::default()
+            }),
+            max_concurrent_signs: None,
+            config: SignerTypeConfig::Memory {
+                config: MemorySignerConfig {
+                    private_key_env: "KORA_TEST_POLICY_SIGNER_KEY".to_string(),
+                },
+            },
+        };
+        assert!(signer.validate_individual_signer_config(0).is_err());
+
+        signer.policy = Some(SignerPolicyConfig {
+            price: Some(PriceConfig { model: PriceModel::Margin { margin: -0.1 } }),
+            ..Default::default()
+        });
+        assert!(signer.validate_individual_signer_config(0).is_err());
+
+        signer.policy = Some(SignerPolicyConfig {
+            allowed_programs: Some(ProgramsConfig::All),
+            max_allowed_lamports: Some(1),
+            ..Default::default()
+        });
+        assert!(signer.validate_individual_signer_config(0).is_ok());
+        std::env::remove_var("KORA_TEST_POLICY_SIGNER_KEY");
+    }
+
+    #[test]
+    fn test_validate_top_up() {
+        std::env::set_var("KORA_TEST_TREASURY_KEY", "treasury-key");
+        let parse = |signer_fields: &str, treasury: &str| -> SignerPoolConfig {
+            toml::from_str(&format!(
+                r#"
+[signer_pool]
+strategy = "round_robin"
+
+[[signers]]
+name = "signer_1"
+type = "memory"
+private_key_env = "SIGNER_1_PRIVATE_KEY"
+{signer_fields}
+{treasury}
+"#
+            ))
+            .unwrap()
+        };
+        let treasury = r#"
+[treasury]
+name = "treasury"
+type = "memory"
+private_key_env = "KORA_TEST_TREASURY_KEY"
+daily_cap_lamports = 5000000000
+"#;
+
+        let config = parse("target_balance = 1000000000\nrefill_below = 200000000", treasury);
+        assert!(config.validate_top_up().is_ok());
+        let parsed = config.treasury.unwrap();
+        assert_eq!(parsed.daily_cap_lamports, 5_000_000_000);
+        assert!(!parsed.dry_run);
+        assert_eq!(parsed.check_interval_seconds, DEFAULT_TOP_UP_INTERVAL_SECONDS);
+
+        let invalid = [
+            ("target_balance = 1000000000", treasury),
+            ("target_balance = 100\nrefill_below = 100", treasury),
+            ("target_balance = 1000000000\nrefill_below = 200000000", ""),
+            ("", &treasury.replace("5000000000", "0")),
+            ("", &treasury.replace("\"treasury\"", "\"signer_1\"")),
+        ];
+        for (signer_fields, treasury) in invalid {
+            assert!(
+                parse(signer_fields, treasury).validate_top_up().is_err(),
+                "expected error for {signer_fields:?} / {treasury:?}"
+            );
+        }
+    }
+
     #[test]
     fn test_validate_config_success() {
         let config = SignerPoolConfig {
-            signer_pool: SignerPoolSettings { strategy: SelectionStrategy::RoundRobin },
+            signer_pool: SignerPoolSettings {
+                strategy: SelectionStrategy::RoundRobin,
+                min_balance_lamports: None,
+                slow_signer_p95_ms: None,
+                user_affinity: None,
+            },
             signers: vec![SignerConfig {
                 name: "test_signer".to_string(),
                 weight: Some(1),
+                target_balance: None,
+                refill_below: None,
+                policy: None,
+                max_concurrent_signs: None,
                 config: SignerTypeConfig::Memory {
                     config: MemorySignerConfig {
                         private_key_env: "KORA_VALIDATE_SUCCESS_KEY_99".to_string(),
                     },
                 },
             }],
+            treasury: None,
         };
 
         std::env::set_var("KORA_VALIDATE_SUCCESS_KEY_99", "dummy");
@@ -1010,8 +1712,14 @@ weight = 2
     #[test]
     fn test_validate_config_empty_signers() {
         let config = SignerPoolConfig {
-            signer_pool: SignerPoolSettings { strategy: SelectionStrategy::RoundRobin },
+            signer_pool: SignerPoolSettings {
+                strategy: SelectionStrategy::RoundRobin,
+                min_balance_lamports: None,
+                slow_signer_p95_ms: None,
+                user_affinity: None,
+            },
             signers: vec![],
+            treasury: None,
         };
 
         assert!(config.validate_signer_config().is_err());
@@ -1020,11 +1728,20 @@ weight = 2
     #[test]
     fn test_validate_config_duplicate_names() {
         let config = SignerPoolConfig {
-            signer_pool: SignerPoolSettings { strategy: SelectionStrategy::RoundRobin },
+            signer_pool: SignerPoolSettings {
+                strategy: SelectionStrategy::RoundRobin,
+                min_balance_lamports: None,
+                slow_signer_p95_ms: None,
+                user_affinity: None,
+            },
             signers: vec![
                 SignerConfig {
                     name: "duplicate".to_string(),
                     weight: Some(1),
+                    target_balance: None,
+                    refill_below: None,
+                    policy: None,
+                    max_concurrent_signs: None,
                     config: SignerTypeConfig::Memory {
                         config: MemorySignerConfig {
                             private_key_env: "TEST_PRIVATE_KEY_1".to_string(),
@@ -1034,6 +1751,10 @@ weight = 2
                 SignerConfig {
                     name: "duplicate".to_string(),
                     weight: Some(1),
+                    target_balance: None,
+                    refill_below: None,
+                    policy: None,
+                    max_concurrent_signs: None,
                     config: SignerTypeConfig::Memory {
                         config: MemorySignerConfig {
                             private_key_env: "TEST_PRIVATE_KEY_2".to_string(),
@@ -1041,6 +1762,7 @@ weight = 2
                     },
                 },
             ],
+            treasury: None,
         };
 
         assert!(config.validate_signer_config().is_err());
@@ -1074,16 +1796,26 @@ private_key_env = "KORA_LOAD_CONFIG_KEY_99"
         let _m = crate::tests::config_mock::ConfigMockBuilder::new().build_and_setup();
 
         let config = SignerPoolConfig {
-            signer_pool: SignerPoolSettings { strategy: SelectionStrategy::RoundRobin },
+            signer_pool: SignerPoolSettings {
+                strategy: SelectionStrategy::RoundRobin,
+                min_balance_lamports: None,
+                slow_signer_p95_ms: None,
+                user_affinity: None,
+            },
             signers: vec![SignerConfig {
                 name: "test_signer_missing".to_string(),
                 weight: Some(1),
+                target_balance: None,
+                refill_below: None,
+                policy: None,
+                max_concurrent_signs: None,
                 config: SignerTypeConfig::Memory {
                     config: MemorySignerConfig {
                         private_key_env: "KORA_TEST_MISSING_KEY_12345".to_string(),
                     },
                 },
             }],
+            treasury: None,
         };
 
         std::env::remove_var("KORA_TEST_MISSING_KEY_12345");
@@ -1097,16 +1829,26 @@ private_key_env = "KORA_LOAD_CONFIG_KEY_99"
         let _m = crate::tests::config_mock::ConfigMockBuilder::new().build_and_setup();
 
         let config = SignerPoolConfig {
-            signer_pool: SignerPoolSettings { strategy: SelectionStrategy::RoundRobin },
+            signer_pool: SignerPoolSettings {
+                strategy: SelectionStrategy::RoundRobin,
+                min_balance_lamports: None,
+                slow_signer_p95_ms: None,
+                user_affinity: None,
+            },
             signers: vec![SignerConfig {
                 name: "test_signer_present".to_string(),
                 weight: Some(1),
+                target_balance: None,
+                refill_below: None,
+                policy: None,
+                max_concurrent_signs: None,
                 config: SignerTypeConfig::Memory {
                     config: MemorySignerConfig {
                         private_key_env: "KORA_TEST_PRESENT_KEY_12345".to_string(),
                     },
                 },
             }],
+            treasury: None,
         };
 
         std::env::set_var("KORA_TEST_PRESENT_KEY_12345", "dummy_value");
@@ -1222,4 +1964,135 @@ private_key_env = "KORA_LOAD_CONFIG_KEY_99"
         std::env::remove_var("FIREBLOCKS_PRIVATE_KEY_PEM");
         std::env::remove_var("FIREBLOCKS_VAULT_ACCOUNT_ID");
     }
+
+    #[test]
+    fn test_keypair_file_signer_builds() {
+        let keypair = Keypair::new();
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "{:?}", keypair.to_bytes().to_vec()).unwrap();
+        let config = KeypairFileSignerConfig { path: file.path().display().to_string() };
+
+        assert!(SignerConfig::validate_keypair_file_config(&config, "test").is_ok());
+        let signer = SignerConfig::build_keypair_file_signer(&config, "test").unwrap();
+        assert_eq!(solana_keychain::SolanaSigner::pubkey(&signer), keypair.pubkey());
+
+        let missing = KeypairFileSignerConfig { path: "/nonexistent/keypair.json".to_string() };
+        assert!(SignerConfig::validate_keypair_file_config(&missing, "test").is_err());
+    }
+
+    #[test]
+    fn test_encrypted_keystore_signer_builds() {
+        let keypair = Keypair::new();
+        let keystore = Keystore::encrypt(
+            &keypair,
+            "correct horse",
+            crate::signer::KeystoreKdf::Scrypt { log_n: 4, r: 8, p: 1 },
+        )
+        .unwrap();
+        let keystore_file = NamedTempFile::new().unwrap();
+        keystore.save(keystore_file.path()).unwrap();
+        let mut passphrase_file = NamedTempFile::new().unwrap();
+        writeln!(passphrase_file, "correct horse").unwrap();
+        std::env::set_var("KORA_TEST_KEYSTORE_PASSPHRASE", "correct horse");
+
+        let path = keystore_file.path().display().to_string();
+        for (passphrase_env, passphrase_file) in [
+            (Some("KORA_TEST_KEYSTORE_PASSPHRASE".to_string()), None),
+            (None, Some(passphrase_file.path().display().to_string())),
+        ] {
+            let config = EncryptedKeystoreSignerConfig {
+                path: path.clone(),
+                passphrase_env,
+                passphrase_file,
+            };
+
+            assert!(SignerConfig::validate_encrypted_keystore_config(&config, "test").is_ok());
+            let signer = SignerConfig::build_encrypted_keystore_signer(&config, "test").unwrap();
+            assert_eq!(solana_keychain::SolanaSigner::pubkey(&signer), keypair.pubkey());
+        }
+
+        let no_passphrase =
+            EncryptedKeystoreSignerConfig { path, passphrase_env: None, passphrase_file: None };
+        assert!(SignerConfig::validate_encrypted_keystore_config(&no_passphrase, "test").is_err());
+    }
+
+    #[test]
+    fn test_validate_remote_http_config() {
+        std::env::set_var("KORA_TEST_REMOTE_URL", "http://127.0.0.1:9000/sign");
+        std::env::set_var("KORA_TEST_REMOTE_PUBKEY", "7EcDhSYGxXyscszYEp35KHN8vvw3svAuLKTzXwCFLtV");
+        std::env::set_var("KORA_TEST_REMOTE_SECRET", "secret");
+        let cert_file = NamedTempFile::new().unwrap();
+        let cert_path = Some(cert_file.path().display().to_string());
+        let config = |hmac_secret_env: Option<&str>, client_cert_path, client_key_path| {
+            RemoteHttpSignerConfig {
+                url_env: "KORA_TEST_REMOTE_URL".to_string(),
+                pubkey_env: "KORA_TEST_REMOTE_PUBKEY".to_string(),
+                hmac_secret_env: hmac_secret_env.map(str::to_string),
+                client_cert_path,
+                client_key_path,
+                ca_cert_path: None,
+                http_config: None,
+            }
+        };
+
+        let hmac = config(Some("KORA_TEST_REMOTE_SECRET"), None, None);
+        assert!(SignerConfig::validate_remote_http_config(&hmac, "test").is_ok());
+
+        let unauthenticated = config(None, None, None);
+        assert!(SignerConfig::validate_remote_http_config(&unauthenticated, "test").is_err());
+
+        let cert_without_key = config(None, cert_path.clone(), None);
+        assert!(SignerConfig::validate_remote_http_config(&cert_without_key, "test").is_err());
+
+        // mTLS needs an https URL
+        let mtls = config(None, cert_path.clone(), cert_path);
+        assert!(SignerConfig::validate_remote_http_config(&mtls, "test").is_err());
+        std::env::set_var("KORA_TEST_REMOTE_URL", "https://signer.internal/sign");
+        assert!(SignerConfig::validate_remote_http_config(&mtls, "test").is_ok());
+
+        std::env::remove_var("KORA_TEST_REMOTE_URL");
+        std::env::remove_var("KORA_TEST_REMOTE_PUBKEY");
+        std::env::remove_var("KORA_TEST_REMOTE_SECRET");
+    }
+
+    #[tokio::test]
+    async fn test_remote_http_signer_builds_and_signs() {
+        let mock = crate::tests::remote_signer_mock::RemoteSignerMock::start("secret").await;
+        std::env::set_var("KORA_TEST_REMOTE_HTTP_URL", mock.url());
+        std::env::set_var("KORA_TEST_REMOTE_HTTP_PUBKEY", mock.pubkey().to_string());
+        std::env::set_var("KORA_TEST_REMOTE_HTTP_SECRET", "secret");
+
+        let config = SignerConfig {
+            name: "in_house".to_string(),
+            weight: None,
+            target_balance: None,
+            refill_below: None,
+            policy: None,
+            max_concurrent_signs: None,
+            config: SignerTypeConfig::RemoteHttp {
+                config: RemoteHttpSignerConfig {
+                    url_env: "KORA_TEST_REMOTE_HTTP_URL".to_string(),
+                    pubkey_env: "KORA_TEST_REMOTE_HTTP_PUBKEY".to_string(),
+                    hmac_secret_env: Some("KORA_TEST_REMOTE_HTTP_SECRET".to_string()),
+                    client_cert_path: None,
+                    client_key_path: None,
+                    ca_cert_path: None,
+                    http_config: Some(RemoteSignerHttpConfig {
+                        request_timeout_secs: Some(5),
+                        connect_timeout_secs: Some(1),
+                    }),
+                },
+            },
+        };
+
+        let signer = SignerConfig::build_signer_from_config(&config).await.unwrap();
+        assert!(matches!(signer, Signer::RemoteHttp(_)));
+        let signature =
+            solana_keychain::SolanaSigner::sign_message(&signer, b"hello").await.unwrap();
+        assert!(signature.verify(mock.pubkey().as_ref(), b"hello"));
+
+        std::env::remove_var("KORA_TEST_REMOTE_HTTP_URL");
+        std::env::remove_var("KORA_TEST_REMOTE_HTTP_PUBKEY");
+        std::env::remove_var("KORA_TEST_REMOTE_HTTP_SECRET");
+    }
 }
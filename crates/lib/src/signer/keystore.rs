@@ -0,0 +1,257 @@
+use crate::{error::KoraError, sanitize_error};
+use aes_gcm::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng, Payload},
+    Aes256Gcm, Nonce,
+};
+use serde::{Deserialize, Serialize};
+use solana_sdk::signature::{Keypair, Signer};
+use std::{fmt, fs, path::Path};
+use zeroize::Zeroizing;
+
+const KEYSTORE_VERSION: u32 = 1;
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+
+/// Key derivation function used to turn the passphrase into the AES key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "name", rename_all = "snake_case")]
+pub enum KeystoreKdf {
+    Scrypt { log_n: u8, r: u32, p: u32 },
+    Argon2id { m_cost: u32, t_cost: u32, p_cost: u32 },
+}
+
+impl KeystoreKdf {
+    /// scrypt with N = 2^15, r = 8, p = 1
+    pub fn scrypt() -> Self {
+        Self::Scrypt { log_n: 15, r: 8, p: 1 }
+    }
+
+    /// Argon2id with 19 MiB of memory and 2 iterations
+    pub fn argon2id() -> Self {
+        Self::Argon2id { m_cost: 19 * 1024, t_cost: 2, p_cost: 1 }
+    }
+
+    /// The derived key is wiped from memory when dropped
+    fn derive_key(
+        &self,
+        passphrase: &[u8],
+        salt: &[u8],
+    ) -> Result<Zeroizing<[u8; KEY_LEN]>, KoraError> {
+        let mut key = Zeroizing::new([0u8; KEY_LEN]);
+        match self {
+            Self::Scrypt { log_n, r, p } => {
+                let params = scrypt::Params::new(*log_n, *r, *p, KEY_LEN).map_err(|e| {
+                    KoraError::ValidationError(format!("Invalid scrypt parameters: {e}"))
+                })?;
+                scrypt::scrypt(passphrase, salt, &params, key.as_mut()).map_err(|e| {
+                    KoraError::InternalServerError(format!("scrypt key derivation failed: {e}"))
+                })?;
+            }
+            Self::Argon2id { m_cost, t_cost, p_cost } => {
+                let params = argon2::Params::new(*m_cost, *t_cost, *p_cost, Some(KEY_LEN))
+                    .map_err(|e| {
+                        KoraError::ValidationError(format!("Invalid argon2 parameters: {e}"))
+                    })?;
+                argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+                    .hash_password_into(passphrase, salt, key.as_mut())
+                    .map_err(|e| {
+                        KoraError::InternalServerError(format!("argon2 key derivation failed: {e}"))
+                    })?;
+            }
+        }
+        Ok(key)
+    }
+}
+
+impl fmt::Display for KeystoreKdf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Scrypt { .. } => write!(f, "scrypt"),
+            Self::Argon2id { .. } => write!(f, "argon2id"),
+        }
+    }
+}
+
+/// A Solana keypair encrypted with AES-256-GCM under a passphrase-derived key.
+/// Produced by `kora signers encrypt` and loaded by the `encrypted_keystore` signer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    /// Public key of the encrypted keypair, also bound to the ciphertext as associated data
+    pub pubkey: String,
+    pub kdf: KeystoreKdf,
+    /// Hex-encoded KDF salt
+    pub salt: String,
+    /// Hex-encoded AES-GCM nonce
+    pub nonce: String,
+    /// Hex-encoded encrypted keypair bytes, including the GCM tag
+    pub ciphertext: String,
+}
+
+impl Keystore {
+    pub fn encrypt(
+        keypair: &Keypair,
+        passphrase: &str,
+        kdf: KeystoreKdf,
+    ) -> Result<Self, KoraError> {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let pubkey = keypair.pubkey().to_string();
+
+        let key = kdf.derive_key(passphrase.as_bytes(), &salt)?;
+        let cipher = Aes256Gcm::new_from_slice(key.as_ref())
+            .map_err(|e| KoraError::InternalServerError(format!("Invalid keystore key: {e}")))?;
+        let secret = Zeroizing::new(keypair.to_bytes());
+        let ciphertext = cipher
+            .encrypt(&nonce, Payload { msg: secret.as_ref(), aad: pubkey.as_bytes() })
+            .map_err(|_| KoraError::InternalServerError("Failed to encrypt keypair".to_string()))?;
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            pubkey,
+            kdf,
+            salt: hex::encode(salt),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    pub fn decrypt(&self, passphrase: &str) -> Result<Keypair, KoraError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(KoraError::ValidationError(format!(
+                "Unsupported keystore version {}",
+                self.version
+            )));
+        }
+
+        let decode = |field: &str, value: &str| {
+            hex::decode(value)
+                .map_err(|e| KoraError::ValidationError(format!("Invalid keystore {field}: {e}")))
+        };
+        let salt = decode("salt", &self.salt)?;
+        let nonce = decode("nonce", &self.nonce)?;
+        let ciphertext = decode("ciphertext", &self.ciphertext)?;
+        if nonce.len() != 12 {
+            return Err(KoraError::ValidationError(format!(
+                "Invalid keystore nonce: expected 12 bytes, got {}",
+                nonce.len()
+            )));
+        }
+
+        let key = self.kdf.derive_key(passphrase.as_bytes(), &salt)?;
+        let cipher = Aes256Gcm::new_from_slice(key.as_ref())
+            .map_err(|e| KoraError::InternalServerError(format!("Invalid keystore key: {e}")))?;
+        let plaintext = Zeroizing::new(
+            cipher
+                .decrypt(
+                    Nonce::from_slice(&nonce),
+                    Payload { msg: &ciphertext, aad: self.pubkey.as_bytes() },
+                )
+                .map_err(|_| {
+                    KoraError::SigningError(
+                        "Failed to decrypt keystore: wrong passphrase or corrupted file"
+                            .to_string(),
+                    )
+                })?,
+        );
+
+        let keypair = Keypair::try_from(plaintext.as_slice()).map_err(|e| {
+            KoraError::SigningError(format!("Invalid keystore keypair: {}", sanitize_error!(e)))
+        })?;
+        if keypair.pubkey().to_string() != self.pubkey {
+            return Err(KoraError::SigningError(
+                "Keystore keypair does not match its pubkey".to_string(),
+            ));
+        }
+
+        Ok(keypair)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, KoraError> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(|e| {
+            KoraError::InternalServerError(format!(
+                "Failed to read keystore file {}: {}",
+                path.display(),
+                sanitize_error!(e)
+            ))
+        })?;
+
+        serde_json::from_str(&contents).map_err(|e| {
+            KoraError::ValidationError(format!(
+                "Failed to parse keystore file {}: {}",
+                path.display(),
+                sanitize_error!(e)
+            ))
+        })
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), KoraError> {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self).map_err(|e| {
+            KoraError::SerializationError(format!("Failed to serialize keystore: {e}"))
+        })?;
+
+        fs::write(path, json).map_err(|e| {
+            KoraError::InternalServerError(format!(
+                "Failed to write keystore file {}: {}",
+                path.display(),
+                sanitize_error!(e)
+            ))
+        })
+    }
+}
+
+/// Read a passphrase from a file, ignoring trailing newlines
+pub fn read_passphrase_file<P: AsRef<Path>>(path: P) -> std::io::Result<String> {
+    fs::read_to_string(path).map(|passphrase| passphrase.trim_end_matches(['\r', '\n']).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    // Cheap parameters, the defaults take a noticeable time per derivation
+    fn test_kdfs() -> [KeystoreKdf; 2] {
+        [
+            KeystoreKdf::Scrypt { log_n: 4, r: 8, p: 1 },
+            KeystoreKdf::Argon2id { m_cost: 64, t_cost: 1, p_cost: 1 },
+        ]
+    }
+
+    #[test]
+    fn test_keystore_round_trip() {
+        for kdf in test_kdfs() {
+            let keypair = Keypair::new();
+            let keystore = Keystore::encrypt(&keypair, "correct horse", kdf).unwrap();
+            assert_eq!(keystore.pubkey, keypair.pubkey().to_string());
+
+            let file = NamedTempFile::new().unwrap();
+            keystore.save(file.path()).unwrap();
+            let decrypted = Keystore::load(file.path()).unwrap().decrypt("correct horse").unwrap();
+
+            assert_eq!(decrypted.to_bytes(), keypair.to_bytes());
+        }
+    }
+
+    #[test]
+    fn test_keystore_rejects_wrong_passphrase() {
+        for kdf in test_kdfs() {
+            let keystore = Keystore::encrypt(&Keypair::new(), "correct horse", kdf).unwrap();
+
+            let result = keystore.decrypt("battery staple");
+            assert!(matches!(result, Err(KoraError::SigningError(_))));
+        }
+    }
+
+    #[test]
+    fn test_keystore_rejects_tampered_pubkey() {
+        let [kdf, _] = test_kdfs();
+        let mut keystore = Keystore::encrypt(&Keypair::new(), "correct horse", kdf).unwrap();
+        keystore.pubkey = Keypair::new().pubkey().to_string();
+
+        assert!(keystore.decrypt("correct horse").is_err());
+    }
+}
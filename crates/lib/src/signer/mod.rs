@@ -1,15 +1,24 @@
+pub mod affinity;
 pub mod bundle_signer;
 pub mod config;
 pub mod init;
 pub mod keypair_util;
+pub mod keystore;
 pub mod pool;
+pub mod remote_http;
 pub mod signer;
+pub mod treasury;
+pub mod treasury_store;
 pub mod utils;
 
 pub use config::{
-    MemorySignerConfig, OpenfortSignerConfig, PrivySignerConfig, SelectionStrategy, SignerConfig,
-    SignerPoolConfig, SignerTypeConfig, TurnkeySignerConfig, VaultSignerConfig,
+    EncryptedKeystoreSignerConfig, KeypairFileSignerConfig, MemorySignerConfig,
+    OpenfortSignerConfig, PrivySignerConfig, RemoteHttpSignerConfig, SelectionStrategy,
+    SignerConfig, SignerPolicyConfig, SignerPoolConfig, SignerTypeConfig, TreasuryConfig,
+    TurnkeySignerConfig, VaultSignerConfig,
 };
 pub use keypair_util::KeypairUtil;
-pub use pool::{SignerInfo, SignerPool};
-pub use signer::SolanaSigner;
+pub use keystore::{Keystore, KeystoreKdf};
+pub use pool::{SignerAvailability, SignerInfo, SignerPool};
+pub use signer::{Signer, SolanaSigner};
+pub use treasury::{Treasury, TreasuryMetrics, TreasuryTopUp};
@@ -1,23 +1,35 @@
 use crate::{
-    config::KoraConfig,
+    config::{KoraConfig, ProgramsConfig},
+    constant::{SIGNER_LATENCY_WINDOW_SAMPLES, SIGNER_LATENCY_WINDOW_SECONDS},
     error::KoraError,
-    signer::config::{SelectionStrategy, SignerConfig, SignerPoolConfig},
+    metrics::SignerMetrics,
+    signer::{
+        config::{SelectionStrategy, SignerConfig, SignerPolicyConfig, SignerPoolConfig},
+        treasury::{TopUpTarget, TopUpThreshold, Treasury},
+        Signer, SolanaSigner,
+    },
     transaction::signing_retry_window,
 };
 use parking_lot::Mutex;
 use rand::RngExt;
-use solana_keychain::{Signer, SolanaSigner};
-use solana_sdk::pubkey::Pubkey;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use solana_keychain::SignerError;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
 use std::{
+    collections::VecDeque,
+    fmt,
     str::FromStr,
     sync::{
         atomic::{AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use utoipa::ToSchema;
 
-const DEFAULT_WEIGHT: u32 = 1;
+pub(crate) const DEFAULT_WEIGHT: u32 = 1;
 
 /// Metadata associated with a signer in the pool
 #[derive(Debug, Clone, Copy)]
@@ -41,6 +53,80 @@ impl Default for HealthState {
     }
 }
 
+/// Durations of a signer's recent signing attempts. Samples expire, so a signer that
+/// was slow and stopped being selected for it gets picked again later.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LatencyWindow {
+    samples: VecDeque<(Instant, Duration)>,
+}
+
+impl LatencyWindow {
+    fn record(&mut self, latency: Duration) {
+        if self.samples.len() == SIGNER_LATENCY_WINDOW_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((Instant::now(), latency));
+    }
+
+    /// 95th percentile latency of the unexpired samples
+    fn p95(&mut self) -> Option<Duration> {
+        let max_age = Duration::from_secs(SIGNER_LATENCY_WINDOW_SECONDS);
+        while self.samples.front().is_some_and(|(recorded_at, _)| recorded_at.elapsed() > max_age) {
+            self.samples.pop_front();
+        }
+        if self.samples.is_empty() {
+            return None;
+        }
+
+        let mut latencies: Vec<Duration> =
+            self.samples.iter().map(|(_, latency)| *latency).collect();
+        latencies.sort_unstable();
+        Some(latencies[(latencies.len() * 95).div_ceil(100) - 1])
+    }
+}
+
+/// Times one signing attempt. An attempt dropped before it finished hit the signing
+/// timeout in `sign_with_retry`.
+struct SignAttempt<'a> {
+    signer: &'a SignerWithMetadata,
+    started_at: Instant,
+    outcome: Option<&'static str>,
+}
+
+impl Drop for SignAttempt<'_> {
+    fn drop(&mut self) {
+        let latency = self.started_at.elapsed();
+        self.signer.latency.lock().record(latency);
+        SignerMetrics::get()
+            .sign_duration_seconds
+            .with_label_values(&[&self.signer.name, self.outcome.unwrap_or("timeout")])
+            .observe(latency.as_secs_f64());
+    }
+}
+
+/// Whether a signer takes part in selection, set through the admin server
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SignerAvailability {
+    #[default]
+    Active,
+    /// Excluded from pool selection, but still serves requests pinned to it with
+    /// `signer_key` so clients can finish in-flight flows
+    Draining,
+    /// Not used for any request
+    Disabled,
+}
+
+impl fmt::Display for SignerAvailability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Active => write!(f, "active"),
+            Self::Draining => write!(f, "draining"),
+            Self::Disabled => write!(f, "disabled"),
+        }
+    }
+}
+
 pub(crate) struct SignerWithMetadata {
     /// Human-readable name for this signer
     name: String,
@@ -52,6 +138,20 @@ pub(crate) struct SignerWithMetadata {
     last_used: AtomicU64,
     /// Tracks health and failure state thread-safely in a single lock
     health: Mutex<HealthState>,
+    /// Admin-controlled availability, independent of health
+    availability: Mutex<SignerAvailability>,
+    /// Last SOL balance seen by the balance tracker, None until the first poll
+    balance_lamports: Mutex<Option<u64>>,
+    /// Balance thresholds for refills from the treasury
+    top_up: Option<TopUpThreshold>,
+    /// Unix timestamp at which a draining signer is removed from the pool
+    removal_at: Mutex<Option<u64>>,
+    /// Overrides of the global validation config for transactions this signer pays for
+    policy: Option<Arc<SignerPolicyConfig>>,
+    /// Recent signing latencies, used to steer selection away from slow signers
+    latency: Mutex<LatencyWindow>,
+    /// Limits signing calls in flight against this signer's backend
+    sign_permits: Option<Arc<Semaphore>>,
 }
 
 impl Clone for SignerWithMetadata {
@@ -64,6 +164,13 @@ impl Clone for SignerWithMetadata {
             weight: self.weight,
             last_used: AtomicU64::new(self.last_used.load(Ordering::Relaxed)),
             health: Mutex::new(health),
+            availability: Mutex::new(*self.availability.lock()),
+            balance_lamports: Mutex::new(*self.balance_lamports.lock()),
+            top_up: self.top_up,
+            removal_at: Mutex::new(*self.removal_at.lock()),
+            policy: self.policy.clone(),
+            latency: Mutex::new(self.latency.lock().clone()),
+            sign_permits: self.sign_permits.clone(),
         }
     }
 }
@@ -82,9 +189,113 @@ impl SignerWithMetadata {
             weight,
             last_used: AtomicU64::new(0),
             health: Mutex::new(HealthState::default()),
+            availability: Mutex::new(SignerAvailability::default()),
+            balance_lamports: Mutex::new(None),
+            top_up: None,
+            removal_at: Mutex::new(None),
+            policy: None,
+            latency: Mutex::new(LatencyWindow::default()),
+            sign_permits: None,
+        }
+    }
+
+    /// Build a signer and its metadata from configuration
+    pub(crate) async fn from_config(config: &SignerConfig) -> Result<Self, KoraError> {
+        let signer = SignerConfig::build_signer_from_config(config).await?;
+        let weight = config.weight.unwrap_or(DEFAULT_WEIGHT);
+
+        Ok(Self::new(config.name.clone(), Arc::new(signer), weight)
+            .with_top_up(TopUpThreshold::from_config(config))
+            .with_policy(config.policy.clone())
+            .with_max_concurrent_signs(config.max_concurrent_signs))
+    }
+
+    /// Take over the health, availability, balance and usage state of the same signer
+    /// from a previous pool
+    fn copy_state_from(&self, previous: &SignerWithMetadata) {
+        self.last_used.store(previous.last_used.load(Ordering::Relaxed), Ordering::Relaxed);
+        *self.health.lock() = *previous.health.lock();
+        *self.availability.lock() = *previous.availability.lock();
+        *self.balance_lamports.lock() = *previous.balance_lamports.lock();
+        *self.removal_at.lock() = *previous.removal_at.lock();
+        *self.latency.lock() = previous.latency.lock().clone();
+    }
+
+    /// Have the treasury keep this signer funded
+    pub(crate) fn with_top_up(mut self, top_up: Option<TopUpThreshold>) -> Self {
+        self.top_up = top_up;
+        self
+    }
+
+    /// Validate transactions paid by this signer with `policy` applied
+    pub(crate) fn with_policy(mut self, policy: Option<SignerPolicyConfig>) -> Self {
+        self.policy = policy.map(Arc::new);
+        self
+    }
+
+    /// Allow at most `max_concurrent_signs` signing calls against this signer at once
+    pub(crate) fn with_max_concurrent_signs(mut self, max_concurrent_signs: Option<usize>) -> Self {
+        self.sign_permits = max_concurrent_signs.map(|permits| Arc::new(Semaphore::new(permits)));
+        self
+    }
+
+    fn p95_latency(&self) -> Option<Duration> {
+        self.latency.lock().p95()
+    }
+
+    /// Slow or saturated signers are only selected when no other signer is eligible
+    fn is_penalized(&self, slow_signer_p95: Option<Duration>) -> bool {
+        let is_slow = slow_signer_p95
+            .is_some_and(|max_p95| self.p95_latency().is_some_and(|p95| p95 > max_p95));
+        let is_saturated =
+            self.sign_permits.as_ref().is_some_and(|permits| permits.available_permits() == 0);
+        is_slow || is_saturated
+    }
+
+    /// Wait up to `wait` for a free slot under `max_concurrent_signs`. The slot is held
+    /// until the returned permit is dropped; `None` means the signer has no limit.
+    async fn acquire_sign_permit(
+        &self,
+        wait: Duration,
+    ) -> Result<Option<OwnedSemaphorePermit>, KoraError> {
+        let Some(permits) = &self.sign_permits else {
+            return Ok(None);
+        };
+
+        match tokio::time::timeout(wait, permits.clone().acquire_owned()).await {
+            // The semaphore is never closed, so acquiring only fails if it somehow was
+            Ok(permit) => Ok(permit.ok()),
+            Err(_) => Err(KoraError::SignerBusy(format!(
+                "Signer {} has no free signing slot after {}s",
+                self.name,
+                wait.as_secs()
+            ))),
         }
     }
 
+    /// Sign `message` and record how long the backend took
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        let mut attempt = SignAttempt { signer: self, started_at: Instant::now(), outcome: None };
+        let result = self.signer.sign_message(message).await;
+        attempt.outcome = Some(if result.is_ok() { "success" } else { "error" });
+        result
+    }
+
+    /// Whether this signer's allowed programs, or `default_allowed_programs` if it does
+    /// not override them, include every program in `program_ids`
+    fn allows_programs(
+        &self,
+        program_ids: &[Pubkey],
+        default_allowed_programs: &ProgramsConfig,
+    ) -> bool {
+        let allowed_programs = self
+            .policy
+            .as_ref()
+            .and_then(|policy| policy.allowed_programs.as_ref())
+            .unwrap_or(default_allowed_programs);
+        program_ids.iter().all(|program_id| allowed_programs.allows(program_id))
+    }
+
     /// Update the last used timestamp to current time
     fn update_last_used(&self) {
         let now = std::time::SystemTime::now()
@@ -130,6 +341,10 @@ impl SignerWithMetadata {
         &self.name
     }
 
+    pub(crate) fn pubkey(&self) -> Pubkey {
+        self.signer.pubkey()
+    }
+
     fn release_stale_probe_lock_if_needed(&self, health: &mut HealthState, probe_lease: Duration) {
         if !health.probe_in_flight {
             return;
@@ -198,6 +413,23 @@ impl SignerWithMetadata {
         !health.probe_in_flight
     }
 
+    fn availability(&self) -> SignerAvailability {
+        *self.availability.lock()
+    }
+
+    fn balance_lamports(&self) -> Option<u64> {
+        *self.balance_lamports.lock()
+    }
+
+    /// A signer whose balance hasn't been polled yet is assumed funded, so a pool is
+    /// usable before the balance tracker's first run
+    fn has_min_balance(&self, min_balance_lamports: Option<u64>) -> bool {
+        match (min_balance_lamports, self.balance_lamports()) {
+            (Some(min), Some(balance)) => balance >= min,
+            _ => true,
+        }
+    }
+
     fn is_eligible_for_selection(&self, probe_lease: Duration) -> bool {
         let health = self.health.lock();
         self.is_probe_eligible_without_lock(&health, probe_lease)
@@ -222,14 +454,22 @@ impl SignerWithMetadata {
 
 /// A pool of signers with different selection strategies
 pub struct SignerPool {
-    /// List of signers with their metadata
-    signers: Vec<SignerWithMetadata>,
+    /// List of signers with their metadata, shared with pools derived from this one
+    signers: Vec<Arc<SignerWithMetadata>>,
     /// Strategy for selecting signers
     strategy: SelectionStrategy,
     /// Current index for round-robin selection
     current_index: AtomicUsize,
     /// Stale probe lease derived from the signing timeout/retry budget.
     probe_lease_ms: AtomicU64,
+    /// Signers below this balance are not selected
+    min_balance_lamports: Option<u64>,
+    /// Signers with a higher p95 signing latency are only selected as a last resort
+    slow_signer_p95: Option<Duration>,
+    /// How long users stay on their signer, when selection is sticky per `user_id`
+    user_affinity_ttl: Option<Duration>,
+    /// Refills signers configured with top-up thresholds
+    treasury: Option<Arc<Treasury>>,
 }
 
 #[derive(Clone, Copy)]
@@ -239,12 +479,21 @@ enum ProbeReservationMode {
 }
 
 /// Information about a signer for monitoring/debugging
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SignerInfo {
     pub public_key: String,
     pub name: String,
     pub weight: u32,
     pub last_used: u64, // Unix timestamp
+    pub availability: SignerAvailability,
+    pub is_healthy: bool,
+    pub consecutive_failures: u32,
+    /// Last balance seen by the balance tracker
+    pub balance_lamports: Option<u64>,
+    /// Unix timestamp at which the draining signer is removed from the pool
+    pub removal_at: Option<u64>,
+    /// 95th percentile signing latency over the last few minutes
+    pub p95_latency_ms: Option<u64>,
 }
 
 impl SignerPool {
@@ -265,13 +514,29 @@ impl SignerPool {
     #[cfg(test)]
     pub(crate) fn new(signers: Vec<SignerWithMetadata>) -> Self {
         Self {
-            signers,
+            signers: signers.into_iter().map(Arc::new).collect(),
             strategy: SelectionStrategy::RoundRobin,
             current_index: AtomicUsize::new(0),
             probe_lease_ms: AtomicU64::new(Self::default_probe_lease_ms()),
+            min_balance_lamports: None,
+            slow_signer_p95: None,
+            user_affinity_ttl: None,
+            treasury: None,
         }
     }
 
+    #[cfg(test)]
+    pub(crate) fn with_treasury(mut self, treasury: Treasury) -> Self {
+        self.treasury = Some(Arc::new(treasury));
+        self
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_user_affinity_ttl(mut self, ttl: Duration) -> Self {
+        self.user_affinity_ttl = Some(ttl);
+        self
+    }
+
     /// Create a new signer pool from configuration
     pub async fn from_config(config: SignerPoolConfig) -> Result<Self, KoraError> {
         if config.signers.is_empty() {
@@ -283,20 +548,14 @@ impl SignerPool {
         for signer_config in config.signers {
             log::info!("Initializing signer: {}", signer_config.name);
 
-            let signer = SignerConfig::build_signer_from_config(&signer_config).await?;
-            let weight = signer_config.weight.unwrap_or(DEFAULT_WEIGHT);
-
-            signers.push(SignerWithMetadata::new(
-                signer_config.name.clone(),
-                Arc::new(signer),
-                weight,
-            ));
+            let signer = SignerWithMetadata::from_config(&signer_config).await?;
 
             log::info!(
                 "Successfully initialized signer: {} (weight: {})",
                 signer_config.name,
-                weight
+                signer.weight
             );
+            signers.push(Arc::new(signer));
         }
 
         let mut seen_pubkeys = std::collections::HashSet::new();
@@ -309,6 +568,25 @@ impl SignerPool {
             }
         }
 
+        let treasury = match &config.treasury {
+            Some(treasury_config) => {
+                let treasury = Treasury::from_config(treasury_config).await?;
+                if let Some(signer) = signers
+                    .iter()
+                    .find(|s| s.top_up.is_some() && s.signer.pubkey() == treasury.pubkey())
+                {
+                    return Err(KoraError::ValidationError(format!(
+                        "Treasury '{}' resolves to the same pubkey as signer '{}', which it is configured to top up",
+                        treasury.name(),
+                        signer.name
+                    )));
+                }
+                log::info!("Initialized treasury: {} ({})", treasury.name(), treasury.pubkey());
+                Some(Arc::new(treasury))
+            }
+            None => None,
+        };
+
         let total_weight: u32 = signers.iter().map(|s| s.weight).sum();
 
         if matches!(config.signer_pool.strategy, SelectionStrategy::Weighted) && total_weight == 0 {
@@ -328,9 +606,35 @@ impl SignerPool {
             strategy: config.signer_pool.strategy,
             current_index: AtomicUsize::new(0),
             probe_lease_ms: AtomicU64::new(Self::default_probe_lease_ms()),
+            min_balance_lamports: config.signer_pool.min_balance_lamports,
+            slow_signer_p95: config.signer_pool.slow_signer_p95_ms.map(Duration::from_millis),
+            user_affinity_ttl: config
+                .signer_pool
+                .user_affinity
+                .map(|affinity| Duration::from_secs(affinity.ttl_seconds)),
+            treasury,
         })
     }
 
+    /// The treasury that refills depleted signers, if one is configured
+    pub fn treasury(&self) -> Option<Arc<Treasury>> {
+        self.treasury.clone()
+    }
+
+    /// Signers the treasury keeps funded
+    pub(crate) fn top_up_targets(&self) -> Vec<TopUpTarget> {
+        self.signers
+            .iter()
+            .filter_map(|s| {
+                s.top_up.map(|threshold| TopUpTarget {
+                    name: s.name.clone(),
+                    pubkey: s.signer.pubkey(),
+                    threshold,
+                })
+            })
+            .collect()
+    }
+
     pub(crate) fn set_probe_lease(&self, probe_lease: Duration) {
         let probe_lease_ms = Self::probe_lease_to_ms(probe_lease);
         self.probe_lease_ms.store(probe_lease_ms, Ordering::Relaxed);
@@ -340,6 +644,40 @@ impl SignerPool {
         Duration::from_millis(self.probe_lease_ms.load(Ordering::Relaxed))
     }
 
+    /// Wait up to `wait` for a free slot under `signer`'s `max_concurrent_signs`, failing
+    /// with [`KoraError::SignerBusy`] otherwise. Acquire it before any signing timeout
+    /// starts and hold it across retries, so that queueing behind other requests is
+    /// neither counted against the sign timeout nor recorded as a signing failure.
+    pub async fn acquire_sign_permit(
+        &self,
+        signer: &Arc<Signer>,
+        wait: Duration,
+    ) -> Result<Option<OwnedSemaphorePermit>, KoraError> {
+        match self.signers.iter().find(|s| Arc::ptr_eq(&s.signer, signer)) {
+            Some(meta) => meta.acquire_sign_permit(wait).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Sign `message` with `signer`, recording the attempt's latency for selection and
+    /// metrics. Callers hold the signer's permit from [`Self::acquire_sign_permit`].
+    pub async fn sign_message(
+        &self,
+        signer: &Arc<Signer>,
+        message: &[u8],
+    ) -> Result<Signature, SignerError> {
+        match self.signers.iter().find(|s| Arc::ptr_eq(&s.signer, signer)) {
+            Some(meta) => meta.sign_message(message).await,
+            None => {
+                log::warn!(
+                    "sign_message called for signer {} not found in pool; latency tracking skipped",
+                    signer.pubkey()
+                );
+                signer.sign_message(message).await
+            }
+        }
+    }
+
     /// Records a successful signature creation, resetting consecutive failures to 0
     pub fn record_signing_success(&self, signer: &Arc<Signer>) {
         self.with_signer_metadata(signer, "record_signing_success", |meta| meta.record_success());
@@ -366,14 +704,24 @@ impl SignerPool {
 
     /// Filters the active signers down to healthy signers plus unhealthy signers whose
     /// recovery probe cooldown has elapsed and whose probe lock is not actively in-flight.
+    /// Draining and disabled signers, and signers below `min_balance_lamports`, are never
+    /// selected.
     fn eligible_signers(&self) -> Result<Vec<&SignerWithMetadata>, KoraError> {
         let probe_lease = self.probe_lease();
-        let eligible: Vec<_> =
-            self.signers.iter().filter(|s| s.is_eligible_for_selection(probe_lease)).collect();
+        let eligible: Vec<_> = self
+            .signers
+            .iter()
+            .filter(|s| {
+                s.availability() == SignerAvailability::Active
+                    && s.has_min_balance(self.min_balance_lamports)
+                    && s.is_eligible_for_selection(probe_lease)
+            })
+            .map(Arc::as_ref)
+            .collect();
 
         if eligible.is_empty() {
             log::error!(
-                "No signer is currently eligible (all unhealthy and recovery cooldown/probe lock active) across {} signers",
+                "No signer is currently eligible (all unavailable, below min balance, or unhealthy with recovery cooldown/probe lock active) across {} signers",
                 self.signers.len()
             );
             return Err(KoraError::InternalServerError(
@@ -393,12 +741,51 @@ impl SignerPool {
             SelectionStrategy::RoundRobin => self.round_robin_select_from(signers),
             SelectionStrategy::Random => self.random_select_from(signers),
             SelectionStrategy::Weighted => self.weighted_select_from(signers),
+            SelectionStrategy::BalanceAware => self.balance_aware_select_from(signers),
         }
     }
 
+    /// Eligible signers narrowed down to the ones that `accepts` the request
+    fn accepting_signers(
+        &self,
+        accepts: &impl Fn(&SignerWithMetadata) -> bool,
+    ) -> Result<Vec<&SignerWithMetadata>, KoraError> {
+        let accepting: Vec<_> =
+            self.eligible_signers()?.into_iter().filter(|signer| accepts(signer)).collect();
+
+        if accepting.is_empty() {
+            return Err(KoraError::ValidationError(
+                "No available signer has a policy that allows this transaction".to_string(),
+            ));
+        }
+
+        Ok(accepting)
+    }
+
+    /// Signers that `accepts` the request, leaving out slow and saturated signers while
+    /// others are left
+    fn routed_signers(
+        &self,
+        accepts: &impl Fn(&SignerWithMetadata) -> bool,
+    ) -> Result<Vec<&SignerWithMetadata>, KoraError> {
+        let routed = self.accepting_signers(accepts)?;
+
+        let preferred: Vec<_> = routed
+            .iter()
+            .copied()
+            .filter(|signer| !signer.is_penalized(self.slow_signer_p95))
+            .collect();
+        if preferred.is_empty() {
+            return Ok(routed);
+        }
+
+        Ok(preferred)
+    }
+
     fn select_next_signer_internal(
         &self,
         mode: ProbeReservationMode,
+        accepts: impl Fn(&SignerWithMetadata) -> bool,
     ) -> Result<Arc<Signer>, KoraError> {
         if self.signers.is_empty() {
             return Err(KoraError::InternalServerError("Signer pool is empty".to_string()));
@@ -406,14 +793,14 @@ impl SignerPool {
 
         match mode {
             ProbeReservationMode::ReadOnly => {
-                let eligible = self.eligible_signers()?;
+                let eligible = self.routed_signers(&accepts)?;
                 let signer_meta = self.select_from_eligible(&eligible)?;
                 signer_meta.update_last_used();
                 Ok(Arc::clone(&signer_meta.signer))
             }
             ProbeReservationMode::Reserve => {
                 for _ in 0..self.signers.len().max(1) {
-                    let eligible = self.eligible_signers()?;
+                    let eligible = self.routed_signers(&accepts)?;
                     let probe_lease = self.probe_lease();
                     let signer_meta = self.select_from_eligible(&eligible)?;
 
@@ -432,7 +819,7 @@ impl SignerPool {
         }
     }
 
-    fn find_signer_by_pubkey(&self, pubkey: &str) -> Result<&SignerWithMetadata, KoraError> {
+    fn find_signer_by_pubkey(&self, pubkey: &str) -> Result<&Arc<SignerWithMetadata>, KoraError> {
         let target_pubkey = Pubkey::from_str(pubkey).map_err(|_| {
             KoraError::ValidationError(format!("Invalid signer signer key pubkey: {pubkey}"))
         })?;
@@ -449,6 +836,10 @@ impl SignerPool {
     ) -> Result<Arc<Signer>, KoraError> {
         let signer_meta = self.find_signer_by_pubkey(pubkey)?;
 
+        if signer_meta.availability() == SignerAvailability::Disabled {
+            return Err(KoraError::ValidationError(format!("Pinned signer {pubkey} is disabled")));
+        }
+
         let signer_available = match mode {
             ProbeReservationMode::ReadOnly => {
                 signer_meta.is_eligible_for_selection(self.probe_lease())
@@ -471,12 +862,121 @@ impl SignerPool {
 
     /// Select the next eligible signer without mutating recovery probe state.
     pub fn select_next_signer(&self) -> Result<Arc<Signer>, KoraError> {
-        self.select_next_signer_internal(ProbeReservationMode::ReadOnly)
+        self.select_next_signer_internal(ProbeReservationMode::ReadOnly, |_| true)
+    }
+
+    /// Select the next eligible signer whose policy allows every program in
+    /// `program_ids`, without mutating recovery probe state. Signers without an
+    /// `allowed_programs` override are checked against `default_allowed_programs`, and
+    /// signers in `excluded` are never selected.
+    pub fn select_next_signer_for_programs(
+        &self,
+        program_ids: &[Pubkey],
+        default_allowed_programs: &ProgramsConfig,
+        excluded: &[Pubkey],
+    ) -> Result<Arc<Signer>, KoraError> {
+        // Without overrides every signer shares the same programs, and validation
+        // reports a disallowed program more precisely than routing would
+        let check_programs = self.has_program_overrides();
+
+        self.select_next_signer_internal(ProbeReservationMode::ReadOnly, |signer| {
+            !excluded.contains(&signer.pubkey())
+                && (!check_programs
+                    || signer.allows_programs(program_ids, default_allowed_programs))
+        })
+    }
+
+    /// How long users stay on their signer, if selection is sticky per `user_id`
+    pub fn user_affinity_ttl(&self) -> Option<Duration> {
+        self.user_affinity_ttl
+    }
+
+    /// Select the signer that serves `user_id` among the eligible signers whose policy
+    /// allows every program in `program_ids`, without mutating recovery probe state.
+    /// `remembered` is the signer the user was last served by and is kept while it is
+    /// eligible. Otherwise the user is placed by weighted rendezvous hashing, so a signer
+    /// leaving the pool only moves its own users. Slow signers are not avoided, as that
+    /// would move users back and forth. Signers in `excluded` are never selected.
+    pub fn select_signer_for_user(
+        &self,
+        user_id: &str,
+        remembered: Option<&Pubkey>,
+        program_ids: &[Pubkey],
+        default_allowed_programs: &ProgramsConfig,
+        excluded: &[Pubkey],
+    ) -> Result<Arc<Signer>, KoraError> {
+        if self.signers.is_empty() {
+            return Err(KoraError::InternalServerError("Signer pool is empty".to_string()));
+        }
+
+        let check_programs = self.has_program_overrides();
+        let accepting = self.accepting_signers(&|signer: &SignerWithMetadata| {
+            !excluded.contains(&signer.pubkey())
+                && (!check_programs
+                    || signer.allows_programs(program_ids, default_allowed_programs))
+        })?;
+
+        let signer_meta = remembered
+            .and_then(|pubkey| accepting.iter().find(|signer| signer.pubkey() == *pubkey))
+            .or_else(|| {
+                accepting.iter().max_by(|a, b| {
+                    Self::affinity_score(user_id, a).total_cmp(&Self::affinity_score(user_id, b))
+                })
+            })
+            .ok_or_else(|| KoraError::InternalServerError("Signer pool is empty".to_string()))?;
+
+        signer_meta.update_last_used();
+        Ok(Arc::clone(&signer_meta.signer))
+    }
+
+    /// Rendezvous score of `signer` for `user_id`, the highest scoring signer serves the
+    /// user. Stable across instances and restarts, and proportional to signer weight.
+    fn affinity_score(user_id: &str, signer: &SignerWithMetadata) -> f64 {
+        let digest = Sha256::new()
+            .chain_update(user_id.as_bytes())
+            .chain_update(signer.pubkey().as_ref())
+            .finalize();
+        let mut hash = [0u8; 8];
+        hash.copy_from_slice(&digest[..8]);
+        // Uniform in (0, 1), so its negated logarithm is positive and finite
+        let unit = ((u64::from_be_bytes(hash) >> 11) as f64 + 0.5) / (1u64 << 53) as f64;
+        f64::from(signer.weight) / -unit.ln()
     }
 
     /// Get the next signer according to the configured strategy
     pub fn get_next_signer(&self) -> Result<Arc<Signer>, KoraError> {
-        self.select_next_signer_internal(ProbeReservationMode::Reserve)
+        self.select_next_signer_internal(ProbeReservationMode::Reserve, |_| true)
+    }
+
+    fn has_program_overrides(&self) -> bool {
+        self.signers.iter().any(|signer| {
+            signer.policy.as_ref().is_some_and(|policy| policy.allowed_programs.is_some())
+        })
+    }
+
+    /// Whether any signer overrides the validation that depends on more than the
+    /// transaction's programs, so routing has to validate the transaction per signer
+    pub fn has_validation_overrides(&self) -> bool {
+        self.signers.iter().any(|signer| {
+            signer.policy.as_ref().is_some_and(|policy| {
+                policy.fee_payer_policy.is_some()
+                    || policy.max_allowed_lamports.is_some()
+                    || policy.price.is_some()
+            })
+        })
+    }
+
+    /// Whether the signer with `pubkey` is a member of this pool
+    pub fn has_signer(&self, pubkey: &Pubkey) -> bool {
+        self.signers.iter().any(|signer| signer.signer.pubkey() == *pubkey)
+    }
+
+    /// Validation overrides configured for the signer with `pubkey`
+    pub fn signer_policy(&self, pubkey: &Pubkey) -> Option<Arc<SignerPolicyConfig>> {
+        self.signers
+            .iter()
+            .find(|signer| signer.signer.pubkey() == *pubkey)
+            .and_then(|signer| signer.policy.clone())
     }
 
     fn round_robin_select_from<'a>(
@@ -512,19 +1012,222 @@ impl SignerPool {
         Ok(signers[0])
     }
 
+    /// Picks a signer with probability proportional to its balance above
+    /// `min_balance_lamports`. Signers without a tracked balance only get picked, in
+    /// round-robin order, while no balance is known at all.
+    fn balance_aware_select_from<'a>(
+        &self,
+        signers: &[&'a SignerWithMetadata],
+    ) -> Result<&'a SignerWithMetadata, KoraError> {
+        let floor = self.min_balance_lamports.unwrap_or(0);
+        let spendable: Vec<u64> = signers
+            .iter()
+            .map(|s| s.balance_lamports().map_or(0, |balance| balance.saturating_sub(floor)))
+            .collect();
+        let total = spendable.iter().fold(0u64, |total, lamports| total.saturating_add(*lamports));
+
+        if total == 0 {
+            return self.round_robin_select_from(signers);
+        }
+
+        let mut rng = rand::rng();
+        let mut target = rng.random_range(0..total);
+        for (signer, lamports) in signers.iter().zip(&spendable) {
+            if target < *lamports {
+                return Ok(signer);
+            }
+            target -= lamports;
+        }
+        Ok(signers[0])
+    }
+
+    /// Record a signer's balance as polled by the balance tracker
+    pub fn record_signer_balance(&self, pubkey: &Pubkey, lamports: u64) {
+        if let Some(signer_meta) = self.signers.iter().find(|s| s.signer.pubkey() == *pubkey) {
+            let previous = signer_meta.balance_lamports.lock().replace(lamports);
+
+            if let Some(min) = self.min_balance_lamports {
+                let was_funded = previous.is_none_or(|balance| balance >= min);
+                if was_funded && lamports < min {
+                    log::warn!(
+                        "Signer '{}' balance {lamports} is below min_balance_lamports {min}, excluding it from selection",
+                        signer_meta.name
+                    );
+                } else if !was_funded && lamports >= min {
+                    log::info!(
+                        "Signer '{}' is funded again ({lamports} lamports)",
+                        signer_meta.name
+                    );
+                }
+            }
+        }
+    }
+
     /// Get information about all signers in the pool
     pub fn get_signers_info(&self) -> Vec<SignerInfo> {
         self.signers
             .iter()
-            .map(|s| SignerInfo {
-                public_key: s.signer.pubkey().to_string(),
-                name: s.name.clone(),
-                weight: s.weight,
-                last_used: s.last_used.load(Ordering::Relaxed),
+            .map(|s| {
+                let health = *s.health.lock();
+                SignerInfo {
+                    public_key: s.signer.pubkey().to_string(),
+                    name: s.name.clone(),
+                    weight: s.weight,
+                    last_used: s.last_used.load(Ordering::Relaxed),
+                    availability: s.availability(),
+                    is_healthy: health.is_healthy,
+                    consecutive_failures: health.consecutive_failures,
+                    balance_lamports: s.balance_lamports(),
+                    removal_at: *s.removal_at.lock(),
+                    p95_latency_ms: s
+                        .p95_latency()
+                        .map(|p95| u64::try_from(p95.as_millis()).unwrap_or(u64::MAX)),
+                }
             })
             .collect()
     }
 
+    /// Drain, disable or re-activate a signer. Returns its previous availability.
+    pub fn set_signer_availability(
+        &self,
+        pubkey: &str,
+        availability: SignerAvailability,
+    ) -> Result<SignerAvailability, KoraError> {
+        let signer_meta = self.find_signer_by_pubkey(pubkey)?;
+        let previous = std::mem::replace(&mut *signer_meta.availability.lock(), availability);
+
+        if previous != availability {
+            log::info!("Signer '{}' is now {availability} (was {previous})", signer_meta.name);
+        }
+        if availability == SignerAvailability::Active
+            && signer_meta.removal_at.lock().take().is_some()
+        {
+            log::info!("Cancelled the scheduled removal of signer '{}'", signer_meta.name);
+        }
+        Ok(previous)
+    }
+
+    /// A copy of this pool over `signers`, keeping the settings and round-robin position
+    fn derive(&self, signers: Vec<Arc<SignerWithMetadata>>) -> Self {
+        Self {
+            signers,
+            strategy: self.strategy.clone(),
+            current_index: AtomicUsize::new(self.current_index.load(Ordering::Relaxed)),
+            probe_lease_ms: AtomicU64::new(self.probe_lease_ms.load(Ordering::Relaxed)),
+            min_balance_lamports: self.min_balance_lamports,
+            slow_signer_p95: self.slow_signer_p95,
+            user_affinity_ttl: self.user_affinity_ttl,
+            treasury: self.treasury.clone(),
+        }
+    }
+
+    /// A pool with `signer` added. Signers already in the pool are shared with the new
+    /// pool, so their health and availability carry over.
+    pub(crate) fn with_added_signer(&self, signer: SignerWithMetadata) -> Result<Self, KoraError> {
+        let pubkey = signer.signer.pubkey();
+        if let Some(existing) =
+            self.signers.iter().find(|s| s.name == signer.name || s.signer.pubkey() == pubkey)
+        {
+            return Err(KoraError::ValidationError(format!(
+                "Signer '{}' ({pubkey}) conflicts with signer '{}' ({}) already in the pool",
+                signer.name,
+                existing.name,
+                existing.signer.pubkey()
+            )));
+        }
+
+        if signer.top_up.is_some() {
+            match &self.treasury {
+                None => {
+                    return Err(KoraError::ValidationError(format!(
+                        "Signer '{}' sets target_balance/refill_below but no treasury is configured",
+                        signer.name
+                    )))
+                }
+                Some(treasury) if treasury.pubkey() == pubkey => {
+                    return Err(KoraError::ValidationError(format!(
+                        "Treasury '{}' resolves to the same pubkey as signer '{}', which it is configured to top up",
+                        treasury.name(),
+                        signer.name
+                    )))
+                }
+                Some(_) => {}
+            }
+        }
+
+        let mut signers = self.signers.clone();
+        signers.push(Arc::new(signer));
+        Ok(self.derive(signers))
+    }
+
+    /// Drain a signer and mark it for removal at `removal_at` (Unix timestamp). A disabled
+    /// signer stays disabled. Setting the signer back to active cancels the removal.
+    pub fn schedule_signer_removal(&self, pubkey: &str, removal_at: u64) -> Result<(), KoraError> {
+        let signer_meta = self.find_signer_by_pubkey(pubkey)?;
+
+        let others_remain = self
+            .signers
+            .iter()
+            .any(|s| !Arc::ptr_eq(s, signer_meta) && s.removal_at.lock().is_none());
+        if !others_remain {
+            return Err(KoraError::ValidationError(format!(
+                "Cannot remove signer '{}', it is the last signer not already being removed",
+                signer_meta.name
+            )));
+        }
+
+        {
+            let mut availability = signer_meta.availability.lock();
+            if *availability == SignerAvailability::Active {
+                *availability = SignerAvailability::Draining;
+            }
+        }
+        *signer_meta.removal_at.lock() = Some(removal_at);
+
+        log::info!("Signer '{}' is draining until its removal at {removal_at}", signer_meta.name);
+        Ok(())
+    }
+
+    /// A pool without the signer if its removal is still scheduled for `removal_at`.
+    /// None if the removal was cancelled or rescheduled, or the signer is already gone.
+    pub(crate) fn without_scheduled_signer(
+        &self,
+        pubkey: &str,
+        removal_at: u64,
+    ) -> Result<Option<Self>, KoraError> {
+        let Ok(signer_meta) = self.find_signer_by_pubkey(pubkey) else {
+            return Ok(None);
+        };
+        if *signer_meta.removal_at.lock() != Some(removal_at) {
+            return Ok(None);
+        }
+
+        let signers: Vec<_> =
+            self.signers.iter().filter(|s| !Arc::ptr_eq(s, signer_meta)).cloned().collect();
+        if signers.is_empty() {
+            return Err(KoraError::ValidationError(format!(
+                "Cannot remove signer '{}', it is the last signer in the pool",
+                signer_meta.name
+            )));
+        }
+
+        Ok(Some(self.derive(signers)))
+    }
+
+    /// Take over the state of signers that were also in `previous`, matched by pubkey,
+    /// and its round-robin position. Used when the pool is rebuilt from `signers.toml`.
+    pub(crate) fn carry_over_state_from(&self, previous: &SignerPool) {
+        for signer_meta in &self.signers {
+            let pubkey = signer_meta.signer.pubkey();
+            if let Some(previous_meta) =
+                previous.signers.iter().find(|s| s.signer.pubkey() == pubkey)
+            {
+                signer_meta.copy_state_from(previous_meta);
+            }
+        }
+        self.current_index.store(previous.current_index.load(Ordering::Relaxed), Ordering::Relaxed);
+    }
+
     /// Get the number of signers in the pool
     pub fn len(&self) -> usize {
         self.signers.len()
@@ -603,11 +1306,33 @@ mod tests {
             config: MemorySignerConfig { private_key_env: "KORA_TEST_DUP_PUBKEY_KEY".to_string() },
         };
         let config = SignerPoolConfig {
-            signer_pool: SignerPoolSettings { strategy: SelectionStrategy::RoundRobin },
+            signer_pool: SignerPoolSettings {
+                strategy: SelectionStrategy::RoundRobin,
+                min_balance_lamports: None,
+                slow_signer_p95_ms: None,
+                user_affinity: None,
+            },
             signers: vec![
-                SignerConfig { name: "signer_a".to_string(), weight: Some(1), config: memory() },
-                SignerConfig { name: "signer_b".to_string(), weight: Some(1), config: memory() },
+                SignerConfig {
+                    name: "signer_a".to_string(),
+                    weight: Some(1),
+                    target_balance: None,
+                    refill_below: None,
+                    policy: None,
+                    max_concurrent_signs: None,
+                    config: memory(),
+                },
+                SignerConfig {
+                    name: "signer_b".to_string(),
+                    weight: Some(1),
+                    target_balance: None,
+                    refill_below: None,
+                    policy: None,
+                    max_concurrent_signs: None,
+                    config: memory(),
+                },
             ],
+            treasury: None,
         };
 
         let result = SignerPool::from_config(config).await;
@@ -625,19 +1350,29 @@ mod tests {
         let keypair1 = Keypair::new();
         let keypair2 = Keypair::new();
 
-        let external_signer1 =
-            solana_keychain::Signer::from_memory(&keypair1.to_base58_string()).unwrap();
-        let external_signer2 =
-            solana_keychain::Signer::from_memory(&keypair2.to_base58_string()).unwrap();
+        let external_signer1 = Signer::from_memory(&keypair1.to_base58_string()).unwrap();
+        let external_signer2 = Signer::from_memory(&keypair2.to_base58_string()).unwrap();
 
         SignerPool {
             signers: vec![
-                SignerWithMetadata::new("signer_1".to_string(), Arc::new(external_signer1), 1),
-                SignerWithMetadata::new("signer_2".to_string(), Arc::new(external_signer2), 2),
+                Arc::new(SignerWithMetadata::new(
+                    "signer_1".to_string(),
+                    Arc::new(external_signer1),
+                    1,
+                )),
+                Arc::new(SignerWithMetadata::new(
+                    "signer_2".to_string(),
+                    Arc::new(external_signer2),
+                    2,
+                )),
             ],
             strategy: SelectionStrategy::RoundRobin,
             current_index: AtomicUsize::new(0),
             probe_lease_ms: AtomicU64::new(SignerPool::default_probe_lease_ms()),
+            min_balance_lamports: None,
+            slow_signer_p95: None,
+            user_affinity_ttl: None,
+            treasury: None,
         }
     }
 
@@ -692,6 +1427,10 @@ mod tests {
             strategy: SelectionStrategy::RoundRobin,
             current_index: AtomicUsize::new(0),
             probe_lease_ms: AtomicU64::new(SignerPool::default_probe_lease_ms()),
+            min_balance_lamports: None,
+            slow_signer_p95: None,
+            user_affinity_ttl: None,
+            treasury: None,
         };
 
         assert!(pool.get_next_signer().is_err());
@@ -962,4 +1701,350 @@ mod tests {
         let healthy_after_full_budget = pool.eligible_signers().unwrap();
         assert_eq!(healthy_after_full_budget.len(), 2);
     }
+
+    #[test]
+    fn test_draining_signer_is_only_used_when_pinned() {
+        let pool = create_test_pool();
+        let pubkey = pool.signers[0].signer.pubkey().to_string();
+
+        let previous = pool.set_signer_availability(&pubkey, SignerAvailability::Draining).unwrap();
+        assert_eq!(previous, SignerAvailability::Active);
+
+        let eligible = pool.eligible_signers().unwrap();
+        assert_eq!(eligible.len(), 1);
+        assert_eq!(eligible[0].name(), "signer_2");
+        assert!(pool.get_signer_by_pubkey(&pubkey).is_ok());
+
+        let info = pool.get_signers_info();
+        assert_eq!(info[0].availability, SignerAvailability::Draining);
+        assert!(info[0].is_healthy);
+    }
+
+    fn memory_signer(name: &str) -> SignerWithMetadata {
+        let signer = Signer::from_memory(&Keypair::new().to_base58_string()).unwrap();
+        SignerWithMetadata::new(name.to_string(), Arc::new(signer), 1)
+    }
+
+    #[test]
+    fn test_added_signer_keeps_existing_signer_state() {
+        let pool = create_test_pool();
+        let existing = pool.signers[0].signer.clone();
+        pool.record_signing_failure(&existing);
+        pool.select_next_signer().unwrap();
+
+        let new_pool = pool.with_added_signer(memory_signer("signer_3")).unwrap();
+
+        assert_eq!(new_pool.len(), 3);
+        assert_eq!(new_pool.get_signers_info()[0].consecutive_failures, 1);
+        assert_eq!(new_pool.current_index.load(Ordering::Relaxed), 1);
+        // Health recorded through the old pool reaches the new one
+        pool.record_signing_failure(&existing);
+        assert_eq!(new_pool.get_signers_info()[0].consecutive_failures, 2);
+
+        let mut duplicate_name = memory_signer("signer_1");
+        assert!(pool.with_added_signer(duplicate_name).is_err());
+        duplicate_name = SignerWithMetadata::new("signer_4".to_string(), existing, 1);
+        assert!(pool.with_added_signer(duplicate_name).is_err());
+    }
+
+    #[test]
+    fn test_scheduled_removal_drains_then_removes_signer() {
+        let pool = create_test_pool();
+        let pubkey = pool.signers[0].signer.pubkey().to_string();
+
+        pool.schedule_signer_removal(&pubkey, 100).unwrap();
+
+        let info = pool.get_signers_info();
+        assert_eq!(info[0].availability, SignerAvailability::Draining);
+        assert_eq!(info[0].removal_at, Some(100));
+        assert!(pool.get_signer_by_pubkey(&pubkey).is_ok());
+        // The other signer is the last one not being removed
+        let other = pool.signers[1].signer.pubkey().to_string();
+        assert!(pool.schedule_signer_removal(&other, 100).is_err());
+
+        // A stale timer for an earlier schedule does nothing
+        assert!(pool.without_scheduled_signer(&pubkey, 50).unwrap().is_none());
+        let new_pool = pool.without_scheduled_signer(&pubkey, 100).unwrap().unwrap();
+        assert_eq!(new_pool.len(), 1);
+        assert!(new_pool.get_signer_by_pubkey(&pubkey).is_err());
+    }
+
+    #[test]
+    fn test_reactivating_signer_cancels_removal() {
+        let pool = create_test_pool();
+        let pubkey = pool.signers[0].signer.pubkey().to_string();
+        pool.schedule_signer_removal(&pubkey, 100).unwrap();
+
+        pool.set_signer_availability(&pubkey, SignerAvailability::Active).unwrap();
+
+        assert_eq!(pool.get_signers_info()[0].removal_at, None);
+        assert!(pool.without_scheduled_signer(&pubkey, 100).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_rebuilt_pool_carries_over_signer_state() {
+        let pool = create_test_pool();
+        let drained = pool.signers[1].signer.clone();
+        pool.set_signer_availability(&drained.pubkey().to_string(), SignerAvailability::Draining)
+            .unwrap();
+        pool.record_signing_failure(&drained);
+        pool.record_signer_balance(&drained.pubkey(), 42);
+
+        let rebuilt = SignerPool::new(vec![
+            SignerWithMetadata::new("renamed".to_string(), drained, 5),
+            memory_signer("signer_new"),
+        ]);
+        rebuilt.carry_over_state_from(&pool);
+
+        let info = rebuilt.get_signers_info();
+        assert_eq!(info[0].name, "renamed");
+        assert_eq!(info[0].weight, 5);
+        assert_eq!(info[0].availability, SignerAvailability::Draining);
+        assert_eq!(info[0].consecutive_failures, 1);
+        assert_eq!(info[0].balance_lamports, Some(42));
+        assert_eq!(info[1].availability, SignerAvailability::Active);
+        assert_eq!(info[1].consecutive_failures, 0);
+    }
+
+    #[test]
+    fn test_select_next_signer_routes_by_program_policy() {
+        let deploy_program = Pubkey::new_unique();
+        let payments_program = Pubkey::new_unique();
+        let allow_only = |program: Pubkey| SignerPolicyConfig {
+            allowed_programs: Some(ProgramsConfig::Allowlist(vec![program.to_string()])),
+            ..Default::default()
+        };
+        let pool = SignerPool::new(vec![
+            memory_signer("deploy").with_policy(Some(allow_only(deploy_program))),
+            memory_signer("payments").with_policy(Some(allow_only(payments_program))),
+            memory_signer("default"),
+        ]);
+        let default_programs = ProgramsConfig::Allowlist(vec![payments_program.to_string()]);
+        let pubkey_of = |index: usize| pool.signers[index].signer.pubkey();
+
+        for _ in 0..4 {
+            let signer = pool
+                .select_next_signer_for_programs(&[deploy_program], &default_programs, &[])
+                .unwrap();
+            assert_eq!(signer.pubkey(), pubkey_of(0));
+
+            let signer = pool
+                .select_next_signer_for_programs(&[payments_program], &default_programs, &[])
+                .unwrap();
+            assert_ne!(signer.pubkey(), pubkey_of(0));
+        }
+
+        let result = pool.select_next_signer_for_programs(
+            &[deploy_program, payments_program],
+            &default_programs,
+            &[],
+        );
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+
+        assert!(pool.signer_policy(&pubkey_of(0)).is_some());
+        assert!(pool.signer_policy(&pubkey_of(2)).is_none());
+    }
+
+    #[test]
+    fn test_select_next_signer_for_programs_without_overrides_ignores_programs() {
+        let pool = create_test_pool();
+        let nothing_allowed = ProgramsConfig::Allowlist(vec![]);
+
+        let result =
+            pool.select_next_signer_for_programs(&[Pubkey::new_unique()], &nothing_allowed, &[]);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_selection_skips_excluded_signers() {
+        let pool = SignerPool::new(vec![memory_signer("signer_1"), memory_signer("signer_2")]);
+        let programs = ProgramsConfig::Allowlist(vec![]);
+        let excluded = [pool.signers[0].pubkey()];
+
+        for _ in 0..4 {
+            let signer = pool.select_next_signer_for_programs(&[], &programs, &excluded).unwrap();
+            assert_eq!(signer.pubkey(), pool.signers[1].pubkey());
+
+            let signer = pool
+                .select_signer_for_user("user", Some(&excluded[0]), &[], &programs, &excluded)
+                .unwrap();
+            assert_eq!(signer.pubkey(), pool.signers[1].pubkey());
+        }
+
+        let everyone = [pool.signers[0].pubkey(), pool.signers[1].pubkey()];
+        let result = pool.select_next_signer_for_programs(&[], &programs, &everyone);
+        assert!(matches!(result, Err(KoraError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_select_signer_for_user_moves_only_users_of_departed_signer() {
+        let pool = SignerPool::new((0..5).map(|i| memory_signer(&format!("signer_{i}"))).collect());
+        let programs = ProgramsConfig::Allowlist(vec![]);
+        let users: Vec<String> = (0..200).map(|i| format!("user_{i}")).collect();
+        let assign = |pool: &SignerPool| -> Vec<Pubkey> {
+            users
+                .iter()
+                .map(|user| {
+                    pool.select_signer_for_user(user, None, &[], &programs, &[]).unwrap().pubkey()
+                })
+                .collect()
+        };
+
+        let before = assign(&pool);
+        assert_eq!(assign(&pool), before);
+        assert_eq!(before.iter().collect::<std::collections::HashSet<_>>().len(), 5);
+
+        let departed = before[0];
+        pool.set_signer_availability(&departed.to_string(), SignerAvailability::Disabled).unwrap();
+        let after = assign(&pool);
+
+        for (before, after) in before.iter().zip(&after) {
+            if *before == departed {
+                assert_ne!(after, before);
+            } else {
+                assert_eq!(after, before);
+            }
+        }
+    }
+
+    #[test]
+    fn test_select_signer_for_user_keeps_remembered_signer() {
+        let pool = SignerPool::new(vec![memory_signer("signer_1"), memory_signer("signer_2")]);
+        let programs = ProgramsConfig::Allowlist(vec![]);
+        let hashed =
+            pool.select_signer_for_user("user", None, &[], &programs, &[]).unwrap().pubkey();
+        let other = pool.signers.iter().map(|s| s.pubkey()).find(|p| *p != hashed).unwrap();
+
+        let selected =
+            pool.select_signer_for_user("user", Some(&other), &[], &programs, &[]).unwrap();
+        assert_eq!(selected.pubkey(), other);
+
+        let unknown = Pubkey::new_unique();
+        let selected =
+            pool.select_signer_for_user("user", Some(&unknown), &[], &programs, &[]).unwrap();
+        assert_eq!(selected.pubkey(), hashed);
+    }
+
+    #[test]
+    fn test_latency_window_p95() {
+        let mut window = LatencyWindow::default();
+        assert_eq!(window.p95(), None);
+
+        for ms in 1..=100 {
+            window.record(Duration::from_millis(ms));
+        }
+        assert_eq!(window.p95(), Some(Duration::from_millis(95)));
+
+        // Only the most recent samples are kept
+        for _ in 0..SIGNER_LATENCY_WINDOW_SAMPLES {
+            window.record(Duration::from_millis(1));
+        }
+        assert_eq!(window.p95(), Some(Duration::from_millis(1)));
+    }
+
+    #[test]
+    fn test_slow_signer_is_only_selected_as_last_resort() {
+        let mut pool = create_test_pool();
+        pool.slow_signer_p95 = Some(Duration::from_millis(500));
+        let slow = &pool.signers[0];
+        for _ in 0..10 {
+            slow.latency.lock().record(Duration::from_secs(2));
+        }
+
+        for _ in 0..4 {
+            let signer = pool.select_next_signer().unwrap();
+            assert_eq!(signer.pubkey(), pool.signers[1].signer.pubkey());
+        }
+        assert_eq!(pool.get_signers_info()[0].p95_latency_ms, Some(2000));
+
+        pool.signers[1].latency.lock().record(Duration::from_secs(1));
+        let picked: std::collections::HashSet<_> =
+            (0..4).map(|_| pool.select_next_signer().unwrap().pubkey()).collect();
+        assert_eq!(picked.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sign_message_limits_concurrency_and_tracks_latency() {
+        let pool =
+            SignerPool::new(vec![memory_signer("limited").with_max_concurrent_signs(Some(1))]);
+        let meta = &pool.signers[0];
+        let signer = meta.signer.clone();
+
+        let permit = pool.acquire_sign_permit(&signer, Duration::ZERO).await.unwrap();
+        assert!(permit.is_some());
+        assert!(meta.is_penalized(None));
+        let blocked = pool.acquire_sign_permit(&signer, Duration::from_millis(50)).await;
+        assert!(matches!(blocked, Err(KoraError::SignerBusy(_))));
+        // Waiting for a free slot is not signing latency
+        assert_eq!(meta.p95_latency(), None);
+
+        drop(permit);
+        let permit = pool.acquire_sign_permit(&signer, Duration::ZERO).await.unwrap();
+        let signature = pool.sign_message(&signer, b"hello").await.unwrap();
+        drop(permit);
+
+        assert!(signature.verify(signer.pubkey().as_ref(), b"hello"));
+        assert!(meta.p95_latency().is_some());
+        assert!(!meta.is_penalized(None));
+    }
+
+    #[test]
+    fn test_disabled_signer_is_never_used() {
+        let pool = create_test_pool();
+        let pubkey = pool.signers[0].signer.pubkey().to_string();
+        let other = pool.signers[1].signer.pubkey().to_string();
+
+        pool.set_signer_availability(&pubkey, SignerAvailability::Disabled).unwrap();
+        pool.set_signer_availability(&other, SignerAvailability::Disabled).unwrap();
+
+        assert!(matches!(pool.get_signer_by_pubkey(&pubkey), Err(KoraError::ValidationError(_))));
+        assert!(pool.get_next_signer().is_err());
+
+        pool.set_signer_availability(&other, SignerAvailability::Active).unwrap();
+        assert_eq!(pool.get_next_signer().unwrap().pubkey().to_string(), other);
+    }
+
+    #[test]
+    fn test_min_balance_excludes_underfunded_signers() {
+        let mut pool = create_test_pool();
+        pool.min_balance_lamports = Some(1_000_000);
+        let underfunded = pool.signers[0].signer.pubkey();
+
+        // Balances not polled yet: every signer stays eligible
+        assert_eq!(pool.eligible_signers().unwrap().len(), 2);
+
+        pool.record_signer_balance(&underfunded, 999_999);
+        pool.record_signer_balance(&pool.signers[1].signer.pubkey(), 5_000_000);
+
+        let eligible = pool.eligible_signers().unwrap();
+        assert_eq!(eligible.len(), 1);
+        assert_eq!(eligible[0].name(), "signer_2");
+
+        pool.record_signer_balance(&underfunded, 1_000_000);
+        assert_eq!(pool.eligible_signers().unwrap().len(), 2);
+        assert_eq!(pool.get_signers_info()[0].balance_lamports, Some(1_000_000));
+    }
+
+    #[test]
+    fn test_balance_aware_selection_prefers_funded_signers() {
+        let mut pool = create_test_pool();
+        pool.strategy = SelectionStrategy::BalanceAware;
+        pool.min_balance_lamports = Some(1_000);
+        let drained = pool.signers[0].signer.pubkey();
+        let funded = pool.signers[1].signer.pubkey();
+
+        // Nothing above the floor is known yet: falls back to round robin
+        assert_ne!(
+            pool.get_next_signer().unwrap().pubkey(),
+            pool.get_next_signer().unwrap().pubkey()
+        );
+
+        // At the floor, drained is still eligible but has no spendable balance
+        pool.record_signer_balance(&drained, 1_000);
+        pool.record_signer_balance(&funded, 50_000);
+        for _ in 0..20 {
+            assert_eq!(pool.get_next_signer().unwrap().pubkey(), funded);
+        }
+    }
 }
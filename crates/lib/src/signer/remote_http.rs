@@ -0,0 +1,278 @@
+//! Vendor-neutral HTTP signer for in-house signing services
+//!
+//! Protocol, for each message Kora needs signed:
+//!
+//! ```text
+//! POST <url>
+//! Content-Type: application/json
+//! x-timestamp: <unix seconds>                                   (HMAC only)
+//! x-hmac-signature: <hex HMAC-SHA256(secret, timestamp + body)> (HMAC only)
+//!
+//! {"pubkey": "<base58 pubkey>", "message": "<base64 message bytes>"}
+//! ```
+//!
+//! The service answers `200` with `{"signature": "<base58>", "pubkey": "<base58>"}`.
+//! The HMAC scheme is the one Kora's own RPC server verifies. The service may instead,
+//! or additionally, authenticate Kora by its mTLS client certificate.
+//! Kora rejects responses for another pubkey and signatures that do not verify.
+
+use crate::constant::{X_HMAC_SIGNATURE, X_TIMESTAMP};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hmac::{Hmac, KeyInit, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use solana_keychain::{
+    transaction_util::TransactionUtil, HttpClientConfig, SignTransactionResult, SignerError,
+    SolanaSigner,
+};
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::Transaction};
+use std::{fmt, str::FromStr};
+
+#[derive(Serialize)]
+struct SignRequest<'a> {
+    pubkey: String,
+    message: &'a str,
+}
+
+#[derive(Deserialize)]
+struct SignResponse {
+    signature: String,
+    pubkey: String,
+}
+
+/// mTLS client identity and trust roots for the signing service
+pub struct RemoteHttpTls {
+    /// PEM client certificate chain
+    pub client_cert_pem: Vec<u8>,
+    /// PEM PKCS#8 private key for the client certificate
+    pub client_key_pem: Vec<u8>,
+    /// Extra PEM CA certificate to trust, for services behind a private CA
+    pub ca_cert_pem: Option<Vec<u8>>,
+}
+
+pub struct RemoteHttpSigner {
+    url: String,
+    pubkey: Pubkey,
+    hmac_secret: Option<String>,
+    client: reqwest::Client,
+}
+
+impl fmt::Debug for RemoteHttpSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteHttpSigner")
+            .field("url", &self.url)
+            .field("pubkey", &self.pubkey)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RemoteHttpSigner {
+    pub fn new(
+        url: String,
+        pubkey: &str,
+        hmac_secret: Option<String>,
+        tls: Option<RemoteHttpTls>,
+        http_config: HttpClientConfig,
+    ) -> Result<Self, SignerError> {
+        let pubkey = Pubkey::from_str(pubkey)
+            .map_err(|e| SignerError::InvalidPublicKey(format!("Invalid pubkey: {e}")))?;
+        if hmac_secret.is_none() && tls.is_none() {
+            return Err(SignerError::ConfigError(
+                "Remote HTTP signer needs an HMAC secret or an mTLS identity".to_string(),
+            ));
+        }
+
+        let mut builder = reqwest::Client::builder()
+            .timeout(http_config.resolved_request_timeout())
+            .connect_timeout(http_config.resolved_connect_timeout());
+        if let Some(tls) = tls {
+            let identity =
+                reqwest::Identity::from_pkcs8_pem(&tls.client_cert_pem, &tls.client_key_pem)
+                    .map_err(|e| {
+                        SignerError::ConfigError(format!("Invalid mTLS client identity: {e}"))
+                    })?;
+            builder = builder.identity(identity);
+            if let Some(ca_cert_pem) = tls.ca_cert_pem {
+                let ca_cert = reqwest::Certificate::from_pem(&ca_cert_pem).map_err(|e| {
+                    SignerError::ConfigError(format!("Invalid mTLS CA certificate: {e}"))
+                })?;
+                builder = builder.tls_certs_merge([ca_cert]);
+            }
+        }
+        let client = builder
+            .build()
+            .map_err(|e| SignerError::ConfigError(format!("Failed to build HTTP client: {e}")))?;
+
+        Ok(Self { url, pubkey, hmac_secret, client })
+    }
+
+    fn hmac_signature(secret: &str, timestamp: &str, body: &str) -> Result<String, SignerError> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .map_err(|e| SignerError::ConfigError(format!("Invalid HMAC secret: {e}")))?;
+        mac.update(timestamp.as_bytes());
+        mac.update(body.as_bytes());
+        Ok(hex::encode(mac.finalize().into_bytes()))
+    }
+
+    async fn sign_bytes(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        let encoded = STANDARD.encode(message);
+        let body = serde_json::to_string(&SignRequest {
+            pubkey: self.pubkey.to_string(),
+            message: &encoded,
+        })
+        .map_err(|e| SignerError::SerializationError(format!("Failed to encode request: {e}")))?;
+
+        let mut request =
+            self.client.post(&self.url).header(reqwest::header::CONTENT_TYPE, "application/json");
+        if let Some(secret) = &self.hmac_secret {
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs()
+                .to_string();
+            let signature = Self::hmac_signature(secret, &timestamp, &body)?;
+            request = request.header(X_TIMESTAMP, timestamp).header(X_HMAC_SIGNATURE, signature);
+        }
+
+        let response = request.body(body).send().await.map_err(|e| {
+            SignerError::RemoteApiError(format!("Failed to send request to remote signer: {e}"))
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            log::error!("Remote HTTP signer error - status: {status}");
+            return Err(SignerError::RemoteApiError(format!("Remote signer error {status}")));
+        }
+
+        let result: SignResponse = response.json().await.map_err(|_| {
+            SignerError::SerializationError("Failed to parse remote signer response".to_string())
+        })?;
+
+        if result.pubkey != self.pubkey.to_string() {
+            return Err(SignerError::SigningFailed(format!(
+                "Remote signer answered for pubkey {} instead of {}",
+                result.pubkey, self.pubkey
+            )));
+        }
+
+        let signature = Signature::from_str(&result.signature)
+            .map_err(|_| SignerError::SigningFailed("Invalid signature format".to_string()))?;
+        if !signature.verify(self.pubkey.as_ref(), message) {
+            return Err(SignerError::SigningFailed(
+                "Signature verification failed — the returned signature does not match the public key".to_string(),
+            ));
+        }
+
+        Ok(signature)
+    }
+}
+
+#[async_trait::async_trait]
+impl SolanaSigner for RemoteHttpSigner {
+    fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut Transaction,
+    ) -> Result<SignTransactionResult, SignerError> {
+        let signature = self.sign_bytes(&tx.message_data()).await?;
+        TransactionUtil::add_signature_to_transaction(tx, &self.pubkey, signature)?;
+        let signed_transaction = (TransactionUtil::serialize_transaction(tx)?, signature);
+        Ok(TransactionUtil::classify_signed_transaction(tx, signed_transaction))
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        self.sign_bytes(message).await
+    }
+
+    async fn is_available(&self) -> bool {
+        // The protocol has no health endpoint, the pool tracks failed signing calls instead
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::remote_signer_mock::RemoteSignerMock;
+    use solana_sdk::{signature::Keypair, signer::Signer as _};
+
+    fn signer_for(mock: &RemoteSignerMock, hmac_secret: Option<&str>) -> RemoteHttpSigner {
+        RemoteHttpSigner::new(
+            mock.url(),
+            &mock.pubkey().to_string(),
+            hmac_secret.map(str::to_string),
+            None,
+            HttpClientConfig::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_new_requires_authentication() {
+        let result = RemoteHttpSigner::new(
+            "http://localhost".to_string(),
+            &Pubkey::new_unique().to_string(),
+            None,
+            None,
+            HttpClientConfig::default(),
+        );
+        assert!(matches!(result, Err(SignerError::ConfigError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_sign_message_with_hmac() {
+        let mock = RemoteSignerMock::start("shared-secret").await;
+        let signer = signer_for(&mock, Some("shared-secret"));
+
+        let signature = signer.sign_message(b"hello").await.unwrap();
+
+        assert!(signature.verify(mock.pubkey().as_ref(), b"hello"));
+    }
+
+    #[tokio::test]
+    async fn test_sign_message_rejects_wrong_hmac_secret() {
+        let mock = RemoteSignerMock::start("shared-secret").await;
+        let signer = signer_for(&mock, Some("other-secret"));
+
+        let result = signer.sign_message(b"hello").await;
+
+        assert!(matches!(result, Err(SignerError::RemoteApiError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_sign_message_rejects_foreign_signature() {
+        let mock = RemoteSignerMock::start("shared-secret").await;
+        let signer = RemoteHttpSigner::new(
+            mock.url(),
+            &Keypair::new().pubkey().to_string(),
+            Some("shared-secret".to_string()),
+            None,
+            HttpClientConfig::default(),
+        )
+        .unwrap();
+
+        let result = signer.sign_message(b"hello").await;
+
+        assert!(matches!(result, Err(SignerError::SigningFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_sign_transaction() {
+        let mock = RemoteSignerMock::start("shared-secret").await;
+        let signer = signer_for(&mock, Some("shared-secret"));
+        let instruction = solana_system_interface::instruction::transfer(
+            &mock.pubkey(),
+            &Pubkey::new_unique(),
+            1,
+        );
+        let mut tx = Transaction::new_with_payer(&[instruction], Some(&mock.pubkey()));
+
+        let result = signer.sign_transaction(&mut tx).await.unwrap();
+
+        assert!(matches!(result, SignTransactionResult::Complete(_)));
+        assert!(tx.verify().is_ok());
+    }
+}
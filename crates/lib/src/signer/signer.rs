@@ -1,7 +1,107 @@
-//! Re-exports for external signer infrastructure
+//! Signer type used throughout Kora
 //!
-//! Kora uses solana-keychain crate as its signing infrastructure.
-//! This module exists only for re-exporting convenience.
+//! Kora uses solana-keychain crate as its signing infrastructure. Its `Signer` enum is
+//! closed, so Kora wraps it together with the signers it implements itself.
+//!
+//! This is a breaking change for code that held `solana_keychain::Signer` values handed out
+//! by the signer pool: hold `kora_lib::signer::Signer` instead, which implements the same
+//! `SolanaSigner` trait. `From`, `TryFrom` and [`Signer::as_keychain`] convert between the two
+//! where a keychain signer is still needed.
+
+use crate::signer::remote_http::RemoteHttpSigner;
+use solana_keychain::{SignTransactionResult, SignerError};
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::Transaction};
+
+pub use solana_keychain::SolanaSigner;
+
+pub enum Signer {
+    /// Any solana-keychain backend, boxed as it is much larger than Kora's own signers
+    Keychain(Box<solana_keychain::Signer>),
+    /// Vendor-neutral HTTP signing service
+    RemoteHttp(RemoteHttpSigner),
+}
+
+impl Signer {
+    /// Create a memory signer from a private key string
+    pub fn from_memory(private_key: &str) -> Result<Self, SignerError> {
+        solana_keychain::Signer::from_memory(private_key).map(Self::from)
+    }
+
+    /// The underlying solana-keychain signer, if this is not one of Kora's own signers
+    pub fn as_keychain(&self) -> Option<&solana_keychain::Signer> {
+        match self {
+            Self::Keychain(signer) => Some(signer),
+            Self::RemoteHttp(_) => None,
+        }
+    }
+}
+
+impl From<solana_keychain::Signer> for Signer {
+    fn from(signer: solana_keychain::Signer) -> Self {
+        Self::Keychain(Box::new(signer))
+    }
+}
+
+impl TryFrom<Signer> for solana_keychain::Signer {
+    /// Kora's own signers have no solana-keychain equivalent and are handed back unchanged
+    type Error = Signer;
+
+    fn try_from(signer: Signer) -> Result<Self, Self::Error> {
+        match signer {
+            Signer::Keychain(signer) => Ok(*signer),
+            other => Err(other),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl SolanaSigner for Signer {
+    fn pubkey(&self) -> Pubkey {
+        match self {
+            Self::Keychain(signer) => signer.pubkey(),
+            Self::RemoteHttp(signer) => signer.pubkey(),
+        }
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut Transaction,
+    ) -> Result<SignTransactionResult, SignerError> {
+        match self {
+            Self::Keychain(signer) => signer.sign_transaction(tx).await,
+            Self::RemoteHttp(signer) => signer.sign_transaction(tx).await,
+        }
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        match self {
+            Self::Keychain(signer) => signer.sign_message(message).await,
+            Self::RemoteHttp(signer) => signer.sign_message(message).await,
+        }
+    }
+
+    async fn is_available(&self) -> bool {
+        match self {
+            Self::Keychain(signer) => signer.is_available().await,
+            Self::RemoteHttp(signer) => signer.is_available().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::{Keypair, Signer as _};
+
+    #[test]
+    fn test_keychain_signer_round_trips_through_wrapper() {
+        let keypair = Keypair::new();
+        let keychain = solana_keychain::Signer::from_memory(&keypair.to_base58_string()).unwrap();
+
+        let signer = Signer::from(keychain);
+        assert_eq!(signer.as_keychain().map(|s| s.pubkey()), Some(keypair.pubkey()));
 
-// Re-export the external signer for use throughout Kora
-pub use solana_keychain::{Signer, SolanaSigner};
+        let keychain = solana_keychain::Signer::try_from(signer).ok().unwrap();
+        assert_eq!(keychain.pubkey(), keypair.pubkey());
+    }
+}
@@ -0,0 +1,651 @@
+use crate::{
+    constant::{DEFAULT_TOP_UP_INTERVAL_SECONDS, TREASURY_TOP_UP_LOCK_SECONDS},
+    error::KoraError,
+    sanitize_error,
+    signer::{
+        config::{SignerConfig, TreasuryConfig},
+        treasury_store::{get_treasury_store, PendingTopUp, TreasuryStore},
+        Signer, SignerPool, SolanaSigner,
+    },
+    state::get_signer_pool,
+    transaction::TransactionUtil,
+};
+use prometheus::{IntCounterVec, IntGauge, Opts};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_commitment_config::CommitmentConfig;
+use solana_message::{Message, VersionedMessage};
+use solana_sdk::{
+    hash::Hash, pubkey::Pubkey, signature::Signature, transaction::VersionedTransaction,
+};
+use solana_system_interface::instruction::transfer;
+use std::{
+    fmt,
+    str::FromStr,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+use tokio::task::JoinHandle;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+static TREASURY_METRICS: OnceLock<TreasuryMetrics> = OnceLock::new();
+
+/// Balance thresholds of a pool signer refilled by the treasury
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TopUpThreshold {
+    pub(crate) target_balance: u64,
+    pub(crate) refill_below: u64,
+}
+
+impl TopUpThreshold {
+    pub(crate) fn from_config(config: &SignerConfig) -> Option<Self> {
+        match (config.target_balance, config.refill_below) {
+            (Some(target_balance), Some(refill_below)) => {
+                Some(Self { target_balance, refill_below })
+            }
+            _ => None,
+        }
+    }
+
+    /// Lamports that bring `balance` back to the target, None while the signer is funded
+    pub(crate) fn top_up_amount(&self, balance: u64) -> Option<u64> {
+        (balance < self.refill_below).then(|| self.target_balance.saturating_sub(balance))
+    }
+}
+
+/// A pool signer the treasury keeps funded
+pub(crate) struct TopUpTarget {
+    pub(crate) name: String,
+    pub(crate) pubkey: Pubkey,
+    pub(crate) threshold: TopUpThreshold,
+}
+
+/// Signer that funds depleted pool signers, built from the `[treasury]` section of the
+/// signers config
+pub struct Treasury {
+    name: String,
+    signer: Arc<Signer>,
+    daily_cap_lamports: u64,
+    dry_run: bool,
+    check_interval: Duration,
+}
+
+impl Treasury {
+    pub(crate) fn new(
+        name: String,
+        signer: Arc<Signer>,
+        daily_cap_lamports: u64,
+        dry_run: bool,
+        check_interval: Duration,
+    ) -> Self {
+        Self { name, signer, daily_cap_lamports, dry_run, check_interval }
+    }
+
+    pub(crate) async fn from_config(config: &TreasuryConfig) -> Result<Self, KoraError> {
+        let signer = SignerConfig::build_signer_from_config(&config.signer_config()).await?;
+
+        Ok(Self::new(
+            config.name.clone(),
+            Arc::new(signer),
+            config.daily_cap_lamports,
+            config.dry_run,
+            Duration::from_secs(config.check_interval_seconds),
+        ))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn pubkey(&self) -> Pubkey {
+        self.signer.pubkey()
+    }
+
+    /// Build and sign a transfer of `lamports` to `to`
+    async fn sign_transfer(
+        &self,
+        rpc_client: &RpcClient,
+        to: &Pubkey,
+        lamports: u64,
+    ) -> Result<VersionedTransaction, KoraError> {
+        let from = self.pubkey();
+        let blockhash = rpc_client
+            .get_latest_blockhash()
+            .await
+            .map_err(|e| KoraError::RpcError(format!("Failed to get blockhash: {e}")))?;
+
+        let message = VersionedMessage::Legacy(Message::new_with_blockhash(
+            &[transfer(&from, to, lamports)],
+            Some(&from),
+            &blockhash,
+        ));
+        let mut tx = TransactionUtil::new_unsigned_versioned_transaction(message);
+
+        let signature = self
+            .signer
+            .sign_message(&tx.message.serialize())
+            .await
+            .map_err(|e| KoraError::SigningError(e.to_string()))?;
+        tx.signatures = vec![signature];
+
+        Ok(tx)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TopUpOutcome {
+    Sent(Signature),
+    /// Sent without a confirmation, the lamports stay reserved until it resolves
+    Unconfirmed(Signature),
+    DryRun,
+    /// The daily cap is used up
+    Capped,
+    Failed,
+}
+
+impl fmt::Display for TopUpOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sent(_) => write!(f, "sent"),
+            Self::Unconfirmed(_) => write!(f, "unconfirmed"),
+            Self::DryRun => write!(f, "dry_run"),
+            Self::Capped => write!(f, "capped"),
+            Self::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+/// A top-up the treasury sent, or would have sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TopUp {
+    pub(crate) signer_name: String,
+    pub(crate) lamports: u64,
+    pub(crate) outcome: TopUpOutcome,
+}
+
+/// Metrics for treasury top-ups
+pub struct TreasuryMetrics {
+    pub top_ups: IntCounterVec,
+    pub top_up_lamports: IntCounterVec,
+    pub daily_spent_lamports: IntGauge,
+}
+
+impl TreasuryMetrics {
+    fn new() -> Self {
+        let top_ups = IntCounterVec::new(
+            Opts::new("treasury_top_ups_total", "Treasury top-ups of pool signers by outcome")
+                .namespace("kora"),
+            &["signer_name", "outcome"],
+        )
+        .unwrap_or_else(|e| {
+            log::error!("Failed to create treasury_top_ups_total metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        let top_up_lamports = IntCounterVec::new(
+            Opts::new("treasury_top_up_lamports_total", "Lamports sent by the treasury")
+                .namespace("kora"),
+            &["signer_name"],
+        )
+        .unwrap_or_else(|e| {
+            log::error!("Failed to create treasury_top_up_lamports_total metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        let daily_spent_lamports = IntGauge::with_opts(
+            Opts::new(
+                "treasury_daily_spent_lamports",
+                "Lamports sent by the treasury during the current UTC day",
+            )
+            .namespace("kora"),
+        )
+        .unwrap_or_else(|e| {
+            log::error!("Failed to create treasury_daily_spent_lamports metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        prometheus::register(Box::new(top_ups.clone())).unwrap_or_else(|e| {
+            log::error!("Failed to register treasury_top_ups_total metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+        prometheus::register(Box::new(top_up_lamports.clone())).unwrap_or_else(|e| {
+            log::error!("Failed to register treasury_top_up_lamports_total metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+        prometheus::register(Box::new(daily_spent_lamports.clone())).unwrap_or_else(|e| {
+            log::error!("Failed to register treasury_daily_spent_lamports metric: {e:?}");
+            panic!("Metrics initialization failed - cannot continue")
+        });
+
+        Self { top_ups, top_up_lamports, daily_spent_lamports }
+    }
+
+    pub fn get() -> &'static TreasuryMetrics {
+        TREASURY_METRICS.get_or_init(TreasuryMetrics::new)
+    }
+
+    fn record(&self, top_up: &TopUp) {
+        self.top_ups.with_label_values(&[&top_up.signer_name, &top_up.outcome.to_string()]).inc();
+        if matches!(top_up.outcome, TopUpOutcome::Sent(_)) {
+            self.top_up_lamports.with_label_values(&[&top_up.signer_name]).inc_by(top_up.lamports);
+        }
+    }
+}
+
+/// What became of an unconfirmed top-up
+enum PendingResolution {
+    /// The transfer can still land
+    InFlight,
+    Landed,
+    /// The transfer failed or expired, its reservation is given back
+    Dropped,
+}
+
+/// Background task that refills pool signers from the treasury
+pub struct TreasuryTopUp;
+
+impl TreasuryTopUp {
+    /// Check the balance of every pool signer with top-up thresholds once, and send a
+    /// transfer from the treasury to each one below `refill_below`. The budget, a lock per
+    /// signer and unconfirmed transfers live in `store`, so instances sharing it never
+    /// top up the same signer twice or exceed the daily cap together.
+    pub(crate) async fn top_up_pool(
+        pool: &SignerPool,
+        rpc_client: &RpcClient,
+        store: &dyn TreasuryStore,
+    ) -> Vec<TopUp> {
+        let Some(treasury) = pool.treasury() else {
+            return Vec::new();
+        };
+        let metrics = TreasuryMetrics::get();
+        let day = chrono::Utc::now().timestamp().div_euclid(SECONDS_PER_DAY);
+        let lock_ttl = Duration::from_secs(TREASURY_TOP_UP_LOCK_SECONDS);
+        let mut top_ups = Vec::new();
+
+        for target in pool.top_up_targets() {
+            match store.try_lock(&target.pubkey, lock_ttl).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    log::debug!("Signer '{}' is being topped up by another instance", target.name);
+                    continue;
+                }
+                Err(e) => {
+                    log::warn!("Failed to lock top-ups of signer {}: {e}", target.name);
+                    continue;
+                }
+            }
+
+            let result = Self::top_up_signer(&treasury, &target, rpc_client, store, day).await;
+            if let Err(e) = store.unlock(&target.pubkey).await {
+                log::warn!("Failed to unlock top-ups of signer {}: {e}", target.name);
+            }
+
+            match result {
+                Ok(Some(top_up)) => {
+                    metrics.record(&top_up);
+                    top_ups.push(top_up);
+                }
+                Ok(None) => {}
+                Err(e) => log::warn!("Failed to top up signer {}: {e}", target.name),
+            }
+        }
+
+        match store.spent(day).await {
+            Ok(spent) => metrics.daily_spent_lamports.set(i64::try_from(spent).unwrap_or(i64::MAX)),
+            Err(e) => log::warn!("Failed to get treasury spend: {e}"),
+        }
+
+        top_ups
+    }
+
+    /// Top up `target` while holding its lock. Returns None when it needs no top-up or
+    /// its previous top-up is still in flight.
+    async fn top_up_signer(
+        treasury: &Treasury,
+        target: &TopUpTarget,
+        rpc_client: &RpcClient,
+        store: &dyn TreasuryStore,
+        day: i64,
+    ) -> Result<Option<TopUp>, KoraError> {
+        if let Some(pending) = store.get_pending(&target.pubkey).await? {
+            match Self::resolve_pending(rpc_client, &pending).await? {
+                PendingResolution::InFlight => return Ok(None),
+                PendingResolution::Landed => {}
+                PendingResolution::Dropped => store.release(pending.day, pending.lamports).await?,
+            }
+            store.set_pending(&target.pubkey, None).await?;
+        }
+
+        let balance = rpc_client.get_balance(&target.pubkey).await.map_err(|e| {
+            KoraError::RpcError(format!("Failed to get balance: {}", sanitize_error!(e)))
+        })?;
+        let Some(wanted) = target.threshold.top_up_amount(balance) else {
+            return Ok(None);
+        };
+
+        let lamports = store.reserve(day, wanted, treasury.daily_cap_lamports).await?;
+
+        let outcome = if lamports == 0 {
+            log::warn!(
+                "Signer '{}' is down to {balance} lamports but treasury '{}' reached its daily cap of {} lamports",
+                target.name,
+                treasury.name,
+                treasury.daily_cap_lamports
+            );
+            TopUpOutcome::Capped
+        } else if treasury.dry_run {
+            // Dry runs don't count against the cap
+            store.release(day, lamports).await?;
+            log::info!(
+                "Dry run: treasury '{}' would send {lamports} lamports to signer '{}' ({balance} lamports)",
+                treasury.name,
+                target.name
+            );
+            TopUpOutcome::DryRun
+        } else {
+            Self::send_top_up(treasury, target, rpc_client, store, day, lamports, balance).await?
+        };
+
+        Ok(Some(TopUp { signer_name: target.name.clone(), lamports, outcome }))
+    }
+
+    async fn send_top_up(
+        treasury: &Treasury,
+        target: &TopUpTarget,
+        rpc_client: &RpcClient,
+        store: &dyn TreasuryStore,
+        day: i64,
+        lamports: u64,
+        balance: u64,
+    ) -> Result<TopUpOutcome, KoraError> {
+        let transaction = match treasury.sign_transfer(rpc_client, &target.pubkey, lamports).await {
+            Ok(transaction) => transaction,
+            Err(e) => {
+                store.release(day, lamports).await?;
+                log::error!(
+                    "Treasury '{}' failed to top up signer '{}': {e}",
+                    treasury.name,
+                    target.name
+                );
+                return Ok(TopUpOutcome::Failed);
+            }
+        };
+        let signature = transaction.signatures[0];
+
+        match rpc_client.send_and_confirm_transaction(&transaction).await {
+            Ok(_) => {
+                log::info!(
+                    "Treasury '{}' sent {lamports} lamports to signer '{}' ({balance} lamports): {signature}",
+                    treasury.name,
+                    target.name
+                );
+                Ok(TopUpOutcome::Sent(signature))
+            }
+            // The transaction was rejected, so it can't land later
+            Err(e) if e.get_transaction_error().is_some() => {
+                store.release(day, lamports).await?;
+                log::error!(
+                    "Treasury '{}' failed to top up signer '{}': {}",
+                    treasury.name,
+                    target.name,
+                    sanitize_error!(e)
+                );
+                Ok(TopUpOutcome::Failed)
+            }
+            // The transfer may still land, so its lamports stay reserved until it resolves
+            Err(e) => {
+                let pending = PendingTopUp {
+                    signature: signature.to_string(),
+                    recent_blockhash: transaction.message.recent_blockhash().to_string(),
+                    day,
+                    lamports,
+                };
+                store.set_pending(&target.pubkey, Some(&pending)).await?;
+                log::warn!(
+                    "Treasury '{}' top-up of signer '{}' is unconfirmed ({signature}): {}",
+                    treasury.name,
+                    target.name,
+                    sanitize_error!(e)
+                );
+                Ok(TopUpOutcome::Unconfirmed(signature))
+            }
+        }
+    }
+
+    /// Look up an unconfirmed top-up on chain. It is dropped once it failed, or once its
+    /// blockhash expired without it landing.
+    async fn resolve_pending(
+        rpc_client: &RpcClient,
+        pending: &PendingTopUp,
+    ) -> Result<PendingResolution, KoraError> {
+        let (Ok(signature), Ok(blockhash)) =
+            (Signature::from_str(&pending.signature), Hash::from_str(&pending.recent_blockhash))
+        else {
+            log::warn!("Dropping malformed pending top-up {}", pending.signature);
+            return Ok(PendingResolution::Dropped);
+        };
+        let rpc_error = |e| KoraError::RpcError(sanitize_error!(e));
+
+        let status = rpc_client
+            .get_signature_statuses_with_history(&[signature])
+            .await
+            .map_err(rpc_error)?
+            .value
+            .into_iter()
+            .next()
+            .flatten();
+
+        Ok(match status {
+            Some(status) if status.err.is_some() => PendingResolution::Dropped,
+            Some(status) if status.satisfies_commitment(CommitmentConfig::confirmed()) => {
+                PendingResolution::Landed
+            }
+            Some(_) => PendingResolution::InFlight,
+            None => {
+                if rpc_client
+                    .is_blockhash_valid(&blockhash, CommitmentConfig::processed())
+                    .await
+                    .map_err(rpc_error)?
+                {
+                    PendingResolution::InFlight
+                } else {
+                    PendingResolution::Dropped
+                }
+            }
+        })
+    }
+
+    /// Start the top-up loop. It runs whether or not a treasury is configured, and reads
+    /// the signer pool every round so a treasury added by a reload starts topping up.
+    /// Returns a JoinHandle to allow for proper task shutdown.
+    pub fn start_background_task(rpc_client: Arc<RpcClient>) -> JoinHandle<()> {
+        log::info!("Starting treasury top-up task");
+
+        tokio::spawn(async move {
+            let store = get_treasury_store();
+            loop {
+                // Re-read the pool every round so reloaded thresholds and treasuries apply
+                let check_interval = match get_signer_pool() {
+                    Ok(pool) => {
+                        Self::top_up_pool(&pool, &rpc_client, store.as_ref()).await;
+                        pool.treasury().map(|treasury| treasury.check_interval)
+                    }
+                    Err(e) => {
+                        log::warn!("Skipping treasury top-up: {e}");
+                        None
+                    }
+                };
+
+                tokio::time::sleep(
+                    check_interval.unwrap_or(Duration::from_secs(DEFAULT_TOP_UP_INTERVAL_SECONDS)),
+                )
+                .await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        signer::{pool::SignerWithMetadata, treasury_store::InMemoryTreasuryStore},
+        tests::rpc_mock::RpcMockBuilder,
+    };
+    use serde_json::json;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_sdk::signature::Keypair;
+
+    fn memory_signer() -> Arc<Signer> {
+        Arc::new(Signer::from_memory(&Keypair::new().to_base58_string()).unwrap())
+    }
+
+    fn pool_with_treasury(daily_cap_lamports: u64, dry_run: bool) -> SignerPool {
+        let threshold = TopUpThreshold { target_balance: 1_000, refill_below: 500 };
+        SignerPool::new(vec![
+            SignerWithMetadata::new("depleted".to_string(), memory_signer(), 1)
+                .with_top_up(Some(threshold)),
+            SignerWithMetadata::new("unmanaged".to_string(), memory_signer(), 1),
+        ])
+        .with_treasury(Treasury::new(
+            "treasury".to_string(),
+            memory_signer(),
+            daily_cap_lamports,
+            dry_run,
+            Duration::from_secs(60),
+        ))
+    }
+
+    fn rpc_client_with_balance(lamports: u64) -> Arc<RpcClient> {
+        RpcMockBuilder::new()
+            .with_blockhash()
+            .with_custom_mock(
+                RpcRequest::GetBalance,
+                json!({ "context": { "slot": 1 }, "value": lamports }),
+            )
+            .build()
+    }
+
+    fn today() -> i64 {
+        chrono::Utc::now().timestamp().div_euclid(SECONDS_PER_DAY)
+    }
+
+    #[test]
+    fn test_top_up_amount() {
+        let threshold = TopUpThreshold { target_balance: 1_000, refill_below: 500 };
+
+        assert_eq!(threshold.top_up_amount(499), Some(501));
+        assert_eq!(threshold.top_up_amount(0), Some(1_000));
+        assert_eq!(threshold.top_up_amount(500), None);
+    }
+
+    #[tokio::test]
+    async fn test_top_up_pool_dry_run_does_not_spend() {
+        let pool = pool_with_treasury(10_000, true);
+        let rpc_client = rpc_client_with_balance(100);
+        let store = InMemoryTreasuryStore::new();
+
+        let top_ups = TreasuryTopUp::top_up_pool(&pool, &rpc_client, &store).await;
+
+        assert_eq!(
+            top_ups,
+            vec![TopUp {
+                signer_name: "depleted".to_string(),
+                lamports: 900,
+                outcome: TopUpOutcome::DryRun
+            }]
+        );
+        assert_eq!(store.spent(today()).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_top_up_pool_sends_until_daily_cap() {
+        let pool = pool_with_treasury(600, false);
+        let rpc_client = rpc_client_with_balance(100);
+        let store = InMemoryTreasuryStore::new();
+
+        let first = TreasuryTopUp::top_up_pool(&pool, &rpc_client, &store).await;
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].lamports, 600);
+        assert!(matches!(first[0].outcome, TopUpOutcome::Sent(_)));
+
+        let second = TreasuryTopUp::top_up_pool(&pool, &rpc_client, &store).await;
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].lamports, 0);
+        assert_eq!(second[0].outcome, TopUpOutcome::Capped);
+    }
+
+    #[tokio::test]
+    async fn test_top_up_pool_skips_funded_signers() {
+        let pool = pool_with_treasury(10_000, false);
+        let rpc_client = rpc_client_with_balance(800);
+        let store = InMemoryTreasuryStore::new();
+
+        assert!(TreasuryTopUp::top_up_pool(&pool, &rpc_client, &store).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_top_up_pool_skips_signers_locked_by_another_instance() {
+        let pool = pool_with_treasury(10_000, false);
+        let rpc_client = rpc_client_with_balance(100);
+        let store = InMemoryTreasuryStore::new();
+        let target = pool.top_up_targets()[0].pubkey;
+
+        assert!(store.try_lock(&target, Duration::from_secs(60)).await.unwrap());
+        assert!(TreasuryTopUp::top_up_pool(&pool, &rpc_client, &store).await.is_empty());
+        assert_eq!(store.spent(today()).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_top_up_pool_keeps_unconfirmed_reservation_while_in_flight() {
+        let pool = pool_with_treasury(10_000, false);
+        let store = InMemoryTreasuryStore::new();
+        let target = pool.top_up_targets()[0].pubkey;
+        let day = today();
+        let pending = PendingTopUp {
+            signature: Signature::default().to_string(),
+            recent_blockhash: Hash::default().to_string(),
+            day,
+            lamports: 900,
+        };
+        store.reserve(day, 900, 10_000).await.unwrap();
+        store.set_pending(&target, Some(&pending)).await.unwrap();
+
+        // Not seen yet and its blockhash is still valid, so the transfer may still land
+        let in_flight = RpcMockBuilder::new()
+            .with_custom_mock(
+                RpcRequest::GetSignatureStatuses,
+                json!({ "context": { "slot": 1 }, "value": [null] }),
+            )
+            .with_custom_mock(
+                RpcRequest::IsBlockhashValid,
+                json!({ "context": { "slot": 1 }, "value": true }),
+            )
+            .build();
+        assert!(TreasuryTopUp::top_up_pool(&pool, &in_flight, &store).await.is_empty());
+        assert_eq!(store.spent(day).await.unwrap(), 900);
+        assert_eq!(store.get_pending(&target).await.unwrap(), Some(pending));
+
+        // Once its blockhash expires the reservation is given back before topping up again
+        let expired = RpcMockBuilder::new()
+            .with_blockhash()
+            .with_custom_mock(
+                RpcRequest::GetSignatureStatuses,
+                json!({ "context": { "slot": 1 }, "value": [null] }),
+            )
+            .with_custom_mock(
+                RpcRequest::IsBlockhashValid,
+                json!({ "context": { "slot": 1 }, "value": false }),
+            )
+            .with_custom_mock(
+                RpcRequest::GetBalance,
+                json!({ "context": { "slot": 1 }, "value": 100 }),
+            )
+            .build();
+        let top_ups = TreasuryTopUp::top_up_pool(&pool, &expired, &store).await;
+        assert_eq!(top_ups.len(), 1);
+        assert!(matches!(top_ups[0].outcome, TopUpOutcome::Sent(_)));
+        assert_eq!(store.spent(day).await.unwrap(), 900);
+        assert_eq!(store.get_pending(&target).await.unwrap(), None);
+    }
+}
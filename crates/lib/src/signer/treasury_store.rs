@@ -0,0 +1,416 @@
+use async_trait::async_trait;
+use deadpool_redis::{Connection, Pool};
+use once_cell::sync::Lazy;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+
+use crate::{cache::CacheUtil, error::KoraError, sanitize_error};
+
+const TREASURY_KEY_PREFIX: &str = "kora:treasury";
+
+/// Daily spend keys outlive their UTC day so late releases still find them
+const DAILY_SPEND_TTL_SECONDS: u64 = 2 * 86_400;
+
+/// Global treasury store. Falls back to an in-memory store when [`init_treasury_store`]
+/// was never called (e.g. in unit tests).
+static TREASURY_STORE: once_cell::sync::OnceCell<Arc<dyn TreasuryStore>> =
+    once_cell::sync::OnceCell::new();
+
+/// Identifies this instance as the holder of a top-up lock
+static LOCK_OWNER: Lazy<String> = Lazy::new(|| format!("{:016x}", rand::random::<u64>()));
+
+/// Grants up to ARGV[1] lamports of the ARGV[2] budget and returns the grant
+static RESERVE_SCRIPT: Lazy<redis::Script> = Lazy::new(|| {
+    redis::Script::new(
+        r"
+            local spent = tonumber(redis.call('GET', KEYS[1]) or '0')
+            local granted = math.min(tonumber(ARGV[1]), math.max(tonumber(ARGV[2]) - spent, 0))
+            if granted > 0 then redis.call('INCRBY', KEYS[1], granted) end
+            redis.call('EXPIRE', KEYS[1], ARGV[3])
+            return granted
+            ",
+    )
+});
+
+/// Gives back up to ARGV[1] lamports without going below zero
+static RELEASE_SCRIPT: Lazy<redis::Script> = Lazy::new(|| {
+    redis::Script::new(
+        r"
+            local spent = tonumber(redis.call('GET', KEYS[1]) or '0')
+            local released = math.min(spent, tonumber(ARGV[1]))
+            if released > 0 then redis.call('DECRBY', KEYS[1], released) end
+            return released
+            ",
+    )
+});
+
+/// Deletes the lock only if this instance still holds it
+static UNLOCK_SCRIPT: Lazy<redis::Script> = Lazy::new(|| {
+    redis::Script::new(
+        r"
+            if redis.call('GET', KEYS[1]) == ARGV[1] then
+                return redis.call('DEL', KEYS[1])
+            end
+            return 0
+            ",
+    )
+});
+
+/// A top-up transfer that was sent but not confirmed. Its lamports stay reserved, and the
+/// signer is not topped up again, until the transfer lands or can no longer land.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingTopUp {
+    pub signature: String,
+    pub recent_blockhash: String,
+    /// Day of the budget the lamports were reserved from
+    pub day: i64,
+    pub lamports: u64,
+}
+
+/// Trait for the treasury state shared by every Kora instance: the daily budget, a lock
+/// per topped up signer and the top-ups still waiting for confirmation
+#[async_trait]
+pub trait TreasuryStore: Send + Sync {
+    /// Reserve up to `amount` lamports of `day`'s budget of `cap`. Returns the amount granted.
+    async fn reserve(&self, day: i64, amount: u64, cap: u64) -> Result<u64, KoraError>;
+
+    /// Give back a reservation that was not spent
+    async fn release(&self, day: i64, amount: u64) -> Result<(), KoraError>;
+
+    /// Lamports reserved on `day`
+    async fn spent(&self, day: i64) -> Result<u64, KoraError>;
+
+    /// Take the top-up lock of `signer` for `ttl`. Returns false while another instance
+    /// holds it.
+    async fn try_lock(&self, signer: &Pubkey, ttl: Duration) -> Result<bool, KoraError>;
+
+    /// Release the top-up lock of `signer` if this instance holds it
+    async fn unlock(&self, signer: &Pubkey) -> Result<(), KoraError>;
+
+    /// Get the unconfirmed top-up of `signer`
+    async fn get_pending(&self, signer: &Pubkey) -> Result<Option<PendingTopUp>, KoraError>;
+
+    /// Record the unconfirmed top-up of `signer`, or clear it with None
+    async fn set_pending(
+        &self,
+        signer: &Pubkey,
+        pending: Option<&PendingTopUp>,
+    ) -> Result<(), KoraError>;
+}
+
+/// Redis-based implementation, shared across Kora instances
+pub struct RedisTreasuryStore {
+    pool: Pool,
+}
+
+impl RedisTreasuryStore {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn spent_key(day: i64) -> String {
+        format!("{TREASURY_KEY_PREFIX}:spent:{day}")
+    }
+
+    fn lock_key(signer: &Pubkey) -> String {
+        format!("{TREASURY_KEY_PREFIX}:lock:{signer}")
+    }
+
+    fn pending_key(signer: &Pubkey) -> String {
+        format!("{TREASURY_KEY_PREFIX}:pending:{signer}")
+    }
+
+    async fn get_connection(&self) -> Result<Connection, KoraError> {
+        self.pool.get().await.map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to get Redis connection: {}",
+                e
+            )))
+        })
+    }
+}
+
+#[async_trait]
+impl TreasuryStore for RedisTreasuryStore {
+    async fn reserve(&self, day: i64, amount: u64, cap: u64) -> Result<u64, KoraError> {
+        let mut conn = self.get_connection().await?;
+        RESERVE_SCRIPT
+            .key(Self::spent_key(day))
+            .arg(amount)
+            .arg(cap)
+            .arg(DAILY_SPEND_TTL_SECONDS)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| {
+                KoraError::InternalServerError(sanitize_error!(format!(
+                    "Failed to reserve treasury budget: {}",
+                    e
+                )))
+            })
+    }
+
+    async fn release(&self, day: i64, amount: u64) -> Result<(), KoraError> {
+        let mut conn = self.get_connection().await?;
+        let _: u64 = RELEASE_SCRIPT
+            .key(Self::spent_key(day))
+            .arg(amount)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| {
+                KoraError::InternalServerError(sanitize_error!(format!(
+                    "Failed to release treasury budget: {}",
+                    e
+                )))
+            })?;
+        Ok(())
+    }
+
+    async fn spent(&self, day: i64) -> Result<u64, KoraError> {
+        let mut conn = self.get_connection().await?;
+        let spent: Option<u64> = conn.get(Self::spent_key(day)).await.map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to get treasury spend: {}",
+                e
+            )))
+        })?;
+        Ok(spent.unwrap_or(0))
+    }
+
+    async fn try_lock(&self, signer: &Pubkey, ttl: Duration) -> Result<bool, KoraError> {
+        let mut conn = self.get_connection().await?;
+        // SET NX EX: one instance tops up a signer at a time, and a crashed holder's lock expires
+        let acquired: Option<String> = redis::cmd("SET")
+            .arg(Self::lock_key(signer))
+            .arg(LOCK_OWNER.as_str())
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl.as_secs().max(1))
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| {
+                KoraError::InternalServerError(sanitize_error!(format!(
+                    "Failed to lock top-ups of signer {}: {}",
+                    signer, e
+                )))
+            })?;
+        Ok(acquired.is_some())
+    }
+
+    async fn unlock(&self, signer: &Pubkey) -> Result<(), KoraError> {
+        let mut conn = self.get_connection().await?;
+        let _: u64 = UNLOCK_SCRIPT
+            .key(Self::lock_key(signer))
+            .arg(LOCK_OWNER.as_str())
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| {
+                KoraError::InternalServerError(sanitize_error!(format!(
+                    "Failed to unlock top-ups of signer {}: {}",
+                    signer, e
+                )))
+            })?;
+        Ok(())
+    }
+
+    async fn get_pending(&self, signer: &Pubkey) -> Result<Option<PendingTopUp>, KoraError> {
+        let mut conn = self.get_connection().await?;
+        let data: Option<String> = conn.get(Self::pending_key(signer)).await.map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to get pending top-up of signer {}: {}",
+                signer, e
+            )))
+        })?;
+
+        data.map(|data| {
+            serde_json::from_str(&data).map_err(|e| {
+                KoraError::SerializationError(format!("Failed to deserialize pending top-up: {e}"))
+            })
+        })
+        .transpose()
+    }
+
+    async fn set_pending(
+        &self,
+        signer: &Pubkey,
+        pending: Option<&PendingTopUp>,
+    ) -> Result<(), KoraError> {
+        let mut conn = self.get_connection().await?;
+        let key = Self::pending_key(signer);
+        let result: redis::RedisResult<()> = match pending {
+            Some(pending) => {
+                let serialized = serde_json::to_string(pending).map_err(|e| {
+                    KoraError::SerializationError(format!(
+                        "Failed to serialize pending top-up: {e}"
+                    ))
+                })?;
+                conn.set(key, serialized).await
+            }
+            None => conn.del(key).await,
+        };
+
+        result.map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to set pending top-up of signer {}: {}",
+                signer, e
+            )))
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct DailySpend {
+    /// Days since the Unix epoch (UTC)
+    day: i64,
+    spent: u64,
+}
+
+impl DailySpend {
+    /// Reserve up to `amount` lamports of the day's budget. Returns the amount granted.
+    fn reserve(&mut self, day: i64, amount: u64, cap: u64) -> u64 {
+        let granted = amount.min(cap.saturating_sub(self.spent_on(day)));
+        self.spent += granted;
+        granted
+    }
+
+    /// Lamports spent on `day`, starting a new budget when the day changed
+    fn spent_on(&mut self, day: i64) -> u64 {
+        if self.day != day {
+            self.day = day;
+            self.spent = 0;
+        }
+        self.spent
+    }
+
+    /// Give back a reservation that was not sent
+    fn release(&mut self, day: i64, amount: u64) {
+        if self.day == day {
+            self.spent = self.spent.saturating_sub(amount);
+        }
+    }
+}
+
+/// In-memory implementation, local to this Kora instance
+#[derive(Default)]
+pub struct InMemoryTreasuryStore {
+    daily_spend: Mutex<DailySpend>,
+    locks: Mutex<HashMap<Pubkey, Instant>>,
+    pending: Mutex<HashMap<Pubkey, PendingTopUp>>,
+}
+
+impl InMemoryTreasuryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TreasuryStore for InMemoryTreasuryStore {
+    async fn reserve(&self, day: i64, amount: u64, cap: u64) -> Result<u64, KoraError> {
+        Ok(self.daily_spend.lock().await.reserve(day, amount, cap))
+    }
+
+    async fn release(&self, day: i64, amount: u64) -> Result<(), KoraError> {
+        self.daily_spend.lock().await.release(day, amount);
+        Ok(())
+    }
+
+    async fn spent(&self, day: i64) -> Result<u64, KoraError> {
+        Ok(self.daily_spend.lock().await.spent_on(day))
+    }
+
+    async fn try_lock(&self, signer: &Pubkey, ttl: Duration) -> Result<bool, KoraError> {
+        let now = Instant::now();
+        let mut locks = self.locks.lock().await;
+        if locks.get(signer).is_some_and(|expires_at| *expires_at > now) {
+            return Ok(false);
+        }
+        locks.insert(*signer, now + ttl);
+        Ok(true)
+    }
+
+    async fn unlock(&self, signer: &Pubkey) -> Result<(), KoraError> {
+        self.locks.lock().await.remove(signer);
+        Ok(())
+    }
+
+    async fn get_pending(&self, signer: &Pubkey) -> Result<Option<PendingTopUp>, KoraError> {
+        Ok(self.pending.lock().await.get(signer).cloned())
+    }
+
+    async fn set_pending(
+        &self,
+        signer: &Pubkey,
+        pending: Option<&PendingTopUp>,
+    ) -> Result<(), KoraError> {
+        let mut entries = self.pending.lock().await;
+        match pending {
+            Some(pending) => entries.insert(*signer, pending.clone()),
+            None => entries.remove(signer),
+        };
+        Ok(())
+    }
+}
+
+/// Initialize the global treasury store. Uses Redis when the cache is enabled, otherwise
+/// an in-memory store. Must run after [`CacheUtil::init`].
+pub fn init_treasury_store() -> Result<(), KoraError> {
+    let (store, backend): (Arc<dyn TreasuryStore>, &str) = match CacheUtil::get_pool() {
+        Some(pool) => (Arc::new(RedisTreasuryStore::new(pool)), "Redis"),
+        None => (Arc::new(InMemoryTreasuryStore::new()), "in-memory"),
+    };
+
+    TREASURY_STORE.set(store).map_err(|_| {
+        KoraError::InternalServerError("Treasury store already initialized".to_string())
+    })?;
+
+    log::info!("Treasury store initialized ({backend})");
+    Ok(())
+}
+
+/// Returns the global treasury store
+pub fn get_treasury_store() -> Arc<dyn TreasuryStore> {
+    TREASURY_STORE.get_or_init(|| Arc::new(InMemoryTreasuryStore::new())).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_daily_spend_caps_and_resets_each_day() {
+        let mut spend = DailySpend::default();
+
+        assert_eq!(spend.reserve(1, 700, 1_000), 700);
+        assert_eq!(spend.reserve(1, 700, 1_000), 300);
+        assert_eq!(spend.reserve(1, 700, 1_000), 0);
+
+        spend.release(1, 300);
+        assert_eq!(spend.spent_on(1), 700);
+
+        assert_eq!(spend.reserve(2, 700, 1_000), 700);
+        // A release from the previous day doesn't touch the new budget
+        spend.release(1, 700);
+        assert_eq!(spend.spent_on(2), 700);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_lock_is_exclusive_until_unlocked_or_expired() {
+        let store = InMemoryTreasuryStore::new();
+        let signer = Pubkey::new_unique();
+
+        assert!(store.try_lock(&signer, Duration::from_secs(60)).await.unwrap());
+        assert!(!store.try_lock(&signer, Duration::from_secs(60)).await.unwrap());
+        assert!(store.try_lock(&Pubkey::new_unique(), Duration::from_secs(60)).await.unwrap());
+
+        store.unlock(&signer).await.unwrap();
+        assert!(store.try_lock(&signer, Duration::ZERO).await.unwrap());
+        assert!(store.try_lock(&signer, Duration::from_secs(60)).await.unwrap());
+    }
+}
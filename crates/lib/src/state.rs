@@ -1,21 +1,37 @@
+use arc_swap::ArcSwapOption;
 use once_cell::sync::Lazy;
 use parking_lot::RwLock;
-use std::sync::{
-    atomic::{AtomicPtr, Ordering},
-    Arc,
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    borrow::Cow,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 use tokio_util::task::TaskTracker;
 
 use crate::{
-    config::Config, error::KoraError, signer::SignerPool, transaction::signing_retry_window,
+    config::{Config, ProgramsConfig},
+    error::KoraError,
+    rpc_server::auth::authenticated_client_id,
+    signer::{affinity::get_signer_affinity_store, SignerPolicyConfig, SignerPool, SolanaSigner},
+    token::token::TransferHookValidationFlow,
+    transaction::{signing_retry_window, TransactionUtil, VersionedTransactionResolved},
+    validator::transaction_validator::TransactionValidator,
 };
+use solana_client::nonblocking::rpc_client::RpcClient;
 use std::time::Duration;
 
 // Global signer pool (for multi-signer support)
 static GLOBAL_SIGNER_POOL: Lazy<RwLock<Option<Arc<SignerPool>>>> = Lazy::new(|| RwLock::new(None));
 
-// Global config with zero-cost reads and hot-reload capability
-static GLOBAL_CONFIG: AtomicPtr<Config> = AtomicPtr::new(std::ptr::null_mut());
+// Global config with lock-free reads and hot-reload capability. A replaced config is freed
+// once the last request holding it finishes.
+static GLOBAL_CONFIG: ArcSwapOption<Config> = ArcSwapOption::const_empty();
+
+// Set through the admin server to stop this instance from signing anything
+static PAUSED: AtomicBool = AtomicBool::new(false);
 
 // Tracks detached background tasks (currently the fire-and-forget broadcasts of
 // `RespondAfter::Signed`) so a graceful shutdown can wait for in-flight sends
@@ -30,6 +46,29 @@ pub fn get_background_tasks() -> &'static TaskTracker {
     &BACKGROUND_TASKS
 }
 
+/// Pause or resume signing on this instance. Returns the previous state.
+pub fn set_paused(paused: bool) -> bool {
+    let previous = PAUSED.swap(paused, Ordering::AcqRel);
+    if previous != paused {
+        log::warn!("Signing {}", if paused { "paused" } else { "resumed" });
+    }
+    previous
+}
+
+pub fn is_paused() -> bool {
+    PAUSED.load(Ordering::Acquire)
+}
+
+/// Rejects the request while signing is paused
+pub fn ensure_not_paused() -> Result<(), KoraError> {
+    if is_paused() {
+        return Err(KoraError::InvalidRequest(
+            "Kora is paused and is not signing transactions".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 /// Waits for all tracked background tasks to finish, up to `timeout`.
 ///
 /// Closes the tracker so no new spawns keep the wait alive, then awaits
@@ -56,10 +95,10 @@ fn sync_probe_lease_from_config(pool: &SignerPool, config: &Config) {
 /// Select a request-scoped signer without mutating recovery probe state.
 pub fn select_request_signer_with_signer_key(
     signer_key: Option<&str>,
-) -> Result<Arc<solana_keychain::Signer>, KoraError> {
+) -> Result<Arc<crate::signer::Signer>, KoraError> {
     let config = get_config()?;
     let pool = get_signer_pool()?;
-    sync_probe_lease_from_config(&pool, config);
+    sync_probe_lease_from_config(&pool, &config);
 
     if let Some(signer_key) = signer_key {
         return pool.select_signer_by_pubkey(signer_key);
@@ -69,13 +108,198 @@ pub fn select_request_signer_with_signer_key(
         .map_err(|e| KoraError::InternalServerError(format!("Failed to get signer from pool: {e}")))
 }
 
+/// Select a request-scoped signer like [`select_request_signer_with_signer_key`]. Without
+/// a `signer_key`, only signers whose policy allows every program returned by
+/// `program_ids` are considered, with `default_allowed_programs` applying to signers that
+/// do not override it. When user affinity is enabled, requests with a `user_id` keep
+/// getting the same signer.
+pub async fn select_request_signer_for_programs<F>(
+    signer_key: Option<&str>,
+    user_id: Option<&str>,
+    program_ids: F,
+    default_allowed_programs: &ProgramsConfig,
+) -> Result<Arc<crate::signer::Signer>, KoraError>
+where
+    F: FnOnce() -> Result<Vec<Pubkey>, KoraError>,
+{
+    if signer_key.is_some() {
+        return select_request_signer_with_signer_key(signer_key);
+    }
+    let program_ids = program_ids()?;
+
+    let config = get_config()?;
+    let pool = get_signer_pool()?;
+    sync_probe_lease_from_config(&pool, &config);
+
+    let affinity = UserAffinity::lookup(&pool, user_id).await;
+    let signer =
+        select_routed_signer(&pool, affinity.as_ref(), &program_ids, default_allowed_programs, &[])
+            .map_err(routing_error)?;
+
+    if let Some(affinity) = &affinity {
+        affinity.remember(&pool, &signer).await;
+    }
+    Ok(signer)
+}
+
+/// Select a request-scoped signer for `transaction` like
+/// [`select_request_signer_for_programs`]. When signers override the fee payer policy,
+/// the lamport limit or the price, each candidate is validated against its own policy
+/// and the request moves on to the next signer while the policy rejects it.
+/// `payment_flow` is set when the transaction must already carry the payment.
+pub async fn select_request_signer_for_transaction(
+    signer_key: Option<&str>,
+    user_id: Option<&str>,
+    config: &Config,
+    transaction: &mut VersionedTransactionResolved,
+    rpc_client: &RpcClient,
+    payment_flow: Option<TransferHookValidationFlow>,
+) -> Result<Arc<crate::signer::Signer>, KoraError> {
+    let program_ids = TransactionUtil::get_program_ids(&transaction.transaction);
+    let default_allowed_programs = &config.validation.allowed_programs;
+
+    let pool = get_signer_pool()?;
+    if signer_key.is_some() || !pool.has_validation_overrides() {
+        return select_request_signer_for_programs(
+            signer_key,
+            user_id,
+            || Ok(program_ids),
+            default_allowed_programs,
+        )
+        .await;
+    }
+    sync_probe_lease_from_config(&pool, config);
+
+    let affinity = UserAffinity::lookup(&pool, user_id).await;
+    let mut rejected = Vec::new();
+    let mut rejection = None;
+    loop {
+        let signer = match select_routed_signer(
+            &pool,
+            affinity.as_ref(),
+            &program_ids,
+            default_allowed_programs,
+            &rejected,
+        ) {
+            Ok(signer) => signer,
+            Err(e) => return Err(rejection.unwrap_or_else(|| routing_error(e))),
+        };
+
+        let fee_payer = signer.pubkey();
+        let signer_config = config_for_signer(config, &fee_payer);
+        let checked = TransactionValidator::new(&signer_config, fee_payer)?
+            .validate_signer_policy(&signer_config, transaction, rpc_client, payment_flow)
+            .await;
+        match checked {
+            Ok(()) => {
+                if let Some(affinity) = &affinity {
+                    affinity.remember(&pool, &signer).await;
+                }
+                return Ok(signer);
+            }
+            Err(e @ (KoraError::InvalidTransaction(_) | KoraError::ValidationError(_))) => {
+                log::debug!("Signer {fee_payer} rejected the transaction by policy: {e}");
+                rejected.push(fee_payer);
+                rejection = Some(e);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn routing_error(e: KoraError) -> KoraError {
+    match e {
+        KoraError::ValidationError(_) => e,
+        e => KoraError::InternalServerError(format!("Failed to get signer from pool: {e}")),
+    }
+}
+
+/// Selects among the signers allowing every program in `program_ids` that are not in
+/// `excluded`, sticking to the user's signer when `affinity` is set
+fn select_routed_signer(
+    pool: &SignerPool,
+    affinity: Option<&UserAffinity>,
+    program_ids: &[Pubkey],
+    default_allowed_programs: &ProgramsConfig,
+    excluded: &[Pubkey],
+) -> Result<Arc<crate::signer::Signer>, KoraError> {
+    match affinity {
+        Some(affinity) => pool.select_signer_for_user(
+            &affinity.user_id,
+            affinity.remembered.as_ref(),
+            program_ids,
+            default_allowed_programs,
+            excluded,
+        ),
+        None => {
+            pool.select_next_signer_for_programs(program_ids, default_allowed_programs, excluded)
+        }
+    }
+}
+
+/// The signer a user sticks to while user affinity is enabled. Requests without a
+/// `user_id` are keyed by the API key they authenticated with. Users still get a stable
+/// signer from hashing alone when the affinity store is unavailable.
+struct UserAffinity {
+    user_id: String,
+    ttl: Duration,
+    remembered: Option<Pubkey>,
+}
+
+impl UserAffinity {
+    async fn lookup(pool: &SignerPool, user_id: Option<&str>) -> Option<Self> {
+        let ttl = pool.user_affinity_ttl()?;
+        let user_id = user_id.map(str::to_string).or_else(authenticated_client_id)?;
+        let remembered = get_signer_affinity_store().get(&user_id).await.unwrap_or_else(|e| {
+            log::warn!("Failed to look up the signer of user {user_id}: {e}");
+            None
+        });
+        Some(Self { user_id, ttl, remembered })
+    }
+
+    /// Remember `signer` as the user's signer for the affinity TTL. A remembered signer
+    /// that is still in the pool is kept when this request was routed elsewhere, as it
+    /// only missed this request's programs or policy, or is briefly unavailable.
+    async fn remember(&self, pool: &SignerPool, signer: &crate::signer::Signer) {
+        if self
+            .remembered
+            .is_some_and(|remembered| remembered != signer.pubkey() && pool.has_signer(&remembered))
+        {
+            return;
+        }
+
+        let user_id = &self.user_id;
+        if let Err(e) = get_signer_affinity_store().set(user_id, &signer.pubkey(), self.ttl).await {
+            log::warn!("Failed to remember the signer of user {user_id}: {e}");
+        }
+    }
+}
+
+/// Validation overrides of the pool signer with `signer_pubkey`, if it has any
+pub fn get_signer_policy(signer_pubkey: &Pubkey) -> Option<Arc<SignerPolicyConfig>> {
+    get_signer_pool().ok()?.signer_policy(signer_pubkey)
+}
+
+/// `config` as it applies to transactions paid by `signer_pubkey`, with the signer's
+/// validation overrides merged in
+pub fn config_for_signer<'a>(config: &'a Config, signer_pubkey: &Pubkey) -> Cow<'a, Config> {
+    match get_signer_policy(signer_pubkey) {
+        Some(policy) => {
+            let mut config = config.clone();
+            config.validation = policy.apply_to(&config.validation);
+            Cow::Owned(config)
+        }
+        None => Cow::Borrowed(config),
+    }
+}
+
 /// Reserve a request-scoped signer, acquiring a recovery probe lock if needed.
 pub fn get_request_signer_with_signer_key(
     signer_key: Option<&str>,
-) -> Result<Arc<solana_keychain::Signer>, KoraError> {
+) -> Result<Arc<crate::signer::Signer>, KoraError> {
     let config = get_config()?;
     let pool = get_signer_pool()?;
-    sync_probe_lease_from_config(&pool, config);
+    sync_probe_lease_from_config(&pool, &config);
 
     // If client provided a signer signer_key, try to use that specific signer
     if let Some(signer_key) = signer_key {
@@ -89,7 +313,7 @@ pub fn get_request_signer_with_signer_key(
 
 pub fn reserve_request_signer_by_pubkey(
     signer_pubkey: &solana_sdk::pubkey::Pubkey,
-) -> Result<Arc<solana_keychain::Signer>, KoraError> {
+) -> Result<Arc<crate::signer::Signer>, KoraError> {
     get_request_signer_with_signer_key(Some(&signer_pubkey.to_string()))
 }
 
@@ -125,6 +349,27 @@ pub fn get_signers_info() -> Result<Vec<crate::signer::SignerInfo>, KoraError> {
     Ok(pool.get_signers_info())
 }
 
+/// Swap in a pool derived from the current one. Runs under the signer pool lock so
+/// concurrent signer changes can't overwrite each other. Returns whether the pool was
+/// replaced, `derive` returns None to leave it as is.
+pub fn modify_signer_pool<F>(derive: F) -> Result<bool, KoraError>
+where
+    F: FnOnce(&SignerPool) -> Result<Option<SignerPool>, KoraError>,
+{
+    let mut pool_guard = GLOBAL_SIGNER_POOL.write();
+    let current = pool_guard
+        .as_ref()
+        .ok_or_else(|| KoraError::InternalServerError("Signer pool not initialized".to_string()))?;
+
+    match derive(current)? {
+        Some(pool) => {
+            *pool_guard = Some(Arc::new(pool));
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
 /// Update the global signer configs with a new config (test only)
 #[cfg(test)]
 pub fn update_signer_pool(new_pool: SignerPool) -> Result<(), KoraError> {
@@ -137,52 +382,87 @@ pub fn update_signer_pool(new_pool: SignerPool) -> Result<(), KoraError> {
 
 /// Initialize the global config with a Config instance
 pub fn init_config(config: Config) -> Result<(), KoraError> {
-    let current_ptr = GLOBAL_CONFIG.load(Ordering::Acquire);
-    if !current_ptr.is_null() {
+    let previous = GLOBAL_CONFIG.compare_and_swap(&None::<Arc<Config>>, Some(Arc::new(config)));
+    if previous.is_some() {
         return Err(KoraError::InternalServerError("Config already initialized".to_string()));
     }
-
-    let config_ptr = Box::into_raw(Box::new(config));
-    GLOBAL_CONFIG.store(config_ptr, Ordering::Release);
     Ok(())
 }
 
-/// Get a reference to the global config (zero-cost read)
-pub fn get_config() -> Result<&'static Config, KoraError> {
-    let config_ptr = GLOBAL_CONFIG.load(Ordering::Acquire);
-    if config_ptr.is_null() {
+/// Get the global config. The returned snapshot stays valid across hot reloads, so a
+/// request keeps reading the same config until it finishes.
+pub fn get_config() -> Result<Arc<Config>, KoraError> {
+    GLOBAL_CONFIG
+        .load_full()
+        .ok_or_else(|| KoraError::InternalServerError("Config not initialized".to_string()))
+}
+
+/// Swap in a hot-reloaded config, and signer pool if one was rebuilt, under the
+/// signer pool lock so no request can pick up the new pool before the new config.
+/// Signers that stay in the rebuilt pool keep their health, availability and balance.
+///
+/// In-flight requests keep reading the snapshot they got from [`get_config`]; the previous
+/// config is freed when the last of them finishes.
+pub fn swap_config_and_signer_pool(
+    new_config: Config,
+    new_pool: Option<SignerPool>,
+) -> Result<(), KoraError> {
+    if GLOBAL_CONFIG.load().is_none() {
         return Err(KoraError::InternalServerError("Config not initialized".to_string()));
     }
 
-    // SAFETY: We ensure the pointer is valid and the config lives for the duration of the program
-    Ok(unsafe { &*config_ptr })
-}
-
-/// Update the global config with a new full config (test only)
-#[cfg(test)]
-pub fn update_config(new_config: Config) -> Result<(), KoraError> {
-    let old_ptr = GLOBAL_CONFIG.load(Ordering::Acquire);
-    let new_ptr = Box::into_raw(Box::new(new_config));
+    let mut pool_guard = GLOBAL_SIGNER_POOL.write();
 
-    GLOBAL_CONFIG.store(new_ptr, Ordering::Release);
+    GLOBAL_CONFIG.store(Some(Arc::new(new_config)));
 
-    // Clean up old config if it exists
-    if !old_ptr.is_null() {
-        unsafe {
-            let _ = Box::from_raw(old_ptr);
+    if let Some(pool) = new_pool {
+        if let Some(current) = pool_guard.as_ref() {
+            pool.carry_over_state_from(current);
         }
+        log::info!(
+            "Swapping global signer pool for {} signers using {:?} strategy",
+            pool.len(),
+            pool.strategy()
+        );
+        *pool_guard = Some(Arc::new(pool));
     }
 
     Ok(())
 }
 
+/// Update the global config with a new full config (test only)
+#[cfg(test)]
+pub fn update_config(new_config: Config) -> Result<(), KoraError> {
+    GLOBAL_CONFIG.store(Some(Arc::new(new_config)));
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{signer::pool::SignerWithMetadata, tests::config_mock::ConfigMockBuilder};
+    use crate::{
+        signer::{pool::SignerWithMetadata, Signer},
+        tests::{config_mock::ConfigMockBuilder, rpc_mock::RpcMockBuilder},
+    };
     use serial_test::serial;
-    use solana_keychain::Signer;
+    use solana_message::{Message, VersionedMessage};
     use solana_sdk::signature::Keypair;
+    use solana_system_interface::instruction::transfer;
+
+    #[test]
+    #[serial]
+    fn test_swapped_config_is_freed_after_last_reader() {
+        update_config(ConfigMockBuilder::new().build()).unwrap();
+        let previous = get_config().unwrap();
+        let weak = Arc::downgrade(&previous);
+
+        swap_config_and_signer_pool(ConfigMockBuilder::new().build(), None).unwrap();
+
+        // An in-flight reader keeps its snapshot alive
+        assert!(weak.upgrade().is_some());
+        drop(previous);
+        assert!(weak.upgrade().is_none());
+    }
 
     #[test]
     #[serial]
@@ -207,6 +487,120 @@ mod tests {
         assert_eq!(pool.probe_lease(), signing_retry_window(Duration::from_secs(15), 5));
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_select_request_signer_for_transaction_routes_around_policy_rejections() {
+        let config = ConfigMockBuilder::new().build();
+        update_config(config.clone()).unwrap();
+
+        let memory_signer = |name: &str, max_allowed_lamports| {
+            let signer = Signer::from_memory(&Keypair::new().to_base58_string()).unwrap();
+            SignerWithMetadata::new(name.to_string(), Arc::new(signer), 1).with_policy(Some(
+                SignerPolicyConfig { max_allowed_lamports, ..Default::default() },
+            ))
+        };
+        let strict = memory_signer("strict", Some(0));
+        let strict_pubkey = strict.pubkey();
+        update_signer_pool(SignerPool::new(vec![strict, memory_signer("default", None)])).unwrap();
+
+        // Mocked RPC responses are consumed by the first request
+        let rpc_client = || RpcMockBuilder::new().with_fee_estimate(5_000).build();
+        let message = VersionedMessage::Legacy(Message::new(
+            &[transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1)],
+            Some(&Pubkey::new_unique()),
+        ));
+        let mut transaction = VersionedTransactionResolved::from_kora_built_transaction(
+            &TransactionUtil::new_unsigned_versioned_transaction(message),
+        )
+        .unwrap();
+
+        for _ in 0..4 {
+            let signer = select_request_signer_for_transaction(
+                None,
+                None,
+                &config,
+                &mut transaction,
+                &rpc_client(),
+                None,
+            )
+            .await
+            .unwrap();
+            assert_ne!(signer.pubkey(), strict_pubkey);
+        }
+
+        update_signer_pool(SignerPool::new(vec![memory_signer("strict", Some(0))])).unwrap();
+        let result = select_request_signer_for_transaction(
+            None,
+            None,
+            &config,
+            &mut transaction,
+            &rpc_client(),
+            None,
+        )
+        .await;
+        assert!(
+            matches!(result, Err(KoraError::InvalidTransaction(message)) if message.contains("exceeds maximum allowed"))
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_user_keeps_remembered_signer_when_routed_elsewhere() {
+        update_config(ConfigMockBuilder::new().build()).unwrap();
+
+        let deploy_program = Pubkey::new_unique();
+        let payments_program = Pubkey::new_unique();
+        let allow_only = |name: &str, program: Pubkey| {
+            let signer = Signer::from_memory(&Keypair::new().to_base58_string()).unwrap();
+            SignerWithMetadata::new(name.to_string(), Arc::new(signer), 1).with_policy(Some(
+                SignerPolicyConfig {
+                    allowed_programs: Some(ProgramsConfig::Allowlist(vec![program.to_string()])),
+                    ..Default::default()
+                },
+            ))
+        };
+        let deploy = allow_only("deploy", deploy_program);
+        let deploy_pubkey = deploy.pubkey();
+        let payments = allow_only("payments", payments_program);
+        let payments_pubkey = payments.pubkey();
+        update_signer_pool(
+            SignerPool::new(vec![deploy, payments]).with_user_affinity_ttl(Duration::from_secs(60)),
+        )
+        .unwrap();
+
+        let user_id = format!("user-{}", Pubkey::new_unique());
+        let defaults = ProgramsConfig::Allowlist(vec![]);
+        let select = |program: Pubkey| {
+            select_request_signer_for_programs(
+                None,
+                Some(&user_id),
+                move || Ok(vec![program]),
+                &defaults,
+            )
+        };
+
+        assert_eq!(select(deploy_program).await.unwrap().pubkey(), deploy_pubkey);
+        assert_eq!(select(payments_program).await.unwrap().pubkey(), payments_pubkey);
+
+        let remembered = get_signer_affinity_store().get(&user_id).await.unwrap();
+        assert_eq!(remembered, Some(deploy_pubkey));
+    }
+
+    #[test]
+    #[serial]
+    fn test_swap_config_keeps_old_snapshot_readable() {
+        let mut config = ConfigMockBuilder::new().build();
+        config.kora.rate_limit = 1;
+        update_config(config.clone()).unwrap();
+        let snapshot = get_config().unwrap();
+
+        config.kora.rate_limit = 2;
+        swap_config_and_signer_pool(config, None).unwrap();
+
+        assert_eq!(snapshot.kora.rate_limit, 1);
+        assert_eq!(get_config().unwrap().kora.rate_limit, 2);
+    }
+
     #[tokio::test]
     async fn test_drain_tracker_waits_for_in_flight_task() {
         let tracker = TaskTracker::new();
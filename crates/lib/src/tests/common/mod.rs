@@ -6,7 +6,7 @@ use std::sync::Arc;
 /// 1. Setup functions for test environment initialization (signer & config)
 /// 2. Centralized re-exports of commonly used mock utilities
 use crate::{
-    signer::{pool::SignerWithMetadata, SignerPool},
+    signer::{pool::SignerWithMetadata, Signer, SignerPool, SolanaSigner},
     state::{get_config, select_request_signer_with_signer_key, update_config, update_signer_pool},
     tests::{account_mock, config_mock::ConfigMockBuilder, rpc_mock},
     usage_limit::UsageTracker,
@@ -17,7 +17,6 @@ use solana_sdk::{pubkey::Pubkey, signature::Keypair};
 // Re-export mock utilities for centralized access
 pub use account_mock::*;
 pub use rpc_mock::*;
-use solana_keychain::{Signer, SolanaSigner};
 
 /// Setup or retrieve test signer for global state initialization
 ///
@@ -73,7 +72,7 @@ pub fn create_probe_eligible_test_pool() -> (SignerPool, String) {
 /// Returns the config object.
 pub fn setup_or_get_test_config() -> Config {
     if let Ok(config) = get_config() {
-        return config.clone();
+        return config.as_ref().clone();
     }
 
     let config = ConfigMockBuilder::new().build();
@@ -1,9 +1,10 @@
 use crate::{
     bundle::{constant::JITO_MOCK_BLOCK_ENGINE_URL, JitoConfig},
     config::{
-        AuthConfig, BundleConfig, CacheConfig, Config, EnabledMethods,
-        FeePayerBalanceMetricsConfig, FeePayerPolicy, KoraConfig, LighthouseConfig, MetricsConfig,
-        NonceInstructionPolicy, PluginsConfig, ProgramsConfig, SplTokenConfig,
+        AdminConfig, AnchorConfig, AuthConfig, BatchConfig, BundleConfig, CacheConfig,
+        ComputeBudgetConfig, Config, EnabledMethods, FeePayerBalanceMetricsConfig, FeePayerPolicy,
+        KoraConfig, LighthouseConfig, MetricsConfig, NonceInstructionPolicy, NoncePoolConfig,
+        PluginsConfig, ProgramsConfig, RpcConfig, SenderConfig, SplTokenConfig,
         SplTokenInstructionPolicy, SystemInstructionPolicy, Token2022Config,
         Token2022InstructionPolicy, ValidationConfig,
     },
@@ -98,6 +99,9 @@ impl ConfigMockBuilder {
                     allow_durable_transactions: false,
                     max_price_staleness_slots: 0,
                     require_one_of_programs: vec![],
+                    program_rules: vec![],
+                    anchor: AnchorConfig::default(),
+                    shadow: None,
                     cross_cluster_check: false,
                     cross_cluster_endpoints: vec![],
                 },
@@ -124,6 +128,12 @@ impl ConfigMockBuilder {
                         },
                     },
                     lighthouse: LighthouseConfig::default(),
+                    sender: SenderConfig::default(),
+                    rpc: RpcConfig::default(),
+                    compute_budget: ComputeBudgetConfig::default(),
+                    nonce_pool: NoncePoolConfig::default(),
+                    batch: BatchConfig::default(),
+                    admin: AdminConfig::default(),
                     force_sig_verify: false,
                     sign_timeout_seconds: 10,
                     sign_max_retries: 2,
@@ -284,6 +294,16 @@ impl ConfigMockBuilder {
         self
     }
 
+    pub fn with_nonce_pool_enabled(mut self, enabled: bool) -> Self {
+        self.config.kora.nonce_pool.enabled = enabled;
+        self
+    }
+
+    pub fn with_max_batch_transactions(mut self, max_transactions: usize) -> Self {
+        self.config.kora.batch.max_transactions = max_transactions;
+        self
+    }
+
     /// Build and setup the config mock with mutex lock
     /// Returns a lock guard that should be held for the duration of the test
     pub fn build_and_setup(self) -> std::sync::MutexGuard<'static, ()> {
@@ -318,6 +338,9 @@ impl ValidationConfigBuilder {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -404,6 +427,12 @@ impl KoraConfigBuilder {
                 plugins: PluginsConfig::default(),
                 bundle: BundleConfig::default(),
                 lighthouse: LighthouseConfig::default(),
+                sender: SenderConfig::default(),
+                rpc: RpcConfig::default(),
+                compute_budget: ComputeBudgetConfig::default(),
+                nonce_pool: NoncePoolConfig::default(),
+                batch: BatchConfig::default(),
+                admin: AdminConfig::default(),
                 force_sig_verify: false,
                 sign_timeout_seconds: 10,
                 sign_max_retries: 2,
@@ -746,6 +775,7 @@ impl FeePayerPolicyBuilder {
                 bpf_loader_upgradeable:
                     crate::config::BpfLoaderUpgradeableInstructionPolicy::default(),
                 loader_v4: crate::config::LoaderV4InstructionPolicy::default(),
+                stake: crate::config::StakeInstructionPolicy::default(),
             },
         }
     }
@@ -816,8 +846,14 @@ impl SignerPoolConfigBuilder {
     pub fn new() -> Self {
         Self {
             config: SignerPoolConfig {
-                signer_pool: SignerPoolSettings { strategy: SelectionStrategy::RoundRobin },
+                signer_pool: SignerPoolSettings {
+                    strategy: SelectionStrategy::RoundRobin,
+                    min_balance_lamports: None,
+                    slow_signer_p95_ms: None,
+                    user_affinity: None,
+                },
                 signers: vec![],
+                treasury: None,
             },
         }
     }
@@ -850,6 +886,10 @@ impl SignerPoolConfigBuilder {
         let signer = SignerConfig {
             name,
             weight,
+            target_balance: None,
+            refill_below: None,
+            policy: None,
+            max_concurrent_signs: None,
             config: SignerTypeConfig::Memory { config: MemorySignerConfig { private_key_env } },
         };
         self.config.signers.push(signer);
@@ -870,6 +910,10 @@ impl SignerPoolConfigBuilder {
         let signer = SignerConfig {
             name,
             weight,
+            target_balance: None,
+            refill_below: None,
+            policy: None,
+            max_concurrent_signs: None,
             config: SignerTypeConfig::Turnkey {
                 config: TurnkeySignerConfig {
                     api_public_key_env,
@@ -896,6 +940,10 @@ impl SignerPoolConfigBuilder {
         let signer = SignerConfig {
             name,
             weight,
+            target_balance: None,
+            refill_below: None,
+            policy: None,
+            max_concurrent_signs: None,
             config: SignerTypeConfig::Privy {
                 config: PrivySignerConfig {
                     app_id_env,
@@ -920,6 +968,10 @@ impl SignerPoolConfigBuilder {
         let signer = SignerConfig {
             name,
             weight,
+            target_balance: None,
+            refill_below: None,
+            policy: None,
+            max_concurrent_signs: None,
             config: SignerTypeConfig::Openfort {
                 config: OpenfortSignerConfig {
                     secret_key_env,
@@ -946,6 +998,10 @@ impl SignerPoolConfigBuilder {
         let signer = SignerConfig {
             name,
             weight,
+            target_balance: None,
+            refill_below: None,
+            policy: None,
+            max_concurrent_signs: None,
             config: SignerTypeConfig::Vault {
                 config: VaultSignerConfig {
                     vault_addr_env: addr_env,
@@ -21,3 +21,6 @@ pub mod transaction_mock;
 
 #[cfg(test)]
 pub mod oracle_mock;
+
+#[cfg(test)]
+pub mod remote_signer_mock;
@@ -0,0 +1,82 @@
+//! Reference `remote_http` signing service, see `signer::remote_http` for the protocol
+use crate::constant::{DEFAULT_MAX_TIMESTAMP_AGE, X_HMAC_SIGNATURE, X_TIMESTAMP};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hmac::{Hmac, KeyInit, Mac};
+use mockito::{Mock, Request, ServerGuard};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use std::sync::Arc;
+
+pub struct RemoteSignerMock {
+    server: ServerGuard,
+    keypair: Arc<Keypair>,
+    _mock: Mock,
+}
+
+impl RemoteSignerMock {
+    /// Start a service that signs with a fresh keypair for requests carrying a valid
+    /// HMAC for `hmac_secret`. Requests that fail authentication get no matching mock.
+    pub async fn start(hmac_secret: &str) -> Self {
+        let mut server = mockito::Server::new_async().await;
+        let keypair = Arc::new(Keypair::new());
+
+        let secret = hmac_secret.to_string();
+        let signing_keypair = keypair.clone();
+        let mock = server
+            .mock("POST", "/sign")
+            .match_request(move |request| Self::is_authenticated(request, &secret))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_request(move |request| Self::sign(request, &signing_keypair))
+            .create_async()
+            .await;
+
+        Self { server, keypair, _mock: mock }
+    }
+
+    pub fn url(&self) -> String {
+        format!("{}/sign", self.server.url())
+    }
+
+    pub fn pubkey(&self) -> Pubkey {
+        self.keypair.pubkey()
+    }
+
+    fn is_authenticated(request: &Request, secret: &str) -> bool {
+        let header = |name| {
+            request.header(name).first().and_then(|value| value.to_str().ok()).map(str::to_string)
+        };
+        let (Some(timestamp), Some(signature), Ok(body)) =
+            (header(X_TIMESTAMP), header(X_HMAC_SIGNATURE), request.body())
+        else {
+            return false;
+        };
+
+        let now =
+            std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs()
+                as i64;
+        match timestamp.parse::<i64>() {
+            Ok(ts) if (now - ts).abs() <= DEFAULT_MAX_TIMESTAMP_AGE => {}
+            _ => return false,
+        }
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(timestamp.as_bytes());
+        mac.update(body);
+        hex::decode(signature).is_ok_and(|signature| mac.verify_slice(&signature).is_ok())
+    }
+
+    fn sign(request: &Request, keypair: &Keypair) -> Vec<u8> {
+        let body: Value = serde_json::from_slice(request.body().unwrap()).unwrap();
+        let message = STANDARD.decode(body["message"].as_str().unwrap()).unwrap();
+        let signature = keypair.sign_message(&message);
+
+        json!({ "signature": signature.to_string(), "pubkey": keypair.pubkey().to_string() })
+            .to_string()
+            .into_bytes()
+    }
+}
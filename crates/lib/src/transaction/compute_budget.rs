@@ -0,0 +1,441 @@
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcSimulateTransactionConfig};
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_message::{compiled_instruction::CompiledInstruction, VersionedMessage};
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::VersionedTransaction};
+
+use crate::{
+    config::ComputeBudgetConfig, constant::MAX_COMPUTE_UNIT_LIMIT, error::KoraError, sanitize_error,
+};
+
+/// Borsh discriminators of the ComputeBudget instructions Kora replaces
+const SET_COMPUTE_UNIT_LIMIT_DISCRIMINATOR: u8 = 2;
+const SET_COMPUTE_UNIT_PRICE_DISCRIMINATOR: u8 = 3;
+
+/// `getRecentPrioritizationFees` accepts at most this many accounts
+const MAX_PRIORITIZATION_FEE_ACCOUNTS: usize = 128;
+
+/// Compute unit price and limit written into a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InjectedComputeBudget {
+    /// Micro-lamports per compute unit
+    pub compute_unit_price: u64,
+    pub compute_unit_limit: u32,
+}
+
+pub struct ComputeBudgetUtil {}
+
+impl ComputeBudgetUtil {
+    /// Estimates the price and limit for `transaction` and writes them into its message,
+    /// replacing any existing SetComputeUnitPrice/SetComputeUnitLimit instructions.
+    ///
+    /// The message changes, so all signatures are cleared and must be collected again.
+    pub async fn inject_compute_budget(
+        rpc_client: &RpcClient,
+        transaction: &mut VersionedTransaction,
+        config: &ComputeBudgetConfig,
+    ) -> Result<InjectedComputeBudget, KoraError> {
+        let compute_unit_price =
+            Self::estimate_compute_unit_price(rpc_client, &transaction.message, config).await?;
+
+        // Simulate with the maximum limit so the measured consumption isn't capped by the
+        // default per-instruction limit
+        let mut simulation_transaction = transaction.clone();
+        Self::set_compute_budget(
+            &mut simulation_transaction.message,
+            MAX_COMPUTE_UNIT_LIMIT,
+            compute_unit_price,
+        )?;
+        let compute_unit_limit =
+            Self::estimate_compute_unit_limit(rpc_client, &simulation_transaction, config).await?;
+
+        transaction.message = simulation_transaction.message;
+        Self::set_compute_budget(&mut transaction.message, compute_unit_limit, compute_unit_price)?;
+        transaction.signatures = vec![
+            Signature::default();
+            transaction.message.header().num_required_signatures as usize
+        ];
+
+        Ok(InjectedComputeBudget { compute_unit_price, compute_unit_limit })
+    }
+
+    /// Handles a client's `inject_compute_budget` opt-in. Rejected when injection is
+    /// disabled, or when signatures are verified since injection clears them.
+    pub async fn inject_requested_compute_budget(
+        rpc_client: &RpcClient,
+        transaction: &mut VersionedTransaction,
+        config: &ComputeBudgetConfig,
+        sig_verify: bool,
+    ) -> Result<InjectedComputeBudget, KoraError> {
+        if !config.enabled {
+            return Err(KoraError::ValidationError(
+                "Compute budget injection is not enabled".to_string(),
+            ));
+        }
+        if sig_verify {
+            return Err(KoraError::ValidationError(
+                "inject_compute_budget cannot be combined with signature verification".to_string(),
+            ));
+        }
+
+        Self::inject_compute_budget(rpc_client, transaction, config).await
+    }
+
+    /// Compute unit price at the configured percentile of recent prioritization fees
+    /// paid for the message's writable accounts, capped at `max_compute_unit_price`
+    pub async fn estimate_compute_unit_price(
+        rpc_client: &RpcClient,
+        message: &VersionedMessage,
+        config: &ComputeBudgetConfig,
+    ) -> Result<u64, KoraError> {
+        let writable_accounts: Vec<Pubkey> = message
+            .static_account_keys()
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| message.is_maybe_writable(*index, None))
+            .map(|(_, key)| *key)
+            .take(MAX_PRIORITIZATION_FEE_ACCOUNTS)
+            .collect();
+
+        let mut fees: Vec<u64> = rpc_client
+            .get_recent_prioritization_fees(&writable_accounts)
+            .await
+            .map_err(|e| {
+                KoraError::RpcError(format!(
+                    "Failed to get recent prioritization fees: {}",
+                    sanitize_error!(e)
+                ))
+            })?
+            .into_iter()
+            .map(|fee| fee.prioritization_fee)
+            .collect();
+
+        Ok(Self::percentile(&mut fees, config.priority_fee_percentile)
+            .min(config.max_compute_unit_price))
+    }
+
+    /// Simulated compute unit consumption plus the configured headroom
+    pub async fn estimate_compute_unit_limit(
+        rpc_client: &RpcClient,
+        transaction: &VersionedTransaction,
+        config: &ComputeBudgetConfig,
+    ) -> Result<u32, KoraError> {
+        let simulation_result = rpc_client
+            .simulate_transaction_with_config(
+                transaction,
+                RpcSimulateTransactionConfig {
+                    commitment: Some(rpc_client.commitment()),
+                    sig_verify: false,
+                    replace_recent_blockhash: true,
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(|e| {
+                KoraError::RpcError(format!(
+                    "Failed to simulate transaction: {}",
+                    sanitize_error!(e)
+                ))
+            })?;
+
+        if let Some(err) = simulation_result.value.err {
+            return Err(KoraError::InvalidTransaction(format!(
+                "Transaction simulation failed: {err}"
+            )));
+        }
+
+        let units_consumed = simulation_result.value.units_consumed.ok_or_else(|| {
+            KoraError::RpcError("Simulation did not report compute units consumed".to_string())
+        })?;
+
+        let headroom = units_consumed
+            .saturating_mul(config.compute_unit_limit_headroom_percent as u64)
+            .div_ceil(100);
+
+        Ok(units_consumed.saturating_add(headroom).min(MAX_COMPUTE_UNIT_LIMIT as u64) as u32)
+    }
+
+    /// Writes SetComputeUnitLimit and SetComputeUnitPrice at the start of the message,
+    /// removing any existing ones. Works on compiled messages so lookup table
+    /// references in v0 messages stay intact.
+    pub fn set_compute_budget(
+        message: &mut VersionedMessage,
+        compute_unit_limit: u32,
+        compute_unit_price: u64,
+    ) -> Result<(), KoraError> {
+        let compute_budget_id = solana_compute_budget_interface::id();
+        let (header, account_keys, instructions) = match message {
+            VersionedMessage::Legacy(message) => {
+                (&mut message.header, &mut message.account_keys, &mut message.instructions)
+            }
+            VersionedMessage::V0(message) => {
+                (&mut message.header, &mut message.account_keys, &mut message.instructions)
+            }
+        };
+
+        let program_id_index = match account_keys.iter().position(|key| *key == compute_budget_id) {
+            Some(index) => index,
+            None => {
+                // Appended as the last readonly unsigned static key. Lookup table accounts
+                // are indexed after the static keys, so their indexes shift by one.
+                let index = account_keys.len();
+                for instruction in instructions.iter_mut() {
+                    for account_index in instruction.accounts.iter_mut() {
+                        if *account_index as usize >= index {
+                            *account_index = account_index.checked_add(1).ok_or_else(|| {
+                                KoraError::InvalidTransaction(
+                                    "Too many accounts to add the ComputeBudget program"
+                                        .to_string(),
+                                )
+                            })?;
+                        }
+                    }
+                }
+                account_keys.push(compute_budget_id);
+                header.num_readonly_unsigned_accounts += 1;
+                index
+            }
+        };
+        let program_id_index = u8::try_from(program_id_index).map_err(|_| {
+            KoraError::InvalidTransaction(
+                "Too many accounts to add the ComputeBudget program".to_string(),
+            )
+        })?;
+
+        instructions.retain(|instruction| {
+            instruction.program_id_index != program_id_index
+                || !matches!(
+                    instruction.data.first(),
+                    Some(&SET_COMPUTE_UNIT_LIMIT_DISCRIMINATOR)
+                        | Some(&SET_COMPUTE_UNIT_PRICE_DISCRIMINATOR)
+                )
+        });
+
+        let budget_instructions = [
+            ComputeBudgetInstruction::set_compute_unit_limit(compute_unit_limit),
+            ComputeBudgetInstruction::set_compute_unit_price(compute_unit_price),
+        ];
+        instructions.splice(
+            0..0,
+            budget_instructions.into_iter().map(|instruction| CompiledInstruction {
+                program_id_index,
+                accounts: vec![],
+                data: instruction.data,
+            }),
+        );
+
+        Ok(())
+    }
+
+    fn percentile(values: &mut [u64], percentile: u8) -> u64 {
+        if values.is_empty() {
+            return 0;
+        }
+        values.sort_unstable();
+        let index = (values.len() - 1) * percentile.min(100) as usize / 100;
+        values[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::rpc_mock::RpcMockBuilder;
+    use serde_json::json;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_message::{v0, Message, MessageHeader};
+    use solana_sdk::{hash::Hash, instruction::Instruction};
+    use solana_system_interface::instruction::transfer;
+
+    fn compute_budget_config() -> ComputeBudgetConfig {
+        ComputeBudgetConfig { enabled: true, ..Default::default() }
+    }
+
+    fn budget_instructions(message: &VersionedMessage) -> Vec<Instruction> {
+        let keys = message.static_account_keys();
+        message
+            .instructions()
+            .iter()
+            .filter(|ix| {
+                keys[ix.program_id_index as usize] == solana_compute_budget_interface::id()
+            })
+            .map(|ix| {
+                Instruction::new_with_bytes(keys[ix.program_id_index as usize], &ix.data, vec![])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_set_compute_budget_legacy_adds_program() {
+        let fee_payer = Pubkey::new_unique();
+        let ix = transfer(&fee_payer, &Pubkey::new_unique(), 1000);
+        let mut message = VersionedMessage::Legacy(Message::new(&[ix], Some(&fee_payer)));
+
+        ComputeBudgetUtil::set_compute_budget(&mut message, 50_000, 1_000).unwrap();
+
+        assert!(message.sanitize().is_ok());
+        assert_eq!(message.instructions().len(), 3);
+        assert_eq!(
+            budget_instructions(&message),
+            vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(50_000),
+                ComputeBudgetInstruction::set_compute_unit_price(1_000),
+            ]
+        );
+        // The transfer is unchanged and still last
+        assert_eq!(
+            message.static_account_keys()[message.instructions()[2].program_id_index as usize],
+            solana_system_interface::program::ID
+        );
+    }
+
+    #[test]
+    fn test_set_compute_budget_replaces_existing_instructions() {
+        let fee_payer = Pubkey::new_unique();
+        let heap_frame = ComputeBudgetInstruction::request_heap_frame(64 * 1024);
+        let instructions = [
+            ComputeBudgetInstruction::set_compute_unit_price(5),
+            ComputeBudgetInstruction::set_compute_unit_limit(200_000),
+            heap_frame.clone(),
+            transfer(&fee_payer, &Pubkey::new_unique(), 1000),
+        ];
+        let mut message = VersionedMessage::Legacy(Message::new(&instructions, Some(&fee_payer)));
+        let account_count = message.static_account_keys().len();
+
+        ComputeBudgetUtil::set_compute_budget(&mut message, 30_000, 2_000).unwrap();
+
+        assert_eq!(message.static_account_keys().len(), account_count);
+        assert_eq!(message.instructions().len(), 4);
+        assert_eq!(
+            budget_instructions(&message),
+            vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(30_000),
+                ComputeBudgetInstruction::set_compute_unit_price(2_000),
+                heap_frame,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_compute_budget_v0_shifts_lookup_table_indexes() {
+        let fee_payer = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let mut message = VersionedMessage::V0(v0::Message {
+            header: MessageHeader {
+                num_required_signatures: 1,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys: vec![fee_payer, program_id],
+            recent_blockhash: Hash::new_unique(),
+            // Index 2 is the first account loaded from the lookup table
+            instructions: vec![CompiledInstruction {
+                program_id_index: 1,
+                accounts: vec![0, 2],
+                data: vec![7],
+            }],
+            address_table_lookups: vec![v0::MessageAddressTableLookup {
+                account_key: Pubkey::new_unique(),
+                writable_indexes: vec![0],
+                readonly_indexes: vec![],
+            }],
+        });
+
+        ComputeBudgetUtil::set_compute_budget(&mut message, 10_000, 0).unwrap();
+
+        assert_eq!(message.static_account_keys()[2], solana_compute_budget_interface::id());
+        assert_eq!(message.header().num_readonly_unsigned_accounts, 2);
+        let original = &message.instructions()[2];
+        assert_eq!(original.program_id_index, 1);
+        assert_eq!(original.accounts, vec![0, 3]);
+    }
+
+    #[test]
+    fn test_percentile() {
+        assert_eq!(ComputeBudgetUtil::percentile(&mut [], 75), 0);
+        assert_eq!(ComputeBudgetUtil::percentile(&mut [40, 10, 30, 20, 50], 0), 10);
+        assert_eq!(ComputeBudgetUtil::percentile(&mut [40, 10, 30, 20, 50], 50), 30);
+        assert_eq!(ComputeBudgetUtil::percentile(&mut [40, 10, 30, 20, 50], 100), 50);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_compute_unit_price_capped() {
+        let rpc_client = RpcMockBuilder::new()
+            .with_custom_mock(
+                RpcRequest::GetRecentPrioritizationFees,
+                json!([
+                    { "slot": 1, "prioritizationFee": 100 },
+                    { "slot": 2, "prioritizationFee": 5_000 },
+                ]),
+            )
+            .build();
+        let fee_payer = Pubkey::new_unique();
+        let ix = transfer(&fee_payer, &Pubkey::new_unique(), 1000);
+        let message = VersionedMessage::Legacy(Message::new(&[ix], Some(&fee_payer)));
+        let config = ComputeBudgetConfig {
+            priority_fee_percentile: 100,
+            max_compute_unit_price: 1_000,
+            ..compute_budget_config()
+        };
+
+        let price =
+            ComputeBudgetUtil::estimate_compute_unit_price(&rpc_client, &message, &config).await;
+
+        assert_eq!(price.unwrap(), 1_000);
+    }
+
+    #[tokio::test]
+    async fn test_inject_compute_budget() {
+        // Simulation reports 1000 units consumed
+        let rpc_client = RpcMockBuilder::new().with_simulation().build();
+        let fee_payer = Pubkey::new_unique();
+        let ix = transfer(&fee_payer, &Pubkey::new_unique(), 1000);
+        let message = VersionedMessage::Legacy(Message::new(&[ix], Some(&fee_payer)));
+        let mut transaction =
+            VersionedTransaction { signatures: vec![Signature::new_unique()], message };
+
+        let injected = ComputeBudgetUtil::inject_compute_budget(
+            &rpc_client,
+            &mut transaction,
+            &compute_budget_config(),
+        )
+        .await
+        .unwrap();
+
+        // The default mock returns a single recent fee of 10_000 micro-lamports
+        assert_eq!(
+            injected,
+            InjectedComputeBudget { compute_unit_price: 10_000, compute_unit_limit: 1_100 }
+        );
+        assert_eq!(
+            budget_instructions(&transaction.message),
+            vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(1_100),
+                ComputeBudgetInstruction::set_compute_unit_price(10_000),
+            ]
+        );
+        assert_eq!(transaction.signatures, vec![Signature::default()]);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_compute_unit_limit_simulation_error() {
+        let rpc_client = RpcMockBuilder::new()
+            .with_custom_mock(
+                RpcRequest::SimulateTransaction,
+                json!({
+                    "context": { "slot": 1 },
+                    "value": { "err": "AccountNotFound", "logs": [], "unitsConsumed": 0 }
+                }),
+            )
+            .build();
+        let transaction = crate::tests::transaction_mock::create_mock_transaction();
+
+        let result = ComputeBudgetUtil::estimate_compute_unit_limit(
+            &rpc_client,
+            &transaction,
+            &compute_budget_config(),
+        )
+        .await;
+
+        assert!(matches!(result, Err(KoraError::InvalidTransaction(_))));
+    }
+}
@@ -0,0 +1,23 @@
+use sha2::{Digest, Sha256};
+
+/// Length of the discriminator Anchor prefixes to instruction data
+pub const ANCHOR_DISCRIMINATOR_LEN: usize = 8;
+
+/// Discriminator of the Anchor instruction `name` (in snake_case, as in the program
+/// source): the first 8 bytes of `sha256("global:<name>")`
+pub fn anchor_instruction_discriminator(name: &str) -> [u8; ANCHOR_DISCRIMINATOR_LEN] {
+    let digest = Sha256::digest(format!("global:{name}").as_bytes());
+    let mut discriminator = [0u8; ANCHOR_DISCRIMINATOR_LEN];
+    discriminator.copy_from_slice(&digest[..ANCHOR_DISCRIMINATOR_LEN]);
+    discriminator
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchor_instruction_discriminator() {
+        assert_eq!(hex::encode(anchor_instruction_discriminator("initialize")), "afaf6d1f0d989bed");
+    }
+}
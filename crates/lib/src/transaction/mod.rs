@@ -1,11 +1,21 @@
+mod compute_budget;
+mod discriminator;
 mod instruction_util;
+mod parser;
 mod retry_util;
+mod sender;
+mod status_store;
 mod token2022_security;
 mod transaction;
 mod versioned_message;
 mod versioned_transaction;
+pub use compute_budget::*;
+pub use discriminator::*;
 pub use instruction_util::*;
+pub use parser::*;
 pub(crate) use retry_util::{sign_with_retry, signing_retry_window};
+pub use sender::*;
+pub use status_store::*;
 pub(crate) use token2022_security::*;
 pub use transaction::*;
 pub use versioned_message::*;
@@ -0,0 +1,116 @@
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+use crate::{
+    constant::BUBBLEGUM_PROGRAM_ID,
+    error::KoraError,
+    transaction::{
+        anchor_instruction_discriminator,
+        parser::{named_accounts, InstructionParser, ParsedInstruction},
+        ANCHOR_DISCRIMINATOR_LEN,
+    },
+};
+
+/// Bubblegum instructions: (Rust name the Anchor discriminator derives from, parsed name,
+/// account roles, required account count)
+const INSTRUCTIONS: &[(&str, &str, &[&str], usize)] = &[
+    ("create_tree", "createTree", &["treeAuthority", "merkleTree", "payer", "treeCreator"], 4),
+    (
+        "mint_v1",
+        "mintV1",
+        &["treeAuthority", "leafOwner", "leafDelegate", "merkleTree", "payer", "treeDelegate"],
+        6,
+    ),
+    (
+        "mint_to_collection_v1",
+        "mintToCollectionV1",
+        &[
+            "treeAuthority",
+            "leafOwner",
+            "leafDelegate",
+            "merkleTree",
+            "payer",
+            "treeDelegate",
+            "collectionAuthority",
+        ],
+        7,
+    ),
+    (
+        "transfer",
+        "transfer",
+        &["treeAuthority", "leafOwner", "leafDelegate", "newLeafOwner", "merkleTree"],
+        5,
+    ),
+    ("burn", "burn", &["treeAuthority", "leafOwner", "leafDelegate", "merkleTree"], 4),
+    (
+        "delegate",
+        "delegate",
+        &["treeAuthority", "leafOwner", "previousLeafDelegate", "newLeafDelegate", "merkleTree"],
+        5,
+    ),
+    ("redeem", "redeem", &[], 0),
+    ("cancel_redeem", "cancelRedeem", &[], 0),
+    ("decompress_v1", "decompressV1", &[], 0),
+    ("verify_creator", "verifyCreator", &[], 0),
+    ("unverify_creator", "unverifyCreator", &[], 0),
+    ("verify_collection", "verifyCollection", &[], 0),
+    ("unverify_collection", "unverifyCollection", &[], 0),
+    ("set_and_verify_collection", "setAndVerifyCollection", &[], 0),
+    ("set_tree_delegate", "setTreeDelegate", &[], 0),
+    ("set_decompressible_state", "setDecompressibleState", &[], 0),
+    ("update_metadata", "updateMetadata", &[], 0),
+];
+
+/// Metaplex Bubblegum (compressed NFTs). Accounts are named for tree creation, minting,
+/// transfers, burns and delegation.
+pub struct BubblegumParser;
+
+impl InstructionParser for BubblegumParser {
+    fn program_id(&self) -> Pubkey {
+        BUBBLEGUM_PROGRAM_ID
+    }
+
+    fn program_name(&self) -> &'static str {
+        "Bubblegum"
+    }
+
+    fn parse(&self, instruction: &Instruction) -> Result<Option<ParsedInstruction>, KoraError> {
+        let Some(discriminator) = instruction.data.get(..ANCHOR_DISCRIMINATOR_LEN) else {
+            return Ok(None);
+        };
+
+        let Some((_, name, roles, required)) = INSTRUCTIONS.iter().find(|(rust_name, ..)| {
+            anchor_instruction_discriminator(rust_name).as_slice() == discriminator
+        }) else {
+            return Ok(None);
+        };
+
+        named_accounts(
+            ParsedInstruction::new(BUBBLEGUM_PROGRAM_ID, name),
+            instruction,
+            roles,
+            *required,
+        )
+        .map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::AccountMeta;
+
+    #[test]
+    fn test_parse_bubblegum_mint_v1() {
+        let accounts: Vec<Pubkey> = (0..9).map(|_| Pubkey::new_unique()).collect();
+        let metas = accounts.iter().map(|pubkey| AccountMeta::new(*pubkey, false)).collect();
+        let data = anchor_instruction_discriminator("mint_v1");
+
+        let instruction = Instruction::new_with_bytes(BUBBLEGUM_PROGRAM_ID, &data, metas);
+        let parsed = BubblegumParser.parse(&instruction).unwrap().unwrap();
+        assert_eq!(parsed.name, "mintV1");
+        assert_eq!(parsed.account("payer"), Some(accounts[4]));
+
+        let unknown = Instruction::new_with_bytes(BUBBLEGUM_PROGRAM_ID, &[0; 8], vec![]);
+        assert!(BubblegumParser.parse(&unknown).unwrap().is_none());
+    }
+}
@@ -0,0 +1,40 @@
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+use crate::{
+    error::KoraError,
+    transaction::parser::{InstructionParser, ParsedInstruction},
+};
+
+/// SPL Memo, v1 or v2. Every instruction is a `memo` with the UTF-8 text as the `memo`
+/// argument and each account as a `signer`.
+pub struct MemoParser {
+    program_id: Pubkey,
+}
+
+impl MemoParser {
+    pub fn new(program_id: Pubkey) -> Self {
+        Self { program_id }
+    }
+}
+
+impl InstructionParser for MemoParser {
+    fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    fn program_name(&self) -> &'static str {
+        "SPL Memo"
+    }
+
+    fn parse(&self, instruction: &Instruction) -> Result<Option<ParsedInstruction>, KoraError> {
+        let memo = std::str::from_utf8(&instruction.data).map_err(|_| {
+            KoraError::InvalidTransaction("Memo instruction data is not valid UTF-8".to_string())
+        })?;
+
+        let parsed = instruction.accounts.iter().fold(
+            ParsedInstruction::new(self.program_id, "memo").with_arg("memo", memo),
+            |parsed, meta| parsed.with_account("signer", meta.pubkey),
+        );
+        Ok(Some(parsed))
+    }
+}
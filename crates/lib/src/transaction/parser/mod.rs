@@ -0,0 +1,107 @@
+mod bubblegum;
+mod memo;
+mod registry;
+mod stake;
+mod token_metadata;
+
+pub use bubblegum::BubblegumParser;
+pub use memo::MemoParser;
+pub use registry::*;
+pub use stake::StakeParser;
+pub use token_metadata::TokenMetadataParser;
+
+use serde_json::{Map, Value};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+use crate::error::KoraError;
+
+/// Parses the instructions of a single program into a [`ParsedInstruction`].
+///
+/// Implementations are registered by program id in the [`InstructionParserRegistry`], either
+/// as a built-in or through [`register_instruction_parser`].
+pub trait InstructionParser: Send + Sync {
+    /// Program whose instructions this parser understands
+    fn program_id(&self) -> Pubkey;
+
+    /// Human-readable program name, for logs and errors
+    fn program_name(&self) -> &'static str;
+
+    /// Parse an instruction of [`Self::program_id`]. Returns `Ok(None)` for instructions the
+    /// parser does not know, and an error for malformed ones.
+    fn parse(&self, instruction: &Instruction) -> Result<Option<ParsedInstruction>, KoraError>;
+}
+
+/// An instruction of a registered program, decoded into a name, the pubkeys it involves by
+/// role and its scalar arguments
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedInstruction {
+    /// Index into `VersionedTransactionResolved::all_instructions`
+    pub instruction_index: usize,
+    pub program_id: Pubkey,
+    /// Instruction name in lowerCamelCase, e.g. `delegateStake`
+    pub name: String,
+    /// Pubkeys by role, from the account metas or the instruction data. A role can
+    /// appear more than once (e.g. memo signers).
+    pub accounts: Vec<(String, Pubkey)>,
+    /// Decoded arguments, e.g. `lamports`
+    pub args: Map<String, Value>,
+}
+
+impl ParsedInstruction {
+    pub fn new(program_id: Pubkey, name: &str) -> Self {
+        Self {
+            instruction_index: 0,
+            program_id,
+            name: name.to_string(),
+            accounts: Vec::new(),
+            args: Map::new(),
+        }
+    }
+
+    pub fn with_account(mut self, role: &str, pubkey: Pubkey) -> Self {
+        self.accounts.push((role.to_string(), pubkey));
+        self
+    }
+
+    pub fn with_arg(mut self, name: &str, value: impl Into<Value>) -> Self {
+        self.args.insert(name.to_string(), value.into());
+        self
+    }
+
+    /// First pubkey with `role`
+    pub fn account(&self, role: &str) -> Option<Pubkey> {
+        self.accounts.iter().find(|(name, _)| name == role).map(|(_, pubkey)| *pubkey)
+    }
+
+    /// Roles `pubkey` appears in
+    pub fn roles_of<'a>(&'a self, pubkey: &'a Pubkey) -> impl Iterator<Item = &'a str> + 'a {
+        self.accounts.iter().filter(move |(_, key)| key == pubkey).map(|(role, _)| role.as_str())
+    }
+
+    pub fn arg(&self, name: &str) -> Option<&Value> {
+        self.args.get(name)
+    }
+}
+
+/// Name the account metas of `instruction` by position. Missing optional accounts are
+/// skipped; fewer than `required` accounts is an error.
+pub(crate) fn named_accounts(
+    mut parsed: ParsedInstruction,
+    instruction: &Instruction,
+    roles: &[&str],
+    required: usize,
+) -> Result<ParsedInstruction, KoraError> {
+    if instruction.accounts.len() < required {
+        return Err(KoraError::InvalidTransaction(format!(
+            "{} instruction {} has {} accounts, expected at least {required}",
+            instruction.program_id,
+            parsed.name,
+            instruction.accounts.len()
+        )));
+    }
+
+    for (role, meta) in roles.iter().zip(&instruction.accounts) {
+        parsed = parsed.with_account(role, meta.pubkey);
+    }
+    Ok(parsed)
+}
@@ -0,0 +1,140 @@
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    constant::{MEMO_PROGRAM_ID, MEMO_V1_PROGRAM_ID},
+    error::KoraError,
+    transaction::parser::{
+        BubblegumParser, InstructionParser, MemoParser, ParsedInstruction, StakeParser,
+        TokenMetadataParser,
+    },
+};
+
+// Process-wide registry used when parsing resolved transactions
+static INSTRUCTION_PARSERS: Lazy<RwLock<InstructionParserRegistry>> =
+    Lazy::new(|| RwLock::new(InstructionParserRegistry::with_builtin_parsers()));
+
+/// Instruction parsers keyed by program id
+#[derive(Clone, Default)]
+pub struct InstructionParserRegistry {
+    parsers: HashMap<Pubkey, Arc<dyn InstructionParser>>,
+}
+
+impl InstructionParserRegistry {
+    /// Registry with the Memo (v1 and v2), Stake, Token Metadata and Bubblegum parsers
+    pub fn with_builtin_parsers() -> Self {
+        let mut registry = Self::default();
+        registry.register(Arc::new(MemoParser::new(MEMO_PROGRAM_ID)));
+        registry.register(Arc::new(MemoParser::new(MEMO_V1_PROGRAM_ID)));
+        registry.register(Arc::new(StakeParser));
+        registry.register(Arc::new(TokenMetadataParser));
+        registry.register(Arc::new(BubblegumParser));
+        registry
+    }
+
+    /// Register `parser` for its program, returning the parser it replaces
+    pub fn register(
+        &mut self,
+        parser: Arc<dyn InstructionParser>,
+    ) -> Option<Arc<dyn InstructionParser>> {
+        self.parsers.insert(parser.program_id(), parser)
+    }
+
+    pub fn get(&self, program_id: &Pubkey) -> Option<&Arc<dyn InstructionParser>> {
+        self.parsers.get(program_id)
+    }
+
+    pub fn contains(&self, program_id: &Pubkey) -> bool {
+        self.parsers.contains_key(program_id)
+    }
+
+    /// Parse every instruction of a registered program, in order. Instructions of other
+    /// programs and ones their parser does not know are skipped.
+    pub fn parse_instructions(
+        &self,
+        instructions: &[Instruction],
+    ) -> Result<Vec<ParsedInstruction>, KoraError> {
+        let mut parsed_instructions = Vec::new();
+        for (index, instruction) in instructions.iter().enumerate() {
+            let Some(parser) = self.parsers.get(&instruction.program_id) else {
+                continue;
+            };
+
+            if let Some(mut parsed) = parser.parse(instruction)? {
+                parsed.instruction_index = index;
+                parsed_instructions.push(parsed);
+            }
+        }
+        Ok(parsed_instructions)
+    }
+}
+
+/// Register `parser` with the process-wide registry, replacing any parser of the same program.
+/// Register before serving requests: transactions already parsed keep their cached results.
+pub fn register_instruction_parser(parser: Arc<dyn InstructionParser>) {
+    let program_id = parser.program_id();
+    if INSTRUCTION_PARSERS.write().register(parser).is_some() {
+        log::info!("Replaced the instruction parser for program {program_id}");
+    }
+}
+
+pub fn has_instruction_parser(program_id: &Pubkey) -> bool {
+    INSTRUCTION_PARSERS.read().contains(program_id)
+}
+
+/// Parse `instructions` with the process-wide registry
+pub fn parse_registered_instructions(
+    instructions: &[Instruction],
+) -> Result<Vec<ParsedInstruction>, KoraError> {
+    INSTRUCTION_PARSERS.read().parse_instructions(instructions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constant::STAKE_PROGRAM_ID;
+
+    struct CounterParser(Pubkey);
+
+    impl InstructionParser for CounterParser {
+        fn program_id(&self) -> Pubkey {
+            self.0
+        }
+
+        fn program_name(&self) -> &'static str {
+            "Counter"
+        }
+
+        fn parse(&self, instruction: &Instruction) -> Result<Option<ParsedInstruction>, KoraError> {
+            Ok(match instruction.data.first() {
+                Some(0) => Some(ParsedInstruction::new(self.0, "increment")),
+                _ => None,
+            })
+        }
+    }
+
+    #[test]
+    fn test_registry_parses_registered_programs_only() {
+        let counter = Pubkey::new_unique();
+        let mut registry = InstructionParserRegistry::with_builtin_parsers();
+        assert!(registry.contains(&STAKE_PROGRAM_ID));
+        assert!(!registry.contains(&counter));
+
+        assert!(registry.register(Arc::new(CounterParser(counter))).is_none());
+
+        let instructions = vec![
+            Instruction::new_with_bytes(Pubkey::new_unique(), &[0], vec![]),
+            Instruction::new_with_bytes(counter, &[1], vec![]),
+            Instruction::new_with_bytes(counter, &[0], vec![]),
+            Instruction::new_with_bytes(MEMO_PROGRAM_ID, b"hello", vec![]),
+        ];
+        let parsed = registry.parse_instructions(&instructions).unwrap();
+
+        assert_eq!(parsed.len(), 2);
+        assert_eq!((parsed[0].instruction_index, parsed[0].name.as_str()), (2, "increment"));
+        assert_eq!((parsed[1].instruction_index, parsed[1].name.as_str()), (3, "memo"));
+        assert_eq!(parsed[1].arg("memo").and_then(|memo| memo.as_str()), Some("hello"));
+    }
+}
@@ -0,0 +1,239 @@
+#![allow(deprecated)] // StakeInstruction::Redelegate
+
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use solana_stake_interface::{instruction::StakeInstruction, state::StakeAuthorize};
+
+use crate::{
+    constant::STAKE_PROGRAM_ID,
+    error::KoraError,
+    sanitize_error,
+    transaction::parser::{named_accounts, InstructionParser, ParsedInstruction},
+};
+
+/// Native Stake program. Account roles follow the names of the RPC `jsonParsed` encoding;
+/// authorities carried in the instruction data (`staker`, `withdrawer`, `newAuthority`,
+/// `custodian`) are included as well.
+pub struct StakeParser;
+
+fn stake_authorize_name(stake_authorize: &StakeAuthorize) -> &'static str {
+    match stake_authorize {
+        StakeAuthorize::Staker => "staker",
+        StakeAuthorize::Withdrawer => "withdrawer",
+    }
+}
+
+impl InstructionParser for StakeParser {
+    fn program_id(&self) -> Pubkey {
+        STAKE_PROGRAM_ID
+    }
+
+    fn program_name(&self) -> &'static str {
+        "Stake"
+    }
+
+    fn parse(&self, instruction: &Instruction) -> Result<Option<ParsedInstruction>, KoraError> {
+        let stake_ix =
+            bincode::deserialize::<StakeInstruction>(&instruction.data).map_err(|e| {
+                KoraError::InvalidTransaction(format!(
+                    "Failed to parse Stake instruction: {}",
+                    sanitize_error!(e)
+                ))
+            })?;
+
+        let new = |name: &str| ParsedInstruction::new(STAKE_PROGRAM_ID, name);
+        let parsed = match stake_ix {
+            StakeInstruction::Initialize(authorized, lockup) => named_accounts(
+                new("initialize")
+                    .with_account("staker", authorized.staker)
+                    .with_account("withdrawer", authorized.withdrawer)
+                    .with_account("custodian", lockup.custodian),
+                instruction,
+                &["stakeAccount", "rentSysvar"],
+                2,
+            )?,
+            StakeInstruction::Authorize(new_authority, stake_authorize) => named_accounts(
+                new("authorize")
+                    .with_account("newAuthority", new_authority)
+                    .with_arg("authorityType", stake_authorize_name(&stake_authorize)),
+                instruction,
+                &["stakeAccount", "clockSysvar", "authority", "custodian"],
+                3,
+            )?,
+            StakeInstruction::DelegateStake => named_accounts(
+                new("delegateStake"),
+                instruction,
+                &[
+                    "stakeAccount",
+                    "voteAccount",
+                    "clockSysvar",
+                    "stakeHistorySysvar",
+                    "stakeConfigAccount",
+                    "stakeAuthority",
+                ],
+                6,
+            )?,
+            StakeInstruction::Split(lamports) => named_accounts(
+                new("split").with_arg("lamports", lamports),
+                instruction,
+                &["stakeAccount", "newSplitAccount", "stakeAuthority"],
+                3,
+            )?,
+            StakeInstruction::Withdraw(lamports) => named_accounts(
+                new("withdraw").with_arg("lamports", lamports),
+                instruction,
+                &[
+                    "stakeAccount",
+                    "destination",
+                    "clockSysvar",
+                    "stakeHistorySysvar",
+                    "withdrawAuthority",
+                    "custodian",
+                ],
+                5,
+            )?,
+            StakeInstruction::Deactivate => named_accounts(
+                new("deactivate"),
+                instruction,
+                &["stakeAccount", "clockSysvar", "stakeAuthority"],
+                3,
+            )?,
+            StakeInstruction::SetLockup(lockup) => {
+                let parsed = match lockup.custodian {
+                    Some(custodian) => new("setLockup").with_account("newCustodian", custodian),
+                    None => new("setLockup"),
+                };
+                named_accounts(parsed, instruction, &["stakeAccount", "custodian"], 2)?
+            }
+            StakeInstruction::Merge => named_accounts(
+                new("merge"),
+                instruction,
+                &["destination", "source", "clockSysvar", "stakeHistorySysvar", "stakeAuthority"],
+                5,
+            )?,
+            StakeInstruction::AuthorizeWithSeed(args) => named_accounts(
+                new("authorizeWithSeed")
+                    .with_account("newAuthority", args.new_authorized_pubkey)
+                    .with_account("authorityOwner", args.authority_owner)
+                    .with_arg("authorityType", stake_authorize_name(&args.stake_authorize)),
+                instruction,
+                &["stakeAccount", "authorityBase", "clockSysvar", "custodian"],
+                3,
+            )?,
+            StakeInstruction::InitializeChecked => named_accounts(
+                new("initializeChecked"),
+                instruction,
+                &["stakeAccount", "rentSysvar", "staker", "withdrawer"],
+                4,
+            )?,
+            StakeInstruction::AuthorizeChecked(stake_authorize) => named_accounts(
+                new("authorizeChecked")
+                    .with_arg("authorityType", stake_authorize_name(&stake_authorize)),
+                instruction,
+                &["stakeAccount", "clockSysvar", "authority", "newAuthority", "custodian"],
+                4,
+            )?,
+            StakeInstruction::AuthorizeCheckedWithSeed(args) => named_accounts(
+                new("authorizeCheckedWithSeed")
+                    .with_account("authorityOwner", args.authority_owner)
+                    .with_arg("authorityType", stake_authorize_name(&args.stake_authorize)),
+                instruction,
+                &["stakeAccount", "authorityBase", "clockSysvar", "newAuthority", "custodian"],
+                4,
+            )?,
+            StakeInstruction::SetLockupChecked(_) => named_accounts(
+                new("setLockupChecked"),
+                instruction,
+                &["stakeAccount", "custodian", "newCustodian"],
+                2,
+            )?,
+            StakeInstruction::GetMinimumDelegation => new("getMinimumDelegation"),
+            StakeInstruction::DeactivateDelinquent => named_accounts(
+                new("deactivateDelinquent"),
+                instruction,
+                &["stakeAccount", "voteAccount", "referenceVoteAccount"],
+                3,
+            )?,
+            StakeInstruction::Redelegate => named_accounts(
+                new("redelegate"),
+                instruction,
+                &[
+                    "stakeAccount",
+                    "newStakeAccount",
+                    "voteAccount",
+                    "stakeConfigAccount",
+                    "stakeAuthority",
+                ],
+                5,
+            )?,
+            StakeInstruction::MoveStake(lamports) => named_accounts(
+                new("moveStake").with_arg("lamports", lamports),
+                instruction,
+                &["source", "destination", "stakeAuthority"],
+                3,
+            )?,
+            StakeInstruction::MoveLamports(lamports) => named_accounts(
+                new("moveLamports").with_arg("lamports", lamports),
+                instruction,
+                &["source", "destination", "stakeAuthority"],
+                3,
+            )?,
+        };
+        Ok(Some(parsed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_stake_interface::{
+        instruction as stake_instruction,
+        state::{Authorized, Lockup},
+    };
+
+    #[test]
+    fn test_parse_stake_instructions() {
+        let stake = Pubkey::new_unique();
+        let staker = Pubkey::new_unique();
+        let withdrawer = Pubkey::new_unique();
+        let vote = Pubkey::new_unique();
+
+        let parsed = StakeParser
+            .parse(&stake_instruction::initialize(
+                &stake,
+                &Authorized { staker, withdrawer },
+                &Lockup::default(),
+            ))
+            .unwrap()
+            .unwrap();
+        assert_eq!(parsed.name, "initialize");
+        assert_eq!(parsed.account("staker"), Some(staker));
+        assert_eq!(parsed.account("withdrawer"), Some(withdrawer));
+        assert_eq!(parsed.account("stakeAccount"), Some(stake));
+
+        let parsed = StakeParser
+            .parse(&stake_instruction::delegate_stake(&stake, &staker, &vote))
+            .unwrap()
+            .unwrap();
+        assert_eq!(parsed.name, "delegateStake");
+        assert_eq!(parsed.roles_of(&staker).collect::<Vec<_>>(), vec!["stakeAuthority"]);
+
+        let parsed = StakeParser
+            .parse(&stake_instruction::withdraw(&stake, &withdrawer, &vote, 42, None))
+            .unwrap()
+            .unwrap();
+        assert_eq!(parsed.account("withdrawAuthority"), Some(withdrawer));
+        assert_eq!(parsed.account("destination"), Some(vote));
+        assert_eq!(parsed.arg("lamports").and_then(|lamports| lamports.as_u64()), Some(42));
+    }
+
+    #[test]
+    fn test_parse_stake_instruction_missing_accounts() {
+        let mut instruction =
+            stake_instruction::deactivate_stake(&Pubkey::new_unique(), &Pubkey::new_unique());
+        instruction.accounts.truncate(2);
+        assert!(StakeParser.parse(&instruction).is_err());
+
+        let garbage = Instruction::new_with_bytes(STAKE_PROGRAM_ID, &[0xff; 3], vec![]);
+        assert!(StakeParser.parse(&garbage).is_err());
+    }
+}
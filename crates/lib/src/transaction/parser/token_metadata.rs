@@ -0,0 +1,201 @@
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+use crate::{
+    constant::TOKEN_METADATA_PROGRAM_ID,
+    error::KoraError,
+    transaction::parser::{named_accounts, InstructionParser, ParsedInstruction},
+};
+
+/// Instruction names of Metaplex Token Metadata, indexed by their 1-byte discriminator
+const INSTRUCTION_NAMES: &[&str] = &[
+    "createMetadataAccount",
+    "updateMetadataAccount",
+    "deprecatedCreateMasterEdition",
+    "deprecatedMintNewEditionFromMasterEditionViaPrintingToken",
+    "updatePrimarySaleHappenedViaToken",
+    "deprecatedSetReservationList",
+    "deprecatedCreateReservationList",
+    "signMetadata",
+    "deprecatedMintPrintingTokensViaToken",
+    "deprecatedMintPrintingTokens",
+    "createMasterEdition",
+    "mintNewEditionFromMasterEditionViaToken",
+    "convertMasterEditionV1ToV2",
+    "mintNewEditionFromMasterEditionViaVaultProxy",
+    "puffMetadata",
+    "updateMetadataAccountV2",
+    "createMetadataAccountV2",
+    "createMasterEditionV3",
+    "verifyCollection",
+    "utilize",
+    "approveUseAuthority",
+    "revokeUseAuthority",
+    "unverifyCollection",
+    "approveCollectionAuthority",
+    "revokeCollectionAuthority",
+    "setAndVerifyCollection",
+    "freezeDelegatedAccount",
+    "thawDelegatedAccount",
+    "removeCreatorVerification",
+    "burnNft",
+    "verifySizedCollectionItem",
+    "unverifySizedCollectionItem",
+    "setAndVerifySizedCollectionItem",
+    "createMetadataAccountV3",
+    "setCollectionSize",
+    "setTokenStandard",
+    "bubblegumSetCollectionSize",
+    "burnEditionNft",
+    "createEscrowAccount",
+    "closeEscrowAccount",
+    "transferOutOfEscrow",
+    "burn",
+    "create",
+    "mint",
+    "delegate",
+    "revoke",
+    "lock",
+    "unlock",
+    "migrate",
+    "transfer",
+    "update",
+    "use",
+    "verify",
+    "unverify",
+    "collect",
+    "print",
+    "resize",
+    "closeAccounts",
+];
+
+/// Metaplex Token Metadata. Every instruction is named; accounts are named for the ones
+/// that take a rent payer: metadata and edition creation, `create`, `mint`, `transfer` and
+/// `update`.
+pub struct TokenMetadataParser;
+
+impl InstructionParser for TokenMetadataParser {
+    fn program_id(&self) -> Pubkey {
+        TOKEN_METADATA_PROGRAM_ID
+    }
+
+    fn program_name(&self) -> &'static str {
+        "Token Metadata"
+    }
+
+    fn parse(&self, instruction: &Instruction) -> Result<Option<ParsedInstruction>, KoraError> {
+        let Some(name) = instruction
+            .data
+            .first()
+            .and_then(|discriminator| INSTRUCTION_NAMES.get(usize::from(*discriminator)))
+        else {
+            return Ok(None);
+        };
+
+        let parsed = ParsedInstruction::new(TOKEN_METADATA_PROGRAM_ID, name);
+        let parsed = match *name {
+            "createMetadataAccountV3" => named_accounts(
+                parsed,
+                instruction,
+                &["metadata", "mint", "mintAuthority", "payer", "updateAuthority", "systemProgram"],
+                5,
+            )?,
+            "createMasterEditionV3" => named_accounts(
+                parsed,
+                instruction,
+                &[
+                    "edition",
+                    "mint",
+                    "updateAuthority",
+                    "mintAuthority",
+                    "payer",
+                    "metadata",
+                    "tokenProgram",
+                    "systemProgram",
+                ],
+                6,
+            )?,
+            "create" => named_accounts(
+                parsed,
+                instruction,
+                &[
+                    "metadata",
+                    "masterEdition",
+                    "mint",
+                    "authority",
+                    "payer",
+                    "updateAuthority",
+                    "systemProgram",
+                    "sysvarInstructions",
+                    "splTokenProgram",
+                ],
+                5,
+            )?,
+            "mint" => named_accounts(
+                parsed,
+                instruction,
+                &[
+                    "token",
+                    "tokenOwner",
+                    "metadata",
+                    "masterEdition",
+                    "tokenRecord",
+                    "mint",
+                    "authority",
+                    "delegateRecord",
+                    "payer",
+                ],
+                9,
+            )?,
+            "transfer" => named_accounts(
+                parsed,
+                instruction,
+                &[
+                    "token",
+                    "tokenOwner",
+                    "destination",
+                    "destinationOwner",
+                    "mint",
+                    "metadata",
+                    "edition",
+                    "ownerTokenRecord",
+                    "destinationTokenRecord",
+                    "authority",
+                    "payer",
+                ],
+                11,
+            )?,
+            "update" => named_accounts(
+                parsed,
+                instruction,
+                &["authority", "delegateRecord", "token", "mint", "metadata", "edition", "payer"],
+                7,
+            )?,
+            _ => parsed,
+        };
+        Ok(Some(parsed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::AccountMeta;
+
+    #[test]
+    fn test_parse_token_metadata_instructions() {
+        let accounts: Vec<Pubkey> = (0..7).map(|_| Pubkey::new_unique()).collect();
+        let metas = accounts.iter().map(|pubkey| AccountMeta::new(*pubkey, false)).collect();
+
+        let instruction = Instruction::new_with_bytes(TOKEN_METADATA_PROGRAM_ID, &[33, 1], metas);
+        let parsed = TokenMetadataParser.parse(&instruction).unwrap().unwrap();
+        assert_eq!(parsed.name, "createMetadataAccountV3");
+        assert_eq!(parsed.account("payer"), Some(accounts[3]));
+        assert_eq!(parsed.account("updateAuthority"), Some(accounts[4]));
+
+        let instruction = Instruction::new_with_bytes(TOKEN_METADATA_PROGRAM_ID, &[49], vec![]);
+        assert!(TokenMetadataParser.parse(&instruction).is_err());
+
+        let instruction = Instruction::new_with_bytes(TOKEN_METADATA_PROGRAM_ID, &[200], vec![]);
+        assert!(TokenMetadataParser.parse(&instruction).unwrap().is_none());
+    }
+}
@@ -0,0 +1,332 @@
+use solana_client::{
+    client_error::ClientError, nonblocking::rpc_client::RpcClient,
+    rpc_config::RpcSendTransactionConfig,
+};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::VersionedTransaction};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    config::SenderConfig, metrics::SenderMetrics, nonce::NoncePoolUtil, sanitize_error,
+    state::get_background_tasks, transaction::RespondAfter,
+};
+
+use super::TransactionStatus;
+
+/// How a rebroadcast loop ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LandingOutcome {
+    /// Landed at `confirmed` commitment
+    Confirmed,
+    /// Landed with an execution error
+    Failed(String),
+    /// Blockhash expired (or durable nonce was advanced) before the transaction landed
+    Expired,
+    /// Stopped before a final answer because the server is shutting down
+    Abandoned,
+}
+
+impl LandingOutcome {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Confirmed => "confirmed",
+            Self::Failed(_) => "failed",
+            Self::Expired => "expired",
+            Self::Abandoned => "abandoned",
+        }
+    }
+
+    /// Status to record for this outcome, `None` when the loop stopped without one
+    pub fn status(&self) -> Option<(TransactionStatus, Option<String>)> {
+        match self {
+            Self::Confirmed => Some((TransactionStatus::Confirmed, None)),
+            Self::Failed(error) => Some((TransactionStatus::Failed, Some(error.clone()))),
+            Self::Expired => Some((TransactionStatus::Expired, None)),
+            Self::Abandoned => None,
+        }
+    }
+}
+
+/// Re-sends a signed transaction at a fixed interval until it is confirmed or its
+/// blockhash expires. Solana leaders drop transactions under load even while the
+/// blockhash is valid, so a single send is not enough to land reliably. A durable
+/// transaction expires once its nonce account is advanced instead.
+pub struct TransactionSender {
+    rpc_client: Arc<RpcClient>,
+    transaction: VersionedTransaction,
+    signature: Signature,
+    /// Nonce account when the transaction is durable
+    nonce_account: Option<Pubkey>,
+    interval: Duration,
+    respond_after: RespondAfter,
+    attempts: u32,
+    first_sent_at: Option<Instant>,
+}
+
+impl TransactionSender {
+    pub fn new(
+        rpc_client: Arc<RpcClient>,
+        transaction: VersionedTransaction,
+        signature: Signature,
+        config: &SenderConfig,
+        respond_after: RespondAfter,
+    ) -> Self {
+        let nonce_account = NoncePoolUtil::durable_nonce_account(&transaction.message);
+        Self {
+            rpc_client,
+            transaction,
+            signature,
+            nonce_account,
+            interval: Duration::from_millis(config.rebroadcast_interval_ms),
+            respond_after,
+            attempts: 0,
+            first_sent_at: None,
+        }
+    }
+
+    /// Broadcasts the transaction once
+    pub async fn send(&mut self, send_config: RpcSendTransactionConfig) -> Result<(), ClientError> {
+        self.attempts += 1;
+        self.first_sent_at.get_or_insert_with(Instant::now);
+
+        self.rpc_client
+            .send_transaction_with_config(&self.transaction, send_config)
+            .await
+            .map(|_| ())
+    }
+
+    /// Polls and re-sends (without preflight) every interval until the transaction
+    /// lands or its blockhash expires. Stops early once shutdown closes the
+    /// background task tracker.
+    pub async fn rebroadcast_until_landed(mut self) -> LandingOutcome {
+        let rebroadcast_config =
+            RpcSendTransactionConfig { skip_preflight: true, ..Default::default() };
+
+        let outcome = loop {
+            tokio::time::sleep(self.interval).await;
+
+            if let Some(outcome) = self.poll_landed().await {
+                break outcome;
+            }
+
+            if self.is_blockhash_expired().await {
+                // The transaction may have landed between the poll and the expiry check
+                break self.poll_landed().await.unwrap_or(LandingOutcome::Expired);
+            }
+
+            if get_background_tasks().is_closed() {
+                break LandingOutcome::Abandoned;
+            }
+
+            if let Err(e) = self.send(rebroadcast_config).await {
+                log::warn!(
+                    "Rebroadcast failed for transaction {}: {}",
+                    self.signature,
+                    sanitize_error!(e)
+                );
+            }
+        };
+
+        self.record_metrics(&outcome);
+        outcome
+    }
+
+    async fn poll_landed(&self) -> Option<LandingOutcome> {
+        let response = match self.rpc_client.get_signature_statuses(&[self.signature]).await {
+            Ok(response) => response,
+            Err(e) => {
+                log::warn!(
+                    "Failed to get signature status for {}: {}",
+                    self.signature,
+                    sanitize_error!(e)
+                );
+                return None;
+            }
+        };
+
+        let status = response.value.into_iter().next().flatten()?;
+        match status.err {
+            Some(err) => Some(LandingOutcome::Failed(err.to_string())),
+            None if status.satisfies_commitment(CommitmentConfig::confirmed()) => {
+                Some(LandingOutcome::Confirmed)
+            }
+            None => None,
+        }
+    }
+
+    async fn is_blockhash_expired(&self) -> bool {
+        let blockhash = self.transaction.message.recent_blockhash();
+        match NoncePoolUtil::is_recent_blockhash_expired(
+            &self.rpc_client,
+            blockhash,
+            self.nonce_account.as_ref(),
+        )
+        .await
+        {
+            Ok(expired) => expired,
+            Err(e) => {
+                log::warn!("Failed to check blockhash validity: {e}");
+                false
+            }
+        }
+    }
+
+    fn record_metrics(&self, outcome: &LandingOutcome) {
+        let metrics = SenderMetrics::get();
+        let respond_after = self.respond_after.as_str();
+
+        metrics
+            .send_attempts
+            .with_label_values(&[respond_after, outcome.label()])
+            .observe(self.attempts as f64);
+
+        if let (LandingOutcome::Confirmed, Some(first_sent_at)) = (outcome, self.first_sent_at) {
+            metrics
+                .landing_latency_seconds
+                .with_label_values(&[respond_after])
+                .observe(first_sent_at.elapsed().as_secs_f64());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::rpc_mock::{RpcMockBuilder, DEFAULT_LOCAL_RPC_URL};
+    use serde_json::json;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_message::{Message, VersionedMessage};
+    use std::str::FromStr;
+
+    const MOCK_SEND_SIGNATURE: &str =
+        "5j7s8Wmt6yZb8kWBBdyKVEE8Pk8z2yBV2bX4Ct4nnEzHrNmHbG8LNHKtPj8F3mJq1vE8Zk2sZf2RjNjVxNz8QdJZ";
+
+    fn create_sender(rpc_client: Arc<RpcClient>) -> TransactionSender {
+        let transaction = VersionedTransaction {
+            signatures: vec![Signature::new_unique()],
+            message: VersionedMessage::Legacy(Message::default()),
+        };
+        let signature = transaction.signatures[0];
+        let config = SenderConfig { rebroadcast_enabled: true, rebroadcast_interval_ms: 1 };
+
+        TransactionSender::new(rpc_client, transaction, signature, &config, RespondAfter::Sent)
+    }
+
+    fn signature_status(err: serde_json::Value, confirmation_status: &str) -> serde_json::Value {
+        json!({
+            "context": { "slot": 1 },
+            "value": [{
+                "slot": 1,
+                "confirmations": null,
+                "err": err,
+                "status": { "Ok": null },
+                "confirmationStatus": confirmation_status
+            }]
+        })
+    }
+
+    #[tokio::test]
+    async fn test_rebroadcast_until_landed_confirmed() {
+        let rpc_client = RpcMockBuilder::new()
+            .with_custom_mock(
+                RpcRequest::GetSignatureStatuses,
+                signature_status(serde_json::Value::Null, "confirmed"),
+            )
+            .build();
+        let sender = create_sender(rpc_client);
+
+        assert_eq!(sender.rebroadcast_until_landed().await, LandingOutcome::Confirmed);
+    }
+
+    #[tokio::test]
+    async fn test_rebroadcast_until_landed_failed() {
+        let rpc_client = RpcMockBuilder::new()
+            .with_custom_mock(
+                RpcRequest::GetSignatureStatuses,
+                signature_status(json!("AccountInUse"), "processed"),
+            )
+            .build();
+        let sender = create_sender(rpc_client);
+
+        let outcome = sender.rebroadcast_until_landed().await;
+
+        assert!(matches!(outcome, LandingOutcome::Failed(_)));
+    }
+
+    #[tokio::test]
+    async fn test_rebroadcast_until_landed_expired() {
+        let not_found = json!({ "context": { "slot": 1 }, "value": [null] });
+        // Statuses are polled twice: before and right after the expiry check
+        let rpc_client = Arc::new(RpcClient::new_mock_with_mocks_map(
+            DEFAULT_LOCAL_RPC_URL.to_string(),
+            vec![
+                (RpcRequest::GetSignatureStatuses, not_found.clone()),
+                (RpcRequest::GetSignatureStatuses, not_found),
+                (RpcRequest::IsBlockhashValid, json!({ "context": { "slot": 1 }, "value": false })),
+            ]
+            .into_iter()
+            .collect(),
+        ));
+        let sender = create_sender(rpc_client);
+
+        assert_eq!(sender.rebroadcast_until_landed().await, LandingOutcome::Expired);
+    }
+
+    #[tokio::test]
+    async fn test_send_counts_attempts() {
+        let rpc_client = RpcMockBuilder::new().with_send_transaction().build();
+        let mut sender = create_sender(rpc_client);
+        // The mocked sendTransaction response must match the transaction's signature
+        sender.signature = Signature::from_str(MOCK_SEND_SIGNATURE).unwrap();
+        sender.transaction.signatures = vec![sender.signature];
+
+        sender.send(RpcSendTransactionConfig::default()).await.unwrap();
+        sender.send(RpcSendTransactionConfig::default()).await.unwrap();
+
+        assert_eq!(sender.attempts, 2);
+        assert!(sender.first_sent_at.is_some());
+    }
+
+    #[test]
+    fn test_landing_outcome_status() {
+        assert_eq!(LandingOutcome::Confirmed.status(), Some((TransactionStatus::Confirmed, None)));
+        assert_eq!(
+            LandingOutcome::Failed("boom".to_string()).status(),
+            Some((TransactionStatus::Failed, Some("boom".to_string())))
+        );
+        assert_eq!(LandingOutcome::Expired.status(), Some((TransactionStatus::Expired, None)));
+        assert_eq!(LandingOutcome::Abandoned.status(), None);
+    }
+
+    #[tokio::test]
+    async fn test_rebroadcast_until_landed_durable_expires_once_nonce_advanced() {
+        use solana_system_interface::instruction::advance_nonce_account;
+
+        let not_found = json!({ "context": { "slot": 1 }, "value": [null] });
+        // Its nonce account is gone, so the nonce can never be used again
+        let rpc_client = Arc::new(RpcClient::new_mock_with_mocks_map(
+            DEFAULT_LOCAL_RPC_URL.to_string(),
+            vec![
+                (RpcRequest::GetSignatureStatuses, not_found.clone()),
+                (RpcRequest::GetSignatureStatuses, not_found),
+                (RpcRequest::GetAccountInfo, json!({ "context": { "slot": 1 }, "value": null })),
+            ]
+            .into_iter()
+            .collect(),
+        ));
+        let mut sender = create_sender(rpc_client);
+        let nonce_account = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        sender.transaction.message = VersionedMessage::Legacy(Message::new(
+            &[advance_nonce_account(&nonce_account, &authority)],
+            Some(&authority),
+        ));
+        sender.nonce_account = NoncePoolUtil::durable_nonce_account(&sender.transaction.message);
+
+        assert_eq!(sender.nonce_account, Some(nonce_account));
+        assert_eq!(sender.rebroadcast_until_landed().await, LandingOutcome::Expired);
+    }
+}
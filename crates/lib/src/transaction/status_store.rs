@@ -0,0 +1,321 @@
+use async_trait::async_trait;
+use deadpool_redis::{Connection, Pool};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use tokio::sync::Mutex;
+use utoipa::ToSchema;
+
+use crate::{
+    cache::CacheUtil,
+    constant::{TRANSACTION_STATUS_PRUNE_INTERVAL, TRANSACTION_STATUS_TTL},
+    error::KoraError,
+    sanitize_error,
+};
+
+const TRANSACTION_STATUS_KEY_PREFIX: &str = "kora:tx_status";
+
+/// Global transaction status store. Falls back to an in-memory store when
+/// [`init_transaction_status_store`] was never called (e.g. in unit tests).
+static TRANSACTION_STATUS_STORE: once_cell::sync::OnceCell<Arc<dyn TransactionStatusStore>> =
+    once_cell::sync::OnceCell::new();
+
+/// Lifecycle state of a transaction Kora signed and broadcasts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionStatus {
+    /// Signed, broadcast not attempted yet
+    Queued,
+    /// Accepted by the RPC node, not confirmed yet
+    Sent,
+    /// Landed on-chain at `confirmed` commitment
+    Confirmed,
+    /// Broadcast was rejected or the transaction failed on-chain
+    Failed,
+    /// Blockhash expired before the transaction landed
+    Expired,
+}
+
+impl TransactionStatus {
+    /// Whether the status can no longer change
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Confirmed | Self::Failed | Self::Expired)
+    }
+}
+
+/// Stored status of a signed transaction, keyed by its signature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionStatusRecord {
+    pub status: TransactionStatus,
+    /// Error message when `status` is `failed`
+    pub error: Option<String>,
+    /// Blockhash the transaction was signed with, used to detect expiry
+    pub recent_blockhash: String,
+    /// Nonce account of a durable transaction, which expires once the nonce is advanced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce_account: Option<String>,
+    /// Unix timestamp of the last status change
+    pub updated_at: i64,
+}
+
+impl TransactionStatusRecord {
+    pub fn new(status: TransactionStatus, recent_blockhash: String) -> Self {
+        Self {
+            status,
+            error: None,
+            recent_blockhash,
+            nonce_account: None,
+            updated_at: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    /// Returns this record for a durable transaction advancing `nonce_account`
+    pub fn with_nonce_account(self, nonce_account: Option<String>) -> Self {
+        Self { nonce_account, ..self }
+    }
+
+    /// Returns a copy of this record moved to `status`
+    pub fn with_status(&self, status: TransactionStatus, error: Option<String>) -> Self {
+        Self {
+            status,
+            error,
+            recent_blockhash: self.recent_blockhash.clone(),
+            nonce_account: self.nonce_account.clone(),
+            updated_at: chrono::Utc::now().timestamp(),
+        }
+    }
+}
+
+/// Trait for storing and retrieving transaction statuses
+#[async_trait]
+pub trait TransactionStatusStore: Send + Sync {
+    /// Store the status for a signature, replacing any previous record
+    async fn set(&self, signature: &str, record: &TransactionStatusRecord)
+        -> Result<(), KoraError>;
+
+    /// Get the status for a signature (returns None if unknown or expired)
+    async fn get(&self, signature: &str) -> Result<Option<TransactionStatusRecord>, KoraError>;
+}
+
+/// Redis-based implementation, shared across Kora instances
+pub struct RedisTransactionStatusStore {
+    pool: Pool,
+}
+
+impl RedisTransactionStatusStore {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn get_key(signature: &str) -> String {
+        format!("{TRANSACTION_STATUS_KEY_PREFIX}:{signature}")
+    }
+
+    async fn get_connection(&self) -> Result<Connection, KoraError> {
+        self.pool.get().await.map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to get Redis connection: {}",
+                e
+            )))
+        })
+    }
+}
+
+#[async_trait]
+impl TransactionStatusStore for RedisTransactionStatusStore {
+    async fn set(
+        &self,
+        signature: &str,
+        record: &TransactionStatusRecord,
+    ) -> Result<(), KoraError> {
+        let mut conn = self.get_connection().await?;
+        let serialized = serde_json::to_string(record).map_err(|e| {
+            KoraError::SerializationError(format!("Failed to serialize transaction status: {e}"))
+        })?;
+
+        conn.set_ex::<_, _, ()>(Self::get_key(signature), serialized, TRANSACTION_STATUS_TTL)
+            .await
+            .map_err(|e| {
+                KoraError::InternalServerError(sanitize_error!(format!(
+                    "Failed to store transaction status for {}: {}",
+                    signature, e
+                )))
+            })
+    }
+
+    async fn get(&self, signature: &str) -> Result<Option<TransactionStatusRecord>, KoraError> {
+        let mut conn = self.get_connection().await?;
+        let data: Option<String> = conn.get(Self::get_key(signature)).await.map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to get transaction status for {}: {}",
+                signature, e
+            )))
+        })?;
+
+        data.map(|data| {
+            serde_json::from_str(&data).map_err(|e| {
+                KoraError::SerializationError(format!(
+                    "Failed to deserialize transaction status: {e}"
+                ))
+            })
+        })
+        .transpose()
+    }
+}
+
+/// In-memory implementation, local to this Kora instance
+#[derive(Default)]
+pub struct InMemoryTransactionStatusStore {
+    data: Mutex<HashMap<String, (TransactionStatusRecord, i64)>>,
+    writes: AtomicU64,
+}
+
+impl InMemoryTransactionStatusStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TransactionStatusStore for InMemoryTransactionStatusStore {
+    async fn set(
+        &self,
+        signature: &str,
+        record: &TransactionStatusRecord,
+    ) -> Result<(), KoraError> {
+        let now = chrono::Utc::now().timestamp();
+        let mut data = self.data.lock().await;
+
+        // Evict expired records every few writes so the map stays bounded by the TTL
+        // window without scanning it on every write
+        if self
+            .writes
+            .fetch_add(1, Ordering::Relaxed)
+            .is_multiple_of(TRANSACTION_STATUS_PRUNE_INTERVAL)
+        {
+            data.retain(|_, (_, expires_at)| *expires_at > now);
+        }
+        data.insert(signature.to_string(), (record.clone(), now + TRANSACTION_STATUS_TTL as i64));
+
+        Ok(())
+    }
+
+    async fn get(&self, signature: &str) -> Result<Option<TransactionStatusRecord>, KoraError> {
+        let now = chrono::Utc::now().timestamp();
+        let data = self.data.lock().await;
+
+        Ok(data
+            .get(signature)
+            .filter(|(_, expires_at)| *expires_at > now)
+            .map(|(record, _)| record.clone()))
+    }
+}
+
+/// Initialize the global transaction status store. Uses Redis when the cache is
+/// enabled, otherwise an in-memory store. Must run after [`CacheUtil::init`].
+pub fn init_transaction_status_store() -> Result<(), KoraError> {
+    let (store, backend): (Arc<dyn TransactionStatusStore>, &str) = match CacheUtil::get_pool() {
+        Some(pool) => (Arc::new(RedisTransactionStatusStore::new(pool)), "Redis"),
+        None => (Arc::new(InMemoryTransactionStatusStore::new()), "in-memory"),
+    };
+
+    TRANSACTION_STATUS_STORE.set(store).map_err(|_| {
+        KoraError::InternalServerError("Transaction status store already initialized".to_string())
+    })?;
+
+    log::info!("Transaction status store initialized ({backend})");
+    Ok(())
+}
+
+/// Returns the global transaction status store
+pub fn get_transaction_status_store() -> Arc<dyn TransactionStatusStore> {
+    TRANSACTION_STATUS_STORE.get_or_init(|| Arc::new(InMemoryTransactionStatusStore::new())).clone()
+}
+
+/// Store a transaction status. Failures are logged, never returned: status tracking
+/// must not fail a request whose transaction was already signed.
+pub async fn record_transaction_status(signature: &str, record: &TransactionStatusRecord) {
+    if let Err(e) = get_transaction_status_store().set(signature, record).await {
+        log::warn!("Failed to record status for transaction {signature}: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_memory_store_set_and_get() {
+        let store = InMemoryTransactionStatusStore::new();
+        let record = TransactionStatusRecord::new(TransactionStatus::Queued, "hash".to_string());
+
+        store.set("sig", &record).await.unwrap();
+        assert_eq!(store.get("sig").await.unwrap(), Some(record.clone()));
+
+        let failed = record.with_status(TransactionStatus::Failed, Some("boom".to_string()));
+        store.set("sig", &failed).await.unwrap();
+
+        let stored = store.get("sig").await.unwrap().unwrap();
+        assert_eq!(stored.status, TransactionStatus::Failed);
+        assert_eq!(stored.error.as_deref(), Some("boom"));
+        assert_eq!(stored.recent_blockhash, "hash");
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_unknown_signature() {
+        let store = InMemoryTransactionStatusStore::new();
+        assert_eq!(store.get("unknown").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_expired_record() {
+        let store = InMemoryTransactionStatusStore::new();
+        let record = TransactionStatusRecord::new(TransactionStatus::Sent, "hash".to_string());
+        store.data.lock().await.insert("sig".to_string(), (record, 0));
+
+        assert_eq!(store.get("sig").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_prunes_expired_records_periodically() {
+        let store = InMemoryTransactionStatusStore::new();
+        let record = TransactionStatusRecord::new(TransactionStatus::Sent, "hash".to_string());
+        store.set("first", &record).await.unwrap();
+        store.data.lock().await.insert("expired".to_string(), (record.clone(), 0));
+
+        // Writes between prunes leave the expired entry in place
+        store.set("second", &record).await.unwrap();
+        assert!(store.data.lock().await.contains_key("expired"));
+
+        for i in 2..TRANSACTION_STATUS_PRUNE_INTERVAL {
+            store.set(&format!("sig-{i}"), &record).await.unwrap();
+        }
+        store.set("pruning", &record).await.unwrap();
+
+        let data = store.data.lock().await;
+        assert!(!data.contains_key("expired"));
+        assert!(data.contains_key("first"));
+        assert!(data.contains_key("pruning"));
+    }
+
+    #[test]
+    fn test_transaction_status_is_final() {
+        assert!(!TransactionStatus::Queued.is_final());
+        assert!(!TransactionStatus::Sent.is_final());
+        assert!(TransactionStatus::Confirmed.is_final());
+        assert!(TransactionStatus::Failed.is_final());
+        assert!(TransactionStatus::Expired.is_final());
+    }
+
+    #[test]
+    fn test_transaction_status_serialization() {
+        assert_eq!(serde_json::to_string(&TransactionStatus::Queued).unwrap(), "\"queued\"");
+        assert_eq!(serde_json::to_string(&TransactionStatus::Expired).unwrap(), "\"expired\"");
+    }
+}
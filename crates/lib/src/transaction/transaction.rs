@@ -1,5 +1,6 @@
 use solana_message::VersionedMessage;
 use solana_sdk::{
+    pubkey::Pubkey,
     signature::Signature,
     transaction::{Transaction, VersionedTransaction},
 };
@@ -32,6 +33,20 @@ impl TransactionUtil {
         })
     }
 
+    /// Programs invoked by the top-level instructions, in order of first use
+    pub fn get_program_ids(transaction: &VersionedTransaction) -> Vec<Pubkey> {
+        let account_keys = transaction.message.static_account_keys();
+        let mut program_ids = Vec::new();
+        for instruction in transaction.message.instructions() {
+            if let Some(program_id) = account_keys.get(instruction.program_id_index as usize) {
+                if !program_ids.contains(program_id) {
+                    program_ids.push(*program_id);
+                }
+            }
+        }
+        program_ids
+    }
+
     pub fn new_unsigned_versioned_transaction(message: VersionedMessage) -> VersionedTransaction {
         let num_required_signatures = message.header().num_required_signatures as usize;
         VersionedTransaction {
@@ -70,6 +85,23 @@ mod tests {
         signer::Signer as _,
     };
 
+    #[test]
+    fn test_get_program_ids() {
+        let payer = Pubkey::new_unique();
+        let first_program = Pubkey::new_unique();
+        let second_program = Pubkey::new_unique();
+        let instruction = |program_id| Instruction::new_with_bytes(program_id, &[], vec![]);
+        let message = VersionedMessage::Legacy(Message::new(
+            &[instruction(first_program), instruction(second_program), instruction(first_program)],
+            Some(&payer),
+        ));
+        let transaction = TransactionUtil::new_unsigned_versioned_transaction(message);
+
+        let program_ids = TransactionUtil::get_program_ids(&transaction);
+
+        assert_eq!(program_ids, vec![first_program, second_program]);
+    }
+
     #[test]
     fn test_decode_b64_transaction_invalid_input() {
         let result = TransactionUtil::decode_b64_transaction("not-base64!");
@@ -2,11 +2,11 @@ use async_trait::async_trait;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use serde::Deserialize;
 use solana_client::{
+    client_error::ClientError,
     nonblocking::rpc_client::RpcClient,
     rpc_config::{RpcSendTransactionConfig, RpcSimulateTransactionConfig},
 };
 use solana_commitment_config::CommitmentConfig;
-use solana_keychain::{Signer, SolanaSigner};
 use solana_message::{
     compiled_instruction::CompiledInstruction, v0::MessageAddressTableLookup, VersionedMessage,
 };
@@ -24,20 +24,28 @@ use utoipa::ToSchema;
 use solana_transaction_status_client_types::UiTransactionEncoding;
 
 use crate::{
+    anchor::{AnchorConfig, AnchorDecoder, DecodedAnchorInstruction},
     config::Config,
     error::KoraError,
     fee::fee::{FeeConfigUtil, TransactionFeeUtil},
+    idempotency::IdempotencyUtil,
     lighthouse::LighthouseUtil,
+    nonce::NoncePoolUtil,
     plugin::{PluginExecutionContext, TransactionPluginRunner},
     sanitize_error,
-    state::{get_background_tasks, get_signer_pool, reserve_request_signer_by_pubkey},
+    signer::{Signer, SolanaSigner},
+    state::{
+        config_for_signer, get_background_tasks, get_signer_pool, reserve_request_signer_by_pubkey,
+    },
     token::token::TransferHookValidationFlow,
     transaction::{
-        instruction_util::IxUtils, ParsedALTInstructionData, ParsedALTInstructionType,
+        instruction_util::IxUtils, parse_registered_instructions, record_transaction_status,
+        LandingOutcome, ParsedALTInstructionData, ParsedALTInstructionType,
         ParsedBpfLoaderUpgradeableInstructionData, ParsedBpfLoaderUpgradeableInstructionType,
-        ParsedLoaderV4InstructionData, ParsedLoaderV4InstructionType, ParsedSPLInstructionData,
-        ParsedSPLInstructionType, ParsedSystemInstructionData, ParsedSystemInstructionType,
-        Token2022SecurityInstruction, Token2022SecurityParser,
+        ParsedInstruction, ParsedLoaderV4InstructionData, ParsedLoaderV4InstructionType,
+        ParsedSPLInstructionData, ParsedSPLInstructionType, ParsedSystemInstructionData,
+        ParsedSystemInstructionType, Token2022SecurityInstruction, Token2022SecurityParser,
+        TransactionSender, TransactionStatus, TransactionStatusRecord,
     },
     validator::transaction_validator::TransactionValidator,
     CacheUtil,
@@ -83,6 +91,12 @@ pub struct VersionedTransactionResolved {
     >,
 
     parsed_token2022_security_instructions: Option<Vec<Token2022SecurityInstruction>>,
+
+    // Instructions decoded with the configured Anchor IDLs (None if not decoded yet)
+    decoded_anchor_instructions: Option<Vec<DecodedAnchorInstruction>>,
+
+    // Instructions of programs in the instruction parser registry (None if not parsed yet)
+    parsed_registered_instructions: Option<Vec<ParsedInstruction>>,
 }
 
 impl Deref for VersionedTransactionResolved {
@@ -106,10 +120,20 @@ pub enum RespondAfter {
     /// Wait only until the RPC node accepts the transaction; no confirmation wait.
     Sent,
     /// Return as soon as signing completes and broadcast in the background. Broadcast
-    /// failures are logged server-side, not returned to the caller.
+    /// failures are not returned to the caller; poll `getTransactionStatus` instead.
     Signed,
 }
 
+impl RespondAfter {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Confirmed => "confirmed",
+            Self::Sent => "sent",
+            Self::Signed => "signed",
+        }
+    }
+}
+
 #[async_trait]
 pub trait VersionedTransactionOps {
     fn encode_b64_transaction(&self) -> Result<String, KoraError>;
@@ -121,7 +145,7 @@ pub trait VersionedTransactionOps {
         &mut self,
         config: &Config,
         signer: &std::sync::Arc<Signer>,
-        rpc_client: &RpcClient,
+        rpc_client: &std::sync::Arc<RpcClient>,
         will_send: bool,
     ) -> Result<(VersionedTransaction, String), KoraError>;
     async fn sign_and_send_transaction(
@@ -151,6 +175,8 @@ impl VersionedTransactionResolved {
             parsed_loader_v4_instructions: None,
             parsed_bpf_loader_upgradeable_instructions: None,
             parsed_token2022_security_instructions: None,
+            decoded_anchor_instructions: None,
+            parsed_registered_instructions: None,
         };
 
         // 1. Resolve lookup table addresses based on transaction type
@@ -205,9 +231,51 @@ impl VersionedTransactionResolved {
             parsed_loader_v4_instructions: None,
             parsed_bpf_loader_upgradeable_instructions: None,
             parsed_token2022_security_instructions: None,
+            decoded_anchor_instructions: None,
+            parsed_registered_instructions: None,
         })
     }
 
+    /// View of this transaction restricted to a single resolved instruction, so per-instruction
+    /// checks can be attributed to an instruction index. Account keys are kept as is.
+    pub fn with_single_instruction(&self, instruction_index: usize) -> Self {
+        Self {
+            transaction: self.transaction.clone(),
+            all_account_keys: self.all_account_keys.clone(),
+            all_instructions: self
+                .all_instructions
+                .get(instruction_index)
+                .cloned()
+                .into_iter()
+                .collect(),
+            parsed_system_instructions: None,
+            parsed_spl_instructions: None,
+            parsed_alt_instructions: None,
+            parsed_loader_v4_instructions: None,
+            parsed_bpf_loader_upgradeable_instructions: None,
+            parsed_token2022_security_instructions: None,
+            decoded_anchor_instructions: None,
+            parsed_registered_instructions: None,
+        }
+    }
+
+    /// Copy of this transaction with every parsed and decoded instruction cache cleared
+    pub fn without_parsed_instructions(&self) -> Self {
+        Self {
+            transaction: self.transaction.clone(),
+            all_account_keys: self.all_account_keys.clone(),
+            all_instructions: self.all_instructions.clone(),
+            parsed_system_instructions: None,
+            parsed_spl_instructions: None,
+            parsed_alt_instructions: None,
+            parsed_loader_v4_instructions: None,
+            parsed_bpf_loader_upgradeable_instructions: None,
+            parsed_token2022_security_instructions: None,
+            decoded_anchor_instructions: None,
+            parsed_registered_instructions: None,
+        }
+    }
+
     /// Fetch inner instructions via simulation
     async fn fetch_inner_instructions(
         &mut self,
@@ -356,6 +424,38 @@ impl VersionedTransactionResolved {
             )
         })
     }
+
+    /// Instructions of programs with an IDL in `anchor`, decoded into named args and
+    /// accounts. Instructions matching none of their IDL's discriminators are left out.
+    pub fn get_or_decode_anchor_instructions(
+        &mut self,
+        anchor: &AnchorConfig,
+    ) -> Result<&Vec<DecodedAnchorInstruction>, KoraError> {
+        if self.decoded_anchor_instructions.is_none() {
+            self.decoded_anchor_instructions = Some(AnchorDecoder::decode_instructions(
+                anchor.loaded_idls(),
+                &self.all_instructions,
+            )?);
+        }
+
+        self.decoded_anchor_instructions.as_ref().ok_or_else(|| {
+            KoraError::SerializationError("Decoded Anchor instructions not found".to_string())
+        })
+    }
+
+    /// Instructions of every program with a registered [`crate::transaction::InstructionParser`]
+    pub fn get_or_parse_registered_instructions(
+        &mut self,
+    ) -> Result<&Vec<ParsedInstruction>, KoraError> {
+        if self.parsed_registered_instructions.is_none() {
+            self.parsed_registered_instructions =
+                Some(parse_registered_instructions(&self.all_instructions)?);
+        }
+
+        self.parsed_registered_instructions.as_ref().ok_or_else(|| {
+            KoraError::SerializationError("Parsed registered instructions not found".to_string())
+        })
+    }
 }
 
 // Implementation of the consolidated trait for VersionedTransactionResolved
@@ -402,10 +502,11 @@ impl VersionedTransactionOps for VersionedTransactionResolved {
         &mut self,
         config: &Config,
         selected_signer: &std::sync::Arc<Signer>,
-        rpc_client: &RpcClient,
+        rpc_client: &std::sync::Arc<RpcClient>,
         will_send: bool,
     ) -> Result<(VersionedTransaction, String), KoraError> {
         let fee_payer = selected_signer.pubkey();
+        let config = &config_for_signer(config, &fee_payer);
         let validator = TransactionValidator::new(config, fee_payer)?;
 
         // Validate transaction and accounts (already resolved)
@@ -495,10 +596,14 @@ impl VersionedTransactionOps for VersionedTransactionResolved {
         let sign_timeout = Duration::from_secs(config.kora.sign_timeout_seconds);
         let max_retries = config.kora.sign_max_retries;
         let signer = reserve_request_signer_by_pubkey(&fee_payer)?;
+        let pool = get_signer_pool()?;
+        // Wait for a slot under the signer's concurrency limit before the sign timeout
+        // starts. A busy signer is not an unhealthy one, so this is not a signing failure.
+        let _permit = pool.acquire_sign_permit(&signer, sign_timeout).await?;
         let signature =
             match sign_with_retry(sign_timeout, max_retries, "signing", "Signing", || async {
-                signer
-                    .sign_message(&message_bytes)
+                // The pool tracks the signer's latency
+                pool.sign_message(&signer, &message_bytes)
                     .await
                     .map_err(|e| KoraError::SigningError(sanitize_error!(e)))
             })
@@ -506,26 +611,13 @@ impl VersionedTransactionOps for VersionedTransactionResolved {
             {
                 Ok(sig) => {
                     // Report success to the pool for health tracking
-                    match get_signer_pool() {
-                        Ok(pool) => pool.record_signing_success(&signer),
-                        Err(e) => log::warn!(
-                            "Could not record signing success to pool: {}",
-                            sanitize_error!(e)
-                        ),
-                    }
+                    pool.record_signing_success(&signer);
                     sig
                 }
                 Err(err) => {
                     // Report failure to the pool to track signer health only after all retries are exhausted.
                     // This prevents a single failing request from immediately blacklisting a signer.
-                    match get_signer_pool() {
-                        Ok(pool) => pool.record_signing_failure(&signer),
-                        Err(pool_err) => log::error!(
-                            "Signing failed AND pool health tracking unavailable: {}; \
-                         signer failure will not be recorded, automatic failover is disabled",
-                            sanitize_error!(pool_err)
-                        ),
-                    }
+                    pool.record_signing_failure(&signer);
                     return Err(err);
                 }
             };
@@ -582,62 +674,85 @@ impl VersionedTransactionOps for VersionedTransactionResolved {
             ));
         }
 
+        // A Solana transaction is identified by its first signature, which is already
+        // present once signing completes.
+        let first_signature = *transaction.signatures.first().ok_or_else(|| {
+            KoraError::InvalidTransaction("Signed transaction has no signatures".to_string())
+        })?;
+        let signature = first_signature.to_string();
+        IdempotencyUtil::record_broadcast(&signature);
+        let queued = TransactionStatusRecord::new(
+            TransactionStatus::Queued,
+            transaction.message.recent_blockhash().to_string(),
+        )
+        .with_nonce_account(
+            NoncePoolUtil::durable_nonce_account(&transaction.message)
+                .map(|nonce_account| nonce_account.to_string()),
+        );
+
+        if config.kora.sender.rebroadcast_enabled {
+            let sender = TransactionSender::new(
+                std::sync::Arc::clone(rpc_client),
+                transaction,
+                first_signature,
+                &config.kora.sender,
+                respond_after,
+            );
+            send_with_rebroadcast(sender, signature.clone(), queued, respond_after).await?;
+            return Ok((signature, encoded));
+        }
+
         match respond_after {
             RespondAfter::Confirmed => {
-                let signature = rpc_client
-                    .send_and_confirm_transaction(&transaction)
-                    .await
-                    .map_err(|e| KoraError::RpcError(sanitize_error!(e)))?;
+                let result = rpc_client.send_and_confirm_transaction(&transaction).await;
+                record_send_result(&signature, &queued, &result, TransactionStatus::Confirmed)
+                    .await;
+                result.map_err(|e| KoraError::RpcError(sanitize_error!(e)))?;
 
-                Ok((signature.to_string(), encoded))
+                Ok((signature, encoded))
             }
             RespondAfter::Sent => {
-                let signature = rpc_client
+                let result = rpc_client
                     .send_transaction_with_config(&transaction, skip_preflight_config)
-                    .await
-                    .map_err(|e| KoraError::RpcError(sanitize_error!(e)))?;
+                    .await;
+                record_send_result(&signature, &queued, &result, TransactionStatus::Sent).await;
+                result.map_err(|e| KoraError::RpcError(sanitize_error!(e)))?;
 
-                Ok((signature.to_string(), encoded))
+                Ok((signature, encoded))
             }
             RespondAfter::Signed => {
-                // A Solana transaction is identified by its first signature, which is
-                // already present once signing completes — so the caller gets it
-                // without waiting for the broadcast.
-                let signature = transaction
-                    .signatures
-                    .first()
-                    .ok_or_else(|| {
-                        KoraError::InvalidTransaction(
-                            "Signed transaction has no signatures".to_string(),
-                        )
-                    })?
-                    .to_string();
+                // Record the queued status before responding so the caller can poll
+                // getTransactionStatus as soon as it has the signature.
+                record_transaction_status(&signature, &queued).await;
 
                 // Broadcast in the background so the response returns instantly. This
                 // mode carries ZERO delivery guarantee: the response (signature +
                 // signed transaction) is returned before the send is even attempted,
                 // so any failure — a transient RPC error, the node dropping the
                 // transaction, or the broadcast never landing on-chain — happens after
-                // the caller already has a "successful" response. Failures are only
-                // logged server-side, never returned. Callers who need delivery
-                // assurance must use Sent or Confirmed, or rebroadcast the returned
+                // the caller already has a "successful" response. Failures are logged
+                // server-side and recorded in the transaction status store, never
+                // returned. Callers who need delivery assurance must use Sent or
+                // Confirmed, or poll getTransactionStatus / rebroadcast the returned
                 // signed transaction themselves and verify it landed.
                 //
                 // The task is registered with the global tracker so a graceful
                 // shutdown drains in-flight broadcasts instead of cancelling them
                 // when the runtime exits.
                 let rpc_client = std::sync::Arc::clone(rpc_client);
-                let log_signature = signature.clone();
+                let task_signature = signature.clone();
                 get_background_tasks().spawn(async move {
-                    if let Err(e) = rpc_client
+                    let result = rpc_client
                         .send_transaction_with_config(&transaction, skip_preflight_config)
-                        .await
-                    {
+                        .await;
+                    if let Err(e) = &result {
                         log::error!(
-                            "Background broadcast failed for transaction {log_signature}: {}",
+                            "Background broadcast failed for transaction {task_signature}: {}",
                             sanitize_error!(e)
                         );
                     }
+                    record_send_result(&task_signature, &queued, &result, TransactionStatus::Sent)
+                        .await;
                 });
 
                 Ok((signature, encoded))
@@ -646,6 +761,116 @@ impl VersionedTransactionOps for VersionedTransactionResolved {
     }
 }
 
+/// Sends through a [`TransactionSender`] that keeps rebroadcasting until the transaction
+/// lands or its blockhash expires. Confirmed waits for the loop; Sent returns after the
+/// first accepted send and Signed right away, leaving the loop to a background task.
+async fn send_with_rebroadcast(
+    mut sender: TransactionSender,
+    signature: String,
+    queued: TransactionStatusRecord,
+    respond_after: RespondAfter,
+) -> Result<(), KoraError> {
+    let skip_preflight_config =
+        RpcSendTransactionConfig { skip_preflight: true, ..Default::default() };
+
+    match respond_after {
+        RespondAfter::Confirmed => {
+            // Preflight on the first send, matching send_and_confirm_transaction
+            let result = sender.send(RpcSendTransactionConfig::default()).await;
+            record_send_result(&signature, &queued, &result, TransactionStatus::Sent).await;
+            result.map_err(|e| KoraError::RpcError(sanitize_error!(e)))?;
+
+            let outcome = sender.rebroadcast_until_landed().await;
+            record_landing_outcome(&signature, &queued, &outcome).await;
+
+            match outcome {
+                LandingOutcome::Confirmed => Ok(()),
+                LandingOutcome::Failed(error) => {
+                    Err(KoraError::RpcError(format!("Transaction {signature} failed: {error}")))
+                }
+                LandingOutcome::Expired => Err(KoraError::RpcError(format!(
+                    "Transaction {signature} expired before it was confirmed"
+                ))),
+                LandingOutcome::Abandoned => Err(KoraError::RpcError(format!(
+                    "Stopped rebroadcasting transaction {signature} before it was confirmed: \
+                     server is shutting down"
+                ))),
+            }
+        }
+        RespondAfter::Sent => {
+            let result = sender.send(skip_preflight_config).await;
+            record_send_result(&signature, &queued, &result, TransactionStatus::Sent).await;
+            result.map_err(|e| KoraError::RpcError(sanitize_error!(e)))?;
+
+            get_background_tasks().spawn(async move {
+                let outcome = sender.rebroadcast_until_landed().await;
+                record_landing_outcome(&signature, &queued, &outcome).await;
+            });
+
+            Ok(())
+        }
+        RespondAfter::Signed => {
+            record_transaction_status(&signature, &queued).await;
+
+            // A failed first send is not final here: the loop keeps re-sending until
+            // the transaction lands or the blockhash expires.
+            get_background_tasks().spawn(async move {
+                match sender.send(skip_preflight_config).await {
+                    Ok(()) => {
+                        record_transaction_status(
+                            &signature,
+                            &queued.with_status(TransactionStatus::Sent, None),
+                        )
+                        .await;
+                    }
+                    Err(e) => {
+                        log::error!(
+                            "Background broadcast failed for transaction {signature}: {}",
+                            sanitize_error!(e)
+                        )
+                    }
+                }
+
+                let outcome = sender.rebroadcast_until_landed().await;
+                record_landing_outcome(&signature, &queued, &outcome).await;
+            });
+
+            Ok(())
+        }
+    }
+}
+
+async fn record_landing_outcome(
+    signature: &str,
+    queued: &TransactionStatusRecord,
+    outcome: &LandingOutcome,
+) {
+    if let Some((status, error)) = outcome.status() {
+        record_transaction_status(signature, &queued.with_status(status, error)).await;
+    }
+}
+
+/// Records the outcome of a broadcast: `on_success` when it succeeded, `failed` when the
+/// transaction itself was rejected (preflight or execution error). Any other error, such
+/// as a transport failure or a confirmation timeout, says nothing about whether the
+/// transaction lands, so it is recorded as `sent` for getTransactionStatus to resolve
+/// against the chain.
+async fn record_send_result<T>(
+    signature: &str,
+    queued: &TransactionStatusRecord,
+    result: &Result<T, ClientError>,
+    on_success: TransactionStatus,
+) {
+    let record = match result {
+        Ok(_) => queued.with_status(on_success, None),
+        Err(e) if e.get_transaction_error().is_some() => {
+            queued.with_status(TransactionStatus::Failed, Some(sanitize_error!(e)))
+        }
+        Err(_) => queued.with_status(TransactionStatus::Sent, None),
+    };
+    record_transaction_status(signature, &record).await;
+}
+
 pub struct LookupTableUtil {}
 
 impl LookupTableUtil {
@@ -737,7 +962,7 @@ mod tests {
             common::RpcMockBuilder, config_mock::mock_state::setup_config_mock,
             toml_mock::ConfigBuilder,
         },
-        transaction::TransactionUtil,
+        transaction::{get_transaction_status_store, TransactionUtil},
         Config,
     };
     use serde_json::json;
@@ -1692,4 +1917,32 @@ mod tests {
         assert_eq!(resolved_addresses[0], address1);
         assert_eq!(resolved_addresses[1], address2);
     }
+
+    #[tokio::test]
+    async fn test_record_send_result_transport_error_stays_sent() {
+        let queued = TransactionStatusRecord::new(TransactionStatus::Queued, "hash".to_string());
+        let signature = Signature::new_unique().to_string();
+        let result: Result<(), ClientError> =
+            Err(ClientError::from(std::io::Error::other("connection reset")));
+
+        record_send_result(&signature, &queued, &result, TransactionStatus::Confirmed).await;
+
+        let stored = get_transaction_status_store().get(&signature).await.unwrap().unwrap();
+        assert_eq!(stored.status, TransactionStatus::Sent);
+        assert_eq!(stored.error, None);
+    }
+
+    #[tokio::test]
+    async fn test_record_send_result_transaction_error_is_failed() {
+        let queued = TransactionStatusRecord::new(TransactionStatus::Queued, "hash".to_string());
+        let signature = Signature::new_unique().to_string();
+        let result: Result<(), ClientError> =
+            Err(ClientError::from(solana_sdk::transaction::TransactionError::AccountNotFound));
+
+        record_send_result(&signature, &queued, &result, TransactionStatus::Sent).await;
+
+        let stored = get_transaction_status_store().get(&signature).await.unwrap().unwrap();
+        assert_eq!(stored.status, TransactionStatus::Failed);
+        assert!(stored.error.is_some());
+    }
 }
@@ -8,4 +8,4 @@ pub use config::{UsageLimitConfig, UsageLimitRuleConfig};
 pub use limiter::{LimiterContext, LimiterResult};
 pub use rules::{InstructionRule, TransactionRule, UsageRule};
 pub use usage_store::{InMemoryUsageStore, RedisUsageStore, UsageStore};
-pub use usage_tracker::UsageTracker;
+pub use usage_tracker::{UsageCounter, UsageTracker};
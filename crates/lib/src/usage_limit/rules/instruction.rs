@@ -6,7 +6,10 @@ use spl_associated_token_account_interface::program::ID as ATA_PROGRAM_ID;
 
 use crate::{
     constant::{BPF_LOADER_UPGRADEABLE_PROGRAM_ID, LOADER_V4_PROGRAM_ID},
-    transaction::{ParsedSystemInstructionData, ParsedSystemInstructionType},
+    transaction::{
+        has_instruction_parser, ParsedInstruction, ParsedSystemInstructionData,
+        ParsedSystemInstructionType,
+    },
 };
 
 use super::super::limiter::LimiterContext;
@@ -52,6 +55,7 @@ const BPF_LOADER_EXTEND_PROGRAM_CHECKED: &str = "extendprogramchecked";
 /// - System: CreateAccount / CreateAccountWithSeed
 /// - ATA: CreateIdempotent / Create
 /// - Loader-v4: Write / Copy / SetProgramLength / Deploy / Retract / TransferAuthority / Finalize
+/// - Any program in the instruction parser registry, by parsed name (e.g. Stake `delegateStake`)
 #[derive(Debug)]
 pub struct InstructionRule {
     program: Pubkey,
@@ -91,6 +95,7 @@ impl InstructionRule {
         // Group rules by program ID
         let mut system_rules: Vec<(usize, &InstructionRule)> = vec![];
         let mut ata_rules: Vec<(usize, &InstructionRule)> = vec![];
+        let mut registered_rules: Vec<(usize, &InstructionRule)> = vec![];
         let mut other_rules: Vec<(usize, &InstructionRule)> = vec![];
 
         for (idx, rule) in rules.iter().enumerate() {
@@ -98,6 +103,8 @@ impl InstructionRule {
                 system_rules.push((idx, rule));
             } else if rule.program == ATA_PROGRAM_ID {
                 ata_rules.push((idx, rule));
+            } else if has_instruction_parser(&rule.program) {
+                registered_rules.push((idx, rule));
             } else {
                 other_rules.push((idx, rule));
             }
@@ -128,6 +135,20 @@ impl InstructionRule {
             Self::count_batch_manual(&ata_rules, ctx, &mut counts);
         }
 
+        // Count instructions of programs with a registered parser
+        if !registered_rules.is_empty() {
+            match ctx.transaction.get_or_parse_registered_instructions() {
+                Ok(parsed) => Self::count_batch_registered_instructions(
+                    &registered_rules,
+                    parsed,
+                    &mut counts,
+                ),
+                Err(_) => {
+                    Self::count_batch_manual(&registered_rules, ctx, &mut counts);
+                }
+            }
+        }
+
         // Count other program instructions (manual parsing)
         if !other_rules.is_empty() {
             Self::count_batch_manual(&other_rules, ctx, &mut counts);
@@ -177,6 +198,24 @@ impl InstructionRule {
         }
     }
 
+    /// Batch count instructions parsed by the instruction parser registry, matching
+    /// names case-insensitively
+    fn count_batch_registered_instructions(
+        rules: &[(usize, &InstructionRule)],
+        parsed: &[ParsedInstruction],
+        counts: &mut [u64],
+    ) {
+        for (idx, rule) in rules {
+            counts[*idx] = parsed
+                .iter()
+                .filter(|instruction| {
+                    instruction.program_id == rule.program
+                        && instruction.name.to_lowercase() == rule.instruction
+                })
+                .count() as u64;
+        }
+    }
+
     /// Batch count using manual parsing
     /// Only counts instructions where Kora is the payer (subsidized operations)
     fn count_batch_manual(
@@ -743,4 +782,34 @@ mod tests {
         };
         assert_eq!(rule.count_increment(&mut ctx), 0);
     }
+
+    #[test]
+    fn test_registered_program_counting_by_parsed_name() {
+        use crate::{constant::STAKE_PROGRAM_ID, transaction::VersionedTransactionResolved};
+        use solana_message::{Message, VersionedMessage};
+        use solana_sdk::transaction::VersionedTransaction;
+        use solana_stake_interface::instruction as stake_instruction;
+
+        let kora = Pubkey::new_unique();
+        let stake = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let instructions = [
+            stake_instruction::delegate_stake(&stake, &authority, &Pubkey::new_unique()),
+            stake_instruction::deactivate_stake(&stake, &authority),
+            stake_instruction::delegate_stake(&stake, &authority, &Pubkey::new_unique()),
+        ];
+        let message = VersionedMessage::Legacy(Message::new(&instructions, Some(&kora)));
+        let tx = VersionedTransaction { signatures: vec![], message };
+        let mut tx = VersionedTransactionResolved::from_kora_built_transaction(&tx).unwrap();
+
+        let delegate = InstructionRule::lifetime(STAKE_PROGRAM_ID, "delegateStake".to_string(), 5);
+        let withdraw = InstructionRule::lifetime(STAKE_PROGRAM_ID, "withdraw".to_string(), 5);
+        let mut ctx = LimiterContext {
+            transaction: &mut tx,
+            user_id: "user".to_string(),
+            kora_signer: Some(kora),
+            timestamp: 0,
+        };
+        assert_eq!(InstructionRule::count_all_rules(&[&delegate, &withdraw], &mut ctx), vec![2, 0]);
+    }
 }
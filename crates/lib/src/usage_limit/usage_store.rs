@@ -43,6 +43,9 @@ pub trait UsageStore: Send + Sync {
         Ok(true)
     }
 
+    /// Remove the usage count for a key
+    async fn delete(&self, key: &str) -> Result<(), KoraError>;
+
     /// Clear all usage data (mainly for testing)
     async fn clear(&self) -> Result<(), KoraError>;
 }
@@ -212,6 +215,17 @@ impl UsageStore for RedisUsageStore {
         Ok(allowed == 1)
     }
 
+    async fn delete(&self, key: &str) -> Result<(), KoraError> {
+        let mut conn = self.get_connection().await?;
+        let _: () = conn.del(key).await.map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to delete usage for {}: {}",
+                key, e
+            )))
+        })?;
+        Ok(())
+    }
+
     async fn clear(&self) -> Result<(), KoraError> {
         let mut conn = self.get_connection().await?;
         let _: () = conn.flushdb().await.map_err(|e| {
@@ -399,6 +413,17 @@ impl UsageStore for InMemoryUsageStore {
         Ok(true)
     }
 
+    async fn delete(&self, key: &str) -> Result<(), KoraError> {
+        let mut data = self.data.lock().map_err(|e| {
+            KoraError::InternalServerError(sanitize_error!(format!(
+                "Failed to lock usage store: {}",
+                e
+            )))
+        })?;
+        data.remove(key);
+        Ok(())
+    }
+
     async fn clear(&self) -> Result<(), KoraError> {
         let mut data = self.data.lock().map_err(|e| {
             KoraError::InternalServerError(sanitize_error!(format!(
@@ -477,6 +502,10 @@ impl UsageStore for ErrorUsageStore {
         }
     }
 
+    async fn delete(&self, _key: &str) -> Result<(), KoraError> {
+        Ok(())
+    }
+
     async fn clear(&self) -> Result<(), KoraError> {
         Ok(())
     }
@@ -508,6 +537,11 @@ mod tests {
         assert_eq!(store.get("wallet2").await.unwrap(), 1);
         assert_eq!(store.get("wallet1").await.unwrap(), 2);
 
+        // Delete should only reset one key
+        store.delete("wallet2").await.unwrap();
+        assert_eq!(store.get("wallet2").await.unwrap(), 0);
+        assert_eq!(store.get("wallet1").await.unwrap(), 2);
+
         // Clear should reset everything
         store.clear().await.unwrap();
         assert_eq!(store.get("wallet1").await.unwrap(), 0);
@@ -19,9 +19,11 @@ use crate::{
 };
 use deadpool_redis::Runtime;
 use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::{program_pack::Pack, pubkey::Pubkey};
 use tokio::sync::OnceCell;
+use utoipa::ToSchema;
 
 #[cfg(not(test))]
 use crate::state::get_config;
@@ -32,6 +34,17 @@ use crate::tests::config_mock::mock_state::get_config;
 /// Global usage limiter instance
 static USAGE_LIMITER: OnceCell<Option<UsageTracker>> = OnceCell::const_new();
 
+/// A user's usage of one rule in the current window
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UsageCounter {
+    pub rule: String,
+    pub rule_type: String,
+    pub count: u32,
+    pub max: u64,
+    /// None for lifetime limits
+    pub window_seconds: Option<u64>,
+}
+
 pub struct UsageTracker {
     enabled: bool,
     store: Arc<dyn UsageStore>,
@@ -73,6 +86,53 @@ impl UsageTracker {
         }
     }
 
+    fn enabled_usage_limiter() -> Result<&'static UsageTracker, KoraError> {
+        Self::get_usage_limiter()?
+            .filter(|tracker| tracker.is_enabled())
+            .ok_or_else(|| KoraError::InvalidRequest("Usage limiting is not enabled".to_string()))
+    }
+
+    /// Current-window usage of every rule for `user_id`
+    pub async fn get_usage(user_id: &str) -> Result<Vec<UsageCounter>, KoraError> {
+        Self::enabled_usage_limiter()?.usage_counters(user_id).await
+    }
+
+    /// Reset `user_id`'s current-window counters for every rule. Returns the number of
+    /// counters that were reset.
+    pub async fn reset_usage(user_id: &str) -> Result<usize, KoraError> {
+        Self::enabled_usage_limiter()?.reset_counters(user_id).await
+    }
+
+    async fn usage_counters(&self, user_id: &str) -> Result<Vec<UsageCounter>, KoraError> {
+        let timestamp = Self::current_timestamp();
+        let mut counters = Vec::with_capacity(self.rules.len());
+
+        for rule in &self.rules {
+            counters.push(UsageCounter {
+                rule: rule.description(),
+                rule_type: rule.rule_type().to_string(),
+                count: self.store.get(&rule.storage_key(user_id, timestamp)).await?,
+                max: rule.max(),
+                window_seconds: rule.window_seconds(),
+            });
+        }
+
+        Ok(counters)
+    }
+
+    async fn reset_counters(&self, user_id: &str) -> Result<usize, KoraError> {
+        let timestamp = Self::current_timestamp();
+        let keys: HashSet<String> =
+            self.rules.iter().map(|rule| rule.storage_key(user_id, timestamp)).collect();
+
+        for key in &keys {
+            self.store.delete(key).await?;
+        }
+
+        log::info!("Reset {} usage counters for user {user_id}", keys.len());
+        Ok(keys.len())
+    }
+
     fn is_enabled(&self) -> bool {
         self.enabled && !self.rules.is_empty()
     }
@@ -242,9 +302,11 @@ impl UsageTracker {
 
     /// Check and record usage for a transaction.
     /// Uses batch checking for all-or-nothing increments across multiple rules.
+    /// When `record` is false, only checks the current counts without incrementing them.
     async fn check_and_record(
         &self,
         ctx: &mut LimiterContext<'_>,
+        record: bool,
     ) -> Result<LimiterResult, KoraError> {
         if !self.is_enabled() {
             return Ok(LimiterResult::Allowed);
@@ -318,6 +380,10 @@ impl UsageTracker {
             pending_increments.push((key, increment_count, max, expiry, description));
         }
 
+        if !record {
+            return Ok(LimiterResult::Allowed);
+        }
+
         if !pending_increments.is_empty() {
             let mut unique_pending = Vec::new();
             for (key, delta, max, expiry, desc) in pending_increments {
@@ -431,6 +497,45 @@ impl UsageTracker {
         user_id: Option<&str>,
         fee_payer: &Pubkey,
         rpc_client: &RpcClient,
+    ) -> Result<(), KoraError> {
+        Self::check_transaction_usage_limit_with_record(
+            config,
+            transaction,
+            user_id,
+            fee_payer,
+            rpc_client,
+            true,
+        )
+        .await
+    }
+
+    /// Same checks as [`Self::check_transaction_usage_limit`], but does not consume any usage.
+    /// Used by dry-run validation.
+    pub async fn preview_transaction_usage_limit(
+        config: &Config,
+        transaction: &mut VersionedTransactionResolved,
+        user_id: Option<&str>,
+        fee_payer: &Pubkey,
+        rpc_client: &RpcClient,
+    ) -> Result<(), KoraError> {
+        Self::check_transaction_usage_limit_with_record(
+            config,
+            transaction,
+            user_id,
+            fee_payer,
+            rpc_client,
+            false,
+        )
+        .await
+    }
+
+    async fn check_transaction_usage_limit_with_record(
+        config: &Config,
+        transaction: &mut VersionedTransactionResolved,
+        user_id: Option<&str>,
+        fee_payer: &Pubkey,
+        rpc_client: &RpcClient,
+        record: bool,
     ) -> Result<(), KoraError> {
         // Validate user_id is provided when required
         if config.kora.usage_limit.enabled
@@ -452,7 +557,7 @@ impl UsageTracker {
             return Ok(());
         };
 
-        tracker.check_transaction(config, transaction, user_id, fee_payer, rpc_client).await
+        tracker.check_transaction(config, transaction, user_id, fee_payer, rpc_client, record).await
     }
 
     async fn check_transaction(
@@ -462,6 +567,7 @@ impl UsageTracker {
         user_id: Option<&str>,
         fee_payer: &Pubkey,
         rpc_client: &RpcClient,
+        record: bool,
     ) -> Result<(), KoraError> {
         if self.has_instruction_rules() {
             transaction.get_or_parse_system_instructions()?;
@@ -494,7 +600,7 @@ impl UsageTracker {
             timestamp: Self::current_timestamp(),
         };
 
-        match self.check_and_record(&mut ctx).await {
+        match self.check_and_record(&mut ctx, record).await {
             Ok(LimiterResult::Allowed) => Ok(()),
             Ok(LimiterResult::Denied { reason }) => Err(KoraError::UsageLimitExceeded(reason)),
             Err(e)
@@ -556,6 +662,10 @@ mod tests {
             self.inner.get(key).await
         }
 
+        async fn delete(&self, key: &str) -> Result<(), KoraError> {
+            self.inner.delete(key).await
+        }
+
         async fn clear(&self) -> Result<(), KoraError> {
             self.inner.clear().await
         }
@@ -626,19 +736,47 @@ mod tests {
 
         // First transaction should succeed
         assert!(matches!(
-            tracker.check_and_record(&mut ctx1).await.unwrap(),
+            tracker.check_and_record(&mut ctx1, true).await.unwrap(),
             LimiterResult::Allowed
         ));
 
         // Second transaction should succeed (at limit)
         assert!(matches!(
-            tracker.check_and_record(&mut ctx2).await.unwrap(),
+            tracker.check_and_record(&mut ctx2, true).await.unwrap(),
             LimiterResult::Allowed
         ));
 
         // Third transaction should fail (over limit)
         assert!(matches!(
-            tracker.check_and_record(&mut ctx3).await.unwrap(),
+            tracker.check_and_record(&mut ctx3, true).await.unwrap(),
+            LimiterResult::Denied { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_usage_limit_check_without_record_does_not_consume() {
+        let tracker = create_test_tracker(1);
+        let mut tx = create_mock_resolved_transaction();
+        let mut ctx = LimiterContext {
+            transaction: &mut tx,
+            user_id: "test-user-preview".to_string(),
+            kora_signer: None,
+            timestamp: 1000000,
+        };
+
+        for _ in 0..3 {
+            assert!(matches!(
+                tracker.check_and_record(&mut ctx, false).await.unwrap(),
+                LimiterResult::Allowed
+            ));
+        }
+
+        assert!(matches!(
+            tracker.check_and_record(&mut ctx, true).await.unwrap(),
+            LimiterResult::Allowed
+        ));
+        assert!(matches!(
+            tracker.check_and_record(&mut ctx, false).await.unwrap(),
             LimiterResult::Denied { .. }
         ));
     }
@@ -676,7 +814,7 @@ mod tests {
             timestamp: stale_timestamp,
         };
         assert!(matches!(
-            tracker.check_and_record(&mut ctx1).await.unwrap(),
+            tracker.check_and_record(&mut ctx1, true).await.unwrap(),
             LimiterResult::Allowed
         ));
 
@@ -688,7 +826,7 @@ mod tests {
             timestamp: stale_timestamp,
         };
         assert!(matches!(
-            tracker.check_and_record(&mut ctx2).await.unwrap(),
+            tracker.check_and_record(&mut ctx2, true).await.unwrap(),
             LimiterResult::Denied { .. }
         ));
     }
@@ -709,7 +847,7 @@ mod tests {
             timestamp: 1000000,
         };
         assert!(matches!(
-            tracker.check_and_record(&mut ctx1a).await.unwrap(),
+            tracker.check_and_record(&mut ctx1a, true).await.unwrap(),
             LimiterResult::Allowed
         ));
         let mut tx1b = create_mock_resolved_transaction();
@@ -720,7 +858,7 @@ mod tests {
             timestamp: 1000000,
         };
         assert!(matches!(
-            tracker.check_and_record(&mut ctx1b).await.unwrap(),
+            tracker.check_and_record(&mut ctx1b, true).await.unwrap(),
             LimiterResult::Allowed
         ));
         let mut tx1c = create_mock_resolved_transaction();
@@ -731,7 +869,7 @@ mod tests {
             timestamp: 1000000,
         };
         assert!(matches!(
-            tracker.check_and_record(&mut ctx1c).await.unwrap(),
+            tracker.check_and_record(&mut ctx1c, true).await.unwrap(),
             LimiterResult::Denied { .. }
         ));
 
@@ -744,7 +882,7 @@ mod tests {
             timestamp: 1000000,
         };
         assert!(matches!(
-            tracker.check_and_record(&mut ctx2a).await.unwrap(),
+            tracker.check_and_record(&mut ctx2a, true).await.unwrap(),
             LimiterResult::Allowed
         ));
         let mut tx2b = create_mock_resolved_transaction();
@@ -755,7 +893,7 @@ mod tests {
             timestamp: 1000000,
         };
         assert!(matches!(
-            tracker.check_and_record(&mut ctx2b).await.unwrap(),
+            tracker.check_and_record(&mut ctx2b, true).await.unwrap(),
             LimiterResult::Allowed
         ));
         let mut tx2c = create_mock_resolved_transaction();
@@ -766,7 +904,7 @@ mod tests {
             timestamp: 1000000,
         };
         assert!(matches!(
-            tracker.check_and_record(&mut ctx2c).await.unwrap(),
+            tracker.check_and_record(&mut ctx2c, true).await.unwrap(),
             LimiterResult::Denied { .. }
         ));
     }
@@ -795,7 +933,7 @@ mod tests {
                 timestamp: 1000000,
             };
             assert!(matches!(
-                tracker.check_and_record(&mut ctx).await.unwrap(),
+                tracker.check_and_record(&mut ctx, true).await.unwrap(),
                 LimiterResult::Allowed
             ));
         }
@@ -833,7 +971,7 @@ mod tests {
             timestamp: now,
         };
         assert!(matches!(
-            tracker.check_and_record(&mut ctx1).await.unwrap(),
+            tracker.check_and_record(&mut ctx1, true).await.unwrap(),
             LimiterResult::Allowed
         ));
         let mut tx2 = create_mock_resolved_transaction();
@@ -844,7 +982,7 @@ mod tests {
             timestamp: now,
         };
         assert!(matches!(
-            tracker.check_and_record(&mut ctx2).await.unwrap(),
+            tracker.check_and_record(&mut ctx2, true).await.unwrap(),
             LimiterResult::Allowed
         ));
 
@@ -857,7 +995,7 @@ mod tests {
             timestamp: now,
         };
         assert!(matches!(
-            tracker.check_and_record(&mut ctx3).await.unwrap(),
+            tracker.check_and_record(&mut ctx3, true).await.unwrap(),
             LimiterResult::Denied { .. }
         ));
     }
@@ -998,13 +1136,21 @@ mod tests {
                     Some("user-b"),
                     &signer_b.pubkey(),
                     &rpc_client,
+                    true,
                 )
                 .await
                 .unwrap_or_else(|e| panic!("request #{attempt} for signer_b should pass: {e}"));
         }
         let mut tx = build_tx();
         let err = tracker
-            .check_transaction(&config, &mut tx, Some("user-b"), &signer_b.pubkey(), &rpc_client)
+            .check_transaction(
+                &config,
+                &mut tx,
+                Some("user-b"),
+                &signer_b.pubkey(),
+                &rpc_client,
+                true,
+            )
             .await
             .expect_err("4th request for signer_b must exceed the CreateAccount limit");
         assert!(matches!(err, KoraError::UsageLimitExceeded(_)));
@@ -1020,6 +1166,7 @@ mod tests {
                     Some("user-a"),
                     &signer_a.pubkey(),
                     &rpc_client,
+                    true,
                 )
                 .await
                 .unwrap_or_else(|e| panic!("request #{attempt} for signer_a should pass: {e}"));
@@ -1311,7 +1458,7 @@ mod tests {
                     kora_signer: None,
                     timestamp: 1000000,
                 };
-                tracker.check_and_record(&mut ctx).await.unwrap()
+                tracker.check_and_record(&mut ctx, true).await.unwrap()
             }));
         }
 
@@ -1353,7 +1500,7 @@ mod tests {
                     kora_signer: None,
                     timestamp: UsageTracker::current_timestamp(),
                 };
-                tracker.check_and_record(&mut ctx).await.unwrap()
+                tracker.check_and_record(&mut ctx, true).await.unwrap()
             }));
         }
 
@@ -1392,4 +1539,31 @@ mod tests {
         assert_eq!(store.get("key1").await.unwrap(), 1);
         assert_eq!(store.get("key2").await.unwrap(), 1);
     }
+
+    #[tokio::test]
+    async fn test_usage_counters_and_reset() {
+        let tracker = create_test_tracker(5);
+        let user_id = "test-user-reset".to_string();
+
+        for _ in 0..2 {
+            let mut tx = create_mock_resolved_transaction();
+            let mut ctx = LimiterContext {
+                transaction: &mut tx,
+                user_id: user_id.clone(),
+                kora_signer: None,
+                timestamp: 0,
+            };
+            tracker.check_and_record(&mut ctx, true).await.unwrap();
+        }
+
+        let counters = tracker.usage_counters(&user_id).await.unwrap();
+        assert_eq!(counters.len(), 1);
+        assert_eq!(counters[0].rule_type, "transaction");
+        assert_eq!(counters[0].count, 2);
+        assert_eq!(counters[0].max, 5);
+        assert_eq!(counters[0].window_seconds, None);
+
+        assert_eq!(tracker.reset_counters(&user_id).await.unwrap(), 1);
+        assert_eq!(tracker.usage_counters(&user_id).await.unwrap()[0].count, 0);
+    }
 }
@@ -3,11 +3,13 @@ use std::{collections::HashSet, path::Path, str::FromStr};
 use crate::{
     admin::token_util::find_missing_atas,
     config::{
-        FeePayerPolicy, SplTokenConfig, Token2022Config, TransferHookPolicy, ValidationConfig,
+        Config, FeePayerPolicy, SplTokenConfig, Token2022Config, TransferHookPolicy,
+        ValidationConfig,
     },
     constant::{
-        BPF_LOADER_UPGRADEABLE_PROGRAM_ID, LIGHTHOUSE_PROGRAM_ID, LOADER_V4_PROGRAM_ID,
-        MAX_RECAPTCHA_SCORE, MIN_RECAPTCHA_SCORE, STAKE_PROGRAM_ID, VOTE_PROGRAM_ID,
+        BPF_LOADER_UPGRADEABLE_PROGRAM_ID, DEFAULT_PROTECTED_METHODS, LIGHTHOUSE_PROGRAM_ID,
+        LOADER_V4_PROGRAM_ID, MAX_RECAPTCHA_SCORE, MIN_RECAPTCHA_SCORE, STAKE_PROGRAM_ID,
+        VOTE_PROGRAM_ID,
     },
     fee::price::PriceModel,
     oracle::PriceSource,
@@ -265,6 +267,33 @@ impl ConfigValidator {
 
             bpf_loader_upgradeable, allow_migrate, "BPF Loader Upgradeable Migrate instructions",
                 "Users can make the fee payer migrate programs from loader-v3 to loader-v4. Authority moves to a less-validated path on this Kora";
+
+            stake, allow_initialize, "Stake Initialize instructions",
+                "Users can make the fee payer the staker or withdrawer of stake accounts. This can create unexpected control relationships";
+
+            stake, allow_authorize, "Stake Authorize instructions",
+                "Users can make the fee payer hand stake or withdraw authority to a different account, or take it on. This can lead to loss of control over staked funds";
+
+            stake, allow_delegate, "Stake DelegateStake instructions",
+                "Users can make the fee payer delegate stake accounts it is the stake authority for to arbitrary validators";
+
+            stake, allow_split, "Stake Split instructions",
+                "Users can make the fee payer split stake accounts it is the stake authority for";
+
+            stake, allow_withdraw, "Stake Withdraw instructions",
+                "Users can make the fee payer withdraw from stake accounts it is the withdraw authority for to arbitrary recipients. This can drain staked funds";
+
+            stake, allow_deactivate, "Stake Deactivate instructions",
+                "Users can make the fee payer deactivate stake accounts it is the stake authority for";
+
+            stake, allow_merge, "Stake Merge instructions",
+                "Users can make the fee payer merge stake accounts it is the stake authority for";
+
+            stake, allow_set_lockup, "Stake SetLockup instructions",
+                "Users can make the fee payer change stake lockups, locking staked funds";
+
+            stake, allow_move, "Stake MoveStake/MoveLamports instructions",
+                "Users can make the fee payer move stake and lamports between stake accounts it is the stake authority for";
         }
 
         // Check nonce policy separately (nested structure)
@@ -301,11 +330,54 @@ impl ConfigValidator {
         }
     }
 
+    /// Errors in the `validation.shadow` candidate. Its validator is built for every
+    /// transaction like the enforced one, so anything that would fail to parse is rejected
+    /// at startup.
+    fn validate_shadow_config(shadow: &ValidationConfig) -> Vec<String> {
+        let mut errors = Vec::new();
+        if shadow.shadow.is_some() {
+            errors.push("validation.shadow cannot contain another shadow config".to_string());
+        }
+
+        let pubkey_lists = [
+            ("allowed_programs", shadow.allowed_programs.as_slice()),
+            ("require_one_of_programs", shadow.require_one_of_programs.as_slice()),
+            ("allowed_tokens", shadow.allowed_tokens.as_slice()),
+            ("disallowed_accounts", shadow.disallowed_accounts.as_slice()),
+        ];
+        for (field, pubkeys) in pubkey_lists {
+            for pubkey_str in pubkeys {
+                if Pubkey::from_str(pubkey_str).is_err() {
+                    errors.push(format!(
+                        "Invalid base58 pubkey format in validation.shadow.{field}: '{pubkey_str}'"
+                    ));
+                }
+            }
+        }
+
+        for rule in &shadow.program_rules {
+            if Pubkey::from_str(&rule.program_id).is_err() {
+                errors.push(format!(
+                    "Invalid base58 pubkey format in validation.shadow.program_rules: '{}'",
+                    rule.program_id
+                ));
+            }
+            if let Err(e) = rule.discriminators() {
+                errors.push(format!("validation.shadow: {e}"));
+            }
+        }
+
+        errors.extend(
+            shadow.anchor.validate().into_iter().map(|e| format!("validation.shadow: {e}")),
+        );
+        errors
+    }
+
     /// Warn about programs in `allowed_programs` that have no dedicated fee-payer
     /// instruction parser.
     ///
     /// Two tiers:
-    /// 1. **High-risk native programs** (Vote, Stake) — these can directly control funds
+    /// 1. **High-risk native programs** (Vote) — these can directly control funds
     ///    without routing through inner System/SPL instructions, so a targeted warning is
     ///    emitted.
     /// 2. **Any other unrecognised program** — custom programs always use inner instructions
@@ -313,10 +385,7 @@ impl ConfigValidator {
     ///    those inner instructions are validated. This is a known, accepted limitation; an
     ///    informational warning is emitted so operators are aware.
     fn warn_unvalidated_programs(allowed_programs: &[String], warnings: &mut Vec<String>) {
-        let high_risk = [
-            (VOTE_PROGRAM_ID.to_string(), "Vote Program"),
-            (STAKE_PROGRAM_ID.to_string(), "Stake Program"),
-        ];
+        let high_risk = [(VOTE_PROGRAM_ID.to_string(), "Vote Program")];
 
         for (program_id, program_name) in &high_risk {
             if allowed_programs.contains(program_id) {
@@ -339,6 +408,7 @@ impl ConfigValidator {
             LIGHTHOUSE_PROGRAM_ID.to_string(),
             LOADER_V4_PROGRAM_ID.to_string(),
             BPF_LOADER_UPGRADEABLE_PROGRAM_ID.to_string(),
+            STAKE_PROGRAM_ID.to_string(),
         ]
         .into_iter()
         .chain(high_risk.iter().map(|(id, _)| id.clone()))
@@ -405,22 +475,67 @@ impl ConfigValidator {
 }
 
 impl ConfigValidator {
+    /// Checks signer policy overrides that depend on the rest of the config
+    fn validate_signer_policies(
+        config: &Config,
+        signer_config: &SignerPoolConfig,
+        errors: &mut Vec<String>,
+    ) {
+        for signer in &signer_config.signers {
+            let Some(price) = signer.policy.as_ref().and_then(|policy| policy.price.as_ref())
+            else {
+                continue;
+            };
+
+            if !matches!(price.model, PriceModel::Free)
+                && !config.validation.allowed_spl_paid_tokens.has_tokens()
+            {
+                errors.push(format!(
+                    "Signer '{}' policy charges fees, but allowed_spl_paid_tokens is empty",
+                    signer.name
+                ));
+            }
+            if let PriceModel::Fixed { token, .. } = &price.model {
+                if !config.validation.supports_token(token) {
+                    errors.push(format!(
+                        "Signer '{}' policy fixed price token is not in allowed spl paid tokens: {token}",
+                        signer.name
+                    ));
+                }
+            }
+        }
+    }
+
     pub async fn validate_with_result_and_signers<P: AsRef<Path>>(
         rpc_client: &RpcClient,
         skip_rpc_validation: bool,
         signers_config_path: Option<P>,
     ) -> Result<Vec<String>, Vec<String>> {
-        let mut errors = Vec::new();
-        let mut warnings = Vec::new();
-
         let config = match get_config() {
             Ok(c) => c,
-            Err(e) => {
-                errors.push(format!("Failed to get config: {e}"));
-                return Err(errors);
-            }
+            Err(e) => return Err(vec![format!("Failed to get config: {e}")]),
         };
 
+        Self::validate_config_with_result_and_signers(
+            &config,
+            rpc_client,
+            skip_rpc_validation,
+            signers_config_path,
+        )
+        .await
+    }
+
+    /// Validates `config` rather than the global config, e.g. a config about to be
+    /// hot-reloaded
+    pub async fn validate_config_with_result_and_signers<P: AsRef<Path>>(
+        config: &Config,
+        rpc_client: &RpcClient,
+        skip_rpc_validation: bool,
+        signers_config_path: Option<P>,
+    ) -> Result<Vec<String>, Vec<String>> {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+
         // Validate rate limit (warn if 0)
         if config.kora.rate_limit == 0 {
             warnings.push("Rate limit is set to 0 - this will block all requests".to_string());
@@ -460,6 +575,94 @@ impl ConfigValidator {
             ));
         }
 
+        // Validate sender configuration
+        if config.kora.sender.rebroadcast_enabled && config.kora.sender.rebroadcast_interval_ms == 0
+        {
+            errors.push(
+                "rebroadcast_interval_ms must be greater than 0 when rebroadcast is enabled"
+                    .to_string(),
+            );
+        }
+
+        // Validate compute budget injection
+        let compute_budget = &config.kora.compute_budget;
+        if compute_budget.priority_fee_percentile > 100 {
+            errors.push(format!(
+                "compute_budget priority_fee_percentile must be between 0 and 100, got {}",
+                compute_budget.priority_fee_percentile
+            ));
+        }
+        if compute_budget.enabled && compute_budget.max_compute_unit_price == 0 {
+            warnings.push(
+                "compute_budget max_compute_unit_price is 0 - injected transactions will not pay a priority fee"
+                    .to_string(),
+            );
+        }
+
+        // Validate nonce pool
+        let nonce_pool = &config.kora.nonce_pool;
+        if nonce_pool.enabled {
+            if nonce_pool.accounts_per_signer == 0 {
+                errors.push(
+                    "nonce_pool accounts_per_signer must be greater than 0 when the nonce pool is enabled"
+                        .to_string(),
+                );
+            }
+            if nonce_pool.lease_duration_seconds == 0 {
+                errors.push(
+                    "nonce_pool lease_duration_seconds must be greater than 0 when the nonce pool is enabled"
+                        .to_string(),
+                );
+            }
+            if !config.kora.enabled_methods.lease_nonce {
+                warnings.push(
+                    "nonce_pool is enabled but the leaseNonce method is disabled - clients cannot lease nonces"
+                        .to_string(),
+                );
+            }
+        }
+
+        // Validate batch signing
+        if config.kora.enabled_methods.sign_transactions {
+            if config.kora.batch.max_transactions == 0 {
+                errors.push(
+                    "batch max_transactions must be greater than 0 when signTransactions is enabled"
+                        .to_string(),
+                );
+            }
+            if config.kora.batch.max_concurrency == 0 {
+                errors.push(
+                    "batch max_concurrency must be greater than 0 when signTransactions is enabled"
+                        .to_string(),
+                );
+            }
+        }
+
+        // Validate RPC endpoints
+        let rpc_config = &config.kora.rpc;
+        for endpoint in &rpc_config.endpoints {
+            if !endpoint.url.starts_with("http://") && !endpoint.url.starts_with("https://") {
+                errors.push(format!(
+                    "Invalid RPC endpoint url '{}': must start with http:// or https://",
+                    endpoint.url
+                ));
+            }
+        }
+        if !rpc_config.endpoints.is_empty() {
+            if rpc_config.send_fanout == 0 {
+                errors.push("rpc send_fanout must be at least 1".to_string());
+            } else if rpc_config.send_fanout > rpc_config.endpoints.len() {
+                warnings.push(format!(
+                    "rpc send_fanout ({}) exceeds the number of RPC endpoints ({})",
+                    rpc_config.send_fanout,
+                    rpc_config.endpoints.len()
+                ));
+            }
+            if rpc_config.max_consecutive_errors == 0 {
+                errors.push("rpc max_consecutive_errors must be at least 1".to_string());
+            }
+        }
+
         let mut unique_plugins = std::collections::HashSet::new();
         for plugin in &config.kora.plugins.enabled {
             if !unique_plugins.insert(plugin.clone()) {
@@ -573,6 +776,28 @@ impl ConfigValidator {
             }
         }
 
+        // Warn about enabled signing methods that skip the reCAPTCHA check, e.g. a batch
+        // signTransactions left open while signTransaction is protected
+        if config.kora.auth.resolved_recaptcha_secret().is_some() {
+            let enabled_methods = config.kora.enabled_methods.get_enabled_method_names();
+            let protected_methods = &config.kora.auth.protected_methods;
+            let unprotected_methods: Vec<&str> = DEFAULT_PROTECTED_METHODS
+                .iter()
+                .copied()
+                .filter(|method| {
+                    enabled_methods.iter().any(|enabled| enabled == method)
+                        && !protected_methods.iter().any(|protected| protected == method)
+                })
+                .collect();
+            if !unprotected_methods.is_empty() {
+                warnings.push(format!(
+                    "reCAPTCHA is enabled but {} can sign without a reCAPTCHA check. \
+                    Add them to [kora.auth] protected_methods to require one.",
+                    unprotected_methods.join(", ")
+                ));
+            }
+        }
+
         // Validate base58 pubkey format for allowed_programs
         for pubkey_str in &config.validation.allowed_programs {
             if Pubkey::from_str(pubkey_str).is_err() {
@@ -604,6 +829,46 @@ impl ConfigValidator {
             }
         }
 
+        // Validate program_rules
+        let mut ruled_programs = HashSet::new();
+        for rule in &config.validation.program_rules {
+            let program_id = &rule.program_id;
+            if Pubkey::from_str(program_id).is_err() {
+                errors
+                    .push(format!("Invalid base58 pubkey format in program_rules: '{program_id}'"));
+            }
+            if !ruled_programs.insert(program_id.as_str()) {
+                errors.push(format!("Program {program_id} has more than one program_rules entry"));
+            }
+            if !config.validation.allowed_programs.contains(program_id) {
+                errors.push(format!(
+                    "Program {program_id} in program_rules must also be in allowed_programs"
+                ));
+            }
+            match rule.discriminators() {
+                Ok(discriminators) if discriminators.is_empty() => errors.push(format!(
+                    "program_rules for {program_id} must allow at least one discriminator or Anchor instruction"
+                )),
+                Ok(_) => {}
+                Err(e) => errors.push(e.to_string()),
+            }
+        }
+
+        // Validate Anchor constraints against the loaded IDLs
+        errors.extend(config.validation.anchor.validate());
+
+        // Validate the shadow candidate config
+        if let Some(shadow) = config.validation.shadow.as_deref() {
+            errors.extend(Self::validate_shadow_config(shadow));
+            if !config.metrics.enabled {
+                warnings.push(
+                    "validation.shadow is set but metrics are disabled - shadow rejections \
+                     will only be logged"
+                        .to_string(),
+                );
+            }
+        }
+
         // Validate allowed tokens. Only required when the price model actually charges fees;
         // a Free-pricing operator (e.g. a devnet-deploy paymaster) has no reason to maintain
         // an allowlist since no SPL token is ever used for payment.
@@ -766,6 +1031,15 @@ impl ConfigValidator {
                 Consider enabling api_key or hmac_secret in [kora.auth]."
                     .to_string(),
             );
+
+            // Anyone could make the server re-read its config files and rebuild its signers
+            if config.kora.enabled_methods.reload_config {
+                errors.push(
+                    "reloadConfig is enabled without authentication. \
+                    Configure api_key or hmac_secret in [kora.auth] or disable reload_config."
+                        .to_string(),
+                );
+            }
         }
 
         // The running server resolves auth env-first, so a stale KORA_* environment variable
@@ -778,6 +1052,39 @@ impl ConfigValidator {
             ));
         }
 
+        // Validate admin server
+        let admin = &config.kora.admin;
+        if admin.enabled {
+            match admin.resolved_api_key() {
+                None => errors.push(
+                    "kora.admin is enabled without an api_key. Set api_key in [kora.admin] \
+                     or the KORA_ADMIN_API_KEY environment variable."
+                        .to_string(),
+                ),
+                Some(admin_key) => {
+                    if config.kora.auth.resolved_api_key().as_deref() == Some(admin_key.as_str()) {
+                        warnings.push(
+                            "⚠️  SECURITY: kora.admin api_key is the same as the public api_key - \
+                             every client can call the admin server. Use a separate admin key."
+                                .to_string(),
+                        );
+                    }
+                }
+            }
+            if admin.bind_address.parse::<std::net::IpAddr>().is_err() {
+                errors.push(format!(
+                    "Invalid kora.admin bind_address '{}': expected an IP address",
+                    admin.bind_address
+                ));
+            }
+            if config.metrics.enabled && admin.port == config.metrics.port {
+                errors.push(format!(
+                    "kora.admin port {} is already used by the metrics server",
+                    admin.port
+                ));
+            }
+        }
+
         // Validate usage limit configuration
         let usage_config = &config.kora.usage_limit;
         if usage_config.enabled {
@@ -898,6 +1205,19 @@ impl ConfigValidator {
                         SignerValidator::validate_with_result(&signer_config);
                     warnings.extend(signer_warnings);
                     errors.extend(signer_errors);
+                    Self::validate_signer_policies(config, &signer_config, &mut errors);
+
+                    // Balances are only polled by the fee payer balance tracker
+                    let balance_tracking =
+                        config.metrics.enabled && config.metrics.fee_payer_balance.enabled;
+                    if signer_config.signer_pool.uses_signer_balances() && !balance_tracking {
+                        errors.push(
+                            "signer_pool uses signer balances (balance_aware strategy or \
+                             min_balance_lamports) but balance tracking is disabled. Enable \
+                             [metrics] and [metrics.fee_payer_balance]."
+                                .to_string(),
+                        );
+                    }
                 }
                 Err(e) => {
                     errors.push(format!("Failed to load signers config: {e}"));
@@ -989,11 +1309,12 @@ fn validate_token2022_extensions(
 mod tests {
     use crate::{
         config::{
-            AuthConfig, BundleConfig, CacheConfig, Config, EnabledMethods, FeePayerPolicy,
-            KoraConfig, LighthouseConfig, MetricsConfig, NonceInstructionPolicy, PluginsConfig,
-            ProgramsConfig, SplTokenConfig, SplTokenInstructionPolicy, SystemInstructionPolicy,
-            Token2022InstructionPolicy, TransactionPluginType, TransferHookPolicy,
-            UsageLimitConfig, ValidationConfig,
+            AdminConfig, AnchorConfig, AuthConfig, BatchConfig, BundleConfig, CacheConfig,
+            ComputeBudgetConfig, Config, EnabledMethods, FeePayerPolicy, KoraConfig,
+            LighthouseConfig, MetricsConfig, NonceInstructionPolicy, NoncePoolConfig,
+            PluginsConfig, ProgramRule, ProgramsConfig, RpcConfig, SenderConfig, SplTokenConfig,
+            SplTokenInstructionPolicy, SystemInstructionPolicy, Token2022InstructionPolicy,
+            TransactionPluginType, TransferHookPolicy, UsageLimitConfig, ValidationConfig,
         },
         constant::{DEFAULT_MAX_REQUEST_BODY_SIZE, LIGHTHOUSE_PROGRAM_ID},
         fee::price::PriceConfig,
@@ -1032,6 +1353,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -1079,6 +1403,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -1122,6 +1449,9 @@ mod tests {
             allow_durable_transactions: false,
             max_price_staleness_slots: 0,
             require_one_of_programs: vec![],
+            program_rules: vec![],
+            anchor: AnchorConfig::default(),
+            shadow: None,
             cross_cluster_check: false,
             cross_cluster_endpoints: vec![],
         }
@@ -1215,6 +1545,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -1265,6 +1598,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -1317,6 +1653,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -1366,6 +1705,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -1404,6 +1746,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -1415,11 +1760,17 @@ mod tests {
                     estimate_transaction_fee: false,
                     get_supported_tokens: false,
                     sign_transaction: false,
+                    sign_transactions: false,
                     sign_and_send_transaction: false,
                     transfer_transaction: false,
+                    validate_transaction: false,
+                    reload_config: false,
                     get_blockhash: false,
                     get_config: false,
                     get_payer_signer: false,
+                    get_payment_instruction: false,
+                    get_transaction_status: false,
+                    lease_nonce: false,
                     get_version: false,
                     estimate_bundle_fee: false,
                     sign_and_send_bundle: false,
@@ -1432,6 +1783,12 @@ mod tests {
                 plugins: PluginsConfig::default(),
                 bundle: BundleConfig::default(),
                 lighthouse: LighthouseConfig::default(),
+                sender: SenderConfig::default(),
+                rpc: RpcConfig::default(),
+                compute_budget: ComputeBudgetConfig::default(),
+                nonce_pool: NoncePoolConfig::default(),
+                batch: BatchConfig::default(),
+                admin: AdminConfig::default(),
                 force_sig_verify: false,
                 sign_timeout_seconds: 10,
                 sign_max_retries: 2,
@@ -1576,6 +1933,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -1617,6 +1977,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -1657,6 +2020,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -1719,6 +2085,61 @@ mod tests {
         }));
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_with_result_invalid_program_rules() {
+        let mut config = ConfigMockBuilder::new().build();
+        config.kora.cache.enabled = false;
+        let unlisted_program = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+        config.validation.program_rules = vec![
+            ProgramRule {
+                program_id: SYSTEM_PROGRAM_ID.to_string(),
+                allowed_discriminators: vec!["not-hex".to_string()],
+                allowed_anchor_instructions: vec![],
+            },
+            ProgramRule {
+                program_id: unlisted_program.clone(),
+                allowed_discriminators: vec![],
+                allowed_anchor_instructions: vec![],
+            },
+        ];
+
+        let _ = update_config(config);
+
+        let rpc_client = RpcMockBuilder::new().build();
+        let result = ConfigValidator::validate_with_result(&rpc_client, true).await;
+        let errors = result.unwrap_err();
+
+        assert!(errors.iter().any(|e| e.contains("Invalid discriminator 'not-hex'")));
+        assert!(errors.iter().any(|e| {
+            e.contains("program_rules must also be in allowed_programs")
+                && e.contains(&unlisted_program)
+        }));
+        assert!(errors.iter().any(|e| e.contains("must allow at least one discriminator")));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_with_result_invalid_shadow_config() {
+        let mut config = ConfigMockBuilder::new().build();
+        config.kora.cache.enabled = false;
+        let mut shadow = config.validation.clone();
+        shadow.disallowed_accounts = vec!["not-a-pubkey".to_string()];
+        shadow.shadow = Some(Box::new(config.validation.clone()));
+        config.validation.shadow = Some(Box::new(shadow));
+
+        let _ = update_config(config);
+
+        let rpc_client = RpcMockBuilder::new().build();
+        let result = ConfigValidator::validate_with_result(&rpc_client, true).await;
+        let errors = result.unwrap_err();
+
+        assert!(errors.iter().any(|e| e.contains(
+            "Invalid base58 pubkey format in validation.shadow.disallowed_accounts: 'not-a-pubkey'"
+        )));
+        assert!(errors.iter().any(|e| e.contains("cannot contain another shadow config")));
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_validate_with_result_require_one_of_programs_allows_compute_budget_program() {
@@ -1775,6 +2196,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -1824,6 +2248,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -1870,6 +2297,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -1925,6 +2355,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -1965,6 +2398,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -2010,6 +2446,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -2053,6 +2492,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -2089,6 +2531,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -2114,6 +2559,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -2182,6 +2630,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -2219,6 +2670,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -2256,6 +2710,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -2299,6 +2756,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -2338,6 +2798,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -2381,6 +2844,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -2567,12 +3033,26 @@ mod tests {
                         allow_transfer_authority: true,
                         allow_finalize: true,
                     },
+                    stake: crate::config::StakeInstructionPolicy {
+                        allow_initialize: true,
+                        allow_authorize: true,
+                        allow_delegate: true,
+                        allow_split: true,
+                        allow_withdraw: true,
+                        allow_deactivate: true,
+                        allow_merge: true,
+                        allow_set_lockup: true,
+                        allow_move: true,
+                    },
                 },
                 price: PriceConfig { model: PriceModel::Free },
                 token_2022: Token2022Config::default(),
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -2893,6 +3373,9 @@ mod tests {
                 allow_durable_transactions: true, // Enabled - should warn
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -2935,6 +3418,9 @@ mod tests {
                 allow_durable_transactions: false, // Disabled - should not warn
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -2978,6 +3464,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -3020,6 +3509,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -3070,6 +3562,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -3120,6 +3615,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -3149,7 +3647,7 @@ mod tests {
     async fn test_validate_sign_timeout_zero() {
         let config = crate::tests::config_mock::ConfigMockBuilder::new().build();
         let _ = crate::state::update_config(config);
-        let mut config = crate::state::get_config().unwrap().clone();
+        let mut config = crate::state::get_config().unwrap().as_ref().clone();
         config.kora.sign_timeout_seconds = 0;
         crate::state::update_config(config.clone()).unwrap();
 
@@ -3163,6 +3661,216 @@ mod tests {
             .any(|e| e.contains("sign_timeout_seconds must be at least 1 second")));
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_rebroadcast_interval_zero() {
+        let config = crate::tests::config_mock::ConfigMockBuilder::new().build();
+        let _ = crate::state::update_config(config);
+        let mut config = crate::state::get_config().unwrap().as_ref().clone();
+        config.kora.sender.rebroadcast_enabled = true;
+        config.kora.sender.rebroadcast_interval_ms = 0;
+        crate::state::update_config(config.clone()).unwrap();
+
+        let rpc_client = crate::tests::rpc_mock::RpcMockBuilder::new().build();
+        let result = ConfigValidator::validate_with_result(&rpc_client, true).await;
+
+        assert!(result.is_err());
+        let errors = result.err().unwrap();
+        assert!(errors
+            .iter()
+            .any(|e| e.contains("rebroadcast_interval_ms must be greater than 0")));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_compute_budget_percentile() {
+        let config = crate::tests::config_mock::ConfigMockBuilder::new().build();
+        let _ = crate::state::update_config(config);
+        let mut config = crate::state::get_config().unwrap().as_ref().clone();
+        config.kora.compute_budget = ComputeBudgetConfig {
+            enabled: true,
+            priority_fee_percentile: 101,
+            ..Default::default()
+        };
+        crate::state::update_config(config.clone()).unwrap();
+
+        let rpc_client = crate::tests::rpc_mock::RpcMockBuilder::new().build();
+        let result = ConfigValidator::validate_with_result(&rpc_client, true).await;
+
+        let errors = result.err().unwrap();
+        assert!(errors
+            .iter()
+            .any(|e| e.contains("priority_fee_percentile must be between 0 and 100")));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_nonce_pool() {
+        let config = crate::tests::config_mock::ConfigMockBuilder::new().build();
+        let _ = crate::state::update_config(config);
+        let mut config = crate::state::get_config().unwrap().as_ref().clone();
+        config.kora.nonce_pool =
+            NoncePoolConfig { enabled: true, accounts_per_signer: 0, lease_duration_seconds: 0 };
+        crate::state::update_config(config.clone()).unwrap();
+
+        let rpc_client = crate::tests::rpc_mock::RpcMockBuilder::new().build();
+        let result = ConfigValidator::validate_with_result(&rpc_client, true).await;
+
+        let errors = result.err().unwrap();
+        assert!(errors.iter().any(|e| e.contains("accounts_per_signer must be greater than 0")));
+        assert!(errors.iter().any(|e| e.contains("lease_duration_seconds must be greater than 0")));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_reload_config_requires_auth() {
+        std::env::remove_var("KORA_API_KEY");
+        std::env::remove_var("KORA_HMAC_SECRET");
+        let mut config = crate::tests::config_mock::ConfigMockBuilder::new().build();
+        config.kora.enabled_methods.reload_config = true;
+        let rpc_client = crate::tests::rpc_mock::RpcMockBuilder::new().build();
+        let reload_error = |errors: &[String]| {
+            errors.iter().any(|e| e.contains("reloadConfig is enabled without authentication"))
+        };
+
+        let result = ConfigValidator::validate_config_with_result_and_signers(
+            &config,
+            &rpc_client,
+            true,
+            None::<&Path>,
+        )
+        .await;
+        assert!(reload_error(&result.err().unwrap()));
+
+        config.kora.auth.api_key = Some("admin-key".to_string());
+        let result = ConfigValidator::validate_config_with_result_and_signers(
+            &config,
+            &rpc_client,
+            true,
+            None::<&Path>,
+        )
+        .await;
+        assert!(!result.err().is_some_and(|errors| reload_error(&errors)));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_batch_signing_config() {
+        std::env::remove_var("KORA_RECAPTCHA_SECRET");
+        let mut config = crate::tests::config_mock::ConfigMockBuilder::new().build();
+        config.kora.cache.enabled = false;
+        config.kora.enabled_methods.sign_transactions = true;
+        config.kora.batch.max_concurrency = 0;
+        let rpc_client = crate::tests::rpc_mock::RpcMockBuilder::new().build();
+
+        let result = ConfigValidator::validate_config_with_result_and_signers(
+            &config,
+            &rpc_client,
+            true,
+            None::<&Path>,
+        )
+        .await;
+        let errors = result.err().unwrap();
+        assert!(errors.iter().any(|e| e.contains("batch max_concurrency must be greater than 0")));
+
+        // signTransaction requires reCAPTCHA but the batch method would bypass it
+        config.kora.batch.max_concurrency = 1;
+        config.kora.auth.recaptcha_secret = Some("recaptcha-secret".to_string());
+        config.kora.auth.protected_methods = vec!["signTransaction".to_string()];
+        let result = ConfigValidator::validate_config_with_result_and_signers(
+            &config,
+            &rpc_client,
+            true,
+            None::<&Path>,
+        )
+        .await;
+        let warnings = result.unwrap();
+        assert!(warnings.iter().any(|w| {
+            w.contains("can sign without a reCAPTCHA check") && w.contains("signTransactions")
+        }));
+
+        config.kora.auth.protected_methods =
+            DEFAULT_PROTECTED_METHODS.iter().map(|method| method.to_string()).collect();
+        let result = ConfigValidator::validate_config_with_result_and_signers(
+            &config,
+            &rpc_client,
+            true,
+            None::<&Path>,
+        )
+        .await;
+        let warnings = result.unwrap();
+        assert!(!warnings.iter().any(|w| w.contains("can sign without a reCAPTCHA check")));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_admin_config() {
+        std::env::remove_var("KORA_API_KEY");
+        std::env::remove_var("KORA_ADMIN_API_KEY");
+        let mut config = crate::tests::config_mock::ConfigMockBuilder::new().build();
+        config.kora.admin.enabled = true;
+        config.metrics.enabled = true;
+        config.kora.admin.port = config.metrics.port;
+        config.kora.admin.bind_address = "not-an-ip".to_string();
+        let rpc_client = crate::tests::rpc_mock::RpcMockBuilder::new().build();
+
+        let result = ConfigValidator::validate_config_with_result_and_signers(
+            &config,
+            &rpc_client,
+            true,
+            None::<&Path>,
+        )
+        .await;
+        let errors = result.err().unwrap();
+        assert!(errors.iter().any(|e| e.contains("kora.admin is enabled without an api_key")));
+        assert!(errors.iter().any(|e| e.contains("already used by the metrics server")));
+        assert!(errors.iter().any(|e| e.contains("Invalid kora.admin bind_address 'not-an-ip'")));
+
+        config.kora.admin.bind_address = crate::constant::DEFAULT_ADMIN_BIND_ADDRESS.to_string();
+        config.metrics.enabled = false;
+        config.kora.cache.enabled = false;
+        config.kora.auth.api_key = Some("shared-key".to_string());
+        config.kora.admin.api_key = Some("shared-key".to_string());
+        let result = ConfigValidator::validate_config_with_result_and_signers(
+            &config,
+            &rpc_client,
+            true,
+            None::<&Path>,
+        )
+        .await;
+        let warnings = result.unwrap();
+        assert!(warnings.iter().any(|w| w.contains("same as the public api_key")));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_rpc_endpoints() {
+        let config = crate::tests::config_mock::ConfigMockBuilder::new().build();
+        let _ = crate::state::update_config(config);
+        let mut config = crate::state::get_config().unwrap().as_ref().clone();
+        config.kora.rpc = RpcConfig {
+            endpoints: vec![
+                crate::config::RpcEndpointConfig { url: "localhost:8899".to_string(), priority: 0 },
+                crate::config::RpcEndpointConfig {
+                    url: "https://api.mainnet-beta.solana.com".to_string(),
+                    priority: 1,
+                },
+            ],
+            send_fanout: 0,
+            max_consecutive_errors: 0,
+            reprobe_interval_seconds: 30,
+        };
+        crate::state::update_config(config.clone()).unwrap();
+
+        let rpc_client = crate::tests::rpc_mock::RpcMockBuilder::new().build();
+        let result = ConfigValidator::validate_with_result(&rpc_client, true).await;
+
+        let errors = result.err().unwrap();
+        assert!(errors.iter().any(|e| e.contains("Invalid RPC endpoint url 'localhost:8899'")));
+        assert!(errors.iter().any(|e| e.contains("rpc send_fanout must be at least 1")));
+        assert!(errors.iter().any(|e| e.contains("rpc max_consecutive_errors must be at least 1")));
+    }
+
     // --- warn_unvalidated_programs ---
 
     #[test]
@@ -3226,13 +3934,13 @@ mod tests {
     }
 
     #[test]
-    fn test_warn_unvalidated_programs_warns_for_stake_program() {
+    fn test_warn_unvalidated_programs_no_warning_for_stake_program() {
+        // The Stake program is covered by `StakeInstructionPolicy`
         use crate::constant::STAKE_PROGRAM_ID;
         let allowed = vec![SYSTEM_PROGRAM_ID.to_string(), STAKE_PROGRAM_ID.to_string()];
         let mut warnings = Vec::new();
         ConfigValidator::warn_unvalidated_programs(&allowed, &mut warnings);
-        assert_eq!(warnings.len(), 1);
-        assert!(warnings[0].contains("Stake Program"));
+        assert!(warnings.is_empty(), "Stake has a parser; no warning expected. got: {warnings:?}");
     }
 
     #[test]
@@ -3302,6 +4010,9 @@ mod tests {
                 allow_durable_transactions: false,
                 max_price_staleness_slots: 0,
                 require_one_of_programs: vec![],
+                program_rules: vec![],
+                anchor: AnchorConfig::default(),
+                shadow: None,
                 cross_cluster_check: false,
                 cross_cluster_endpoints: vec![],
             },
@@ -3364,4 +4075,57 @@ http_config = { request_timeout_secs = 0, connect_timeout_secs = 0 }
             "connect_timeout_secs must be greater than 0 for signer 'turnkey_signer_invalid'"
         )));
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_balance_aware_signers_require_balance_tracking() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let toml_content = r#"
+[signer_pool]
+strategy = "balance_aware"
+min_balance_lamports = 10000000
+
+[[signers]]
+name = "memory_signer"
+type = "memory"
+private_key_env = "KORA_TEST_BALANCE_AWARE_KEY"
+"#;
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(toml_content.as_bytes()).unwrap();
+        temp_file.flush().unwrap();
+
+        std::env::set_var(
+            "KORA_TEST_BALANCE_AWARE_KEY",
+            solana_sdk::signature::Keypair::new().to_base58_string(),
+        );
+        let mut config = crate::tests::config_mock::ConfigMockBuilder::new().build();
+        config.kora.cache.enabled = false;
+        let rpc_client = crate::tests::rpc_mock::RpcMockBuilder::new().build();
+        let balance_error =
+            |errors: &[String]| errors.iter().any(|e| e.contains("balance tracking is disabled"));
+
+        let result = ConfigValidator::validate_config_with_result_and_signers(
+            &config,
+            &rpc_client,
+            true,
+            Some(temp_file.path()),
+        )
+        .await;
+        let errors = result.unwrap_err();
+        assert!(balance_error(&errors), "{errors:?}");
+
+        config.metrics.enabled = true;
+        config.metrics.fee_payer_balance.enabled = true;
+        let result = ConfigValidator::validate_config_with_result_and_signers(
+            &config,
+            &rpc_client,
+            true,
+            Some(temp_file.path()),
+        )
+        .await;
+        std::env::remove_var("KORA_TEST_BALANCE_AWARE_KEY");
+        assert!(!result.err().is_some_and(|errors| balance_error(&errors)));
+    }
 }
@@ -152,6 +152,26 @@ macro_rules! validate_loader_v4 {
     };
 }
 
+/// Macro to validate Stake instructions parsed by `StakeParser`. The fee payer is in use
+/// when it appears in any role except `destination`, which only receives lamports.
+macro_rules! validate_stake {
+    ($self:expr, $instructions:expr, [$($name:literal),+ $(,)?], $policy:expr, $label:expr) => {
+        for instruction in $instructions.iter().filter(|instruction| {
+            instruction.program_id == STAKE_PROGRAM_ID
+                && [$($name),+].contains(&instruction.name.as_str())
+        }) {
+            if !$policy
+                && instruction.roles_of(&$self.fee_payer_pubkey).any(|role| role != "destination")
+            {
+                return Err(KoraError::InvalidTransaction(format!(
+                    "Fee payer cannot be used for '{}'",
+                    $label
+                )));
+            }
+        }
+    };
+}
+
 /// Macro to validate Token2022-only instructions with custom fee-payer matching logic
 macro_rules! validate_token2022 {
     ($self:expr, $instructions:expr, $type:ident, $pattern:pat => $fee_payer_used:expr, $message:expr) => {
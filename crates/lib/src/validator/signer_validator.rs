@@ -25,8 +25,12 @@ impl SignerValidator {
         // Validate strategy weights - delegate to existing method
         Self::try_result(config.validate_strategy_weights(), &mut errors);
 
+        // Validate treasury top-up thresholds - delegate to existing method
+        Self::try_result(config.validate_top_up(), &mut errors);
+
         // Generate strategy-specific warnings
         Self::validate_strategy_warnings(config, &mut warnings);
+        Self::validate_treasury_warnings(config, &mut warnings);
 
         for signer in &config.signers {
             let name = &signer.name;
@@ -38,7 +42,10 @@ impl SignerValidator {
                 SignerTypeConfig::Fireblocks { config } => &config.http_config,
                 SignerTypeConfig::Dfns { config } => &config.http_config,
                 SignerTypeConfig::Openfort { config } => &config.http_config,
+                SignerTypeConfig::RemoteHttp { config } => &config.http_config,
                 SignerTypeConfig::Memory { .. } => &None,
+                SignerTypeConfig::KeypairFile { .. } => &None,
+                SignerTypeConfig::EncryptedKeystore { .. } => &None,
                 SignerTypeConfig::AwsKms { .. } => &None,
                 SignerTypeConfig::GcpKms { .. } => &None,
                 SignerTypeConfig::Para { .. } => &None,
@@ -73,6 +80,25 @@ impl SignerValidator {
         }
     }
 
+    fn validate_treasury_warnings(config: &SignerPoolConfig, warnings: &mut Vec<String>) {
+        let Some(treasury) = &config.treasury else {
+            return;
+        };
+
+        if treasury.dry_run {
+            warnings.push(format!(
+                "Treasury '{}' is in dry_run mode - top-ups are logged but not sent",
+                treasury.name
+            ));
+        }
+        if config.signers.iter().all(|signer| signer.target_balance.is_none()) {
+            warnings.push(format!(
+                "Treasury '{}' is configured but no signer sets target_balance and refill_below",
+                treasury.name
+            ));
+        }
+    }
+
     /// Generate strategy-specific warnings (warnings don't fail fast)
     fn validate_strategy_warnings(config: &SignerPoolConfig, warnings: &mut Vec<String>) {
         match config.signer_pool.strategy {
@@ -106,21 +132,31 @@ impl SignerValidator {
 mod tests {
     use super::*;
     use crate::signer::config::{
-        MemorySignerConfig, SignerConfig, SignerPoolSettings, SignerTypeConfig,
+        MemorySignerConfig, SignerConfig, SignerPoolSettings, SignerTypeConfig, TreasuryConfig,
     };
 
     #[test]
     fn test_validate_with_result_warnings() {
         let _m = crate::tests::config_mock::ConfigMockBuilder::new().build_and_setup();
         let config = SignerPoolConfig {
-            signer_pool: SignerPoolSettings { strategy: SelectionStrategy::RoundRobin },
+            signer_pool: SignerPoolSettings {
+                strategy: SelectionStrategy::RoundRobin,
+                min_balance_lamports: None,
+                slow_signer_p95_ms: None,
+                user_affinity: None,
+            },
             signers: vec![SignerConfig {
                 name: "test_signer".to_string(),
                 weight: Some(10), // Weight specified for non-weighted strategy
+                target_balance: None,
+                refill_below: None,
+                policy: None,
+                max_concurrent_signs: None,
                 config: SignerTypeConfig::Memory {
                     config: MemorySignerConfig { private_key_env: "TEST_KEY".to_string() },
                 },
             }],
+            treasury: None,
         };
 
         std::env::set_var("TEST_KEY", "dummy");
@@ -135,11 +171,20 @@ mod tests {
     #[test]
     fn test_validate_duplicate_names() {
         let config = SignerPoolConfig {
-            signer_pool: SignerPoolSettings { strategy: SelectionStrategy::RoundRobin },
+            signer_pool: SignerPoolSettings {
+                strategy: SelectionStrategy::RoundRobin,
+                min_balance_lamports: None,
+                slow_signer_p95_ms: None,
+                user_affinity: None,
+            },
             signers: vec![
                 SignerConfig {
                     name: "duplicate".to_string(),
                     weight: None,
+                    target_balance: None,
+                    refill_below: None,
+                    policy: None,
+                    max_concurrent_signs: None,
                     config: SignerTypeConfig::Memory {
                         config: MemorySignerConfig { private_key_env: "TEST_KEY_1".to_string() },
                     },
@@ -147,11 +192,16 @@ mod tests {
                 SignerConfig {
                     name: "duplicate".to_string(),
                     weight: None,
+                    target_balance: None,
+                    refill_below: None,
+                    policy: None,
+                    max_concurrent_signs: None,
                     config: SignerTypeConfig::Memory {
                         config: MemorySignerConfig { private_key_env: "TEST_KEY_2".to_string() },
                     },
                 },
             ],
+            treasury: None,
         };
 
         let (_warnings, errors) = SignerValidator::validate_with_result(&config);
@@ -162,14 +212,24 @@ mod tests {
     #[test]
     fn test_validate_with_result_zero_weight() {
         let config = SignerPoolConfig {
-            signer_pool: SignerPoolSettings { strategy: SelectionStrategy::Weighted },
+            signer_pool: SignerPoolSettings {
+                strategy: SelectionStrategy::Weighted,
+                min_balance_lamports: None,
+                slow_signer_p95_ms: None,
+                user_affinity: None,
+            },
             signers: vec![SignerConfig {
                 name: "test_signer".to_string(),
                 weight: Some(0),
+                target_balance: None,
+                refill_below: None,
+                policy: None,
+                max_concurrent_signs: None,
                 config: SignerTypeConfig::Memory {
                     config: MemorySignerConfig { private_key_env: "TEST_KEY".to_string() },
                 },
             }],
+            treasury: None,
         };
 
         let (_warnings, errors) = SignerValidator::validate_with_result(&config);
@@ -180,12 +240,57 @@ mod tests {
     #[test]
     fn test_validate_with_result_empty_signers() {
         let config = SignerPoolConfig {
-            signer_pool: SignerPoolSettings { strategy: SelectionStrategy::RoundRobin },
+            signer_pool: SignerPoolSettings {
+                strategy: SelectionStrategy::RoundRobin,
+                min_balance_lamports: None,
+                slow_signer_p95_ms: None,
+                user_affinity: None,
+            },
             signers: vec![],
+            treasury: None,
         };
 
         let (_warnings, errors) = SignerValidator::validate_with_result(&config);
         assert!(!errors.is_empty());
         assert!(errors.iter().any(|e| e.contains("At least one signer must be configured")));
     }
+
+    #[test]
+    fn test_validate_with_result_treasury_warnings() {
+        std::env::set_var("KORA_TEST_VALIDATOR_TREASURY_KEY", "treasury-key");
+        let config = SignerPoolConfig {
+            signer_pool: SignerPoolSettings {
+                strategy: SelectionStrategy::RoundRobin,
+                min_balance_lamports: None,
+                slow_signer_p95_ms: None,
+                user_affinity: None,
+            },
+            signers: vec![SignerConfig {
+                name: "test_signer".to_string(),
+                weight: None,
+                target_balance: None,
+                refill_below: None,
+                policy: None,
+                max_concurrent_signs: None,
+                config: SignerTypeConfig::Memory {
+                    config: MemorySignerConfig { private_key_env: "TEST_KEY".to_string() },
+                },
+            }],
+            treasury: Some(TreasuryConfig {
+                name: "treasury".to_string(),
+                daily_cap_lamports: 1_000_000_000,
+                dry_run: true,
+                check_interval_seconds: 60,
+                config: SignerTypeConfig::Memory {
+                    config: MemorySignerConfig {
+                        private_key_env: "KORA_TEST_VALIDATOR_TREASURY_KEY".to_string(),
+                    },
+                },
+            }),
+        };
+
+        let (warnings, _errors) = SignerValidator::validate_with_result(&config);
+        assert!(warnings.iter().any(|w| w.contains("dry_run mode")));
+        assert!(warnings.iter().any(|w| w.contains("no signer sets target_balance")));
+    }
 }
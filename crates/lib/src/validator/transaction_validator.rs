@@ -1,44 +1,178 @@
 use crate::{
-    config::{Config, FeePayerPolicy, ProgramsConfig},
+    config::{Config, FeePayerPolicy, ProgramsConfig, ValidationConfig},
+    constant::STAKE_PROGRAM_ID,
     error::KoraError,
-    fee::fee::{FeeConfigUtil, TotalFeeCalculation},
+    fee::fee::{FeeConfigUtil, TotalFeeCalculation, TransactionFeeUtil},
+    metrics::ShadowMetrics,
+    nonce::NoncePoolUtil,
     oracle::PriceSource,
+    plugin::{PluginExecutionContext, TransactionPluginRunner},
+    state::{get_background_tasks, get_signer_policy},
     token::{
         interface::TokenMint,
         token::{TokenUtil, TransferHookValidationFlow},
     },
     transaction::{
-        ParsedALTInstructionData, ParsedALTInstructionType,
+        InstructionParser, ParsedALTInstructionData, ParsedALTInstructionType,
         ParsedBpfLoaderUpgradeableInstructionData, ParsedBpfLoaderUpgradeableInstructionType,
         ParsedLoaderV4InstructionData, ParsedLoaderV4InstructionType, ParsedSPLInstructionData,
         ParsedSPLInstructionType, ParsedSystemInstructionData, ParsedSystemInstructionType,
-        Token2022AccountUsagePolicy, VersionedTransactionResolved,
+        StakeParser, Token2022AccountUsagePolicy, VersionedTransactionOps,
+        VersionedTransactionResolved,
     },
+    usage_limit::UsageTracker,
 };
+use serde::{Deserialize, Serialize};
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::{pubkey::Pubkey, transaction::VersionedTransaction};
-use std::{collections::HashSet, str::FromStr};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt,
+    str::FromStr,
+    sync::Arc,
+};
+use utoipa::ToSchema;
 
 use crate::fee::price::PriceModel;
 
+/// Policy area a dry-run validation finding belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationRule {
+    /// Empty transaction, only ComputeBudget instructions or no account keys
+    TransactionStructure,
+    /// Signature count or the fee payer not being a signer
+    Signatures,
+    /// `validation.allowed_programs` and `validation.program_rules`
+    AllowedPrograms,
+    /// `validation.require_one_of_programs`
+    RequireOneOfPrograms,
+    /// `validation.anchor` constraints on decoded instruction arguments and accounts
+    AnchorConstraints,
+    /// `validation.max_allowed_lamports`, for transfers and the transaction fee
+    MaxAllowedLamports,
+    /// `validation.disallowed_accounts`
+    DisallowedAccounts,
+    /// `validation.fee_payer_policy`
+    FeePayerPolicy,
+    /// `validation.token_2022` blocked extensions and extension authority policies
+    Token2022Extensions,
+    /// Enabled transaction plugins
+    Plugin,
+    /// `kora.usage_limit`
+    UsageLimit,
+    /// Fee estimation, strict pricing and token payment sufficiency
+    Payment,
+    /// The transaction failed simulation while resolving inner instructions
+    Simulation,
+}
+
+impl fmt::Display for ValidationRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::TransactionStructure => "transaction_structure",
+            Self::Signatures => "signatures",
+            Self::AllowedPrograms => "allowed_programs",
+            Self::RequireOneOfPrograms => "require_one_of_programs",
+            Self::AnchorConstraints => "anchor_constraints",
+            Self::MaxAllowedLamports => "max_allowed_lamports",
+            Self::DisallowedAccounts => "disallowed_accounts",
+            Self::FeePayerPolicy => "fee_payer_policy",
+            Self::Token2022Extensions => "token_2022_extensions",
+            Self::Plugin => "plugin",
+            Self::UsageLimit => "usage_limit",
+            Self::Payment => "payment",
+            Self::Simulation => "simulation",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A violated policy reported by a dry-run validation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ValidationFinding {
+    pub rule: ValidationRule,
+    /// Index of the offending instruction: top-level instructions first, then inner
+    /// instructions in execution order. Null for transaction-wide checks.
+    pub instruction_index: Option<usize>,
+    pub message: String,
+}
+
+impl ValidationFinding {
+    pub fn new(rule: ValidationRule, instruction_index: Option<usize>, error: &KoraError) -> Self {
+        Self { rule, instruction_index, message: error.to_string() }
+    }
+}
+
+/// Records a failed check as a finding. RPC and internal errors are not policy
+/// violations, so they are returned instead.
+fn record_finding(
+    findings: &mut Vec<ValidationFinding>,
+    rule: ValidationRule,
+    instruction_index: Option<usize>,
+    result: Result<(), KoraError>,
+) -> Result<(), KoraError> {
+    match result {
+        Ok(()) => Ok(()),
+        Err(e @ (KoraError::RpcError(_) | KoraError::InternalServerError(_))) => Err(e),
+        Err(e) => {
+            findings.push(ValidationFinding::new(rule, instruction_index, &e));
+            Ok(())
+        }
+    }
+}
+
 pub struct TransactionValidator {
     fee_payer_pubkey: Pubkey,
     max_allowed_lamports: u64,
     allowed_programs: HashSet<Pubkey>,
     allow_all_programs: bool,
     require_one_of_programs: HashSet<Pubkey>,
+    /// Allowed instruction data prefixes per program from `validation.program_rules`
+    program_rules: HashMap<Pubkey, Vec<Vec<u8>>>,
     max_signatures: u64,
     allowed_tokens: HashSet<Pubkey>,
     disallowed_accounts: HashSet<Pubkey>,
     _price_source: PriceSource,
     fee_payer_policy: FeePayerPolicy,
     allow_durable_transactions: bool,
+    nonce_pool_enabled: bool,
+    /// Validator for the candidate config in `validation.shadow`, never enforced
+    shadow: Option<Arc<TransactionValidator>>,
 }
 
 impl TransactionValidator {
+    /// Builds the validator for transactions paid by `fee_payer_pubkey`, merging that
+    /// signer's policy overrides into `config.validation`
     pub fn new(config: &Config, fee_payer_pubkey: Pubkey) -> Result<Self, KoraError> {
-        let config = &config.validation;
+        let nonce_pool_enabled = config.kora.nonce_pool.enabled;
+        let signer_policy = get_signer_policy(&fee_payer_pubkey);
+        let with_signer_policy = |validation| match &signer_policy {
+            Some(policy) => Cow::Owned(policy.apply_to(validation)),
+            None => Cow::Borrowed(validation),
+        };
+
+        let mut validator = Self::from_validation_config(
+            &with_signer_policy(&config.validation),
+            fee_payer_pubkey,
+            nonce_pool_enabled,
+        )?;
+        if let Some(candidate) = config.validation.shadow.as_deref() {
+            validator.shadow = Some(Arc::new(Self::from_validation_config(
+                &with_signer_policy(candidate),
+                fee_payer_pubkey,
+                nonce_pool_enabled,
+            )?));
+        }
+        Ok(validator)
+    }
 
+    fn from_validation_config(
+        config: &ValidationConfig,
+        fee_payer_pubkey: Pubkey,
+        nonce_pool_enabled: bool,
+    ) -> Result<Self, KoraError> {
         let (allow_all_programs, allowed_programs) = match &config.allowed_programs {
             ProgramsConfig::All => (true, HashSet::new()),
             ProgramsConfig::Allowlist(programs) => (
@@ -68,12 +202,29 @@ impl TransactionValidator {
             })
             .collect::<Result<HashSet<Pubkey>, KoraError>>()?;
 
+        let program_rules = config
+            .program_rules
+            .iter()
+            .map(|rule| {
+                let program_id = Pubkey::from_str(&rule.program_id).map_err(|e| {
+                    KoraError::InternalServerError(format!(
+                        "Invalid program address in program_rules config: {e}"
+                    ))
+                })?;
+                let discriminators = rule
+                    .discriminators()
+                    .map_err(|e| KoraError::InternalServerError(e.to_string()))?;
+                Ok((program_id, discriminators))
+            })
+            .collect::<Result<HashMap<Pubkey, Vec<Vec<u8>>>, KoraError>>()?;
+
         Ok(Self {
             fee_payer_pubkey,
             max_allowed_lamports: config.max_allowed_lamports,
             allowed_programs,
             allow_all_programs,
             require_one_of_programs,
+            program_rules,
             max_signatures: config.max_signatures,
             _price_source: config.price_source.clone(),
             allowed_tokens: config
@@ -96,6 +247,8 @@ impl TransactionValidator {
                 })?,
             fee_payer_policy: config.fee_payer_policy.clone(),
             allow_durable_transactions: config.allow_durable_transactions,
+            nonce_pool_enabled,
+            shadow: None,
         })
     }
 
@@ -124,7 +277,321 @@ impl TransactionValidator {
         &self,
         config: &Config,
         transaction_resolved: &mut VersionedTransactionResolved,
+        rpc_client: &Arc<RpcClient>,
+    ) -> Result<(), KoraError> {
+        self.check_transaction(config, &config.validation, transaction_resolved, rpc_client)
+            .await
+            .map_err(|(_, e)| e)?;
+
+        if let (Some(shadow), Some(candidate)) = (&self.shadow, config.validation.shadow.as_deref())
+        {
+            shadow.spawn_shadow_validation(config, candidate, transaction_resolved, rpc_client);
+        }
+
+        Ok(())
+    }
+
+    /// Runs the checks that a signer policy's `fee_payer_policy`, `max_allowed_lamports`
+    /// and `price` overrides take part in, so requests can be routed to a signer whose
+    /// policy accepts the transaction. The price is only checked when `payment_flow` is
+    /// set, as the transaction carries no payment before it is signed.
+    pub async fn validate_signer_policy(
+        &self,
+        config: &Config,
+        transaction_resolved: &mut VersionedTransactionResolved,
+        rpc_client: &RpcClient,
+        payment_flow: Option<TransferHookValidationFlow>,
+    ) -> Result<(), KoraError> {
+        self.validate_transfer_amounts(config, transaction_resolved, rpc_client).await?;
+        let leased_nonce_accounts = self.find_leased_nonce_accounts(transaction_resolved).await?;
+        self.validate_fee_payer_usage(transaction_resolved, &leased_nonce_accounts)?;
+
+        let estimated_fee =
+            TransactionFeeUtil::get_estimate_fee_resolved(rpc_client, transaction_resolved).await?;
+        self.validate_lamport_fee(estimated_fee)?;
+
+        let Some(payment_flow) = payment_flow else {
+            return Ok(());
+        };
+        let fee_calculation = FeeConfigUtil::estimate_kora_fee(
+            transaction_resolved,
+            &self.fee_payer_pubkey,
+            config.validation.is_payment_required(),
+            rpc_client,
+            config,
+            payment_flow,
+            None,
+        )
+        .await?;
+        Self::validate_strict_pricing_with_fee(config, &fee_calculation)?;
+
+        let required_lamports = fee_calculation.total_fee_lamports;
+        if required_lamports > 0 {
+            let payment_destination = config.kora.get_payment_address(&self.fee_payer_pubkey)?;
+            Self::validate_token_payment(
+                config,
+                transaction_resolved,
+                required_lamports,
+                rpc_client,
+                &payment_destination,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Runs the checks of [`Self::validate_transaction`] against `validation`, returning the
+    /// first failure along with the rule it belongs to
+    async fn check_transaction(
+        &self,
+        config: &Config,
+        validation: &ValidationConfig,
+        transaction_resolved: &mut VersionedTransactionResolved,
         rpc_client: &RpcClient,
+    ) -> Result<(), (ValidationRule, KoraError)> {
+        let tag = |rule| move |e| (rule, e);
+
+        self.validate_structure(transaction_resolved)
+            .map_err(tag(ValidationRule::TransactionStructure))?;
+
+        self.validate_signatures(&transaction_resolved.transaction)
+            .map_err(tag(ValidationRule::Signatures))?;
+
+        self.validate_programs(transaction_resolved)
+            .map_err(tag(ValidationRule::AllowedPrograms))?;
+        self.validate_require_one_of_programs(transaction_resolved)
+            .map_err(tag(ValidationRule::RequireOneOfPrograms))?;
+        self.validate_anchor_constraints(validation, transaction_resolved)
+            .map_err(tag(ValidationRule::AnchorConstraints))?;
+        self.validate_transfer_amounts(config, transaction_resolved, rpc_client)
+            .await
+            .map_err(tag(ValidationRule::MaxAllowedLamports))?;
+        self.validate_disallowed_accounts(transaction_resolved)
+            .map_err(tag(ValidationRule::DisallowedAccounts))?;
+        let leased_nonce_accounts = self
+            .find_leased_nonce_accounts(transaction_resolved)
+            .await
+            .map_err(tag(ValidationRule::FeePayerPolicy))?;
+        self.validate_fee_payer_usage(transaction_resolved, &leased_nonce_accounts)
+            .map_err(tag(ValidationRule::FeePayerPolicy))?;
+        self.validate_token2022_extension_security(validation, transaction_resolved)
+            .map_err(tag(ValidationRule::Token2022Extensions))?;
+
+        Ok(())
+    }
+
+    /// Evaluates the `validation.shadow` candidate against a transaction the enforced config
+    /// accepted, in a background task off the response path. A rejection is only logged
+    /// and counted; it never affects the response.
+    fn spawn_shadow_validation(
+        self: &Arc<Self>,
+        config: &Config,
+        candidate: &ValidationConfig,
+        transaction_resolved: &VersionedTransactionResolved,
+        rpc_client: &Arc<RpcClient>,
+    ) {
+        let validator = Arc::clone(self);
+        let config = config.clone();
+        let candidate = candidate.clone();
+        // Decoded Anchor instructions depend on the config's IDLs, so the candidate works on
+        // its own copy rather than the enforced transaction's caches
+        let mut transaction = transaction_resolved.without_parsed_instructions();
+        let rpc_client = Arc::clone(rpc_client);
+
+        get_background_tasks().spawn(async move {
+            match validator
+                .check_transaction(&config, &candidate, &mut transaction, &rpc_client)
+                .await
+            {
+                Ok(()) => {}
+                Err((_, e @ (KoraError::RpcError(_) | KoraError::InternalServerError(_)))) => {
+                    log::warn!("Shadow validation could not evaluate transaction: {e}");
+                }
+                Err((rule, e)) => {
+                    log::warn!("Shadow validation would reject transaction ({rule}): {e}");
+                    ShadowMetrics::get().rejections.with_label_values(&[&rule.to_string()]).inc();
+                }
+            }
+        });
+    }
+
+    /// Dry-run of the signing flow: runs every check that signTransaction would, collecting
+    /// all violations instead of returning the first one. Never signs.
+    ///
+    /// Per-instruction checks run against each resolved instruction on its own, so every
+    /// offending instruction is reported with its index.
+    pub async fn collect_findings(
+        &self,
+        config: &Config,
+        transaction_resolved: &mut VersionedTransactionResolved,
+        rpc_client: &RpcClient,
+        user_id: Option<&str>,
+    ) -> Result<Vec<ValidationFinding>, KoraError> {
+        let mut findings = Vec::new();
+
+        // Every other check assumes a well-formed transaction
+        if let Err(e) = self.validate_structure(transaction_resolved) {
+            findings.push(ValidationFinding::new(ValidationRule::TransactionStructure, None, &e));
+            return Ok(findings);
+        }
+
+        record_finding(
+            &mut findings,
+            ValidationRule::Signatures,
+            None,
+            self.validate_signatures(&transaction_resolved.transaction),
+        )?;
+        record_finding(
+            &mut findings,
+            ValidationRule::Signatures,
+            None,
+            transaction_resolved.find_signer_position(&self.fee_payer_pubkey).map(|_| ()),
+        )?;
+
+        let leased_nonce_accounts = self.find_leased_nonce_accounts(transaction_resolved).await?;
+        for index in 0..transaction_resolved.all_instructions.len() {
+            let mut instruction = transaction_resolved.with_single_instruction(index);
+
+            record_finding(
+                &mut findings,
+                ValidationRule::AllowedPrograms,
+                Some(index),
+                self.validate_programs(&instruction),
+            )?;
+            record_finding(
+                &mut findings,
+                ValidationRule::AnchorConstraints,
+                Some(index),
+                self.validate_anchor_constraints(&config.validation, &mut instruction),
+            )?;
+            record_finding(
+                &mut findings,
+                ValidationRule::DisallowedAccounts,
+                Some(index),
+                self.validate_disallowed_accounts(&mut instruction),
+            )?;
+            record_finding(
+                &mut findings,
+                ValidationRule::FeePayerPolicy,
+                Some(index),
+                self.validate_fee_payer_usage(&mut instruction, &leased_nonce_accounts),
+            )?;
+            record_finding(
+                &mut findings,
+                ValidationRule::Token2022Extensions,
+                Some(index),
+                self.validate_token2022_extension_security(&config.validation, &mut instruction),
+            )?;
+        }
+
+        record_finding(
+            &mut findings,
+            ValidationRule::RequireOneOfPrograms,
+            None,
+            self.validate_require_one_of_programs(transaction_resolved),
+        )?;
+        record_finding(
+            &mut findings,
+            ValidationRule::MaxAllowedLamports,
+            None,
+            self.validate_transfer_amounts(config, transaction_resolved, rpc_client).await,
+        )?;
+
+        let plugin_errors = TransactionPluginRunner::from_config(config)
+            .run_all(
+                transaction_resolved,
+                config,
+                rpc_client,
+                &self.fee_payer_pubkey,
+                PluginExecutionContext::SignTransaction,
+            )
+            .await;
+        for error in plugin_errors {
+            record_finding(&mut findings, ValidationRule::Plugin, None, Err(error))?;
+        }
+
+        record_finding(
+            &mut findings,
+            ValidationRule::UsageLimit,
+            None,
+            UsageTracker::preview_transaction_usage_limit(
+                config,
+                transaction_resolved,
+                user_id,
+                &self.fee_payer_pubkey,
+                rpc_client,
+            )
+            .await,
+        )?;
+
+        record_finding(
+            &mut findings,
+            ValidationRule::Token2022Extensions,
+            None,
+            self.validate_token2022_transfer_hook_signing_policies(
+                config,
+                transaction_resolved,
+                TransferHookValidationFlow::DelayedSigning,
+            ),
+        )?;
+
+        match FeeConfigUtil::estimate_kora_fee(
+            transaction_resolved,
+            &self.fee_payer_pubkey,
+            config.validation.is_payment_required(),
+            rpc_client,
+            config,
+            TransferHookValidationFlow::DelayedSigning,
+            None,
+        )
+        .await
+        {
+            Ok(fee_calculation) => {
+                record_finding(
+                    &mut findings,
+                    ValidationRule::Payment,
+                    None,
+                    Self::validate_strict_pricing_with_fee(config, &fee_calculation),
+                )?;
+
+                let required_lamports = fee_calculation.total_fee_lamports;
+                if required_lamports > 0 {
+                    let payment_destination =
+                        config.kora.get_payment_address(&self.fee_payer_pubkey)?;
+                    record_finding(
+                        &mut findings,
+                        ValidationRule::Payment,
+                        None,
+                        Self::validate_token_payment(
+                            config,
+                            transaction_resolved,
+                            required_lamports,
+                            rpc_client,
+                            &payment_destination,
+                        )
+                        .await,
+                    )?;
+                }
+            }
+            Err(e) => record_finding(&mut findings, ValidationRule::Payment, None, Err(e))?,
+        }
+
+        let estimated_fee =
+            TransactionFeeUtil::get_estimate_fee_resolved(rpc_client, transaction_resolved).await?;
+        record_finding(
+            &mut findings,
+            ValidationRule::MaxAllowedLamports,
+            None,
+            self.validate_lamport_fee(estimated_fee),
+        )?;
+
+        Ok(findings)
+    }
+
+    fn validate_structure(
+        &self,
+        transaction_resolved: &VersionedTransactionResolved,
     ) -> Result<(), KoraError> {
         if transaction_resolved.all_instructions.is_empty() {
             return Err(KoraError::InvalidTransaction(
@@ -140,15 +607,40 @@ impl TransactionValidator {
             ));
         }
 
-        self.validate_signatures(&transaction_resolved.transaction)?;
+        Ok(())
+    }
 
-        self.validate_programs(transaction_resolved)?;
-        self.validate_require_one_of_programs(transaction_resolved)?;
-        self.validate_transfer_amounts(config, transaction_resolved, rpc_client).await?;
-        self.validate_disallowed_accounts(transaction_resolved)?;
-        self.validate_fee_payer_usage(config, transaction_resolved)?;
+    /// Nonce accounts advanced by the fee payer that are leased from Kora's nonce pool
+    async fn find_leased_nonce_accounts(
+        &self,
+        transaction_resolved: &mut VersionedTransactionResolved,
+    ) -> Result<HashSet<Pubkey>, KoraError> {
+        let mut leased = HashSet::new();
+        if !self.nonce_pool_enabled {
+            return Ok(leased);
+        }
 
-        Ok(())
+        let advanced_nonce_accounts: Vec<Pubkey> = transaction_resolved
+            .get_or_parse_system_instructions()?
+            .get(&ParsedSystemInstructionType::SystemAdvanceNonceAccount)
+            .unwrap_or(&vec![])
+            .iter()
+            .filter_map(|instruction| match instruction {
+                ParsedSystemInstructionData::SystemAdvanceNonceAccount {
+                    nonce_account,
+                    nonce_authority,
+                } if *nonce_authority == self.fee_payer_pubkey => Some(*nonce_account),
+                _ => None,
+            })
+            .collect();
+
+        for nonce_account in advanced_nonce_accounts {
+            if NoncePoolUtil::is_leased(&nonce_account, &self.fee_payer_pubkey).await? {
+                leased.insert(nonce_account);
+            }
+        }
+
+        Ok(leased)
     }
 
     pub(crate) fn validate_token2022_transfer_hook_signing_policies(
@@ -231,16 +723,29 @@ impl TransactionValidator {
         &self,
         transaction_resolved: &VersionedTransactionResolved,
     ) -> Result<(), KoraError> {
-        if self.allow_all_programs {
-            return Ok(());
-        }
         for instruction in &transaction_resolved.all_instructions {
-            if !self.allowed_programs.contains(&instruction.program_id) {
+            if !self.allow_all_programs && !self.allowed_programs.contains(&instruction.program_id)
+            {
                 return Err(KoraError::InvalidTransaction(format!(
                     "Program {} is not in the allowed list",
                     instruction.program_id
                 )));
             }
+
+            let Some(discriminators) = self.program_rules.get(&instruction.program_id) else {
+                continue;
+            };
+            if !discriminators.iter().any(|d| instruction.data.starts_with(d)) {
+                // Show as many bytes as the longest allowed discriminator, so the rejected
+                // value is comparable with the configured ones
+                let len = discriminators.iter().map(Vec::len).max().unwrap_or(0);
+                let rejected = &instruction.data[..len.min(instruction.data.len())];
+                return Err(KoraError::InvalidTransaction(format!(
+                    "Instruction discriminator {} is not allowed for program {} by program_rules",
+                    if rejected.is_empty() { "(empty)".to_string() } else { hex::encode(rejected) },
+                    instruction.program_id
+                )));
+            }
         }
         Ok(())
     }
@@ -267,6 +772,20 @@ impl TransactionValidator {
         Ok(())
     }
 
+    fn validate_anchor_constraints(
+        &self,
+        validation: &ValidationConfig,
+        transaction_resolved: &mut VersionedTransactionResolved,
+    ) -> Result<(), KoraError> {
+        let anchor = &validation.anchor;
+        if anchor.constraints.is_empty() {
+            return Ok(());
+        }
+
+        let instructions = transaction_resolved.get_or_decode_anchor_instructions(anchor)?;
+        anchor.check_constraints(instructions)
+    }
+
     fn validate_create_account_owner(&self, owner: &Pubkey) -> Result<(), KoraError> {
         if !self.allow_all_programs && !self.allowed_programs.contains(owner) {
             return Err(KoraError::InvalidTransaction(format!(
@@ -283,18 +802,27 @@ impl TransactionValidator {
 
     fn validate_fee_payer_usage(
         &self,
-        config: &Config,
         transaction_resolved: &mut VersionedTransactionResolved,
+        leased_nonce_accounts: &HashSet<Pubkey>,
     ) -> Result<(), KoraError> {
         self.validate_ata_create_instructions(transaction_resolved)?;
 
         let system_instructions = transaction_resolved.get_or_parse_system_instructions()?;
 
-        // Check for durable transactions (nonce-based) - reject if not allowed
-        if !self.allow_durable_transactions
-            && system_instructions
-                .contains_key(&ParsedSystemInstructionType::SystemAdvanceNonceAccount)
-        {
+        // Check for durable transactions (nonce-based) - reject if not allowed, unless every
+        // advance is on a nonce leased from Kora's nonce pool
+        let advances_unleased_nonce = system_instructions
+            .get(&ParsedSystemInstructionType::SystemAdvanceNonceAccount)
+            .unwrap_or(&vec![])
+            .iter()
+            .any(|instruction| {
+                !matches!(
+                    instruction,
+                    ParsedSystemInstructionData::SystemAdvanceNonceAccount { nonce_account, .. }
+                        if leased_nonce_accounts.contains(nonce_account)
+                )
+            });
+        if !self.allow_durable_transactions && advances_unleased_nonce {
             return Err(KoraError::InvalidTransaction(
                 "Durable transactions (nonce-based) are not allowed".to_string(),
             ));
@@ -364,9 +892,12 @@ impl TransactionValidator {
             ParsedSystemInstructionData::SystemInitializeNonceAccount { nonce_authority, .. } => nonce_authority,
             self.fee_payer_policy.system.nonce.allow_initialize, "System Initialize Nonce Account");
 
+        // Advancing a leased pool nonce is what the lease is for, so it bypasses allow_advance
         validate_system!(self, system_instructions, SystemAdvanceNonceAccount,
-            ParsedSystemInstructionData::SystemAdvanceNonceAccount { nonce_authority, .. } => nonce_authority,
-            self.fee_payer_policy.system.nonce.allow_advance, "System Advance Nonce Account");
+            ParsedSystemInstructionData::SystemAdvanceNonceAccount { nonce_authority, nonce_account } => nonce_authority,
+            self.fee_payer_policy.system.nonce.allow_advance
+                || leased_nonce_accounts.contains(nonce_account),
+            "System Advance Nonce Account");
 
         validate_system!(self, system_instructions, SystemAuthorizeNonceAccount,
             ParsedSystemInstructionData::SystemAuthorizeNonceAccount { nonce_authority, .. } => nonce_authority,
@@ -691,6 +1222,88 @@ impl TransactionValidator {
             self.fee_payer_policy.bpf_loader_upgradeable.allow_migrate,
             "BPF Loader Upgradeable Migrate");
 
+        // Validate Stake instructions. Only the Stake parser runs here, so other registered
+        // parsers can't reject transactions they happen to consider malformed.
+        let stake_instructions = transaction_resolved
+            .all_instructions
+            .iter()
+            .filter(|instruction| instruction.program_id == STAKE_PROGRAM_ID)
+            .filter_map(|instruction| StakeParser.parse(instruction).transpose())
+            .collect::<Result<Vec<_>, _>>()?;
+        let stake_policy = &self.fee_payer_policy.stake;
+
+        validate_stake!(
+            self,
+            stake_instructions,
+            ["initialize", "initializeChecked"],
+            stake_policy.allow_initialize,
+            "Stake Initialize"
+        );
+
+        validate_stake!(
+            self,
+            stake_instructions,
+            ["authorize", "authorizeChecked", "authorizeWithSeed", "authorizeCheckedWithSeed"],
+            stake_policy.allow_authorize,
+            "Stake Authorize"
+        );
+
+        validate_stake!(
+            self,
+            stake_instructions,
+            ["delegateStake", "redelegate"],
+            stake_policy.allow_delegate,
+            "Stake DelegateStake"
+        );
+
+        validate_stake!(
+            self,
+            stake_instructions,
+            ["split"],
+            stake_policy.allow_split,
+            "Stake Split"
+        );
+
+        validate_stake!(
+            self,
+            stake_instructions,
+            ["withdraw"],
+            stake_policy.allow_withdraw,
+            "Stake Withdraw"
+        );
+
+        validate_stake!(
+            self,
+            stake_instructions,
+            ["deactivate"],
+            stake_policy.allow_deactivate,
+            "Stake Deactivate"
+        );
+
+        validate_stake!(
+            self,
+            stake_instructions,
+            ["merge"],
+            stake_policy.allow_merge,
+            "Stake Merge"
+        );
+
+        validate_stake!(
+            self,
+            stake_instructions,
+            ["setLockup", "setLockupChecked"],
+            stake_policy.allow_set_lockup,
+            "Stake SetLockup"
+        );
+
+        validate_stake!(
+            self,
+            stake_instructions,
+            ["moveStake", "moveLamports"],
+            stake_policy.allow_move,
+            "Stake MoveStake"
+        );
+
         let spl_instructions = transaction_resolved.get_or_parse_spl_instructions()?;
         validate_token2022!(self, spl_instructions, SplTokenReallocate,
             ParsedSPLInstructionData::SplTokenReallocate {
@@ -719,8 +1332,6 @@ impl TransactionValidator {
             self.fee_payer_policy.token_2022.allow_thaw_account,
             "Fee payer cannot be used for Token2022 Resume");
 
-        self.validate_token2022_extension_security(config, transaction_resolved)?;
-
         Ok(())
     }
 
@@ -879,7 +1490,7 @@ impl TransactionValidator {
 
     fn validate_token2022_extension_security(
         &self,
-        config: &Config,
+        validation: &ValidationConfig,
         transaction_resolved: &mut VersionedTransactionResolved,
     ) -> Result<(), KoraError> {
         for instruction in transaction_resolved.get_or_parse_token2022_security_instructions()? {
@@ -895,8 +1506,8 @@ impl TransactionValidator {
             }
 
             if let Some(extension_type) = instruction.extension_type {
-                if config.validation.token_2022.is_mint_extension_blocked(extension_type)
-                    || config.validation.token_2022.is_account_extension_blocked(extension_type)
+                if validation.token_2022.is_mint_extension_blocked(extension_type)
+                    || validation.token_2022.is_account_extension_blocked(extension_type)
                 {
                     return Err(KoraError::InvalidTransaction(format!(
                         "Token2022 instruction '{}' is not allowed because extension '{extension_type:?}' is blocked",
@@ -1001,14 +1612,17 @@ impl TransactionValidator {
 #[cfg(test)]
 mod tests {
     use crate::{
-        config::{Config, FeePayerPolicy, TransferHookPolicy},
+        config::{
+            AnchorConstraint, AnchorIdlConfig, Config, FeePayerPolicy, ProgramRule,
+            TransferHookPolicy,
+        },
         state::{get_config, update_config},
         tests::{
             account_mock::{AccountMockBuilder, MintAccountMockBuilder, TokenAccountMockBuilder},
             config_mock::{mock_state::setup_config_mock, ConfigMockBuilder},
             rpc_mock::RpcMockBuilder,
         },
-        transaction::TransactionUtil,
+        transaction::{anchor_instruction_discriminator, TransactionUtil},
     };
     use serial_test::serial;
     use spl_pod::optional_keys::OptionalNonZeroPubkey;
@@ -1132,7 +1746,7 @@ mod tests {
         setup_default_config();
         let rpc_client = RpcMockBuilder::new().build();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let recipient = Pubkey::new_unique();
@@ -1155,7 +1769,7 @@ mod tests {
         setup_default_config();
         let rpc_client = RpcMockBuilder::new().build();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let sender = Pubkey::new_unique();
         let recipient = Pubkey::new_unique();
@@ -1190,7 +1804,7 @@ mod tests {
         setup_default_config();
         let rpc_client = RpcMockBuilder::new().build();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let sender = Pubkey::new_unique();
         let recipient = Pubkey::new_unique();
@@ -1231,7 +1845,7 @@ mod tests {
         setup_both_configs(config);
         let rpc_client = RpcMockBuilder::new().build();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let arbitrary_program = Pubkey::new_unique();
@@ -1240,11 +1854,129 @@ mod tests {
         let mut transaction =
             TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
         assert!(validator
-            .validate_transaction(get_config().unwrap(), &mut transaction, &rpc_client)
+            .validate_transaction(&get_config().unwrap(), &mut transaction, &rpc_client)
             .await
             .is_ok());
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_program_rules_discriminators() {
+        let fee_payer = Pubkey::new_unique();
+        let anchor_program = Pubkey::new_unique();
+        let mut config = ConfigMockBuilder::new().with_price_source(PriceSource::Mock).build();
+        config.validation.allowed_programs = ProgramsConfig::All;
+        config.validation.program_rules = vec![ProgramRule {
+            program_id: anchor_program.to_string(),
+            allowed_discriminators: vec!["03".to_string()],
+            allowed_anchor_instructions: vec!["initialize".to_string()],
+        }];
+        setup_both_configs(config);
+        let rpc_client = RpcMockBuilder::new().build();
+
+        let config = &get_config().unwrap();
+        let validator = TransactionValidator::new(config, fee_payer).unwrap();
+        let resolve = |data: Vec<u8>| {
+            let instruction = Instruction::new_with_bytes(anchor_program, &data, vec![]);
+            let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap()
+        };
+
+        let mut initialize = anchor_instruction_discriminator("initialize").to_vec();
+        initialize.extend_from_slice(&42u64.to_le_bytes());
+        for data in [initialize, vec![3, 1, 2]] {
+            let mut transaction = resolve(data);
+            assert!(validator
+                .validate_transaction(config, &mut transaction, &rpc_client)
+                .await
+                .is_ok());
+        }
+
+        let set_admin = anchor_instruction_discriminator("set_admin").to_vec();
+        let mut transaction = resolve(set_admin.clone());
+        let err = validator
+            .validate_transaction(config, &mut transaction, &rpc_client)
+            .await
+            .expect_err("instruction outside program_rules must be rejected");
+        assert!(err.to_string().contains(&hex::encode(&set_admin)), "unexpected error: {err}");
+
+        // Inner instructions are held to the same rules as top-level ones
+        let mut transaction = resolve(vec![3]);
+        transaction.all_instructions.push(Instruction::new_with_bytes(
+            anchor_program,
+            &set_admin,
+            vec![],
+        ));
+        assert!(validator
+            .validate_transaction(config, &mut transaction, &rpc_client)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validate_anchor_constraints() {
+        use std::io::Write;
+
+        let fee_payer = Pubkey::new_unique();
+        let anchor_program = Pubkey::new_unique();
+        let mut idl = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            idl,
+            r#"{{
+                "address": "{anchor_program}",
+                "instructions": [{{
+                    "name": "deposit",
+                    "args": [{{ "name": "amount", "type": "u64" }}]
+                }}]
+            }}"#
+        )
+        .unwrap();
+
+        let mut config = ConfigMockBuilder::new().with_price_source(PriceSource::Mock).build();
+        config.validation.allowed_programs = ProgramsConfig::All;
+        config.validation.anchor.idls = vec![AnchorIdlConfig {
+            path: idl.path().to_str().unwrap().to_string(),
+            program_id: None,
+        }];
+        config.validation.anchor.constraints = vec![AnchorConstraint {
+            program_id: anchor_program.to_string(),
+            instruction: "deposit".to_string(),
+            arg: Some("amount".to_string()),
+            account: None,
+            min: None,
+            max: Some(1_000),
+            equals: None,
+            one_of: None,
+        }];
+        config.validation.anchor.initialize().unwrap();
+        setup_both_configs(config);
+        let rpc_client = RpcMockBuilder::new().build();
+
+        let config = &get_config().unwrap();
+        let validator = TransactionValidator::new(config, fee_payer).unwrap();
+        let resolve = |amount: u64| {
+            let mut data = anchor_instruction_discriminator("deposit").to_vec();
+            data.extend_from_slice(&amount.to_le_bytes());
+            let instruction = Instruction::new_with_bytes(anchor_program, &data, vec![]);
+            let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap()
+        };
+
+        let mut transaction = resolve(1_000);
+        assert!(validator
+            .validate_transaction(config, &mut transaction, &rpc_client)
+            .await
+            .is_ok());
+
+        let mut transaction = resolve(1_001);
+        let err = validator
+            .validate_transaction(config, &mut transaction, &rpc_client)
+            .await
+            .expect_err("deposit above max must be rejected");
+        assert!(err.to_string().contains("exceeds max 1000"), "unexpected error: {err}");
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_validate_require_one_of_programs_empty_no_restriction() {
@@ -1257,7 +1989,7 @@ mod tests {
         setup_both_configs(config);
         let rpc_client = RpcMockBuilder::new().build();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let sender = Pubkey::new_unique();
         let recipient = Pubkey::new_unique();
@@ -1267,7 +1999,7 @@ mod tests {
         let mut transaction =
             TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
         assert!(validator
-            .validate_transaction(get_config().unwrap(), &mut transaction, &rpc_client)
+            .validate_transaction(&get_config().unwrap(), &mut transaction, &rpc_client)
             .await
             .is_ok());
     }
@@ -1286,7 +2018,7 @@ mod tests {
         setup_both_configs(config);
         let rpc_client = RpcMockBuilder::new().build();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let cu_ix =
@@ -1295,7 +2027,7 @@ mod tests {
         let mut transaction =
             TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
         assert!(validator
-            .validate_transaction(get_config().unwrap(), &mut transaction, &rpc_client)
+            .validate_transaction(&get_config().unwrap(), &mut transaction, &rpc_client)
             .await
             .is_err());
     }
@@ -1314,7 +2046,7 @@ mod tests {
         setup_both_configs(config);
         let rpc_client = RpcMockBuilder::new().build();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let sender = Pubkey::new_unique();
         let recipient = Pubkey::new_unique();
@@ -1327,7 +2059,7 @@ mod tests {
         let mut transaction =
             TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
         assert!(validator
-            .validate_transaction(get_config().unwrap(), &mut transaction, &rpc_client)
+            .validate_transaction(&get_config().unwrap(), &mut transaction, &rpc_client)
             .await
             .is_ok());
     }
@@ -1346,7 +2078,7 @@ mod tests {
         setup_both_configs(config);
         let rpc_client = RpcMockBuilder::new().build();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let sender = Pubkey::new_unique();
         let recipient = Pubkey::new_unique();
@@ -1356,7 +2088,7 @@ mod tests {
         let mut transaction =
             TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
         assert!(validator
-            .validate_transaction(get_config().unwrap(), &mut transaction, &rpc_client)
+            .validate_transaction(&get_config().unwrap(), &mut transaction, &rpc_client)
             .await
             .is_err());
     }
@@ -1375,7 +2107,7 @@ mod tests {
         setup_both_configs(config);
         let rpc_client = RpcMockBuilder::new().build();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let sender = Pubkey::new_unique();
         let recipient = Pubkey::new_unique();
@@ -1386,7 +2118,7 @@ mod tests {
         let mut transaction =
             TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
         assert!(validator
-            .validate_transaction(get_config().unwrap(), &mut transaction, &rpc_client)
+            .validate_transaction(&get_config().unwrap(), &mut transaction, &rpc_client)
             .await
             .is_ok());
     }
@@ -1405,7 +2137,7 @@ mod tests {
         update_config(config).unwrap();
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let sender = Pubkey::new_unique();
         let recipient = Pubkey::new_unique();
@@ -1433,7 +2165,7 @@ mod tests {
         setup_default_config();
         let rpc_client = RpcMockBuilder::new().build();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let sender = Pubkey::new_unique();
         let recipient = Pubkey::new_unique();
@@ -1466,7 +2198,7 @@ mod tests {
         setup_default_config();
         let rpc_client = RpcMockBuilder::new().build();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         // Create an empty message using Message::new with empty instructions
@@ -1492,7 +2224,7 @@ mod tests {
         update_config(config).unwrap();
         let rpc_client = RpcMockBuilder::new().build();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(200_000);
@@ -1525,7 +2257,7 @@ mod tests {
         update_config(config).unwrap();
         let rpc_client = RpcMockBuilder::new().build();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(200_000);
@@ -1559,7 +2291,7 @@ mod tests {
         update_config(config).unwrap();
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let instruction = transfer(
             &Pubkey::from_str("hndXZGK45hCxfBYvxejAXzCfCujoqkNf7rk4sTB8pek").unwrap(),
@@ -1575,6 +2307,135 @@ mod tests {
             .is_err());
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_shadow_validation_counts_rejections_without_enforcing() {
+        let fee_payer = Pubkey::new_unique();
+        let sender = Pubkey::new_unique();
+        let mut config = ConfigMockBuilder::new()
+            .with_price_source(PriceSource::Mock)
+            .with_allowed_programs(vec![SYSTEM_PROGRAM_ID.to_string()])
+            .with_max_allowed_lamports(1_000_000)
+            .with_fee_payer_policy(FeePayerPolicy::default())
+            .build();
+        let mut candidate = config.validation.clone();
+        candidate.disallowed_accounts = vec![sender.to_string()];
+        config.validation.shadow = Some(Box::new(candidate));
+        setup_both_configs(config);
+
+        let rpc_client = RpcMockBuilder::new().build();
+        let config = &get_config().unwrap();
+        let validator = TransactionValidator::new(config, fee_payer).unwrap();
+        let rejections =
+            ShadowMetrics::get().rejections.with_label_values(&["disallowed_accounts"]);
+        let before = rejections.get();
+
+        // Accepted by the enforced config, rejected by the candidate
+        let instruction = transfer(&sender, &Pubkey::new_unique(), 1000);
+        let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        assert!(validator
+            .validate_transaction(config, &mut transaction, &rpc_client)
+            .await
+            .is_ok());
+        // The candidate runs in the background
+        for _ in 0..100 {
+            if rejections.get() > before {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(rejections.get(), before + 1);
+
+        // Rejected by the enforced config: the candidate is not evaluated
+        let instruction = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[0],
+            vec![AccountMeta::new(sender, true)],
+        );
+        let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        assert!(validator
+            .validate_transaction(config, &mut transaction, &rpc_client)
+            .await
+            .is_err());
+        assert_eq!(rejections.get(), before + 1);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_collect_findings_reports_every_violation() {
+        let fee_payer = Pubkey::new_unique();
+        let disallowed = Pubkey::new_unique();
+        let mut policy = FeePayerPolicy::default();
+        policy.system.allow_transfer = false;
+        let config = ConfigMockBuilder::new()
+            .with_price_source(PriceSource::Mock)
+            .with_allowed_programs(vec![SYSTEM_PROGRAM_ID.to_string()])
+            .with_max_allowed_lamports(1_000_000)
+            .with_disallowed_accounts(vec![disallowed.to_string()])
+            .with_fee_payer_policy(policy)
+            .build();
+        setup_both_configs(config);
+        crate::tests::common::setup_or_get_test_usage_limiter().await.unwrap();
+
+        let rpc_client = RpcMockBuilder::new().build();
+        let config = &get_config().unwrap();
+        let validator = TransactionValidator::new(config, fee_payer).unwrap();
+
+        let instructions = [
+            Instruction::new_with_bincode(Pubkey::new_unique(), &[0u8], vec![]),
+            transfer(&disallowed, &Pubkey::new_unique(), 1000),
+            transfer(&fee_payer, &Pubkey::new_unique(), 1000),
+        ];
+        let message = VersionedMessage::Legacy(Message::new(&instructions, Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+
+        // validate_transaction only reports the first violation
+        assert!(validator
+            .validate_transaction(config, &mut transaction, &rpc_client)
+            .await
+            .is_err());
+
+        let findings =
+            validator.collect_findings(config, &mut transaction, &rpc_client, None).await.unwrap();
+        let has_finding = |rule: ValidationRule, index: usize| {
+            findings.iter().any(|f| f.rule == rule && f.instruction_index == Some(index))
+        };
+
+        assert!(has_finding(ValidationRule::AllowedPrograms, 0), "{findings:?}");
+        assert!(has_finding(ValidationRule::DisallowedAccounts, 1), "{findings:?}");
+        assert!(has_finding(ValidationRule::FeePayerPolicy, 2), "{findings:?}");
+        assert!(!has_finding(ValidationRule::FeePayerPolicy, 1), "{findings:?}");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_collect_findings_stops_at_structure() {
+        let fee_payer = Pubkey::new_unique();
+        setup_default_config();
+        let rpc_client = RpcMockBuilder::new().build();
+        let config = &get_config().unwrap();
+        let validator = TransactionValidator::new(config, fee_payer).unwrap();
+
+        let message = VersionedMessage::Legacy(Message::new(
+            &[ComputeBudgetInstruction::set_compute_unit_limit(200_000)],
+            Some(&fee_payer),
+        ));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+
+        let findings =
+            validator.collect_findings(config, &mut transaction, &rpc_client, None).await.unwrap();
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule, ValidationRule::TransactionStructure);
+        assert_eq!(findings[0].instruction_index, None);
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_disallowed_instruction_data_spl_set_authority_new_authority() {
@@ -1591,7 +2452,7 @@ mod tests {
             vec![disallowed_account.to_string()],
         );
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let instruction = spl_token_interface::instruction::set_authority(
@@ -1627,7 +2488,7 @@ mod tests {
             vec![disallowed_account.to_string()],
         );
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let instruction = spl_token_2022_interface::instruction::set_authority(
@@ -1665,7 +2526,7 @@ mod tests {
             vec![disallowed_account.to_string()],
         );
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let instruction = authorize_nonce_account(&nonce_account, &fee_payer, &disallowed_account);
@@ -1695,7 +2556,7 @@ mod tests {
             vec![disallowed_account.to_string()],
         );
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let instructions =
@@ -1727,7 +2588,7 @@ mod tests {
             vec![disallowed_account.to_string()],
         );
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let instruction = spl_token_interface::instruction::initialize_account2(
@@ -1761,7 +2622,7 @@ mod tests {
             vec![disallowed_account.to_string()],
         );
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let instruction = spl_token_interface::instruction::initialize_mint2(
@@ -1792,7 +2653,7 @@ mod tests {
         policy.system.allow_transfer = true;
         setup_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let instruction = transfer(&fee_payer, &recipient, 1000);
@@ -1811,7 +2672,7 @@ mod tests {
         policy.system.allow_transfer = false;
         setup_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let instruction = transfer(&fee_payer, &recipient, 1000);
@@ -1839,7 +2700,7 @@ mod tests {
         policy.system.allow_assign = true;
         setup_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let instruction = assign(&fee_payer, &new_owner);
@@ -1859,7 +2720,7 @@ mod tests {
         policy.system.allow_assign = false;
         setup_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let instruction = assign(&fee_payer, &new_owner);
@@ -1886,7 +2747,7 @@ mod tests {
 
         // allow_assign is true, but an owner outside the allowed programs list is still rejected.
         setup_config_with_policy(policy.clone());
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let instruction = assign(&fee_payer, &off_policy_owner);
@@ -1913,7 +2774,7 @@ mod tests {
             vec![SYSTEM_PROGRAM_ID.to_string(), off_policy_owner.to_string()],
             vec![off_policy_owner.to_string()],
         );
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let instruction = assign(&fee_payer, &off_policy_owner);
@@ -1948,7 +2809,7 @@ mod tests {
         let mut policy = FeePayerPolicy::default();
         policy.system.allow_assign = true;
         setup_config_with_policy(policy.clone());
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let instruction = assign(&other_account, &off_policy_owner);
@@ -1966,7 +2827,7 @@ mod tests {
             vec![SYSTEM_PROGRAM_ID.to_string(), off_policy_owner.to_string()],
             vec![off_policy_owner.to_string()],
         );
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let instruction = assign(&other_account, &off_policy_owner);
@@ -2000,7 +2861,7 @@ mod tests {
         policy.spl_token.allow_transfer = true;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let transfer_ix = spl_token_interface::instruction::transfer(
@@ -2029,7 +2890,7 @@ mod tests {
         policy.spl_token.allow_transfer = false;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let transfer_ix = spl_token_interface::instruction::transfer(
@@ -2108,7 +2969,7 @@ mod tests {
         let mut policy = FeePayerPolicy::default();
         policy.spl_token.allow_transfer = false;
         setup_spl_config_with_policy(policy);
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let mut transaction = build_batched_transfer();
         assert!(validator
@@ -2122,7 +2983,7 @@ mod tests {
         let mut policy = FeePayerPolicy::default();
         policy.spl_token.allow_transfer = true;
         setup_spl_config_with_policy(policy);
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let mut transaction = build_batched_transfer();
         assert!(validator
@@ -2163,7 +3024,7 @@ mod tests {
         let mut policy = FeePayerPolicy::default();
         policy.spl_token.allow_unwrap_lamports = false;
         setup_spl_config_with_policy(policy);
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let mut transaction = build_unwrap();
         assert!(validator
@@ -2177,7 +3038,7 @@ mod tests {
         let mut policy = FeePayerPolicy::default();
         policy.spl_token.allow_unwrap_lamports = true;
         setup_spl_config_with_policy(policy);
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let mut transaction = build_unwrap();
         assert!(validator
@@ -2204,7 +3065,7 @@ mod tests {
         policy.token_2022.allow_transfer = true;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let transfer_ix = spl_token_2022_interface::instruction::transfer_checked(
@@ -2235,7 +3096,7 @@ mod tests {
         policy.token_2022.allow_transfer = false;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let transfer_ix = spl_token_2022_interface::instruction::transfer_checked(
@@ -2297,7 +3158,7 @@ mod tests {
         policy.alt.allow_freeze = true;
         setup_alt_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let freeze_ix = alt_instruction::freeze_lookup_table(lookup_table, fee_payer);
@@ -2315,7 +3176,7 @@ mod tests {
         policy.alt.allow_freeze = false;
         setup_alt_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let freeze_ix = alt_instruction::freeze_lookup_table(lookup_table, fee_payer);
@@ -2352,7 +3213,7 @@ mod tests {
         policy.alt.allow_create = false;
         setup_alt_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let (create_ix, _table_address) =
@@ -2371,7 +3232,7 @@ mod tests {
         policy.alt.allow_create = true;
         setup_alt_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let (create_ix, _table_address) =
@@ -2397,7 +3258,7 @@ mod tests {
         policy.alt.allow_extend = false;
         setup_alt_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let extend_ix = alt_instruction::extend_lookup_table(
@@ -2437,7 +3298,7 @@ mod tests {
         policy.alt.allow_extend = true;
         setup_alt_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let extend_ix = alt_instruction::extend_lookup_table(
@@ -2460,7 +3321,7 @@ mod tests {
         policy.alt.allow_extend = false;
         setup_alt_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let extend_ix = alt_instruction::extend_lookup_table(
@@ -2490,7 +3351,7 @@ mod tests {
         policy.alt.allow_deactivate = true;
         setup_alt_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let deactivate_ix = alt_instruction::deactivate_lookup_table(lookup_table, fee_payer);
@@ -2508,7 +3369,7 @@ mod tests {
         policy.alt.allow_deactivate = false;
         setup_alt_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let deactivate_ix = alt_instruction::deactivate_lookup_table(lookup_table, fee_payer);
@@ -2550,7 +3411,7 @@ mod tests {
         policy.alt.allow_close = true;
         setup_alt_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let close_ix = alt_instruction::close_lookup_table(lookup_table, fee_payer, recipient);
@@ -2568,7 +3429,7 @@ mod tests {
         policy.alt.allow_close = false;
         setup_alt_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let close_ix = alt_instruction::close_lookup_table(lookup_table, fee_payer, recipient);
@@ -2616,7 +3477,7 @@ mod tests {
         setup_both_configs(config);
 
         let rpc_client = RpcMockBuilder::new().with_account_info(&alt_account).build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let close_ix = alt_instruction::close_lookup_table(lookup_table, fee_payer, recipient);
@@ -2653,7 +3514,7 @@ mod tests {
         setup_both_configs(config);
 
         let rpc_client = RpcMockBuilder::new().with_account_info(&alt_account).build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let close_ix = alt_instruction::close_lookup_table(lookup_table, fee_payer, fee_payer);
@@ -2686,7 +3547,7 @@ mod tests {
         setup_both_configs(config);
 
         let rpc_client = RpcMockBuilder::new().with_account_info(&rent_account).build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let close_ix = spl_token_2022_interface::instruction::close_account(
@@ -2722,7 +3583,7 @@ mod tests {
         update_config(config).unwrap();
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         // Test 1: Fee payer as sender in Transfer - should add to outflow
@@ -2858,7 +3719,7 @@ mod tests {
             .build();
         update_config(config).unwrap();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let nonce_account = Pubkey::new_unique();
         let withdraw_instruction = solana_system_interface::instruction::withdraw_nonce_account(
@@ -2893,7 +3754,7 @@ mod tests {
         policy.spl_token.allow_burn = true;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let burn_ix = spl_token_interface::instruction::burn(
@@ -2922,7 +3783,7 @@ mod tests {
         policy.spl_token.allow_burn = false;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let burn_ix = spl_token_interface::instruction::burn(
@@ -2983,7 +3844,7 @@ mod tests {
         policy.spl_token.allow_close_account = true;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let close_ix = spl_token_interface::instruction::close_account(
@@ -3010,7 +3871,7 @@ mod tests {
         policy.spl_token.allow_close_account = false;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let close_ix = spl_token_interface::instruction::close_account(
@@ -3047,7 +3908,7 @@ mod tests {
         policy.spl_token.allow_approve = true;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let approve_ix = spl_token_interface::instruction::approve(
@@ -3075,7 +3936,7 @@ mod tests {
         policy.spl_token.allow_approve = false;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let approve_ix = spl_token_interface::instruction::approve(
@@ -3138,7 +3999,7 @@ mod tests {
         policy.token_2022.allow_burn = false;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let burn_ix = spl_token_2022_interface::instruction::burn(
@@ -3175,7 +4036,7 @@ mod tests {
         policy.token_2022.allow_close_account = false;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let close_ix = spl_token_2022_interface::instruction::close_account(
@@ -3211,7 +4072,7 @@ mod tests {
         policy.token_2022.allow_approve = true;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let approve_ix = spl_token_2022_interface::instruction::approve(
@@ -3240,7 +4101,7 @@ mod tests {
         policy.token_2022.allow_approve = false;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let approve_ix = spl_token_2022_interface::instruction::approve(
@@ -3305,7 +4166,7 @@ mod tests {
         policy.system.allow_create_account = true;
         setup_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let instruction = create_account(&fee_payer, &new_account, 1000, 100, &owner);
         let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
@@ -3322,7 +4183,7 @@ mod tests {
         policy.system.allow_create_account = false;
         setup_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let instruction = create_account(&fee_payer, &new_account, 1000, 100, &owner);
         let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
@@ -3395,7 +4256,7 @@ mod tests {
             policy.system.allow_create_account = allow;
             setup_config_with_policy(policy);
 
-            let config = get_config().unwrap();
+            let config = &get_config().unwrap();
             let validator = TransactionValidator::new(config, fee_payer).unwrap();
             let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
             let mut transaction =
@@ -3418,7 +4279,7 @@ mod tests {
         let mut policy = FeePayerPolicy::default();
         policy.system.allow_create_account = false;
         setup_config_with_policy(policy);
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         // Outer message: a transfer not involving the fee payer — policy-neutral, keeps the tx valid.
@@ -3458,7 +4319,7 @@ mod tests {
         policy.system.allow_create_account = true;
         setup_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let instruction = create_account(&fee_payer, &new_account, 1000, 100, &disallowed_owner);
         let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
@@ -3490,7 +4351,7 @@ mod tests {
             .build();
         setup_both_configs(config);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let instruction = create_account(&fee_payer, &new_account, 1000, 100, &allowed_owner);
         let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
@@ -3519,7 +4380,7 @@ mod tests {
         policy.system.allow_create_account = true;
         setup_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         // Kora sponsors a CreateAccountWithSeed funded by the attacker. The owner must still be
@@ -3557,7 +4418,7 @@ mod tests {
         policy.system.allow_create_account = false;
         setup_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         // Owner is System (allowed), so the rejection must come from the allow_create_account gate
@@ -3597,7 +4458,7 @@ mod tests {
         policy.system.allow_create_account = false;
         setup_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         // The authoritative base lives in the instruction data. A decoy sits at the account slot
@@ -3644,7 +4505,7 @@ mod tests {
         policy.system.allow_create_account = false;
         setup_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         // base == from, so the runtime accepts two accounts (no separate base meta). Kora is
@@ -3693,7 +4554,7 @@ mod tests {
             .build();
         update_config(config).unwrap();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let ata_ix =
             spl_associated_token_account_interface::instruction::create_associated_token_account_idempotent(
@@ -3740,7 +4601,7 @@ mod tests {
             .build();
         update_config(config).unwrap();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let ata_ix =
             spl_associated_token_account_interface::instruction::create_associated_token_account_idempotent(
@@ -3776,7 +4637,7 @@ mod tests {
         policy.system.allow_allocate = true;
         setup_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let instruction = allocate(&fee_payer, 100);
         let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
@@ -3793,7 +4654,7 @@ mod tests {
         policy.system.allow_allocate = false;
         setup_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let instruction = allocate(&fee_payer, 100);
         let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
@@ -3819,7 +4680,7 @@ mod tests {
         policy.system.nonce.allow_initialize = true;
         setup_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let instructions = create_nonce_account(&fee_payer, &nonce_account, &fee_payer, 1_000_000);
         // Only test the InitializeNonceAccount instruction (second one)
@@ -3838,7 +4699,7 @@ mod tests {
         policy.system.nonce.allow_initialize = false;
         setup_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let instructions = create_nonce_account(&fee_payer, &nonce_account, &fee_payer, 1_000_000);
         let message =
@@ -3872,7 +4733,7 @@ mod tests {
             .build();
         update_config(config).unwrap();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let instruction = advance_nonce_account(&nonce_account, &fee_payer);
         let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
@@ -3896,7 +4757,7 @@ mod tests {
             .build();
         update_config(config).unwrap();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let instruction = advance_nonce_account(&nonce_account, &fee_payer);
         let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
@@ -3923,7 +4784,7 @@ mod tests {
         policy.system.nonce.allow_withdraw = true;
         setup_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let instruction = withdraw_nonce_account(&nonce_account, &fee_payer, &recipient, 1000);
         let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
@@ -3940,7 +4801,7 @@ mod tests {
         policy.system.nonce.allow_withdraw = false;
         setup_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let instruction = withdraw_nonce_account(&nonce_account, &fee_payer, &recipient, 1000);
         let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
@@ -3974,7 +4835,7 @@ mod tests {
             .build();
         update_config(config).unwrap();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let instructions = vec![
             withdraw_nonce_account(&nonce_account, &fee_payer, &fee_payer, 1_000),
@@ -4009,7 +4870,7 @@ mod tests {
         policy.system.nonce.allow_authorize = true;
         setup_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let instruction = authorize_nonce_account(&nonce_account, &fee_payer, &new_authority);
         let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
@@ -4026,7 +4887,7 @@ mod tests {
         policy.system.nonce.allow_authorize = false;
         setup_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let instruction = authorize_nonce_account(&nonce_account, &fee_payer, &new_authority);
         let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
@@ -4052,7 +4913,7 @@ mod tests {
         // Fixed price = 5000, but total = 3000 + 2000 + 5000 = 10000 > 5000
         let fee_calc = TotalFeeCalculation::new(5000, 3000, 2000, 5000, 0, 0);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let result = TransactionValidator::validate_strict_pricing_with_fee(config, &fee_calc);
 
         assert!(result.is_err());
@@ -4078,7 +4939,7 @@ mod tests {
         // Fixed price = 5000, total = 1000 + 1000 + 1000 = 3000 < 5000
         let fee_calc = TotalFeeCalculation::new(5000, 1000, 1000, 1000, 0, 0);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let result = TransactionValidator::validate_strict_pricing_with_fee(config, &fee_calc);
 
         assert!(result.is_ok());
@@ -4097,7 +4958,7 @@ mod tests {
 
         let fee_calc = TotalFeeCalculation::new(5000, 10000, 0, 0, 0, 0);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let result = TransactionValidator::validate_strict_pricing_with_fee(config, &fee_calc);
 
         assert!(result.is_ok(), "Should pass when strict=false");
@@ -4116,7 +4977,7 @@ mod tests {
 
         let fee_calc = TotalFeeCalculation::new(5000, 10000, 0, 0, 0, 0);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let result = TransactionValidator::validate_strict_pricing_with_fee(config, &fee_calc);
 
         assert!(result.is_ok());
@@ -4140,7 +5001,7 @@ mod tests {
         // Total exactly equals fixed price (5000 = 5000)
         let fee_calc = TotalFeeCalculation::new(5000, 2000, 1000, 2000, 0, 0);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let result = TransactionValidator::validate_strict_pricing_with_fee(config, &fee_calc);
 
         assert!(result.is_ok(), "Should pass when total equals fixed price");
@@ -4160,7 +5021,7 @@ mod tests {
         // Sub-lamport configured price floors to 0 but real cost is positive (base_fee = 5000).
         let fee_calc = TotalFeeCalculation::new(0, 5000, 0, 0, 0, 0);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let result = TransactionValidator::validate_strict_pricing_with_fee(config, &fee_calc);
 
         assert!(result.is_err(), "Strict mode must reject a zero quote with positive real cost");
@@ -4184,7 +5045,7 @@ mod tests {
         setup_default_config();
         let rpc_client = RpcMockBuilder::new().build();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         // Transaction with AdvanceNonceAccount (authority is NOT fee payer)
@@ -4223,7 +5084,7 @@ mod tests {
         update_config(mock_config).unwrap();
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         // Transaction with AdvanceNonceAccount (authority is NOT fee payer)
@@ -4239,6 +5100,66 @@ mod tests {
             .is_ok());
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_durable_transaction_allowed_on_leased_pool_nonce() {
+        use crate::nonce::{get_nonce_lease_store, NonceLease};
+        use solana_system_interface::instruction::advance_nonce_account;
+
+        let fee_payer = Pubkey::new_unique();
+        let leased_nonce = Pubkey::new_unique();
+        let unleased_nonce = Pubkey::new_unique();
+        let foreign_nonce = Pubkey::new_unique();
+
+        // Durable transactions and fee payer advances stay disabled
+        let mock_config = ConfigMockBuilder::new()
+            .with_price_source(PriceSource::Mock)
+            .with_allowed_programs(vec![SYSTEM_PROGRAM_ID.to_string()])
+            .with_max_allowed_lamports(1_000_000)
+            .with_fee_payer_policy(FeePayerPolicy::default())
+            .with_nonce_pool_enabled(true)
+            .build();
+        update_config(mock_config).unwrap();
+
+        let lease = |nonce_account: &Pubkey, lessee: Option<&str>| NonceLease {
+            nonce_account: nonce_account.to_string(),
+            nonce_authority: fee_payer.to_string(),
+            nonce: solana_sdk::hash::Hash::new_unique().to_string(),
+            lessee: lessee.map(str::to_string),
+            expires_at: chrono::Utc::now().timestamp() + 60,
+        };
+        let store = get_nonce_lease_store();
+        store.try_lease(&lease(&leased_nonce, None)).await.unwrap();
+        // Leased to another API key than the requester's (none in this test)
+        store.try_lease(&lease(&foreign_nonce, Some("api-key:other"))).await.unwrap();
+
+        let rpc_client = RpcMockBuilder::new().build();
+        let config = &get_config().unwrap();
+        let validator = TransactionValidator::new(config, fee_payer).unwrap();
+
+        let instruction = advance_nonce_account(&leased_nonce, &fee_payer);
+        let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        assert!(validator
+            .validate_transaction(config, &mut transaction, &rpc_client)
+            .await
+            .is_ok());
+
+        for nonce_account in [unleased_nonce, foreign_nonce] {
+            let instruction = advance_nonce_account(&nonce_account, &fee_payer);
+            let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
+            let mut transaction =
+                TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+            let result =
+                validator.validate_transaction(config, &mut transaction, &rpc_client).await;
+            assert!(matches!(
+                result,
+                Err(KoraError::InvalidTransaction(msg)) if msg.contains("Durable transactions")
+            ));
+        }
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_non_durable_transaction_passes() {
@@ -4250,7 +5171,7 @@ mod tests {
         setup_default_config();
         let rpc_client = RpcMockBuilder::new().build();
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         // Regular transfer (no nonce instruction)
@@ -4278,7 +5199,7 @@ mod tests {
         policy.spl_token.allow_revoke = true;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let revoke_ix = spl_token_interface::instruction::revoke(
@@ -4303,7 +5224,7 @@ mod tests {
         policy.spl_token.allow_revoke = false;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let revoke_ix = spl_token_interface::instruction::revoke(
@@ -4337,7 +5258,7 @@ mod tests {
         policy.token_2022.allow_revoke = true;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let revoke_ix = spl_token_2022_interface::instruction::revoke(
@@ -4362,7 +5283,7 @@ mod tests {
         policy.token_2022.allow_revoke = false;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let revoke_ix = spl_token_2022_interface::instruction::revoke(
@@ -4397,7 +5318,7 @@ mod tests {
         policy.spl_token.allow_set_authority = true;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let set_authority_ix = spl_token_interface::instruction::set_authority(
@@ -4424,7 +5345,7 @@ mod tests {
         policy.spl_token.allow_set_authority = false;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let set_authority_ix = spl_token_interface::instruction::set_authority(
@@ -4461,7 +5382,7 @@ mod tests {
         policy.token_2022.allow_set_authority = true;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let set_authority_ix = spl_token_2022_interface::instruction::set_authority(
@@ -4488,7 +5409,7 @@ mod tests {
         policy.token_2022.allow_set_authority = false;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let set_authority_ix = spl_token_2022_interface::instruction::set_authority(
@@ -4525,7 +5446,7 @@ mod tests {
         policy.spl_token.allow_mint_to = true;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let mint_to_ix = spl_token_interface::instruction::mint_to(
@@ -4552,7 +5473,7 @@ mod tests {
         policy.spl_token.allow_mint_to = false;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let mint_to_ix = spl_token_interface::instruction::mint_to(
@@ -4589,7 +5510,7 @@ mod tests {
         policy.token_2022.allow_mint_to = true;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let mint_to_ix = spl_token_2022_interface::instruction::mint_to(
@@ -4616,7 +5537,7 @@ mod tests {
         policy.token_2022.allow_mint_to = false;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let mint_to_ix = spl_token_2022_interface::instruction::mint_to(
@@ -4652,7 +5573,7 @@ mod tests {
         policy.spl_token.allow_initialize_mint = true;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         // fee_payer is the mint_authority (encoded in instruction data)
@@ -4679,7 +5600,7 @@ mod tests {
         policy.spl_token.allow_initialize_mint = false;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let init_mint_ix = spl_token_interface::instruction::initialize_mint(
@@ -4714,7 +5635,7 @@ mod tests {
         policy.token_2022.allow_initialize_mint = true;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let init_mint_ix = spl_token_2022_interface::instruction::initialize_mint(
@@ -4740,7 +5661,7 @@ mod tests {
         policy.token_2022.allow_initialize_mint = false;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let init_mint_ix = spl_token_2022_interface::instruction::initialize_mint(
@@ -4777,7 +5698,7 @@ mod tests {
         policy.spl_token.allow_initialize_account = true;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let init_account_ix = spl_token_interface::instruction::initialize_account(
@@ -4802,7 +5723,7 @@ mod tests {
         policy.spl_token.allow_initialize_account = false;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let init_account_ix = spl_token_interface::instruction::initialize_account(
@@ -4837,7 +5758,7 @@ mod tests {
         policy.token_2022.allow_initialize_account = true;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let init_account_ix = spl_token_2022_interface::instruction::initialize_account(
@@ -4862,7 +5783,7 @@ mod tests {
         policy.token_2022.allow_initialize_account = false;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let init_account_ix = spl_token_2022_interface::instruction::initialize_account(
@@ -4898,7 +5819,7 @@ mod tests {
         policy.spl_token.allow_initialize_multisig = true;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let init_multisig_ix = spl_token_interface::instruction::initialize_multisig(
@@ -4923,7 +5844,7 @@ mod tests {
         policy.spl_token.allow_initialize_multisig = false;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let init_multisig_ix = spl_token_interface::instruction::initialize_multisig(
@@ -4958,7 +5879,7 @@ mod tests {
         policy.token_2022.allow_initialize_multisig = true;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let init_multisig_ix = spl_token_2022_interface::instruction::initialize_multisig(
@@ -4983,7 +5904,7 @@ mod tests {
         policy.token_2022.allow_initialize_multisig = false;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let init_multisig_ix = spl_token_2022_interface::instruction::initialize_multisig(
@@ -5019,7 +5940,7 @@ mod tests {
         policy.spl_token.allow_freeze_account = true;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let freeze_ix = spl_token_interface::instruction::freeze_account(
@@ -5045,7 +5966,7 @@ mod tests {
         policy.spl_token.allow_freeze_account = false;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let freeze_ix = spl_token_interface::instruction::freeze_account(
@@ -5081,7 +6002,7 @@ mod tests {
         policy.token_2022.allow_freeze_account = true;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let freeze_ix = spl_token_2022_interface::instruction::freeze_account(
@@ -5107,7 +6028,7 @@ mod tests {
         policy.token_2022.allow_freeze_account = false;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let freeze_ix = spl_token_2022_interface::instruction::freeze_account(
@@ -5144,7 +6065,7 @@ mod tests {
         policy.spl_token.allow_thaw_account = true;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let thaw_ix = spl_token_interface::instruction::thaw_account(
@@ -5170,7 +6091,7 @@ mod tests {
         policy.spl_token.allow_thaw_account = false;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let thaw_ix = spl_token_interface::instruction::thaw_account(
@@ -5206,7 +6127,7 @@ mod tests {
         policy.token_2022.allow_thaw_account = true;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let thaw_ix = spl_token_2022_interface::instruction::thaw_account(
@@ -5232,7 +6153,7 @@ mod tests {
         policy.token_2022.allow_thaw_account = false;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let thaw_ix = spl_token_2022_interface::instruction::thaw_account(
@@ -5264,7 +6185,7 @@ mod tests {
         let rpc_client = RpcMockBuilder::new().build();
         setup_token2022_config_with_policy(FeePayerPolicy::default());
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let reallocate_ix = spl_token_2022_interface::instruction::reallocate(
@@ -5296,7 +6217,7 @@ mod tests {
         let rpc_client = RpcMockBuilder::new().build();
         setup_token2022_config_with_policy(FeePayerPolicy::default());
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer.pubkey()).unwrap();
         let mint = Pubkey::new_unique();
 
@@ -5328,7 +6249,7 @@ mod tests {
         policy.token_2022.allow_thaw_account = true;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer.pubkey()).unwrap();
         let mint = Pubkey::new_unique();
 
@@ -5355,7 +6276,7 @@ mod tests {
         let rpc_client = RpcMockBuilder::new().build();
         setup_token2022_config_with_policy(FeePayerPolicy::default());
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer.pubkey()).unwrap();
         let mint = Pubkey::new_unique();
 
@@ -5384,7 +6305,7 @@ mod tests {
         let rpc_client = RpcMockBuilder::new().build();
         setup_token2022_config_with_policy(FeePayerPolicy::default());
 
-        let mut config = get_config().unwrap().clone();
+        let mut config = get_config().unwrap().as_ref().clone();
         config.validation.token_2022.transfer_hook_policy = TransferHookPolicy::DenyAll;
         config.validation.fee_payer_policy.token_2022.allow_update_extension_authority = true;
         let validator = TransactionValidator::new(&config, fee_payer.pubkey()).unwrap();
@@ -5425,7 +6346,7 @@ mod tests {
         let rpc_client = RpcMockBuilder::new().build();
         setup_token2022_config_with_policy(FeePayerPolicy::default());
 
-        let mut config = get_config().unwrap().clone();
+        let mut config = get_config().unwrap().as_ref().clone();
         config.validation.token_2022.transfer_hook_policy =
             TransferHookPolicy::DenyMutableForDelayedSigning;
         config.validation.fee_payer_policy.token_2022.allow_update_extension_authority = true;
@@ -5473,7 +6394,7 @@ mod tests {
             vec![disallowed_program_id.to_string()],
         );
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer.pubkey()).unwrap();
 
         let ix = spl_token_2022_interface::extension::transfer_hook::instruction::initialize(
@@ -5504,7 +6425,7 @@ mod tests {
         let rpc_client = RpcMockBuilder::new().build();
         setup_token2022_config_with_policy(FeePayerPolicy::default());
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer.pubkey()).unwrap();
 
         let ix = spl_token_2022_interface::extension::transfer_fee::instruction::initialize_transfer_fee_config(
@@ -5542,7 +6463,7 @@ mod tests {
             vec![disallowed_authority.to_string()],
         );
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer.pubkey()).unwrap();
 
         let ix = spl_token_2022_interface::extension::transfer_fee::instruction::initialize_transfer_fee_config(
@@ -5575,7 +6496,7 @@ mod tests {
         let rpc_client = RpcMockBuilder::new().build();
         setup_token2022_config_with_policy(FeePayerPolicy::default());
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer.pubkey()).unwrap();
 
         let ix = spl_token_2022_interface::extension::metadata_pointer::instruction::update(
@@ -5609,7 +6530,7 @@ mod tests {
         policy.token_2022.allow_update_extension_authority = true;
         setup_token2022_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer.pubkey()).unwrap();
 
         let ix = spl_token_2022_interface::extension::metadata_pointer::instruction::update(
@@ -5645,7 +6566,7 @@ mod tests {
             vec![disallowed_authority.to_string()],
         );
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer.pubkey()).unwrap();
 
         let ix = spl_token_2022_interface::instruction::initialize_mint_close_authority(
@@ -5836,7 +6757,7 @@ mod tests {
         let rpc_client = RpcMockBuilder::new().build();
         setup_token2022_config_with_policy(FeePayerPolicy::default());
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let confidential_ix = Instruction {
@@ -5865,7 +6786,7 @@ mod tests {
         let rpc_client = RpcMockBuilder::new().build();
         setup_token2022_config_with_policy(FeePayerPolicy::default());
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let confidential_ix = Instruction {
@@ -5894,7 +6815,7 @@ mod tests {
         let rpc_client = RpcMockBuilder::new().build();
         setup_token2022_config_confidential_allowed(FeePayerPolicy::default());
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let confidential_variants = [
@@ -5929,7 +6850,7 @@ mod tests {
         let rpc_client = RpcMockBuilder::new().build();
         setup_token2022_config_confidential_allowed(FeePayerPolicy::default());
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let confidential_ix = Instruction {
@@ -5988,7 +6909,7 @@ mod tests {
         policy.spl_token.allow_burn = true;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let message = VersionedMessage::Legacy(Message::new(
@@ -6009,7 +6930,7 @@ mod tests {
         policy.spl_token.allow_burn = false;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let message = VersionedMessage::Legacy(Message::new(
@@ -6032,7 +6953,7 @@ mod tests {
         policy.spl_token.allow_burn = true;
         setup_spl_config_with_policy(policy);
 
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let message = VersionedMessage::Legacy(Message::new(
@@ -6053,6 +6974,147 @@ mod tests {
     // Loader-v4 tests
     // ----------------------------------------------------------------------------
 
+    fn setup_stake_config_with_policy(policy: FeePayerPolicy) {
+        let config = ConfigMockBuilder::new()
+            .with_price_source(PriceSource::Mock)
+            .with_allowed_programs(vec![
+                STAKE_PROGRAM_ID.to_string(),
+                SYSTEM_PROGRAM_ID.to_string(),
+            ])
+            .with_max_allowed_lamports(10_000_000_000)
+            .with_fee_payer_policy(policy)
+            .build();
+        setup_both_configs(config);
+    }
+
+    async fn validate_stake_instruction(
+        fee_payer: Pubkey,
+        instruction: Instruction,
+    ) -> Result<(), KoraError> {
+        let rpc_client = RpcMockBuilder::new().build();
+        let config = &get_config().unwrap();
+        let validator = TransactionValidator::new(config, fee_payer).unwrap();
+
+        let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer)));
+        let mut transaction =
+            TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
+        validator.validate_transaction(config, &mut transaction, &rpc_client).await
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_stake_delegate_requires_policy() {
+        use solana_stake_interface::instruction as stake_instruction;
+        let fee_payer = Pubkey::new_unique();
+        let stake = Pubkey::new_unique();
+        let vote = Pubkey::new_unique();
+
+        setup_stake_config_with_policy(FeePayerPolicy::default());
+        let err = validate_stake_instruction(
+            fee_payer,
+            stake_instruction::delegate_stake(&stake, &fee_payer, &vote),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("Stake DelegateStake"), "unexpected error: {err}");
+
+        // A user-owned stake authority does not involve the fee payer
+        let user = Pubkey::new_unique();
+        assert!(validate_stake_instruction(
+            fee_payer,
+            stake_instruction::delegate_stake(&stake, &user, &vote)
+        )
+        .await
+        .is_ok());
+
+        let mut policy = FeePayerPolicy::default();
+        policy.stake.allow_delegate = true;
+        setup_stake_config_with_policy(policy);
+        assert!(validate_stake_instruction(
+            fee_payer,
+            stake_instruction::delegate_stake(&stake, &fee_payer, &vote)
+        )
+        .await
+        .is_ok());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_stake_initialize_checks_authorities_in_instruction_data() {
+        use solana_stake_interface::{
+            instruction as stake_instruction,
+            state::{Authorized, Lockup},
+        };
+        let fee_payer = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let initialize = |withdrawer: Pubkey| {
+            stake_instruction::initialize(
+                &Pubkey::new_unique(),
+                &Authorized { staker: user, withdrawer },
+                &Lockup::default(),
+            )
+        };
+
+        setup_stake_config_with_policy(FeePayerPolicy::default());
+        assert!(validate_stake_instruction(fee_payer, initialize(user)).await.is_ok());
+        let err = validate_stake_instruction(fee_payer, initialize(fee_payer)).await.unwrap_err();
+        assert!(err.to_string().contains("Stake Initialize"), "unexpected error: {err}");
+
+        let mut policy = FeePayerPolicy::default();
+        policy.stake.allow_initialize = true;
+        setup_stake_config_with_policy(policy);
+        assert!(validate_stake_instruction(fee_payer, initialize(fee_payer)).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_stake_withdraw_to_fee_payer_is_not_fee_payer_usage() {
+        use solana_stake_interface::instruction as stake_instruction;
+        let fee_payer = Pubkey::new_unique();
+        let stake = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+
+        setup_stake_config_with_policy(FeePayerPolicy::default());
+        assert!(validate_stake_instruction(
+            fee_payer,
+            stake_instruction::withdraw(&stake, &user, &fee_payer, 1_000, None)
+        )
+        .await
+        .is_ok());
+
+        let err = validate_stake_instruction(
+            fee_payer,
+            stake_instruction::withdraw(&stake, &fee_payer, &user, 1_000, None),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("Stake Withdraw"), "unexpected error: {err}");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_stake_policy_ignores_other_registered_parsers() {
+        let fee_payer = Pubkey::new_unique();
+        let config = ConfigMockBuilder::new()
+            .with_price_source(PriceSource::Mock)
+            .with_allowed_programs(vec![
+                STAKE_PROGRAM_ID.to_string(),
+                crate::constant::MEMO_PROGRAM_ID.to_string(),
+            ])
+            .with_max_allowed_lamports(10_000_000_000)
+            .with_fee_payer_policy(FeePayerPolicy::default())
+            .build();
+        setup_both_configs(config);
+
+        // The Memo parser rejects non-UTF-8 data, which must not fail fee payer validation
+        let memo = Instruction::new_with_bytes(
+            crate::constant::MEMO_PROGRAM_ID,
+            &[0xff, 0xfe],
+            vec![AccountMeta::new_readonly(fee_payer, true)],
+        );
+        assert!(validate_stake_instruction(fee_payer, memo).await.is_ok());
+    }
+
     fn setup_loader_v4_config_with_policy(policy: FeePayerPolicy) {
         use crate::constant::LOADER_V4_PROGRAM_ID;
         let config = ConfigMockBuilder::new()
@@ -6080,7 +7142,7 @@ mod tests {
         setup_loader_v4_config_with_policy(policy);
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let ix = loader_v4::write(&program, &fee_payer, 0, vec![1, 2, 3]);
@@ -6095,7 +7157,7 @@ mod tests {
         // default (allow_write = false) -> rejected
         setup_loader_v4_config_with_policy(FeePayerPolicy::default());
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let ix = loader_v4::write(&program, &fee_payer, 0, vec![1, 2, 3]);
@@ -6120,7 +7182,7 @@ mod tests {
         setup_loader_v4_config_with_policy(policy);
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let ix = loader_v4::deploy(&program, &fee_payer);
@@ -6135,7 +7197,7 @@ mod tests {
         // disabled -> rejected
         setup_loader_v4_config_with_policy(FeePayerPolicy::default());
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let ix = loader_v4::deploy(&program, &fee_payer);
@@ -6162,7 +7224,7 @@ mod tests {
         setup_loader_v4_config_with_policy(policy);
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let ix = loader_v4::copy(&destination, &fee_payer, &source, 0, 0, 64);
@@ -6177,7 +7239,7 @@ mod tests {
         // default (allow_copy = false) -> rejected
         setup_loader_v4_config_with_policy(FeePayerPolicy::default());
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let ix = loader_v4::copy(&destination, &fee_payer, &source, 0, 0, 64);
@@ -6202,7 +7264,7 @@ mod tests {
         setup_loader_v4_config_with_policy(policy);
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let ix = loader_v4::retract(&program, &fee_payer);
@@ -6228,7 +7290,7 @@ mod tests {
         setup_loader_v4_config_with_policy(policy);
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let ix = loader_v4::set_program_length(&program, &fee_payer, 1024, &fee_payer);
@@ -6256,7 +7318,7 @@ mod tests {
         setup_loader_v4_config_with_policy(policy);
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let ix = loader_v4::set_program_length(&program, &fee_payer, 0, &user_recipient);
@@ -6284,7 +7346,7 @@ mod tests {
         setup_loader_v4_config_with_policy(FeePayerPolicy::default());
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let ix = loader_v4::set_program_length(&program, &fee_payer, 1024, &fee_payer);
@@ -6308,7 +7370,7 @@ mod tests {
         setup_loader_v4_config_with_policy(FeePayerPolicy::default());
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let ix = loader_v4::transfer_authority(&program, &fee_payer, &new_authority);
@@ -6337,7 +7399,7 @@ mod tests {
         setup_loader_v4_config_with_policy(FeePayerPolicy::default());
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         // current_authority = attacker (not fee_payer), new_authority = fee_payer.
@@ -6362,7 +7424,7 @@ mod tests {
         setup_loader_v4_config_with_policy(FeePayerPolicy::default());
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let ix = loader_v4::finalize(&program, &fee_payer, &next_version);
@@ -6404,7 +7466,7 @@ mod tests {
         setup_bpf_v3_config_with_policy(policy);
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let ix = loader_v3::write(&buffer, &fee_payer, 0, vec![1, 2, 3]);
@@ -6419,7 +7481,7 @@ mod tests {
         // default → rejected
         setup_bpf_v3_config_with_policy(FeePayerPolicy::default());
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
         let ix = loader_v3::write(&buffer, &fee_payer, 0, vec![1, 2, 3]);
         let message = VersionedMessage::Legacy(Message::new(&[ix], Some(&fee_payer)));
@@ -6441,7 +7503,7 @@ mod tests {
         setup_bpf_v3_config_with_policy(FeePayerPolicy::default());
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         // Try to transfer authority away from Kora.
@@ -6467,7 +7529,7 @@ mod tests {
         setup_bpf_v3_config_with_policy(policy);
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let ix = loader_v3::close(&buffer, &attacker, &fee_payer);
@@ -6497,7 +7559,7 @@ mod tests {
         setup_bpf_v3_config_with_policy(policy);
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let ix = loader_v3::close(&buffer, &fee_payer, &fee_payer);
@@ -6525,7 +7587,7 @@ mod tests {
         setup_bpf_v3_config_with_policy(FeePayerPolicy::default());
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         // user_authority signs as upgrade_authority; spill = fee_payer.
@@ -6551,7 +7613,7 @@ mod tests {
         setup_bpf_v3_config_with_policy(FeePayerPolicy::default());
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let ix = loader_v3::close(&buffer, &fee_payer, &user_authority);
@@ -6580,7 +7642,7 @@ mod tests {
         setup_bpf_v3_config_with_policy(FeePayerPolicy::default());
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         // attacker is the authority; fee_payer is the (optional) payer.
@@ -6606,7 +7668,7 @@ mod tests {
         setup_bpf_v3_config_with_policy(FeePayerPolicy::default());
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let ix = loader_v3::migrate_program(&programdata, &program, &fee_payer);
@@ -6630,7 +7692,7 @@ mod tests {
         setup_token2022_config_with_policy(FeePayerPolicy::default());
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let instruction = spl_token_metadata_interface::instruction::remove_key(
@@ -6660,7 +7722,7 @@ mod tests {
         setup_token2022_config_with_policy(FeePayerPolicy::default());
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let instruction = spl_token_group_interface::instruction::update_group_max_size(
@@ -6725,7 +7787,7 @@ mod tests {
         setup_token2022_config_interface_allowed(FeePayerPolicy::default());
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         // Attacker tries to borrow the fee payer's signature as the metadata update
@@ -6758,7 +7820,7 @@ mod tests {
         setup_token2022_config_interface_allowed(policy);
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         // Same instruction as the rejection test above, but the operator has
@@ -6791,7 +7853,7 @@ mod tests {
         setup_token2022_config_interface_allowed(FeePayerPolicy::default());
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         // A legitimate token issuance: the fee payer only pays; it is not an
@@ -6826,7 +7888,7 @@ mod tests {
         setup_token2022_config_interface_allowed(FeePayerPolicy::default());
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         // Fee payer is NOT an account here; it is planted as the new authority
@@ -6859,7 +7921,7 @@ mod tests {
         setup_token2022_config_interface_allowed(policy);
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let instruction = spl_token_metadata_interface::instruction::update_authority(
@@ -6888,7 +7950,7 @@ mod tests {
         setup_token2022_config_interface_allowed(FeePayerPolicy::default());
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let instruction = spl_token_group_interface::instruction::update_group_authority(
@@ -6918,7 +7980,7 @@ mod tests {
         setup_token2022_config_interface_allowed(policy);
 
         let rpc_client = RpcMockBuilder::new().build();
-        let config = get_config().unwrap();
+        let config = &get_config().unwrap();
         let validator = TransactionValidator::new(config, fee_payer).unwrap();
 
         let instruction = spl_token_group_interface::instruction::update_group_max_size(
@@ -7054,7 +8116,7 @@ mod fee_payer_policy_props {
         let message = VersionedMessage::Legacy(Message::new(&[ix], Some(&fee_payer)));
         let mut resolved =
             TransactionUtil::new_unsigned_versioned_transaction_resolved(message).unwrap();
-        validator.validate_fee_payer_usage(&config, &mut resolved)
+        validator.validate_fee_payer_usage(&mut resolved, &HashSet::new())
     }
 
     proptest! {
@@ -18,7 +18,7 @@ use super::{AccountKind, ClosedProgram, Loader, OwnedProgram};
 pub async fn close_program(
     rpc: &Arc<RpcClient>,
     program: &OwnedProgram,
-    signer: &solana_keychain::Signer,
+    signer: &kora_lib::signer::Signer,
     fee_payer: &Pubkey,
 ) -> Result<ClosedProgram> {
     let reclaimed_lamports = sum_reclaimable_lamports(rpc, program)
@@ -27,7 +27,7 @@ pub struct OwnedProgram {
 #[derive(Clone)]
 pub struct ReaperConfig {
     pub fee_payer: Pubkey,
-    pub signer: Arc<solana_keychain::Signer>,
+    pub signer: Arc<kora_lib::signer::Signer>,
     pub threshold: Duration,
     pub dry_run: bool,
     pub max_closes: Option<usize>,
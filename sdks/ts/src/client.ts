@@ -1,5 +1,4 @@
-import { Address, assertIsAddress, Instruction, isTransactionSigner } from '@solana/kit';
-import { findAssociatedTokenPda, getTransferInstruction, TOKEN_PROGRAM_ADDRESS } from '@solana-program/token';
+import { Address, assertIsAddress, isTransactionSigner } from '@solana/kit';
 import crypto from 'crypto';
 import { KoraError } from './error.js';
 
@@ -14,6 +13,7 @@ import {
     GetPayerSignerResponse,
     GetPaymentInstructionRequest,
     GetPaymentInstructionResponse,
+    GetPaymentInstructionRpcResponse,
     GetSupportedTokensResponse,
     GetVersionResponse,
     KoraClientOptions,
@@ -26,7 +26,10 @@ import {
     SignBundleResponse,
     SignTransactionRequest,
     SignTransactionResponse,
+    SignTransactionsRequest,
+    SignTransactionsResponse,
 } from './types/index.js';
+import { deserializeInstruction } from './utils/transaction.js';
 
 /**
  * Kora RPC client for interacting with the Kora paymaster service.
@@ -259,6 +262,32 @@ export class KoraClient {
         return await this.rpcRequest<SignTransactionResponse, SignTransactionRequest>('signTransaction', request);
     }
 
+    /**
+     * Signs a batch of independent transactions with the Kora fee payer without broadcasting.
+     * Each transaction is validated and signed on its own; a rejected transaction is reported
+     * in its result and does not fail the rest of the batch.
+     * @param request - Sign transactions request parameters
+     * @param request.transactions - Array of base64-encoded transactions to sign
+     * @param request.signer_key - Optional signer address for the transactions
+     * @param request.sig_verify - Optional signature verification (defaults to false)
+     * @returns One result per transaction, in request order
+     * @throws {Error} When the RPC call fails or the batch itself is rejected (e.g. too many transactions)
+     *
+     * @example
+     * ```typescript
+     * const { results } = await client.signTransactions({
+     *   transactions: ['base64Tx1', 'base64Tx2']
+     * });
+     * for (const result of results) {
+     *   if (result.error) console.error('Rejected:', result.error.message);
+     *   else console.log('Signed tx:', result.signed_transaction);
+     * }
+     * ```
+     */
+    async signTransactions(request: SignTransactionsRequest): Promise<SignTransactionsResponse> {
+        return await this.rpcRequest<SignTransactionsResponse, SignTransactionsRequest>('signTransactions', request);
+    }
+
     /**
      * Signs a transaction and immediately broadcasts it to the Solana network.
      * @param request - Sign and send request parameters
@@ -268,6 +297,8 @@ export class KoraClient {
      * `'sent'` returns once the RPC node accepts the transaction, `'signed'` returns as soon
      * as signing completes and broadcasts in the background (broadcast failures are logged
      * server-side; rebroadcast the returned signed transaction if it never lands)
+     * @param request.idempotency_key - Optional key; retrying with the same key returns the
+     * original response without signing or sending the transaction again
      * @returns Signature and the signed transaction
      * @throws {Error} When the RPC call fails, validation fails, or broadcast fails
      *
@@ -322,6 +353,8 @@ export class KoraClient {
      * @param request.signer_key - Optional signer address for the transactions
      * @param request.sig_verify - Optional signature verification (defaults to false)
      * @param request.sign_only_indices - Optional indices of transactions to sign (defaults to all)
+     * @param request.idempotency_key - Optional key; retrying with the same key returns the
+     * original response without signing or sending the bundle again
      * @returns Array of signed transactions, signer public key, and Jito bundle UUID
      * @throws {Error} When the RPC call fails, validation fails, or Jito submission fails
      *
@@ -339,20 +372,20 @@ export class KoraClient {
     }
 
     /**
-     * Creates a payment instruction to append to a transaction for fee payment to the Kora paymaster.
+     * Creates the payment instructions to append to a transaction for fee payment to the Kora paymaster.
      *
-     * This method estimates the required fee and generates a token transfer instruction
-     * from the source wallet to the Kora payment address. The server handles decimal
-     * conversion internally, so the raw token amount is used directly.
+     * The server estimates the required fee and returns the token transfer from the source
+     * wallet to the Kora payment address, preceded by the creation of the destination token
+     * account when it does not exist yet. The token program is resolved from the mint.
      *
      * @param request - Payment instruction request parameters
      * @param request.transaction - Base64-encoded transaction to estimate fees for
      * @param request.fee_token - Mint address of the token to use for payment
-     * @param request.source_wallet - Public key of the wallet paying the fees
-     * @param request.token_program_id - Optional token program ID (defaults to TOKEN_PROGRAM_ADDRESS)
+     * @param request.source_wallet - Address or TransactionSigner of the wallet paying the fees
      * @param request.signer_key - Optional signer address for the transaction
      * @param request.sig_verify - Optional signer verification during transaction simulation (defaults to false)
-     * @returns Payment instruction details including the instruction, amount, and addresses
+     * @param request.user_id - Optional user ID for signer affinity
+     * @returns Payment instructions, amount, and addresses
      * @throws {Error} When the token is not supported, payment is not required, or invalid addresses are provided
      *
      * @example
@@ -362,62 +395,45 @@ export class KoraClient {
      *   fee_token: 'EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v',
      *   source_wallet: 'sourceWalletPublicKey'
      * });
-     * // Append paymentInfo.payment_instruction to your transaction
+     * // Append paymentInfo.instructions to your transaction
      * ```
      */
     async getPaymentInstruction({
         transaction,
         fee_token,
         source_wallet,
-        token_program_id = TOKEN_PROGRAM_ADDRESS,
         signer_key,
         sig_verify,
+        user_id,
     }: GetPaymentInstructionRequest): Promise<GetPaymentInstructionResponse> {
         const isSigner = typeof source_wallet !== 'string' && isTransactionSigner(source_wallet);
         const walletAddress: Address = isSigner ? source_wallet.address : (source_wallet as Address);
 
         assertIsAddress(walletAddress);
         assertIsAddress(fee_token);
-        assertIsAddress(token_program_id);
-
-        const { fee_in_token, payment_address, signer_pubkey } = await this.estimateTransactionFee({
-            fee_token,
-            sig_verify,
-            signer_key,
-            transaction,
-        });
-        assertIsAddress(payment_address);
-
-        const [sourceTokenAccount] = await findAssociatedTokenPda({
-            mint: fee_token,
-            owner: walletAddress,
-            tokenProgram: token_program_id,
-        });
 
-        const [destinationTokenAccount] = await findAssociatedTokenPda({
-            mint: fee_token,
-            owner: payment_address,
-            tokenProgram: token_program_id,
-        });
+        const result = await this.rpcRequest<GetPaymentInstructionRpcResponse, Record<string, unknown>>(
+            'getPaymentInstruction',
+            { fee_token, sig_verify, signer_key, source_wallet: walletAddress, transaction, user_id },
+        );
 
-        if (fee_in_token === undefined) {
-            throw new Error('Fee token was specified but fee_in_token was not returned from server');
+        const instructions = result.instructions.map(instruction =>
+            deserializeInstruction(instruction, isSigner ? source_wallet : undefined),
+        );
+        const paymentInstruction = instructions[instructions.length - 1];
+        if (paymentInstruction === undefined) {
+            throw new Error('No payment instruction was returned from server');
         }
 
-        const paymentInstruction: Instruction = getTransferInstruction({
-            amount: fee_in_token,
-            authority: isSigner ? source_wallet : walletAddress,
-            destination: destinationTokenAccount,
-            source: sourceTokenAccount,
-        });
-
         return {
-            original_transaction: transaction,
-            payment_address,
-            payment_amount: fee_in_token,
+            instructions,
+            original_transaction: result.original_transaction,
+            payment_address: result.payment_address,
+            payment_amount: result.payment_amount,
             payment_instruction: paymentInstruction,
-            payment_token: fee_token,
-            signer_address: signer_pubkey,
+            payment_token: result.payment_token,
+            payment_token_account: result.payment_token_account,
+            signer_pubkey: result.signer_pubkey,
         };
     }
 }
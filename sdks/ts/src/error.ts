@@ -13,6 +13,7 @@ export enum KoraErrorCode {
 
     // Signing errors (-32020 to -32029)
     SigningError = -32020,
+    SignerBusy = -32021,
 
     // Auth / Rate limiting (-32030 to -32039)
     RateLimitExceeded = -32030,
@@ -16,12 +16,14 @@ import type {
     KitSignAndSendTransactionResponse,
     KitSignBundleResponse,
     KitSignTransactionResponse,
+    KitSignTransactionsResponse,
     KitSupportedTokensResponse,
     KoraPluginConfig,
     SignAndSendBundleRequest,
     SignAndSendTransactionRequest,
     SignBundleRequest,
     SignTransactionRequest,
+    SignTransactionsRequest,
 } from './types/index.js';
 
 /**
@@ -142,12 +144,14 @@ export function koraPlugin(config: KoraPluginConfig) {
             async getPaymentInstruction(request: GetPaymentInstructionRequest): Promise<KitPaymentInstructionResponse> {
                 const result = await client.getPaymentInstruction(request);
                 return {
+                    instructions: result.instructions,
                     original_transaction: result.original_transaction as Base64EncodedWireTransaction,
                     payment_address: address(result.payment_address),
                     payment_amount: result.payment_amount,
                     payment_instruction: result.payment_instruction,
                     payment_token: address(result.payment_token),
-                    signer_address: address(result.signer_address),
+                    payment_token_account: address(result.payment_token_account),
+                    signer_pubkey: address(result.signer_pubkey),
                 };
             },
 
@@ -215,6 +219,20 @@ export function koraPlugin(config: KoraPluginConfig) {
                     signer_pubkey: address(result.signer_pubkey),
                 };
             },
+
+            /**
+             * Signs a batch of independent transactions with Kit-typed response.
+             */
+            async signTransactions(request: SignTransactionsRequest): Promise<KitSignTransactionsResponse> {
+                const result = await client.signTransactions(request);
+                return {
+                    results: result.results.map(item => ({
+                        error: item.error,
+                        signed_transaction: item.signed_transaction as Base64EncodedWireTransaction | undefined,
+                        signer_pubkey: item.signer_pubkey ? address(item.signer_pubkey) : undefined,
+                    })),
+                };
+            },
         },
     });
 }
@@ -8,6 +8,9 @@ import { Instruction, type MicroLamports, type TransactionSigner } from '@solana
  * Parameters for signing a transaction.
  */
 export interface SignTransactionRequest {
+    /** Let the server set the compute unit price and limit (defaults to false). Clears all other
+     *  signatures, so the returned transaction must be signed again by the client */
+    inject_compute_budget?: boolean;
     /** Optional signer verification during transaction simulation (defaults to false) */
     sig_verify?: boolean;
     /** Optional signer address for the transaction */
@@ -27,10 +30,28 @@ export interface SignTransactionRequest {
  */
 export type RespondAfter = 'confirmed' | 'sent' | 'signed';
 
+/**
+ * Parameters for signing a batch of independent transactions.
+ */
+export interface SignTransactionsRequest {
+    /** Let the server set the compute unit price and limit of each transaction (defaults to false) */
+    inject_compute_budget?: boolean;
+    /** Optional signer verification during transaction simulation (defaults to false) */
+    sig_verify?: boolean;
+    /** Optional signer address for the transactions */
+    signer_key?: string;
+    /** Array of base64-encoded transactions to sign (at most `kora.batch.max_transactions`) */
+    transactions: string[];
+    /** Optional user ID for usage tracking, applied to every transaction in the batch */
+    user_id?: string;
+}
+
 /**
  * Parameters for signing and sending a transaction.
  */
 export interface SignAndSendTransactionRequest {
+    /** Optional key so that retries return the original response instead of sending again */
+    idempotency_key?: string;
     /** Optional milestone to wait for before responding (defaults to "confirmed") */
     respond_after?: RespondAfter;
     /** Optional signer verification during transaction simulation (defaults to false) */
@@ -63,6 +84,8 @@ export interface SignBundleRequest {
  * Parameters for signing and sending a bundle of transactions via Jito.
  */
 export interface SignAndSendBundleRequest {
+    /** Optional key so that retries return the original response instead of sending again */
+    idempotency_key?: string;
     /** Optional signer verification during transaction simulation (defaults to false) */
     sig_verify?: boolean;
     /** Optional indices of transactions to sign (defaults to all if not specified) */
@@ -81,6 +104,8 @@ export interface SignAndSendBundleRequest {
 export interface EstimateTransactionFeeRequest {
     /** Mint address of the token to calculate fees in */
     fee_token?: string;
+    /** Estimate with the server's compute unit price and limit applied (defaults to false) */
+    inject_compute_budget?: boolean;
     /** Optional signer verification during transaction simulation (defaults to false) */
     sig_verify?: boolean;
     /** Optional signer address for the transaction */
@@ -120,10 +145,10 @@ export interface GetPaymentInstructionRequest {
      *  it is used as the transfer authority on the payment instruction, preserving signer identity
      *  and avoiding conflicts with other instructions that reference the same address. */
     source_wallet: TransactionSigner | string;
-    /** The token program id to use for the payment (defaults to TOKEN_PROGRAM_ID) */
-    token_program_id?: string;
     /** Base64-encoded transaction to estimate fees for */
     transaction: string;
+    /** Optional user ID, served by the same signer across calls when signer affinity is enabled */
+    user_id?: string;
 }
 
 /**
@@ -140,6 +165,39 @@ export interface SignTransactionResponse {
     signer_pubkey: string;
 }
 
+/**
+ * Error of a single transaction in a signTransactions batch.
+ */
+export interface SignTransactionsItemError {
+    /** JSON-RPC error code */
+    code: number;
+    /** Kora error variant, e.g. `ValidationError` */
+    error_type: string;
+    /** Error message */
+    message: string;
+}
+
+/**
+ * Result of a single transaction in a signTransactions batch. Exactly one of
+ * `signed_transaction` and `error` is set.
+ */
+export interface SignTransactionsItem {
+    /** Why the transaction was not signed */
+    error?: SignTransactionsItemError;
+    /** Base64-encoded signed transaction */
+    signed_transaction?: string;
+    /** Public key of the signer used to sign the transaction */
+    signer_pubkey?: string;
+}
+
+/**
+ * Response from signing a batch of independent transactions.
+ */
+export interface SignTransactionsResponse {
+    /** One result per request transaction, in request order */
+    results: SignTransactionsItem[];
+}
+
 /**
  * Response from signing and sending a transaction.
  */
@@ -241,21 +299,70 @@ export interface GetPayerSignerResponse {
 }
 
 /**
- * Response containing a payment instruction.
+ * Account metadata of an instruction as returned by the server.
+ */
+export interface InstructionAccountMeta {
+    /** Whether the account must sign the transaction */
+    is_signer: boolean;
+    /** Whether the account is writable */
+    is_writable: boolean;
+    /** Account public key */
+    pubkey: string;
+}
+
+/**
+ * An instruction as returned by the server.
+ */
+export interface SerializedInstruction {
+    /** Accounts referenced by the instruction, in order */
+    accounts: InstructionAccountMeta[];
+    /** Base64-encoded instruction data */
+    data: string;
+    /** Program invoked by the instruction */
+    program_id: string;
+}
+
+/**
+ * Raw server response of the getPaymentInstruction method.
+ */
+export interface GetPaymentInstructionRpcResponse {
+    /** Instructions to append to the transaction, in order */
+    instructions: SerializedInstruction[];
+    /** Base64-encoded original transaction */
+    original_transaction: string;
+    /** Public key of the payment destination */
+    payment_address: string;
+    /** Payment amount in the smallest unit of the payment token */
+    payment_amount: number;
+    /** Mint address of the token used for payment */
+    payment_token: string;
+    /** Token account that receives the payment */
+    payment_token_account: string;
+    /** Public key of the signer used to estimate the fee */
+    signer_pubkey: string;
+}
+
+/**
+ * Response containing the payment instructions.
  */
 export interface GetPaymentInstructionResponse {
+    /** Instructions to append to the transaction, in order: the destination ATA creation
+     *  (only when that account does not exist yet) followed by the token transfer */
+    instructions: Instruction[];
     /** Base64-encoded original transaction */
     original_transaction: string;
     /** Public key of the payment destination */
     payment_address: string;
-    /** Payment amount in the requested token */
+    /** Payment amount in the smallest unit of the payment token */
     payment_amount: number;
-    /** Base64-encoded payment instruction */
+    /** The token transfer instruction (the last entry of `instructions`) */
     payment_instruction: Instruction;
     /** Mint address of the token used for payment */
     payment_token: string;
-    /** Public key of the payer signer */
-    signer_address: string;
+    /** Token account that receives the payment */
+    payment_token_account: string;
+    /** Public key of the signer used to estimate the fee */
+    signer_pubkey: string;
 }
 
 /**
@@ -332,6 +439,12 @@ export interface EnabledMethods {
     get_config: boolean;
     /** Whether the get_payer_signer method is enabled */
     get_payer_signer: boolean;
+    /** Whether the get_payment_instruction method is enabled */
+    get_payment_instruction: boolean;
+    /** Whether the get_transaction_status method is enabled */
+    get_transaction_status: boolean;
+    /** Whether the lease_nonce method is enabled */
+    lease_nonce: boolean;
     /** Whether the get_supported_tokens method is enabled */
     get_supported_tokens: boolean;
     /** Whether the get_version method is enabled */
@@ -346,8 +459,14 @@ export interface EnabledMethods {
     sign_bundle: boolean;
     /** Whether the sign_transaction method is enabled */
     sign_transaction: boolean;
+    /** Whether the sign_transactions batch method is enabled */
+    sign_transactions: boolean;
     /** Whether the transfer_transaction method is enabled */
     transfer_transaction: boolean;
+    /** Whether the validate_transaction method is enabled */
+    validate_transaction: boolean;
+    /** Whether the reload_config method is enabled */
+    reload_config: boolean;
 }
 
 /**
@@ -602,6 +721,22 @@ export interface KitSignTransactionResponse {
     signer_pubkey: Address;
 }
 
+/** Result of a single transaction in a signTransactions batch with Kit types */
+export interface KitSignTransactionsItem {
+    /** Why the transaction was not signed */
+    error?: SignTransactionsItemError;
+    /** Base64-encoded signed transaction */
+    signed_transaction?: Base64EncodedWireTransaction;
+    /** Public key of the signer used to sign the transaction */
+    signer_pubkey?: Address;
+}
+
+/** Plugin response for signTransactions with Kit types */
+export interface KitSignTransactionsResponse {
+    /** One result per request transaction, in request order */
+    results: KitSignTransactionsItem[];
+}
+
 /** Plugin response for signAndSendTransaction with Kit types */
 export interface KitSignAndSendTransactionResponse {
     /** Transaction signature */
@@ -614,18 +749,22 @@ export interface KitSignAndSendTransactionResponse {
 
 /** Plugin response for getPaymentInstruction with Kit types */
 export interface KitPaymentInstructionResponse {
+    /** Instructions to append to the transaction, in order */
+    instructions: KitInstruction[];
     /** Base64-encoded original transaction */
     original_transaction: Base64EncodedWireTransaction;
     /** Public key of the payment destination */
     payment_address: Address;
-    /** Payment amount in the requested token */
+    /** Payment amount in the smallest unit of the payment token */
     payment_amount: number;
-    /** Payment instruction */
+    /** The token transfer instruction (the last entry of `instructions`) */
     payment_instruction: KitInstruction;
     /** Mint address of the token used for payment */
     payment_token: Address;
-    /** Public key of the payer signer */
-    signer_address: Address;
+    /** Token account that receives the payment */
+    payment_token_account: Address;
+    /** Public key of the signer used to estimate the fee */
+    signer_pubkey: Address;
 }
 
 /** Plugin response for getConfig with Kit Address types */
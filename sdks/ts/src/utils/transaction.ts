@@ -1,10 +1,15 @@
 import {
+    AccountRole,
+    address,
     decompileTransactionMessage,
     getBase64Codec,
     getCompiledTransactionMessageCodec,
     Instruction,
+    type TransactionSigner,
 } from '@solana/kit';
 
+import type { SerializedInstruction } from '../types/index.js';
+
 /**
  * Deserializes a base64-encoded transaction message.
  * @param message - Base64-encoded transaction message
@@ -37,3 +42,30 @@ export function getInstructionsFromBase64Message(message: string): Instruction[]
         return [];
     }
 }
+
+/**
+ * Converts an instruction returned by the server into a Kit instruction.
+ * @param instruction - Instruction as returned by the server
+ * @param signer - Optional signer attached to the account metas of its own address
+ * @returns Kit instruction
+ * @internal
+ */
+export function deserializeInstruction(instruction: SerializedInstruction, signer?: TransactionSigner): Instruction {
+    return {
+        accounts: instruction.accounts.map(meta => {
+            const role = meta.is_signer
+                ? meta.is_writable
+                    ? AccountRole.WRITABLE_SIGNER
+                    : AccountRole.READONLY_SIGNER
+                : meta.is_writable
+                  ? AccountRole.WRITABLE
+                  : AccountRole.READONLY;
+            const accountAddress = address(meta.pubkey);
+            return signer && meta.is_signer && signer.address === accountAddress
+                ? { address: accountAddress, role, signer }
+                : { address: accountAddress, role };
+        }),
+        data: getBase64Codec().encode(instruction.data),
+        programAddress: address(instruction.program_id),
+    };
+}
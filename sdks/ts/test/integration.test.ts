@@ -317,7 +317,8 @@ describe(`KoraClient Integration Tests (${AUTH_ENABLED ? 'with auth' : 'without
                 payment_amount: _payment_amount,
                 payment_token,
                 payment_address,
-                signer_address,
+                payment_token_account,
+                signer_pubkey,
                 original_transaction,
             } = await client.getPaymentInstruction({
                 fee_token: usdcMint,
@@ -327,13 +328,15 @@ describe(`KoraClient Integration Tests (${AUTH_ENABLED ? 'with auth' : 'without
             expect(payment_instruction).toBeDefined();
             expect(payment_instruction.programAddress).toBe(TOKEN_PROGRAM_ADDRESS);
             expect(payment_instruction.accounts?.[0].address).toBe(expectedSenderAta);
-            expect(payment_instruction.accounts?.[1].address).toBe(koraAta);
-            expect(payment_instruction.accounts?.[2].address).toBe(testWalletAddress);
+            expect(payment_instruction.accounts?.[1].address).toBe(usdcMint);
+            expect(payment_instruction.accounts?.[2].address).toBe(koraAta);
+            expect(payment_instruction.accounts?.[3].address).toBe(testWalletAddress);
             // todo math to verify payment amount
             // expect(payment_amount).toBe(1000000);
             expect(payment_token).toBe(usdcMint);
             expect(payment_address).toBe(koraAddress);
-            expect(signer_address).toBe(koraAddress);
+            expect(payment_token_account).toBe(koraAta);
+            expect(signer_pubkey).toBe(koraAddress);
             expect(original_transaction).toBe(transaction);
         });
     });
@@ -14,6 +14,7 @@ import type {
     KitSignAndSendTransactionResponse,
     KitSignBundleResponse,
     KitSignTransactionResponse,
+    KitSignTransactionsResponse,
     KitSupportedTokensResponse,
     KoraPluginConfig,
 } from '../src/types/index.js';
@@ -70,6 +71,7 @@ describe('Kora Kit Plugin', () => {
             expect(typeof enhanced.kora.estimateTransactionFee).toBe('function');
             expect(typeof enhanced.kora.estimateBundleFee).toBe('function');
             expect(typeof enhanced.kora.signTransaction).toBe('function');
+            expect(typeof enhanced.kora.signTransactions).toBe('function');
             expect(typeof enhanced.kora.signAndSendTransaction).toBe('function');
             expect(typeof enhanced.kora.signBundle).toBe('function');
             expect(typeof enhanced.kora.signAndSendBundle).toBe('function');
@@ -404,6 +406,36 @@ describe('Kora Kit Plugin', () => {
             });
         });
 
+        describe('signTransactions', () => {
+            it('should return Kit-typed per-transaction results', async () => {
+                const rawResponse = {
+                    results: [
+                        {
+                            signed_transaction: 'base64SignedTx1',
+                            signer_pubkey: 'DemoKMZWkk483QoFPLRPQ2XVKB7bWnuXwSjvDE1JsWk7',
+                        },
+                        { error: { code: -32602, error_type: 'ValidationError', message: 'Invalid transaction' } },
+                    ],
+                };
+
+                mockSuccessfulResponse(rawResponse);
+
+                const result: KitSignTransactionsResponse = await kora.signTransactions({
+                    transactions: ['base64Tx1', 'base64Tx2'],
+                });
+
+                // Type assertions - verify Kit types
+                const signedTx: Base64EncodedWireTransaction | undefined = result.results[0].signed_transaction;
+                const signerPubkey: Address | undefined = result.results[0].signer_pubkey;
+
+                expect(result.results).toHaveLength(2);
+                expect(signedTx).toBe('base64SignedTx1');
+                expect(signerPubkey).toBe('DemoKMZWkk483QoFPLRPQ2XVKB7bWnuXwSjvDE1JsWk7');
+                expect(result.results[1].signed_transaction).toBeUndefined();
+                expect(result.results[1].error?.message).toBe('Invalid transaction');
+            });
+        });
+
         describe('signBundle', () => {
             it('should return Kit-typed response with Base64EncodedWireTransaction array', async () => {
                 const rawResponse = {
@@ -456,22 +488,47 @@ describe('Kora Kit Plugin', () => {
 
         describe('getPaymentInstruction', () => {
             it('should return Kit-typed response with Base64EncodedWireTransaction and Address fields', async () => {
-                const mockFeeEstimate = {
-                    fee_in_lamports: 5000,
-                    fee_in_token: 50000,
+                const testTx =
+                    'Aoq7ymA5OGP+gmDXiY5m3cYXlY2Rz/a/gFjOgt9ZuoCS7UzuiGGaEnW2OOtvHvMQHkkD7Z4LRF5B63ftu+1oZwIAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAgECB1urjQEjgFgzqYhJ8IXJeSg4cJP1j1g2CJstOQTDchOKUzqH3PxgGW3c4V3vZV05A5Y30/MggOBs0Kd00s1JEwg5TaEeaV4+KL2y7fXIAuf6cN0ZQitbhY+G9ExtBSChspOXPgNcy9pYpETe4bmB+fg4bfZx1tnicA/kIyyubczAmbcIKIuniNOOQYG2ggKCz8NjEsHVezrWMatndu1wk6J5miGP26J6Vwp31AljiAajAFuP0D9mWJwSeFuA7J5rPwbd9uHXZaGT2cvhRs7reawctIXtX1s3kTqM9YV+/wCpd/O36SW02zRtNtqk6GFeip2+yBQsVTeSbLL4rWJRkd4CBgQCBQQBCgxAQg8AAAAAAAYGBAIFAwEKDBAnAAAAAAAABg==';
+
+                const rawResponse = {
+                    instructions: [
+                        {
+                            accounts: [
+                                {
+                                    is_signer: false,
+                                    is_writable: true,
+                                    pubkey: 'So11111111111111111111111111111111111111112',
+                                },
+                                {
+                                    is_signer: false,
+                                    is_writable: false,
+                                    pubkey: '4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU',
+                                },
+                                {
+                                    is_signer: false,
+                                    is_writable: true,
+                                    pubkey: '9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin',
+                                },
+                                { is_signer: true, is_writable: false, pubkey: '11111111111111111111111111111111' },
+                            ],
+                            data: 'DFDDAAAAAAAABg==',
+                            program_id: 'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA',
+                        },
+                    ],
+                    original_transaction: testTx,
                     payment_address: 'PayKMZWkk483QoFPLRPQ2XVKB7bWnuXwSjvDE1JsWk7',
+                    payment_amount: 50000,
+                    payment_token: '4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU',
+                    payment_token_account: '9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin',
                     signer_pubkey: 'DemoKMZWkk483QoFPLRPQ2XVKB7bWnuXwSjvDE1JsWk7',
                 };
 
-                const testTx =
-                    'Aoq7ymA5OGP+gmDXiY5m3cYXlY2Rz/a/gFjOgt9ZuoCS7UzuiGGaEnW2OOtvHvMQHkkD7Z4LRF5B63ftu+1oZwIAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAgECB1urjQEjgFgzqYhJ8IXJeSg4cJP1j1g2CJstOQTDchOKUzqH3PxgGW3c4V3vZV05A5Y30/MggOBs0Kd00s1JEwg5TaEeaV4+KL2y7fXIAuf6cN0ZQitbhY+G9ExtBSChspOXPgNcy9pYpETe4bmB+fg4bfZx1tnicA/kIyyubczAmbcIKIuniNOOQYG2ggKCz8NjEsHVezrWMatndu1wk6J5miGP26J6Vwp31AljiAajAFuP0D9mWJwSeFuA7J5rPwbd9uHXZaGT2cvhRs7reawctIXtX1s3kTqM9YV+/wCpd/O36SW02zRtNtqk6GFeip2+yBQsVTeSbLL4rWJRkd4CBgQCBQQBCgxAQg8AAAAAAAYGBAIFAwEKDBAnAAAAAAAABg==';
-
-                mockSuccessfulResponse(mockFeeEstimate);
+                mockSuccessfulResponse(rawResponse);
 
                 const result: KitPaymentInstructionResponse = await kora.getPaymentInstruction({
                     fee_token: '4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU',
                     source_wallet: '11111111111111111111111111111111',
-                    token_program_id: 'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA',
                     transaction: testTx,
                 });
 
@@ -479,13 +536,18 @@ describe('Kora Kit Plugin', () => {
                 const originalTx: Base64EncodedWireTransaction = result.original_transaction;
                 const paymentToken: Address = result.payment_token;
                 const paymentAddr: Address = result.payment_address;
-                const signerAddr: Address = result.signer_address;
+                const paymentTokenAccount: Address = result.payment_token_account;
+                const signerPubkey: Address = result.signer_pubkey;
 
                 expect(originalTx).toBe(testTx);
                 expect(paymentToken).toBe('4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU');
                 expect(paymentAddr).toBe('PayKMZWkk483QoFPLRPQ2XVKB7bWnuXwSjvDE1JsWk7');
-                expect(signerAddr).toBe('DemoKMZWkk483QoFPLRPQ2XVKB7bWnuXwSjvDE1JsWk7');
+                expect(paymentTokenAccount).toBe('9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin');
+                expect(signerPubkey).toBe('DemoKMZWkk483QoFPLRPQ2XVKB7bWnuXwSjvDE1JsWk7');
                 expect(result.payment_amount).toBe(50000);
+                expect(result.instructions).toHaveLength(1);
+                expect(result.payment_instruction).toBe(result.instructions[0]);
+                expect(result.payment_instruction.programAddress).toBe('TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA');
             });
         });
     });
@@ -543,6 +605,7 @@ describe('Kora Kit Plugin', () => {
             expect(typeof client.kora.estimateTransactionFee).toBe('function');
             expect(typeof client.kora.estimateBundleFee).toBe('function');
             expect(typeof client.kora.signTransaction).toBe('function');
+            expect(typeof client.kora.signTransactions).toBe('function');
             expect(typeof client.kora.signAndSendTransaction).toBe('function');
             expect(typeof client.kora.signBundle).toBe('function');
             expect(typeof client.kora.signAndSendBundle).toBe('function');
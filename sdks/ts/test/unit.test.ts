@@ -19,8 +19,10 @@ import {
     EstimateTransactionFeeResponse,
     GetBlockhashResponse,
     GetPayerSignerResponse,
+    GetPaymentInstructionRpcResponse,
     GetSupportedTokensResponse,
     GetVersionResponse,
+    SerializedInstruction,
     SignAndSendBundleRequest,
     SignAndSendBundleResponse,
     SignAndSendTransactionRequest,
@@ -29,6 +31,8 @@ import {
     SignBundleResponse,
     SignTransactionRequest,
     SignTransactionResponse,
+    SignTransactionsRequest,
+    SignTransactionsResponse,
 } from '../src/types/index.js';
 import { getInstructionsFromBase64Message } from '../src/utils/transaction.js';
 
@@ -132,6 +136,9 @@ describe('KoraClient Unit Tests', () => {
                     get_blockhash: true,
                     get_config: true,
                     get_payer_signer: true,
+                    get_payment_instruction: true,
+                    get_transaction_status: true,
+                    lease_nonce: false,
                     get_supported_tokens: true,
                     get_version: true,
                     liveness: true,
@@ -139,7 +146,10 @@ describe('KoraClient Unit Tests', () => {
                     sign_and_send_transaction: true,
                     sign_bundle: true,
                     sign_transaction: true,
+                    sign_transactions: false,
                     transfer_transaction: true,
+                    validate_transaction: true,
+                    reload_config: false,
                 },
                 fee_payers: ['test_fee_payer_address'],
                 validation_config: {
@@ -338,6 +348,36 @@ describe('KoraClient Unit Tests', () => {
         });
     });
 
+    describe('signTransactions', () => {
+        it('should sign a batch of transactions with per-transaction results', async () => {
+            const request: SignTransactionsRequest = {
+                transactions: ['base64_tx_1', 'base64_tx_2'],
+            };
+            const mockResponse: SignTransactionsResponse = {
+                results: [
+                    { signed_transaction: 'base64_signed_tx_1', signer_pubkey: 'test_signer_pubkey' },
+                    { error: { code: -32602, error_type: 'ValidationError', message: 'Invalid transaction' } },
+                ],
+            };
+
+            await testSuccessfulRpcMethod(
+                'signTransactions',
+                () => client.signTransactions(request),
+                mockResponse,
+                request,
+            );
+        });
+
+        it('should handle RPC error', async () => {
+            const request: SignTransactionsRequest = {
+                transactions: ['base64_tx_1'],
+            };
+            const mockError = { code: -32602, message: 'Too many transactions' };
+            mockErrorResponse(mockError);
+            await expect(client.signTransactions(request)).rejects.toThrow('Kora Error -32602: Too many transactions');
+        });
+    });
+
     describe('signBundle', () => {
         it('should sign bundle of transactions', async () => {
             const request: SignBundleRequest = {
@@ -400,6 +440,9 @@ describe('KoraClient Unit Tests', () => {
                 get_blockhash: true,
                 get_config: true,
                 get_payer_signer: true,
+                get_payment_instruction: true,
+                get_transaction_status: true,
+                lease_nonce: false,
                 get_supported_tokens: true,
                 get_version: true,
                 liveness: true,
@@ -407,7 +450,10 @@ describe('KoraClient Unit Tests', () => {
                 sign_and_send_transaction: true,
                 sign_bundle: true,
                 sign_transaction: true,
+                sign_transactions: false,
                 transfer_transaction: true,
+                validate_transaction: true,
+                reload_config: false,
             },
             fee_payers: ['11111111111111111111111111111111'],
             validation_config: {
@@ -471,12 +517,37 @@ describe('KoraClient Unit Tests', () => {
             },
         };
 
-        const mockFeeEstimate: EstimateTransactionFeeResponse = {
-            fee_in_lamports: 5000,
-            fee_in_token: 50000,
+        const mockSourceWallet = '11111111111111111111111111111111';
+        const mockPaymentTokenAccount = '9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin';
+
+        const transferInstruction: SerializedInstruction = {
+            // Source token account, mint, destination token account, authority
+            accounts: [
+                { is_signer: false, is_writable: true, pubkey: 'So11111111111111111111111111111111111111112' },
+                { is_signer: false, is_writable: false, pubkey: '4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU' },
+                { is_signer: false, is_writable: true, pubkey: mockPaymentTokenAccount },
+                { is_signer: true, is_writable: false, pubkey: mockSourceWallet },
+            ],
+            data: 'DFDDAAAAAAAABg==',
+            program_id: TOKEN_PROGRAM_ADDRESS,
+        };
+
+        const mockPaymentInstruction = (sourceWallet: string = mockSourceWallet): GetPaymentInstructionRpcResponse => ({
+            instructions: [
+                {
+                    ...transferInstruction,
+                    accounts: transferInstruction.accounts.map((meta, i) =>
+                        i === 3 ? { ...meta, pubkey: sourceWallet } : meta,
+                    ),
+                },
+            ],
+            original_transaction: mockTransactionBase64,
             payment_address: 'PayKMZWkk483QoFPLRPQ2XVKB7bWnuXwSjvDE1JsWk7',
+            payment_amount: 50000,
+            payment_token: '4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU',
+            payment_token_account: mockPaymentTokenAccount,
             signer_pubkey: 'DemoKMZWkk483QoFPLRPQ2XVKB7bWnuXwSjvDE1JsWk7',
-        };
+        });
 
         // Create a mock base64-encoded transaction
         // This is a minimal valid transaction structure
@@ -485,8 +556,7 @@ describe('KoraClient Unit Tests', () => {
 
         const validRequest = {
             fee_token: '4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU',
-            source_wallet: '11111111111111111111111111111111',
-            token_program_id: 'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA',
+            source_wallet: mockSourceWallet,
             transaction: mockTransactionBase64,
         };
 
@@ -499,52 +569,43 @@ describe('KoraClient Unit Tests', () => {
             jest.restoreAllMocks();
         });
 
-        it('should successfully append payment instruction', async () => {
-            // Mock estimateTransactionFee call
-            mockFetch.mockResolvedValueOnce({
-                json: jest.fn().mockResolvedValueOnce({
-                    id: 1,
-                    jsonrpc: '2.0',
-                    result: mockFeeEstimate,
-                }),
-            });
+        it('should call the server and convert the returned instructions', async () => {
+            const serverResponse = mockPaymentInstruction();
+            mockSuccessfulResponse(serverResponse);
 
             const result = await client.getPaymentInstruction(validRequest);
 
+            const expectedInstruction = expect.objectContaining({
+                accounts: [
+                    { address: transferInstruction.accounts[0].pubkey, role: 1 }, // writable
+                    { address: transferInstruction.accounts[1].pubkey, role: 0 }, // readonly
+                    { address: transferInstruction.accounts[2].pubkey, role: 1 }, // writable
+                    // readonly-signer (plain address, no signer attached)
+                    { address: validRequest.source_wallet, role: 2 },
+                ],
+                data: new Uint8Array([12, 80, 195, 0, 0, 0, 0, 0, 0, 6]),
+                programAddress: TOKEN_PROGRAM_ADDRESS,
+            });
             expect(result).toEqual({
+                instructions: [expectedInstruction],
                 original_transaction: validRequest.transaction,
-                payment_address: mockFeeEstimate.payment_address,
-                payment_amount: mockFeeEstimate.fee_in_token,
-                payment_instruction: expect.objectContaining({
-                    accounts: [
-                        expect.objectContaining({
-                            role: 1, // writable
-                        }), // Source token account
-                        expect.objectContaining({
-                            role: 1, // writable
-                        }), // Destination token account
-                        expect.objectContaining({
-                            // readonly (plain address, no signer attached)
-                            address: validRequest.source_wallet,
-                            role: 0,
-                        }), // Authority
-                    ],
-                    data: expect.any(Uint8Array),
-                    programAddress: TOKEN_PROGRAM_ADDRESS,
-                }),
+                payment_address: serverResponse.payment_address,
+                payment_amount: serverResponse.payment_amount,
+                payment_instruction: expectedInstruction,
                 payment_token: validRequest.fee_token,
-                signer_address: mockFeeEstimate.signer_pubkey,
+                payment_token_account: mockPaymentTokenAccount,
+                signer_pubkey: serverResponse.signer_pubkey,
             });
 
-            // Verify only estimateTransactionFee was called
             expect(mockFetch).toHaveBeenCalledTimes(1);
             expect(mockFetch).toHaveBeenCalledWith(mockRpcUrl, {
                 body: JSON.stringify({
                     id: 1,
                     jsonrpc: '2.0',
-                    method: 'estimateTransactionFee',
+                    method: 'getPaymentInstruction',
                     params: {
                         fee_token: validRequest.fee_token,
+                        source_wallet: validRequest.source_wallet,
                         transaction: validRequest.transaction,
                     },
                 }),
@@ -555,44 +616,39 @@ describe('KoraClient Unit Tests', () => {
             });
         });
 
-        it('should handle fixed pricing configuration', async () => {
-            // Mock estimateTransactionFee call
-            mockFetch.mockResolvedValueOnce({
-                json: jest.fn().mockResolvedValueOnce({
-                    id: 1,
-                    jsonrpc: '2.0',
-                    result: mockFeeEstimate,
-                }),
-            });
+        it('should return the ATA creation instruction before the transfer', async () => {
+            const serverResponse = mockPaymentInstruction();
+            const createAtaInstruction: SerializedInstruction = {
+                accounts: [{ is_signer: true, is_writable: true, pubkey: mockSourceWallet }],
+                data: '',
+                program_id: 'ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL',
+            };
+            serverResponse.instructions.unshift(createAtaInstruction);
+            mockSuccessfulResponse(serverResponse);
 
             const result = await client.getPaymentInstruction(validRequest);
 
-            expect(result.payment_amount).toBe(mockFeeEstimate.fee_in_token);
-            expect(result.payment_token).toBe(validRequest.fee_token);
+            expect(result.instructions).toHaveLength(2);
+            expect(result.instructions[0].programAddress).toBe(createAtaInstruction.program_id);
+            expect(result.instructions[0].accounts?.[0]).toEqual({ address: mockSourceWallet, role: 3 });
+            expect(result.payment_instruction).toBe(result.instructions[1]);
         });
 
         it('should throw error for invalid addresses', async () => {
             const invalidRequests = [
                 { ...validRequest, source_wallet: 'invalid_address' },
                 { ...validRequest, fee_token: 'invalid_token' },
-                { ...validRequest, token_program_id: 'invalid_program' },
             ];
 
             for (const invalidRequest of invalidRequests) {
                 await expect(client.getPaymentInstruction(invalidRequest)).rejects.toThrow();
             }
+            expect(mockFetch).not.toHaveBeenCalled();
         });
 
-        it('should handle estimateTransactionFee RPC error', async () => {
-            // Mock failed estimateTransactionFee
+        it('should handle getPaymentInstruction RPC error', async () => {
             const mockError = { code: -32602, message: 'Invalid transaction' };
-            mockFetch.mockResolvedValueOnce({
-                json: jest.fn().mockResolvedValueOnce({
-                    error: mockError,
-                    id: 1,
-                    jsonrpc: '2.0',
-                }),
-            });
+            mockErrorResponse(mockError);
 
             await expect(client.getPaymentInstruction(validRequest)).rejects.toThrow(
                 'Kora Error -32602: Invalid transaction',
@@ -608,15 +664,7 @@ describe('KoraClient Unit Tests', () => {
         it('should produce a payment instruction compatible with a real signer for the same address', async () => {
             // Generate a real KeyPairSigner (simulates a user's wallet)
             const userSigner = await generateKeyPairSigner();
-
-            // Mock estimateTransactionFee to return the user's address as source_wallet context
-            const feeEstimate: EstimateTransactionFeeResponse = {
-                fee_in_lamports: 5000,
-                fee_in_token: 50000,
-                payment_address: 'PayKMZWkk483QoFPLRPQ2XVKB7bWnuXwSjvDE1JsWk7',
-                signer_pubkey: 'DemoKMZWkk483QoFPLRPQ2XVKB7bWnuXwSjvDE1JsWk7',
-            };
-            mockSuccessfulResponse(feeEstimate);
+            mockSuccessfulResponse(mockPaymentInstruction(userSigner.address));
 
             // Get payment instruction — authority is a plain address (no signer attached)
             const result = await client.getPaymentInstruction({
@@ -633,9 +681,8 @@ describe('KoraClient Unit Tests', () => {
                 source: '11111111111111111111111111111111' as any,
             });
 
-            // Combine both instructions in a transaction — previously this would throw
-            // "Multiple distinct signers" because the payment instruction had a NoopSigner.
-            // Now the payment instruction uses a plain address, so no conflict.
+            // Combine both instructions in a transaction. The payment instruction carries no
+            // signer object, so it does not conflict with the real signer.
             const feePayer = createNoopSigner('DemoKMZWkk483QoFPLRPQ2XVKB7bWnuXwSjvDE1JsWk7' as any);
             const txMessage = appendTransactionMessageInstructions(
                 [userOwnedIx, result.payment_instruction],
@@ -651,14 +698,7 @@ describe('KoraClient Unit Tests', () => {
 
         it('should accept a TransactionSigner as source_wallet and preserve signer identity', async () => {
             const userSigner = await generateKeyPairSigner();
-
-            const feeEstimate: EstimateTransactionFeeResponse = {
-                fee_in_lamports: 5000,
-                fee_in_token: 50000,
-                payment_address: 'PayKMZWkk483QoFPLRPQ2XVKB7bWnuXwSjvDE1JsWk7',
-                signer_pubkey: 'DemoKMZWkk483QoFPLRPQ2XVKB7bWnuXwSjvDE1JsWk7',
-            };
-            mockSuccessfulResponse(feeEstimate);
+            mockSuccessfulResponse(mockPaymentInstruction(userSigner.address));
 
             // Pass the signer directly as source_wallet
             const result = await client.getPaymentInstruction({
@@ -667,7 +707,7 @@ describe('KoraClient Unit Tests', () => {
             });
 
             // The authority account meta should carry the signer
-            const authorityMeta = result.payment_instruction.accounts?.[2];
+            const authorityMeta = result.payment_instruction.accounts?.[3];
             expect(authorityMeta).toEqual(
                 expect.objectContaining({
                     address: userSigner.address,
@@ -696,25 +736,12 @@ describe('KoraClient Unit Tests', () => {
             await expect(partiallySignTransactionMessageWithSigners(txMessage)).resolves.toBeDefined();
         });
 
-        it('should return correct payment details in response', async () => {
-            mockFetch.mockResolvedValueOnce({
-                json: jest.fn().mockResolvedValueOnce({
-                    id: 1,
-                    jsonrpc: '2.0',
-                    result: mockFeeEstimate,
-                }),
-            });
+        it('should reject a response without instructions', async () => {
+            mockSuccessfulResponse({ ...mockPaymentInstruction(), instructions: [] });
 
-            const result = await client.getPaymentInstruction(validRequest);
-
-            expect(result).toMatchObject({
-                original_transaction: validRequest.transaction,
-                payment_address: mockFeeEstimate.payment_address,
-                payment_amount: mockFeeEstimate.fee_in_token,
-                payment_instruction: expect.any(Object),
-                payment_token: validRequest.fee_token,
-                signer_address: mockFeeEstimate.signer_pubkey,
-            });
+            await expect(client.getPaymentInstruction(validRequest)).rejects.toThrow(
+                'No payment instruction was returned from server',
+            );
         });
     });
 